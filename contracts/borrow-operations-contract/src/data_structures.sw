@@ -38,6 +38,7 @@ pub struct LocalVariablesAdjustTrove {
     pub coll: u64,
     pub old_icr: u64,
     pub new_icr: u64,
+    pub old_tcr: u64,
     pub new_tcr: u64,
     pub usdm_fee: u64,
     pub new_debt: u64,
@@ -56,6 +57,7 @@ impl LocalVariablesAdjustTrove {
             coll: 0,
             old_icr: 0,
             new_icr: 0,
+            old_tcr: 0,
             new_tcr: 0,
             usdm_fee: 0,
             new_debt: 0,
@@ -1,5 +1,7 @@
 library;
 
+use libraries::borrow_operations_interface::data_structures::OperatorPermissions;
+
 pub struct OpenTroveEvent {
     pub user: Identity,
     pub asset_id: AssetId,
@@ -24,3 +26,23 @@ pub struct CloseTroveEvent {
     pub collateral: u64,
     pub debt: u64,
 }
+
+pub struct SetOperatorEvent {
+    pub owner: Identity,
+    pub operator: Identity,
+    pub asset_id: AssetId,
+    pub permissions: OperatorPermissions,
+}
+
+pub struct FlashMintEvent {
+    pub initiator: Identity,
+    pub receiver: ContractId,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+pub struct TransferTroveEvent {
+    pub previous_owner: Identity,
+    pub new_owner: Identity,
+    pub asset_id: AssetId,
+}
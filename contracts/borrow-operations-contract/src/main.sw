@@ -15,7 +15,14 @@ mod events;
 
 use standards::{src3::SRC3,};
 use ::data_structures::{AssetContracts, LocalVariablesAdjustTrove, LocalVariablesOpenTrove};
-use ::events::{AdjustTroveEvent, CloseTroveEvent, OpenTroveEvent};
+use ::events::{
+    AdjustTroveEvent,
+    CloseTroveEvent,
+    FlashMintEvent,
+    OpenTroveEvent,
+    SetOperatorEvent,
+    TransferTroveEvent,
+};
 use libraries::trove_manager_interface::data_structures::Status;
 use libraries::active_pool_interface::ActivePool;
 use libraries::token_interface::Token;
@@ -25,6 +32,10 @@ use libraries::fpt_staking_interface::FPTStaking;
 use libraries::coll_surplus_pool_interface::CollSurplusPool;
 use libraries::oracle_interface::Oracle;
 use libraries::borrow_operations_interface::BorrowOperations;
+use libraries::borrow_operations_interface::data_structures::OperatorPermissions;
+use libraries::flash_mint_receiver_interface::FlashMintReceiver;
+use libraries::protocol_manager_interface::ProtocolManager;
+use libraries::protocol_manager_interface::data_structures::AssetStatus;
 use libraries::fluid_math::*;
 use sway_libs::ownership::*;
 use std::{
@@ -35,6 +46,7 @@ use std::{
     },
     context::{
         msg_amount,
+        this_balance,
     },
     hash::*,
     logging::log,
@@ -58,8 +70,17 @@ storage {
     is_initialized: bool = false,
     is_paused: bool = false, // paused protocol still allows trove operations which do not increase trove debt
     pauser: Identity = Identity::Address(Address::zero()),
+    // Same as `is_paused` for a single asset
+    paused_assets: StorageMap<AssetId, bool> = StorageMap::<AssetId, bool> {},
+    // (owner, operator, asset) to what the operator may do with the owner's trove of the asset
+    operators: StorageMap<(Identity, Identity, AssetId), OperatorPermissions> = StorageMap::<(Identity, Identity, AssetId), OperatorPermissions> {},
+    // (owner, asset) to the number of troves the owner has opened or taken over
+    trove_nonces: StorageMap<(Identity, AssetId), u64> = StorageMap::<(Identity, AssetId), u64> {},
+    // (owner, asset) to the identity proposed to take the trove over and the nonce of that trove
+    pending_trove_transfers: StorageMap<(Identity, AssetId), (Identity, u64)> = StorageMap::<(Identity, AssetId), (Identity, u64)> {},
     lock_close_trove: bool = false,
     lock_internal_adjust_trove: bool = false,
+    lock_flash_mint: bool = false,
 }
 impl BorrowOperations for Contract {
     #[storage(read, write)]
@@ -118,12 +139,18 @@ impl BorrowOperations for Contract {
 
     // --- Borrower Trove Operations ---
     // Open a new trove by borrowing USDM
-    // Differences from Liquity:0% frontend fees, no recovery mode, no gas compensation
-    #[storage(read), payable]
-    fn open_trove(usdm_amount: u64, upper_hint: Identity, lower_hint: Identity) {
+    // Differences from Liquity:0% frontend fees, no gas compensation
+    #[storage(read, write), payable]
+    fn open_trove(
+        max_fee_percentage: u64,
+        usdm_amount: u64,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) {
         require_is_not_paused();
         require_valid_asset_id();
         let asset_contract = msg_asset_id();
+        require_asset_is_not_paused(asset_contract);
         let asset_contracts = storage.asset_contracts.get(asset_contract).read();
         let usdm_contract = storage.usdm_contract.read();
         let fpt_staking_contract = storage.fpt_staking_contract.read();
@@ -132,17 +159,41 @@ impl BorrowOperations for Contract {
         let oracle = abi(Oracle, asset_contracts.oracle.bits());
         let trove_manager = abi(TroveManager, asset_contracts.trove_manager.bits());
         let sorted_troves = abi(SortedTroves, sorted_troves_contract.bits());
+        let protocol_manager_contract = storage.protocol_manager_contract.read();
+        let protocol_manager = abi(ProtocolManager, protocol_manager_contract.bits());
+        require_asset_is_active(protocol_manager.get_asset_status(asset_contract));
+        let risk_parameters = protocol_manager.get_risk_parameters(asset_contract);
+        require_valid_max_fee_percentage(max_fee_percentage, risk_parameters.borrowing_fee_floor);
         let mut vars = LocalVariablesOpenTrove::new();
         let sender = msg_sender().unwrap();
         vars.net_debt = usdm_amount;
         vars.price = oracle.get_price();
         require_trove_is_not_active(sender, asset_contracts.trove_manager);
-        vars.usdm_fee = internal_trigger_borrowing_fee(vars.net_debt, usdm_contract, fpt_staking_contract);
+        internal_increment_trove_nonce(sender, asset_contract);
+        vars.usdm_fee = internal_trigger_borrowing_fee(
+            vars.net_debt,
+            max_fee_percentage,
+            asset_contract,
+            usdm_contract,
+            fpt_staking_contract,
+            protocol_manager_contract,
+        );
         vars.net_debt += vars.usdm_fee;
-        require_at_least_min_net_debt(vars.net_debt);
+        require_at_least_min_net_debt(vars.net_debt, risk_parameters.min_net_debt);
+        require_within_debt_ceiling(
+            trove_manager
+                .get_entire_system_debt(),
+            vars.net_debt,
+            protocol_manager
+                .get_debt_ceiling(asset_contract),
+        );
         vars.icr = fm_compute_cr(msg_amount(), vars.net_debt, vars.price);
         vars.nicr = fm_compute_nominal_cr(msg_amount(), vars.net_debt);
-        require_at_least_mcr(vars.icr);
+        require_at_least_mcr(vars.icr, risk_parameters.mcr);
+        // In recovery mode new troves have to open at or above CCR, which can only raise the TCR
+        if trove_manager.check_recovery_mode(vars.price) {
+            require_at_least_ccr(vars.icr, risk_parameters.ccr);
+        }
         // Set the trove struct's properties
         trove_manager.set_trove_status(sender, Status::Active);
         let _ = trove_manager.increase_trove_coll(sender, msg_amount());
@@ -175,6 +226,9 @@ impl BorrowOperations for Contract {
         internal_adjust_trove(
             msg_sender()
                 .unwrap(),
+            msg_sender()
+                .unwrap(),
+            0,
             msg_amount(),
             0,
             0,
@@ -195,6 +249,9 @@ impl BorrowOperations for Contract {
         internal_adjust_trove(
             msg_sender()
                 .unwrap(),
+            msg_sender()
+                .unwrap(),
+            0,
             0,
             amount,
             0,
@@ -207,6 +264,7 @@ impl BorrowOperations for Contract {
     // Withdraw USDM from an existing trove
     #[storage(read, write)]
     fn withdraw_usdm(
+        max_fee_percentage: u64,
         amount: u64,
         upper_hint: Identity,
         lower_hint: Identity,
@@ -215,6 +273,9 @@ impl BorrowOperations for Contract {
         internal_adjust_trove(
             msg_sender()
                 .unwrap(),
+            msg_sender()
+                .unwrap(),
+            max_fee_percentage,
             0,
             0,
             amount,
@@ -235,6 +296,9 @@ impl BorrowOperations for Contract {
         internal_adjust_trove(
             msg_sender()
                 .unwrap(),
+            msg_sender()
+                .unwrap(),
+            0,
             0,
             0,
             msg_amount(),
@@ -244,63 +308,253 @@ impl BorrowOperations for Contract {
             asset_contract,
         );
     }
+    // Change collateral and debt in one call with a single ICR check and reinsertion. The payment is
+    // either collateral to add or USDM to repay, so adding collateral can only go with borrowing and
+    // repaying only with withdrawing collateral
+    #[storage(read, write), payable]
+    fn adjust_trove(
+        max_fee_percentage: u64,
+        coll_withdrawal: u64,
+        usdm_change: u64,
+        is_debt_increase: bool,
+        upper_hint: Identity,
+        lower_hint: Identity,
+        asset_contract: AssetId,
+    ) {
+        let (coll_added, _) = internal_get_adjustment_payment(
+            asset_contract,
+            usdm_change,
+            is_debt_increase,
+        );
+        internal_adjust_trove(
+            msg_sender()
+                .unwrap(),
+            msg_sender()
+                .unwrap(),
+            max_fee_percentage,
+            coll_added,
+            coll_withdrawal,
+            usdm_change,
+            is_debt_increase,
+            upper_hint,
+            lower_hint,
+            asset_contract,
+        );
+    }
     // Close an existing trove
     #[storage(read, write), payable]
     fn close_trove(asset_contract: AssetId) {
+        internal_close_trove(
+            msg_sender()
+                .unwrap(),
+            msg_sender()
+                .unwrap(),
+            asset_contract,
+        );
+    }
+    // Approving an operator with no permissions revokes it
+    #[storage(read, write)]
+    fn set_operator(
+        operator: Identity,
+        asset_contract: AssetId,
+        permissions: OperatorPermissions,
+    ) {
+        require_valid_asset(asset_contract);
+        let owner = msg_sender().unwrap();
+        storage
+            .operators
+            .insert((owner, operator, asset_contract), permissions);
+        log(SetOperatorEvent {
+            owner: owner,
+            operator: operator,
+            asset_id: asset_contract,
+            permissions: permissions,
+        });
+    }
+    #[storage(read)]
+    fn get_operator_permissions(
+        owner: Identity,
+        operator: Identity,
+        asset_contract: AssetId,
+    ) -> OperatorPermissions {
+        return internal_get_operator_permissions(owner, operator, asset_contract);
+    }
+    // `adjust_trove` on the trove of an owner who approved the caller. The payment comes from the
+    // operator and every part of the adjustment needs its permission
+    #[storage(read, write), payable]
+    fn adjust_trove_for(
+        owner: Identity,
+        max_fee_percentage: u64,
+        coll_withdrawal: u64,
+        usdm_change: u64,
+        is_debt_increase: bool,
+        upper_hint: Identity,
+        lower_hint: Identity,
+        asset_contract: AssetId,
+    ) {
+        let (coll_added, usdm_repaid) = internal_get_adjustment_payment(
+            asset_contract,
+            usdm_change,
+            is_debt_increase,
+        );
+        let operator = msg_sender().unwrap();
+        let permissions = internal_get_operator_permissions(owner, operator, asset_contract);
+        require_operator_is_approved(
+            (coll_added == 0 || permissions.add_coll)
+            && (usdm_repaid == 0 || permissions.repay)
+            && (coll_withdrawal == 0 || permissions.withdraw_coll)
+            && (!is_debt_increase || permissions.borrow),
+        );
+        internal_adjust_trove(
+            owner,
+            internal_get_operator_recipient(owner, operator, permissions),
+            max_fee_percentage,
+            coll_added,
+            coll_withdrawal,
+            usdm_change,
+            is_debt_increase,
+            upper_hint,
+            lower_hint,
+            asset_contract,
+        );
+    }
+    // The operator pays the debt and gets back any excess USDM
+    #[storage(read, write), payable]
+    fn close_trove_for(owner: Identity, asset_contract: AssetId) {
+        let operator = msg_sender().unwrap();
+        let permissions = internal_get_operator_permissions(owner, operator, asset_contract);
+        require_operator_is_approved(permissions.close);
+        internal_close_trove(
+            owner,
+            internal_get_operator_recipient(owner, operator, permissions),
+            asset_contract,
+        );
+    }
+    // The trove moves once the new owner accepts, a new proposal replaces the previous one
+    #[storage(read, write)]
+    fn propose_trove_transfer(new_owner: Identity, asset_contract: AssetId) {
+        require_valid_asset(asset_contract);
+        let owner = msg_sender().unwrap();
+        require_trove_is_active(
+            owner,
+            storage
+                .asset_contracts
+                .get(asset_contract)
+                .read()
+                .trove_manager,
+        );
+        storage
+            .pending_trove_transfers
+            .insert(
+                (owner, asset_contract),
+                (new_owner, internal_get_trove_nonce(owner, asset_contract)),
+            );
+    }
+    #[storage(read, write)]
+    fn cancel_trove_transfer(asset_contract: AssetId) {
+        let _ = storage
+            .pending_trove_transfers
+            .remove((msg_sender().unwrap(), asset_contract));
+    }
+    // Debt, collateral, stake and pending rewards move as they are, without a borrowing fee.
+    // A proposal only holds for the trove it was made for, not for a trove reopened after it closed
+    #[storage(read, write)]
+    fn accept_trove_transfer(previous_owner: Identity, asset_contract: AssetId) {
+        let new_owner = msg_sender().unwrap();
+        let pending = internal_get_pending_trove_transfer(previous_owner, asset_contract);
         require(
+            pending
+                .is_some() && pending
+                .unwrap() == new_owner,
+            "Borrow Operations: No pending trove transfer",
+        );
+        let _ = storage
+            .pending_trove_transfers
+            .remove((previous_owner, asset_contract));
+        internal_increment_trove_nonce(new_owner, asset_contract);
+        let trove_manager = abi(
+            TroveManager,
             storage
-                .lock_close_trove
+                .asset_contracts
+                .get(asset_contract)
+                .read()
+                .trove_manager
+                .bits(),
+        );
+        trove_manager.transfer_trove(previous_owner, new_owner);
+        log(TransferTroveEvent {
+            previous_owner: previous_owner,
+            new_owner: new_owner,
+            asset_id: asset_contract,
+        });
+    }
+    #[storage(read)]
+    fn get_pending_trove_transfer(owner: Identity, asset_contract: AssetId) -> Option<Identity> {
+        return internal_get_pending_trove_transfer(owner, asset_contract);
+    }
+    // Mints USDM to the receiver and calls it back, the receiver has to send the amount and the fee
+    // back before returning. The amount is burned and the fee goes to FPT stakers
+    #[storage(read, write)]
+    fn flash_mint(amount: u64, receiver: ContractId) {
+        require(
+            storage
+                .lock_flash_mint
                 .read() == false,
-            "BorrowOperations: Close trove is locked",
+            "BorrowOperations: Flash mint is locked",
+        );
+        storage.lock_flash_mint.write(true);
+        require_is_not_paused();
+        require(
+            amount > 0,
+            "Borrow Operations: Flash mint amount must be greater than 0",
         );
-        storage.lock_close_trove.write(true);
 
-        // Read all storage values at the beginning
-        let asset_contracts_cache = storage.asset_contracts.get(asset_contract).read();
-        let usdm_contract_cache = storage.usdm_contract.read();
-        let active_pool_contract_cache = storage.active_pool_contract.read();
+        let usdm_contract = storage.usdm_contract.read();
+        let fpt_staking_contract = storage.fpt_staking_contract.read();
         let usdm_asset_id = storage.usdm_asset_id.read();
+        let usdm = abi(SRC3, usdm_contract.bits());
+        let fpt_staking = abi(FPTStaking, fpt_staking_contract.bits());
+        let receiver_contract = abi(FlashMintReceiver, receiver.bits());
+        let initiator = msg_sender().unwrap();
+        let fee = fm_compute_fee(amount, FLASH_MINT_FEE);
 
-        let trove_manager = abi(TroveManager, asset_contracts_cache.trove_manager.bits());
-        let active_pool = abi(ActivePool, active_pool_contract_cache.bits());
-        let borrower = msg_sender().unwrap();
-
-        require_trove_is_active(borrower, asset_contracts_cache.trove_manager);
-        trove_manager.apply_pending_rewards(borrower);
-        let coll = trove_manager.get_trove_coll(borrower);
-        let debt = trove_manager.get_trove_debt(borrower);
+        // Only USDM sent to this contract during the callback counts as repaid
+        let balance_before = this_balance(usdm_asset_id);
+        usdm.mint(Identity::ContractId(receiver), Some(SubId::zero()), amount);
+        receiver_contract.on_flash_mint(initiator, amount, fee);
+        let repaid = this_balance(usdm_asset_id) - balance_before;
+        require(
+            repaid >= amount + fee,
+            "Borrow Operations: Flash mint not repaid",
+        );
 
-        if debt > 0 {
-            require_valid_usdm_id(msg_asset_id());
-            require(
-                debt <= msg_amount(),
-                "Borrow Operations: cannot close trove with insufficient usdm balance",
-            );
+        usdm
+            .burn {
+                coins: amount,
+                asset_id: usdm_asset_id.bits(),
+            }(SubId::zero(), amount);
+        if fee > 0 {
+            fpt_staking.increase_f_usdm(fee);
+            transfer(Identity::ContractId(fpt_staking_contract), usdm_asset_id, fee);
         }
-
-        trove_manager.remove_stake(borrower);
-        trove_manager.close_trove(borrower);
-
-        internal_repay_usdm(
-            debt,
-            active_pool_contract_cache,
-            usdm_contract_cache,
-            asset_contract,
-        );
-        active_pool.send_asset(borrower, coll, asset_contract);
-        if (debt < msg_amount()) {
-            require_valid_usdm_id(msg_asset_id());
-            let excess_usdm_returned = msg_amount() - debt;
-            transfer(borrower, usdm_asset_id, excess_usdm_returned);
+        if repaid > amount + fee {
+            transfer(
+                Identity::ContractId(receiver),
+                usdm_asset_id,
+                repaid - amount - fee,
+            );
         }
 
-        log(CloseTroveEvent {
-            user: borrower,
-            asset_id: asset_contract,
-            collateral: coll,
-            debt: debt,
+        log(FlashMintEvent {
+            initiator: initiator,
+            receiver: receiver,
+            amount: amount,
+            fee: fee,
         });
-        storage.lock_close_trove.write(false);
+        storage.lock_flash_mint.write(false);
+    }
+    fn get_flash_mint_fee(amount: u64) -> u64 {
+        return fm_compute_fee(amount, FLASH_MINT_FEE);
     }
     // Claim collateral from liquidations
     #[storage(read)]
@@ -327,6 +581,18 @@ impl BorrowOperations for Contract {
     fn get_is_paused() -> bool {
         return storage.is_paused.read();
     }
+
+    #[storage(read, write)]
+    fn set_asset_pause_status(asset: AssetId, is_paused: bool) {
+        require_is_pauser();
+        require_valid_asset(asset);
+        storage.paused_assets.insert(asset, is_paused);
+    }
+
+    #[storage(read)]
+    fn get_asset_is_paused(asset: AssetId) -> bool {
+        return storage.paused_assets.get(asset).try_read().unwrap_or(false);
+    }
     #[storage(read, write)]
     fn add_asset(
         asset_contract: AssetId,
@@ -346,15 +612,24 @@ impl BorrowOperations for Contract {
 }
 
 // --- Internal Functions ---
-// Note: flat borrowing fee
+// Decays the base rate and charges the borrowing rate on top of it
 fn internal_trigger_borrowing_fee(
     usdm_amount: u64,
+    max_fee_percentage: u64,
+    asset: AssetId,
     usdm_contract: ContractId,
     fpt_staking_contract: ContractId,
+    protocol_manager_contract: ContractId,
 ) -> u64 {
     let usdm = abi(SRC3, usdm_contract.bits());
     let fpt_staking = abi(FPTStaking, fpt_staking_contract.bits());
-    let usdm_fee = fm_compute_borrow_fee(usdm_amount);
+    let protocol_manager = abi(ProtocolManager, protocol_manager_contract.bits());
+    let borrowing_rate = protocol_manager.decay_base_rate_from_borrowing(asset);
+    require(
+        borrowing_rate <= max_fee_percentage,
+        "Borrow Operations: Fee exceeded provided maximum",
+    );
+    let usdm_fee = fm_compute_fee(usdm_amount, borrowing_rate);
 
     //increase fpt staking rewards
     fpt_staking.increase_f_usdm(usdm_fee);
@@ -367,10 +642,72 @@ fn internal_trigger_borrowing_fee(
 
     return usdm_fee
 }
+// Collateral goes to `recipient`, excess USDM back to the caller who paid the debt
+#[storage(read, write)]
+fn internal_close_trove(borrower: Identity, recipient: Identity, asset_contract: AssetId) {
+    require(
+        storage
+            .lock_close_trove
+            .read() == false,
+        "BorrowOperations: Close trove is locked",
+    );
+    storage.lock_close_trove.write(true);
+
+    // Read all storage values at the beginning
+    let asset_contracts_cache = storage.asset_contracts.get(asset_contract).read();
+    let usdm_contract_cache = storage.usdm_contract.read();
+    let active_pool_contract_cache = storage.active_pool_contract.read();
+    let usdm_asset_id = storage.usdm_asset_id.read();
+
+    let trove_manager = abi(TroveManager, asset_contracts_cache.trove_manager.bits());
+    let active_pool = abi(ActivePool, active_pool_contract_cache.bits());
+
+    require_trove_is_active(borrower, asset_contracts_cache.trove_manager);
+    trove_manager.apply_pending_rewards(borrower);
+    let coll = trove_manager.get_trove_coll(borrower);
+    let debt = trove_manager.get_trove_debt(borrower);
+
+    if debt > 0 {
+        require_valid_usdm_id(msg_asset_id());
+        require(
+            debt <= msg_amount(),
+            "Borrow Operations: cannot close trove with insufficient usdm balance",
+        );
+    }
+
+    trove_manager.remove_stake(borrower);
+    trove_manager.close_trove(borrower);
+    let _ = storage
+        .pending_trove_transfers
+        .remove((borrower, asset_contract));
+
+    internal_repay_usdm(
+        debt,
+        active_pool_contract_cache,
+        usdm_contract_cache,
+        asset_contract,
+    );
+    active_pool.send_asset(recipient, coll, asset_contract);
+    if (debt < msg_amount()) {
+        require_valid_usdm_id(msg_asset_id());
+        let excess_usdm_returned = msg_amount() - debt;
+        transfer(msg_sender().unwrap(), usdm_asset_id, excess_usdm_returned);
+    }
+
+    log(CloseTroveEvent {
+        user: borrower,
+        asset_id: asset_contract,
+        collateral: coll,
+        debt: debt,
+    });
+    storage.lock_close_trove.write(false);
+}
 // Note: no frontend fees
 #[storage(read, write)]
 fn internal_adjust_trove(
     borrower: Identity,
+    recipient: Identity,
+    max_fee_percentage: u64,
     asset_coll_added: u64,
     coll_withdrawal: u64,
     usdm_change: u64,
@@ -395,10 +732,16 @@ fn internal_adjust_trove(
     let oracle = abi(Oracle, asset_contracts_cache.oracle.bits());
     let trove_manager = abi(TroveManager, asset_contracts_cache.trove_manager.bits());
     let sorted_troves = abi(SortedTroves, sorted_troves_contract_cache.bits());
+    let protocol_manager_contract_cache = storage.protocol_manager_contract.read();
+    let protocol_manager = abi(ProtocolManager, protocol_manager_contract_cache.bits());
+    let risk_parameters = protocol_manager.get_risk_parameters(asset);
     let price = oracle.get_price();
     let mut vars = LocalVariablesAdjustTrove::new();
     if is_debt_increase {
         require_is_not_paused();
+        require_asset_is_not_paused(asset);
+        require_asset_is_active(protocol_manager.get_asset_status(asset));
+        require_valid_max_fee_percentage(max_fee_percentage, risk_parameters.borrowing_fee_floor);
         require_non_zero_debt_change(usdm_change);
     }
     require_trove_is_active(borrower, asset_contracts_cache.trove_manager);
@@ -412,10 +755,20 @@ fn internal_adjust_trove(
     if is_debt_increase {
         vars.usdm_fee = internal_trigger_borrowing_fee(
             vars.net_debt_change,
+            max_fee_percentage,
+            asset,
             usdm_contract_cache,
             fpt_staking_contract_cache,
+            protocol_manager_contract_cache,
         );
         vars.net_debt_change = vars.net_debt_change + vars.usdm_fee;
+        require_within_debt_ceiling(
+            trove_manager
+                .get_entire_system_debt(),
+            vars.net_debt_change,
+            protocol_manager
+                .get_debt_ceiling(asset),
+        );
     }
     vars.debt = trove_manager.get_trove_debt(borrower);
     vars.coll = trove_manager.get_trove_coll(borrower);
@@ -433,10 +786,32 @@ fn internal_adjust_trove(
         coll_withdrawal <= vars.coll,
         "Cannot withdraw more than the Trove's collateral",
     );
-    require_at_least_mcr(vars.new_icr);
+    require_at_least_mcr(vars.new_icr, risk_parameters.mcr);
+    vars.old_tcr = trove_manager.get_tcr(price);
+    if vars.old_tcr < risk_parameters.ccr {
+        vars.new_tcr = internal_get_new_icr_from_trove_change(
+            trove_manager
+                .get_entire_system_coll(),
+            trove_manager
+                .get_entire_system_debt(),
+            vars.coll_change,
+            vars.is_coll_increase,
+            vars.net_debt_change,
+            is_debt_increase,
+            price,
+        );
+        require_valid_adjustment_in_recovery_mode(
+            coll_withdrawal,
+            is_debt_increase,
+            vars.new_icr,
+            vars.old_tcr,
+            vars.new_tcr,
+            risk_parameters.ccr,
+        );
+    }
 
     if !is_debt_increase && usdm_change > 0 {
-        require_at_least_min_net_debt(vars.debt - vars.net_debt_change);
+        require_at_least_min_net_debt(vars.debt - vars.net_debt_change, risk_parameters.min_net_debt);
     }
 
     let new_position_res = internal_update_trove_from_adjustment(
@@ -459,7 +834,7 @@ fn internal_adjust_trove(
     );
     sorted_troves.re_insert(borrower, new_nicr, upper_hint, lower_hint, asset);
     internal_move_usdm_and_asset_from_adjustment(
-        borrower,
+        recipient,
         vars.coll_change,
         vars.is_coll_increase,
         usdm_change,
@@ -511,6 +886,24 @@ fn require_is_not_paused() {
         "Borrow Operations: Contract is paused",
     );
 }
+#[storage(read)]
+fn require_asset_is_not_paused(asset: AssetId) {
+    require(
+        !storage
+            .paused_assets
+            .get(asset)
+            .try_read()
+            .unwrap_or(false),
+        "Borrow Operations: Asset is paused",
+    );
+}
+// Sunset and delisted assets only allow operations which do not increase trove debt
+fn require_asset_is_active(status: AssetStatus) {
+    require(
+        status == AssetStatus::Active,
+        "Borrow Operations: Asset is not active",
+    );
+}
 fn require_trove_is_not_active(borrower: Identity, trove_manager: ContractId) {
     let trove_manager = abi(TroveManager, trove_manager.bits());
     let status = trove_manager.get_trove_status(borrower);
@@ -533,30 +926,80 @@ fn require_non_zero_adjustment(asset_amount: u64, coll_withdrawl: u64, usdm_chan
         "Borrow Operations: coll withdrawal and debt change must be greater than 0",
     );
 }
-fn require_at_least_min_net_debt(_net_debt: u64) {
+fn require_valid_max_fee_percentage(max_fee_percentage: u64, borrowing_fee_floor: u64) {
+    require(
+        max_fee_percentage >= borrowing_fee_floor && max_fee_percentage <= DECIMAL_PRECISION,
+        "Borrow Operations: Max fee percentage must be between the borrowing fee floor and 100%",
+    );
+}
+fn require_at_least_min_net_debt(_net_debt: u64, min_net_debt: u64) {
     require(
-        _net_debt >= MIN_NET_DEBT,
+        _net_debt >= min_net_debt,
         "Borrow Operations: net debt must be greater than 0",
     );
 }
+// The ceiling can be lowered below the asset's debt, which then only allows repaying
+fn require_within_debt_ceiling(system_debt: u64, debt_increase: u64, debt_ceiling: u64) {
+    require(
+        system_debt <= debt_ceiling && debt_increase <= debt_ceiling - system_debt,
+        "Borrow Operations: Debt ceiling reached",
+    );
+}
 fn require_non_zero_debt_change(debt_change: u64) {
     require(
         debt_change > 0,
         "Borrow Operations: debt change must be greater than 0",
     );
 }
-fn require_at_least_mcr(icr: u64) {
+fn require_at_least_mcr(icr: u64, mcr: u64) {
     require(
-        icr >= MCR,
+        icr >= mcr,
         "Borrow Operations: Minimum collateral ratio not met",
     );
 }
+fn require_at_least_ccr(icr: u64, ccr: u64) {
+    require(
+        icr >= ccr,
+        "Borrow Operations: ICR must be at least CCR in Recovery Mode",
+    );
+}
+// Repaying and adding collateral are always allowed in recovery mode
+fn require_valid_adjustment_in_recovery_mode(
+    coll_withdrawal: u64,
+    is_debt_increase: bool,
+    new_icr: u64,
+    old_tcr: u64,
+    new_tcr: u64,
+    ccr: u64,
+) {
+    require(
+        coll_withdrawal == 0 || new_tcr >= old_tcr,
+        "Borrow Operations: Collateral withdrawal cannot lower TCR in Recovery Mode",
+    );
+    if is_debt_increase {
+        require_at_least_ccr(new_icr, ccr);
+        require(
+            new_tcr > old_tcr,
+            "Borrow Operations: Borrowing must improve TCR in Recovery Mode",
+        );
+    }
+}
 fn require_singular_coll_change(coll_added_amount: u64, coll_withdrawl: u64) {
     require(
         coll_withdrawl == 0 || 0 == coll_added_amount,
         "Borrow Operations: collateral change must be 0 or equal to the amount sent",
     );
 }
+fn require_usdm_repaid_matches_debt_change(
+    usdm_repaid: u64,
+    usdm_change: u64,
+    is_debt_increase: bool,
+) {
+    require(
+        (is_debt_increase && usdm_repaid == 0) || (!is_debt_increase && usdm_repaid == usdm_change),
+        "Borrow Operations: USDM sent must equal the debt repaid",
+    );
+}
 #[storage(read)]
 fn require_valid_asset_id() {
     require(
@@ -568,6 +1011,20 @@ fn require_valid_asset_id() {
     );
 }
 #[storage(read)]
+fn require_valid_asset(asset: AssetId) {
+    require(
+        storage
+            .valid_asset_ids
+            .get(asset)
+            .try_read()
+            .unwrap_or(false),
+        "Borrow Operations: Invalid asset",
+    );
+}
+fn require_operator_is_approved(is_approved: bool) {
+    require(is_approved, "Borrow Operations: Operator is not approved");
+}
+#[storage(read)]
 fn require_valid_usdm_id(recieved_asset: AssetId) {
     require(
         recieved_asset == storage
@@ -576,6 +1033,79 @@ fn require_valid_usdm_id(recieved_asset: AssetId) {
         "Borrow Operations: Invalid USDM asset being transfered",
     );
 }
+// The payment of `adjust_trove` is either collateral to add or USDM to repay, as `(coll_added, usdm_repaid)`
+#[storage(read)]
+fn internal_get_adjustment_payment(
+    asset_contract: AssetId,
+    usdm_change: u64,
+    is_debt_increase: bool,
+) -> (u64, u64) {
+    let mut coll_added = 0;
+    let mut usdm_repaid = 0;
+    if msg_amount() > 0 {
+        if msg_asset_id() == storage.usdm_asset_id.read() {
+            usdm_repaid = msg_amount();
+        } else {
+            require_valid_asset_id();
+            require(
+                msg_asset_id() == asset_contract,
+                "Borrow Operations: Invalid collateral asset being transfered",
+            );
+            coll_added = msg_amount();
+        }
+    }
+    require_usdm_repaid_matches_debt_change(usdm_repaid, usdm_change, is_debt_increase);
+    return (coll_added, usdm_repaid);
+}
+#[storage(read)]
+fn internal_get_operator_permissions(
+    owner: Identity,
+    operator: Identity,
+    asset: AssetId,
+) -> OperatorPermissions {
+    return storage
+        .operators
+        .get((owner, operator, asset))
+        .try_read()
+        .unwrap_or(OperatorPermissions::none());
+}
+#[storage(read)]
+fn internal_get_trove_nonce(owner: Identity, asset_contract: AssetId) -> u64 {
+    return storage.trove_nonces.get((owner, asset_contract)).try_read().unwrap_or(0);
+}
+#[storage(read, write)]
+fn internal_increment_trove_nonce(owner: Identity, asset_contract: AssetId) {
+    storage
+        .trove_nonces
+        .insert(
+            (owner, asset_contract),
+            internal_get_trove_nonce(owner, asset_contract) + 1,
+        );
+}
+// Proposals made for an earlier trove of the owner are ignored
+#[storage(read)]
+fn internal_get_pending_trove_transfer(owner: Identity, asset_contract: AssetId) -> Option<Identity> {
+    match storage.pending_trove_transfers.get((owner, asset_contract)).try_read() {
+        Some((new_owner, nonce)) => {
+            if nonce == internal_get_trove_nonce(owner, asset_contract) {
+                return Some(new_owner);
+            }
+            return None;
+        },
+        None => return None,
+    }
+}
+fn internal_get_operator_recipient(
+    owner: Identity,
+    operator: Identity,
+    permissions: OperatorPermissions,
+) -> Identity {
+    if permissions.withdraw_to_operator {
+        operator
+    } else {
+        owner
+    }
+}
 fn internal_withdraw_usdm(
     recipient: Identity,
     amount: u64,
@@ -705,7 +1235,7 @@ fn internal_repay_usdm(
 }
 #[storage(read)]
 fn internal_move_usdm_and_asset_from_adjustment(
-    borrower: Identity,
+    recipient: Identity,
     coll_change: u64,
     is_coll_increase: bool,
     usdm_change: u64,
@@ -720,13 +1250,13 @@ fn internal_move_usdm_and_asset_from_adjustment(
         if is_coll_increase {
             internal_active_pool_add_coll(coll_change, asset, active_pool_contract);
         } else {
-            active_pool.send_asset(borrower, coll_change, asset);
+            active_pool.send_asset(recipient, coll_change, asset);
         }
     }
     if usdm_change > 0 {
         if is_debt_increase {
             internal_withdraw_usdm(
-                borrower,
+                recipient,
                 usdm_change,
                 net_debt_change,
                 active_pool_contract,
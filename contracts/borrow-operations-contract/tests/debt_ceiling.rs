@@ -0,0 +1,231 @@
+use fuels::{prelude::*, types::Identity};
+use test_utils::{
+    assert_reverts_with,
+    data_structures::{ContractInstance, PRECISION},
+    errors::ProtocolError,
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
+        oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, ProtocolManager},
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        token::token_abi,
+        trove_manager::trove_manager_abi,
+    },
+    setup::common::setup_protocol,
+};
+
+#[tokio::test]
+async fn borrowing_stops_at_the_debt_ceiling_while_repaying_still_works() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, true, false).await;
+    let asset = &contracts.asset_contracts[0];
+    let protocol_manager = &contracts.protocol_manager;
+
+    for asset in &contracts.asset_contracts {
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    // Assets start without a ceiling
+    assert_eq!(
+        protocol_manager_abi::get_debt_ceiling(protocol_manager, asset.asset_id)
+            .await
+            .unwrap()
+            .value,
+        u64::MAX
+    );
+
+    let attacker = wallets.pop().unwrap();
+    let protocol_manager_attacker = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            attacker.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    let result = protocol_manager_abi::set_debt_ceiling(
+        &protocol_manager_attacker,
+        asset.asset_id,
+        2_000 * PRECISION,
+    )
+    .await;
+    assert!(
+        result.is_err(),
+        "Unauthorized user should not be able to set a debt ceiling"
+    );
+    if let Err(error) = result {
+        assert!(
+            error.to_string().contains("NotOwner"),
+            "Unexpected error message: {}",
+            error
+        );
+    }
+    assert_reverts_with!(
+        protocol_manager_abi::set_debt_ceiling(protocol_manager, AssetId::zeroed(), PRECISION)
+            .await,
+        ProtocolError::ProtocolManagerAssetNotRegistered
+    );
+
+    token_abi::mint_to_id(
+        &asset.asset,
+        10_000 * PRECISION,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+    borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &asset.oracle,
+        &asset.mock_pyth_oracle,
+        &asset.mock_redstone_oracle,
+        &asset.asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &asset.trove_manager,
+        &contracts.active_pool,
+        5_000 * PRECISION,
+        1_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+    let borrower = Identity::Address(admin.address().into());
+    let debt = trove_manager_abi::get_trove_debt(&asset.trove_manager, borrower)
+        .await
+        .value;
+
+    protocol_manager_abi::set_debt_ceiling(protocol_manager, asset.asset_id, 2_000 * PRECISION)
+        .await
+        .unwrap();
+    assert_eq!(
+        protocol_manager_abi::get_remaining_debt_capacity(protocol_manager, asset.asset_id)
+            .await
+            .unwrap()
+            .value,
+        2_000 * PRECISION - debt
+    );
+
+    // A new trove whose debt with the fee is above the remaining capacity can't be opened
+    let wallet = wallets.pop().unwrap();
+    token_abi::mint_to_id(
+        &asset.asset,
+        3_000 * PRECISION,
+        Identity::Address(wallet.address().into()),
+    )
+    .await;
+    let borrow_operations_wallet = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+    assert_reverts_with!(
+        borrow_operations_abi::open_trove(
+            &borrow_operations_wallet,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            3_000 * PRECISION,
+            1_000 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await,
+        ProtocolError::BorrowOperationsDebtCeilingReached
+    );
+
+    // The ceiling is per asset
+    borrow_operations_utils::mint_token_and_open_trove(
+        wallet.clone(),
+        &contracts.asset_contracts[1],
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        3_000 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    let withdraw_usdm = |amount: u64| {
+        borrow_operations_abi::withdraw_usdm(
+            &contracts.borrow_operations,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            amount,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+    };
+
+    // 502.5 USDM of debt with the fee fits under the ceiling once, not twice
+    withdraw_usdm(500 * PRECISION).await.unwrap();
+    assert_reverts_with!(
+        withdraw_usdm(500 * PRECISION).await,
+        ProtocolError::BorrowOperationsDebtCeilingReached
+    );
+
+    // Lowering the ceiling below the asset's debt leaves no capacity, but repaying still works
+    protocol_manager_abi::set_debt_ceiling(protocol_manager, asset.asset_id, 1_000 * PRECISION)
+        .await
+        .unwrap();
+    assert_eq!(
+        protocol_manager_abi::get_remaining_debt_capacity(protocol_manager, asset.asset_id)
+            .await
+            .unwrap()
+            .value,
+        0
+    );
+    assert_reverts_with!(
+        withdraw_usdm(PRECISION).await,
+        ProtocolError::BorrowOperationsDebtCeilingReached
+    );
+
+    let debt_before = trove_manager_abi::get_trove_debt(&asset.trove_manager, borrower)
+        .await
+        .value;
+    borrow_operations_abi::repay_usdm(
+        &contracts.borrow_operations,
+        &asset.oracle,
+        &asset.mock_pyth_oracle,
+        &asset.mock_redstone_oracle,
+        &asset.asset,
+        &contracts.usdm,
+        &contracts.sorted_troves,
+        &asset.trove_manager,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        200 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&asset.trove_manager, borrower)
+            .await
+            .value,
+        debt_before - 200 * PRECISION
+    );
+
+    // Raising the ceiling frees up capacity again
+    protocol_manager_abi::set_debt_ceiling(protocol_manager, asset.asset_id, 5_000 * PRECISION)
+        .await
+        .unwrap();
+    withdraw_usdm(500 * PRECISION).await.unwrap();
+}
@@ -1,7 +1,12 @@
 use fuels::{prelude::*, types::Identity};
 
 use test_utils::{
+    assert_emitted,
     data_structures::{ContractInstance, PRECISION},
+    events::{
+        assert_events_in_order, decode_event, AdjustTroveEvent, CloseTroveEvent, OpenTroveEvent,
+        ProtocolEvent,
+    },
     interfaces::{
         borrow_operations::{borrow_operations_abi, BorrowOperations},
         oracle::oracle_abi,
@@ -56,30 +61,14 @@ async fn test_trove_events() {
     .await
     .unwrap();
 
-    let logs = response.decode_logs();
-    let open_trove_event = logs
-        .results
-        .iter()
-        .find(|log| log.as_ref().unwrap().contains("OpenTroveEvent"))
-        .expect("OpenTroveEvent not found")
-        .as_ref()
-        .unwrap();
-
-    assert!(
-        open_trove_event.contains(&admin.address().hash().to_string()),
-        "OpenTroveEvent should contain user address"
-    );
-    assert!(
-        open_trove_event.contains(&deposit_amount.to_string()),
-        "OpenTroveEvent should contain collateral amount"
-    );
-    assert!(
-        open_trove_event.contains(&with_min_borrow_fee(borrow_amount).to_string()),
-        "OpenTroveEvent should contain debt amount"
-    );
-    assert!(
-        open_trove_event.contains(&contracts.asset_contracts[0].asset_id.to_string()),
-        "OpenTroveEvent should contain asset id"
+    assert_emitted!(
+        response,
+        OpenTroveEvent {
+            user: Identity::Address(admin.address().into()),
+            asset_id: contracts.asset_contracts[0].asset_id,
+            collateral: deposit_amount,
+            debt: with_min_borrow_fee(borrow_amount),
+        }
     );
 
     // Test AdjustTroveEvent
@@ -100,46 +89,20 @@ async fn test_trove_events() {
     .await
     .unwrap();
 
-    let logs = response.decode_logs();
-    let adjust_event = logs
-        .results
-        .iter()
-        .find(|log| log.as_ref().unwrap().contains("AdjustTroveEvent"))
-        .expect("AdjustTroveEvent not found")
-        .as_ref()
-        .unwrap();
-    println!("adjust_event: {:?}", adjust_event);
-    assert!(
-        adjust_event.contains(&admin.address().hash().to_string()),
-        "AdjustTroveEvent should contain user address"
-    );
-    assert!(
-        adjust_event.contains(&additional_collateral.to_string()),
-        "AdjustTroveEvent should contain collateral change amount"
-    );
-    assert!(
-        adjust_event.contains("is_collateral_increase: true"),
-        "AdjustTroveEvent should indicate collateral increase"
-    );
-    assert!(
-        adjust_event.contains("is_debt_increase: false"),
-        "AdjustTroveEvent should indicate debt is not increased"
-    );
-    // assetid
-    assert!(
-        adjust_event.contains(&contracts.asset_contracts[0].asset_id.to_string()),
-        "AdjustTroveEvent should contain asset id"
-    );
-    // total debt
-    assert!(
-        adjust_event.contains(&with_min_borrow_fee(borrow_amount).to_string()),
-        "AdjustTroveEvent should contain total debt"
-    );
-    // total coll
-    assert!(
-        adjust_event.contains(&(deposit_amount + additional_collateral).to_string()),
-        "AdjustTroveEvent should contain total collateral"
-    );
+    assert_emitted!(
+        response,
+        AdjustTroveEvent {
+            user: Identity::Address(admin.address().into()),
+            asset_id: contracts.asset_contracts[0].asset_id,
+            collateral_change: additional_collateral,
+            debt_change: 0,
+            is_collateral_increase: true,
+            is_debt_increase: false,
+            total_collateral: deposit_amount + additional_collateral,
+            total_debt: with_min_borrow_fee(borrow_amount),
+        }
+    );
+    assert_events_in_order(&response, &[AdjustTroveEvent::NAME]);
 
     // create one more trove to allow for closing
 
@@ -206,29 +169,19 @@ async fn test_trove_events() {
     .await
     .unwrap();
 
-    let logs = response.decode_logs();
-    let close_event = logs
-        .results
-        .iter()
-        .find(|log| log.as_ref().unwrap().contains("CloseTroveEvent"))
-        .expect("CloseTroveEvent not found")
-        .as_ref()
-        .unwrap();
-
-    assert!(
-        close_event.contains(&admin.address().hash().to_string()),
+    let close_event = decode_event::<CloseTroveEvent, _>(&response);
+    assert_eq!(
+        close_event.user,
+        Identity::Address(admin.address().into()),
         "CloseTroveEvent should contain user address"
     );
-    assert!(
-        close_event.contains(&contracts.asset_contracts[0].asset_id.to_string()),
-        "CloseTroveEvent should contain asset id"
-    );
-    assert!(
-        close_event.contains(&(deposit_amount + additional_collateral).to_string()),
-        "CloseTroveEvent should contain collateral amount"
-    );
-    assert!(
-        close_event.contains(&with_min_borrow_fee(borrow_amount).to_string()),
-        "CloseTroveEvent should contain debt amount"
+    assert_emitted!(
+        response,
+        CloseTroveEvent {
+            asset_id: contracts.asset_contracts[0].asset_id,
+            collateral: deposit_amount + additional_collateral,
+            debt: with_min_borrow_fee(borrow_amount),
+            ..
+        }
     );
 }
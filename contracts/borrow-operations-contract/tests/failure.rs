@@ -1,12 +1,17 @@
 use fuels::{prelude::*, types::Identity};
 
 use test_utils::{
-    data_structures::PRECISION,
+    assert_reverts_with,
+    data_structures::{ContractInstance, MAX_FEE_PERCENTAGE, PRECISION},
+    errors::ProtocolError,
     interfaces::{
         active_pool::active_pool_abi,
-        borrow_operations::borrow_operations_abi,
+        borrow_operations::{borrow_operations_abi, BorrowOperations},
         oracle::oracle_abi,
-        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
         sorted_troves::sorted_troves_abi,
         token::token_abi,
         trove_manager::trove_manager_abi,
@@ -203,10 +208,7 @@ async fn fails_open_trove_under_minimum_collateral_ratio() {
     )
     .await;
 
-    assert!(
-        res.is_err(),
-        "Borrow operation: Should not be able to open trove with MCR < 135%"
-    );
+    assert_reverts_with!(res, ProtocolError::BorrowOperationsMinimumCollateralRatioNotMet);
 
     // open trove with 136% collateral ratio
     let coll_amount = 1360 * PRECISION;
@@ -275,10 +277,7 @@ async fn fails_open_trove_under_min_usdm_required() {
     )
     .await;
 
-    assert!(
-        res.is_err(),
-        "Borrow operation: Should not be able to open trove with debt < 500 USDM"
-    );
+    assert_reverts_with!(res, ProtocolError::BorrowOperationsNetDebtMustBeGreaterThan0);
 }
 
 #[tokio::test]
@@ -556,3 +555,375 @@ async fn fails_incorrect_token_as_collateral_or_repayment() {
         "Borrow operation: Should not be able to repay with incorrect token as repayment"
     );
 }
+
+#[tokio::test]
+async fn fails_adjust_trove_with_mismatched_payment_or_under_mcr() {
+    let (contracts, admin, _) = setup_protocol(2, false, false).await;
+
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        5_000 * PRECISION,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+
+    let coll_amount = 1_200 * PRECISION;
+    let debt_amount = 600 * PRECISION;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        coll_amount,
+        debt_amount,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let asset_payment = Some((contracts.asset_contracts[0].asset_id, 100 * PRECISION));
+    let usdm_payment = Some((contracts.usdm_asset_id, 100 * PRECISION));
+
+    // Adding and withdrawing collateral at once
+    let res = borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        asset_payment,
+        MAX_FEE_PERCENTAGE,
+        100 * PRECISION,
+        0,
+        false,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsCollateralChangeMustBe0OrEqualToTheAmountSent
+    );
+
+    // USDM sent while borrowing
+    let res = borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        usdm_payment,
+        MAX_FEE_PERCENTAGE,
+        0,
+        100 * PRECISION,
+        true,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsUSDMSentMustEqualTheDebtRepaid
+    );
+
+    // Less USDM sent than repaid
+    let res = borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        usdm_payment,
+        MAX_FEE_PERCENTAGE,
+        0,
+        200 * PRECISION,
+        false,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsUSDMSentMustEqualTheDebtRepaid
+    );
+
+    // Collateral sent while repaying
+    let res = borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        asset_payment,
+        MAX_FEE_PERCENTAGE,
+        0,
+        100 * PRECISION,
+        false,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsUSDMSentMustEqualTheDebtRepaid
+    );
+
+    // 1800 collateral against 1608 debt is under MCR even with the added collateral
+    let res = borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        Some((contracts.asset_contracts[0].asset_id, 600 * PRECISION)),
+        MAX_FEE_PERCENTAGE,
+        0,
+        1_000 * PRECISION,
+        true,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(res, ProtocolError::BorrowOperationsMinimumCollateralRatioNotMet);
+
+    let trove_coll = trove_manager_abi::get_trove_coll(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    let trove_debt = trove_manager_abi::get_trove_debt(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_coll, coll_amount, "Trove Collateral is wrong");
+    assert_eq!(
+        trove_debt,
+        with_min_borrow_fee(debt_amount),
+        "Trove Debt is wrong"
+    );
+}
+
+#[tokio::test]
+async fn fails_borrowing_or_withdrawing_coll_that_lowers_tcr_in_recovery_mode() {
+    let (contracts, admin, mut wallets) = setup_protocol(3, false, false).await;
+
+    let wallet1 = wallets.pop().unwrap();
+    let wallet2 = wallets.pop().unwrap();
+
+    for (wallet, balance) in [
+        (&admin, 5_000 * PRECISION),
+        (&wallet1, 5_000 * PRECISION),
+        (&wallet2, 2_000 * PRECISION),
+    ] {
+        token_abi::mint_to_id(
+            &contracts.asset_contracts[0].asset,
+            balance,
+            Identity::Address(wallet.address().into()),
+        )
+        .await;
+    }
+
+    let borrow_operations_wallet1 = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+
+    let borrow_operations_wallet2 = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet2.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_abi::open_trove(
+        &borrow_operations_wallet1,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        4_200 * PRECISION,
+        3_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        1_000 * PRECISION,
+        500 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // At a price of 1 the TCR is 5200 / 3517.5, under CCR
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    assert!(
+        trove_manager_abi::check_recovery_mode(
+            &contracts.asset_contracts[0].trove_manager,
+            PRECISION,
+        )
+        .await
+        .value
+    );
+
+    // Admin's trove stays above CCR but any borrowing without collateral lowers the TCR
+    let res = borrow_operations_abi::withdraw_usdm(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        100 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsBorrowingMustImproveTCRInRecoveryMode
+    );
+
+    // Not enough added collateral to make up for the new debt
+    let res = borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        Some((contracts.asset_contracts[0].asset_id, 100 * PRECISION)),
+        MAX_FEE_PERCENTAGE,
+        0,
+        100 * PRECISION,
+        true,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsBorrowingMustImproveTCRInRecoveryMode
+    );
+
+    let res = borrow_operations_abi::withdraw_coll(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        10 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsCollateralWithdrawalCannotLowerTCRInRecoveryMode
+    );
+
+    // Wallet 1 is at 139%, above MCR but under CCR
+    let res = borrow_operations_abi::withdraw_usdm(
+        &borrow_operations_wallet1,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        10 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsICRMustBeAtLeastCCRInRecoveryMode
+    );
+
+    // New troves have to open at CCR
+    let res = borrow_operations_abi::open_trove(
+        &borrow_operations_wallet2,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        1_400 * PRECISION,
+        1_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert_reverts_with!(
+        res,
+        ProtocolError::BorrowOperationsICRMustBeAtLeastCCRInRecoveryMode
+    );
+
+    let trove_coll = trove_manager_abi::get_trove_coll(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    let trove_debt = trove_manager_abi::get_trove_debt(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_coll, 1_000 * PRECISION, "Trove Collateral is wrong");
+    assert_eq!(
+        trove_debt,
+        with_min_borrow_fee(500 * PRECISION),
+        "Trove Debt is wrong"
+    );
+}
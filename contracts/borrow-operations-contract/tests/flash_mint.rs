@@ -0,0 +1,255 @@
+use fuels::{prelude::*, types::Identity};
+use test_utils::{
+    assert_emitted, assert_reverts_with,
+    data_structures::{ContractInstance, ProtocolContracts, PRECISION},
+    errors::ProtocolError,
+    events::FlashMintEvent,
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
+        flash_mint_receiver::{flash_mint_receiver_abi, MockFlashMintReceiver},
+        fpt_staking::fpt_staking_abi,
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        token::{token_abi, Token},
+        usdm_token::usdm_token_abi,
+    },
+    setup::common::{deploy_flash_mint_receiver, setup_protocol},
+};
+
+// Receiver holding `usdm_balance` USDM to pay flash mint fees with, minted by a borrower
+async fn setup_receiver(
+    contracts: &ProtocolContracts<Wallet>,
+    borrower: &Wallet,
+    usdm_balance: u64,
+) -> MockFlashMintReceiver<Wallet> {
+    let asset = &contracts.asset_contracts[0];
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        borrower.clone(),
+        asset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        5_000 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    let receiver = deploy_flash_mint_receiver(borrower).await;
+    flash_mint_receiver_abi::initialize(
+        &receiver,
+        contracts.borrow_operations.contract.contract_id().into(),
+        contracts.usdm_asset_id,
+    )
+    .await
+    .unwrap();
+    borrower
+        .force_transfer_to_contract(
+            receiver.contract_id(),
+            usdm_balance,
+            contracts.usdm_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    receiver
+}
+
+#[tokio::test]
+async fn flash_mint_is_repaid_with_a_fee_for_fpt_stakers() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, true).await;
+    let provider = admin.provider();
+    let borrower = wallets.pop().unwrap();
+    let borrower_identity = Identity::Address(borrower.address().into());
+    let receiver = setup_receiver(&contracts, &borrower, 100 * PRECISION).await;
+
+    // A single staker gets the whole fee
+    let admin_identity = Identity::Address(admin.address().into());
+    let fpt_token = Token::new(
+        contracts.fpt_token.contract.contract_id().clone(),
+        admin.clone(),
+    );
+    token_abi::mint_to_id(&fpt_token, PRECISION, admin_identity).await;
+    fpt_staking_abi::stake(&contracts.fpt_staking, contracts.fpt_asset_id, PRECISION)
+        .await
+        .unwrap();
+
+    let amount = 10_000 * PRECISION;
+    let fee = borrow_operations_abi::get_flash_mint_fee(&contracts.borrow_operations, amount)
+        .await
+        .value;
+    assert_eq!(fee, 10 * PRECISION);
+
+    let supply = usdm_token_abi::total_supply(&contracts.usdm).await.value;
+    let pending_usdm_gain =
+        fpt_staking_abi::get_pending_usdm_gain(&contracts.fpt_staking, admin_identity)
+            .await
+            .value;
+    let staking_balance = provider
+        .get_contract_asset_balance(
+            contracts.fpt_staking.contract.contract_id().into(),
+            contracts.usdm_asset_id,
+        )
+        .await
+        .unwrap();
+
+    let borrow_operations_borrower = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            borrower.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+    let response = borrow_operations_abi::flash_mint(
+        &borrow_operations_borrower,
+        &receiver,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        amount,
+    )
+    .await
+    .unwrap();
+    assert_emitted!(
+        response,
+        FlashMintEvent {
+            initiator: borrower_identity,
+            receiver: receiver.contract_id().into(),
+            amount,
+            fee,
+        }
+    );
+    assert_eq!(
+        flash_mint_receiver_abi::get_last_flash_mint(&receiver)
+            .await
+            .value,
+        (borrower_identity, amount, fee)
+    );
+
+    // The minted amount is burned again, only the fee changes hands
+    assert_eq!(
+        usdm_token_abi::total_supply(&contracts.usdm).await.value,
+        supply
+    );
+    assert_eq!(
+        provider
+            .get_contract_asset_balance(receiver.contract_id(), contracts.usdm_asset_id)
+            .await
+            .unwrap(),
+        100 * PRECISION - fee
+    );
+    assert_eq!(
+        provider
+            .get_contract_asset_balance(
+                contracts.fpt_staking.contract.contract_id().into(),
+                contracts.usdm_asset_id,
+            )
+            .await
+            .unwrap(),
+        staking_balance + fee
+    );
+    assert_eq!(
+        fpt_staking_abi::get_pending_usdm_gain(&contracts.fpt_staking, admin_identity)
+            .await
+            .value,
+        pending_usdm_gain + fee
+    );
+}
+
+#[tokio::test]
+async fn flash_mint_reverts_unless_repaid_in_full() {
+    let (contracts, _admin, mut wallets) = setup_protocol(4, false, false).await;
+    let borrower = wallets.pop().unwrap();
+    let receiver = setup_receiver(&contracts, &borrower, 100 * PRECISION).await;
+    let supply = usdm_token_abi::total_supply(&contracts.usdm).await.value;
+
+    assert_reverts_with!(
+        borrow_operations_abi::flash_mint(
+            &contracts.borrow_operations,
+            &receiver,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            0,
+        )
+        .await,
+        ProtocolError::BorrowOperationsFlashMintAmountMustBeGreaterThan0
+    );
+
+    // Repaying the amount without the fee is not enough
+    let amount = 10_000 * PRECISION;
+    let fee = borrow_operations_abi::get_flash_mint_fee(&contracts.borrow_operations, amount)
+        .await
+        .value;
+    flash_mint_receiver_abi::set_shortfall(&receiver, fee)
+        .await
+        .unwrap();
+    assert_reverts_with!(
+        borrow_operations_abi::flash_mint(
+            &contracts.borrow_operations,
+            &receiver,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            amount,
+        )
+        .await,
+        ProtocolError::BorrowOperationsFlashMintNotRepaid
+    );
+
+    // Flash mints can't be nested
+    flash_mint_receiver_abi::set_shortfall(&receiver, 0)
+        .await
+        .unwrap();
+    flash_mint_receiver_abi::set_reenter(&receiver, true)
+        .await
+        .unwrap();
+    assert_reverts_with!(
+        borrow_operations_abi::flash_mint(
+            &contracts.borrow_operations,
+            &receiver,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            amount,
+        )
+        .await,
+        ProtocolError::BorrowOperationsFlashMintIsLocked
+    );
+
+    // Nor can the callback be called by anyone but BorrowOperations
+    let result = receiver
+        .methods()
+        .on_flash_mint(Identity::Address(borrower.address().into()), amount, fee)
+        .call()
+        .await;
+    assert_reverts_with!(
+        result,
+        ProtocolError::TestsArtifactsFlashMintReceiverContractCallerIsNotTheBorrowOperationsContract
+    );
+
+    // Paused protocols don't flash mint
+    flash_mint_receiver_abi::set_reenter(&receiver, false)
+        .await
+        .unwrap();
+    borrow_operations_abi::set_pause_status(&contracts.borrow_operations, true)
+        .await
+        .unwrap();
+    assert_reverts_with!(
+        borrow_operations_abi::flash_mint(
+            &contracts.borrow_operations,
+            &receiver,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            amount,
+        )
+        .await,
+        ProtocolError::BorrowOperationsContractIsPaused
+    );
+
+    assert_eq!(
+        usdm_token_abi::total_supply(&contracts.usdm).await.value,
+        supply
+    );
+}
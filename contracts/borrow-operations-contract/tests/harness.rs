@@ -1,4 +1,8 @@
+pub mod debt_ceiling;
 pub mod events;
 pub mod failure;
+pub mod flash_mint;
+pub mod operators;
 pub mod pausing;
 pub mod success;
+pub mod trove_transfer;
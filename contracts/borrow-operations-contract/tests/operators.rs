@@ -0,0 +1,416 @@
+use fuels::{accounts::ViewOnlyAccount, prelude::*, types::Identity};
+use test_utils::{
+    assert_emitted, assert_reverts_with,
+    data_structures::{ContractInstance, PRECISION},
+    errors::ProtocolError,
+    events::SetOperatorEvent,
+    interfaces::{
+        borrow_operations::{
+            borrow_operations_abi, borrow_operations_utils, BorrowOperations, OperatorPermissions,
+        },
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        token::token_abi,
+        trove_manager::{trove_manager_abi, Status},
+    },
+    setup::common::setup_protocol,
+};
+
+#[tokio::test]
+async fn operators_act_on_troves_within_their_permissions() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, true, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    for asset in &contracts.asset_contracts {
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    let owner = wallets.pop().unwrap();
+    let operator = wallets.pop().unwrap();
+    let stranger = wallets.pop().unwrap();
+    let owner_identity = Identity::Address(owner.address().into());
+    let operator_identity = Identity::Address(operator.address().into());
+    let provider = owner.provider();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        owner.clone(),
+        asset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        5_000 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+    // The operator's own trove gives it USDM to repay with
+    borrow_operations_utils::mint_token_and_open_trove(
+        operator.clone(),
+        asset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        5_000 * PRECISION,
+        2_000 * PRECISION,
+    )
+    .await;
+    for asset in &contracts.asset_contracts {
+        token_abi::mint_to_id(&asset.asset, 2_000 * PRECISION, operator_identity).await;
+    }
+
+    let borrow_operations_owner = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            owner.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+    let borrow_operations_operator = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            operator.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+    let borrow_operations_stranger = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            stranger.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+
+    let no_permissions = OperatorPermissions {
+        add_coll: false,
+        repay: false,
+        withdraw_coll: false,
+        borrow: false,
+        close: false,
+        withdraw_to_operator: false,
+    };
+    assert_eq!(
+        borrow_operations_abi::get_operator_permissions(
+            &contracts.borrow_operations,
+            owner_identity,
+            operator_identity,
+            asset.asset_id,
+        )
+        .await
+        .unwrap()
+        .value,
+        no_permissions
+    );
+
+    let zero = Identity::Address(Address::zeroed());
+    let add_coll = |index: usize, amount: u64| {
+        let asset = &contracts.asset_contracts[index];
+        borrow_operations_abi::adjust_trove_for(
+            &borrow_operations_operator,
+            &asset.asset,
+            owner_identity,
+            Some((asset.asset_id, amount)),
+            0,
+            0,
+            0,
+            false,
+            zero,
+            zero,
+        )
+    };
+    let withdraw_coll = |amount: u64| {
+        borrow_operations_abi::adjust_trove_for(
+            &borrow_operations_operator,
+            &asset.asset,
+            owner_identity,
+            None,
+            0,
+            amount,
+            0,
+            false,
+            zero,
+            zero,
+        )
+    };
+    let borrow = |amount: u64| {
+        borrow_operations_abi::adjust_trove_for(
+            &borrow_operations_operator,
+            &asset.asset,
+            owner_identity,
+            None,
+            PRECISION,
+            0,
+            amount,
+            true,
+            zero,
+            zero,
+        )
+    };
+
+    // Nobody is approved by default
+    assert_reverts_with!(
+        add_coll(0, 100 * PRECISION).await,
+        ProtocolError::BorrowOperationsOperatorIsNotApproved
+    );
+    assert_reverts_with!(
+        borrow_operations_abi::set_operator(
+            &borrow_operations_owner,
+            operator_identity,
+            AssetId::zeroed(),
+            no_permissions.clone(),
+        )
+        .await,
+        ProtocolError::BorrowOperationsInvalidAsset
+    );
+
+    // Adding collateral and repaying only
+    let add_coll_and_repay = OperatorPermissions {
+        add_coll: true,
+        repay: true,
+        ..no_permissions.clone()
+    };
+    let response = borrow_operations_abi::set_operator(
+        &borrow_operations_owner,
+        operator_identity,
+        asset.asset_id,
+        add_coll_and_repay.clone(),
+    )
+    .await
+    .unwrap();
+    assert_emitted!(
+        response,
+        SetOperatorEvent {
+            owner: owner_identity,
+            operator: operator_identity,
+            asset_id: asset.asset_id,
+            permissions: add_coll_and_repay,
+        }
+    );
+
+    add_coll(0, 1_000 * PRECISION).await.unwrap();
+    assert_eq!(
+        trove_manager_abi::get_trove_coll(&asset.trove_manager, owner_identity)
+            .await
+            .value,
+        6_000 * PRECISION
+    );
+
+    let debt = trove_manager_abi::get_trove_debt(&asset.trove_manager, owner_identity)
+        .await
+        .value;
+    borrow_operations_abi::adjust_trove_for(
+        &borrow_operations_operator,
+        &asset.asset,
+        owner_identity,
+        Some((contracts.usdm_asset_id, 200 * PRECISION)),
+        0,
+        0,
+        200 * PRECISION,
+        false,
+        zero,
+        zero,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&asset.trove_manager, owner_identity)
+            .await
+            .value,
+        debt - 200 * PRECISION
+    );
+
+    assert_reverts_with!(
+        withdraw_coll(100 * PRECISION).await,
+        ProtocolError::BorrowOperationsOperatorIsNotApproved
+    );
+    assert_reverts_with!(
+        borrow(100 * PRECISION).await,
+        ProtocolError::BorrowOperationsOperatorIsNotApproved
+    );
+    // Approvals are per operator and per asset
+    token_abi::mint_to_id(
+        &asset.asset,
+        100 * PRECISION,
+        Identity::Address(stranger.address().into()),
+    )
+    .await;
+    assert_reverts_with!(
+        borrow_operations_abi::adjust_trove_for(
+            &borrow_operations_stranger,
+            &asset.asset,
+            owner_identity,
+            Some((asset.asset_id, 100 * PRECISION)),
+            0,
+            0,
+            0,
+            false,
+            zero,
+            zero,
+        )
+        .await,
+        ProtocolError::BorrowOperationsOperatorIsNotApproved
+    );
+    assert_reverts_with!(
+        add_coll(1, 100 * PRECISION).await,
+        ProtocolError::BorrowOperationsOperatorIsNotApproved
+    );
+
+    // Withdrawals go to the owner unless the approval sends them to the operator
+    borrow_operations_abi::set_operator(
+        &borrow_operations_owner,
+        operator_identity,
+        asset.asset_id,
+        OperatorPermissions {
+            withdraw_coll: true,
+            borrow: true,
+            ..no_permissions.clone()
+        },
+    )
+    .await
+    .unwrap();
+    let owner_balance = provider
+        .get_asset_balance(owner.address().into(), asset.asset_id)
+        .await
+        .unwrap();
+    let operator_balance = provider
+        .get_asset_balance(operator.address().into(), asset.asset_id)
+        .await
+        .unwrap();
+    withdraw_coll(1_000 * PRECISION).await.unwrap();
+    assert_eq!(
+        provider
+            .get_asset_balance(owner.address().into(), asset.asset_id)
+            .await
+            .unwrap(),
+        owner_balance + 1_000 * PRECISION
+    );
+    assert_eq!(
+        provider
+            .get_asset_balance(operator.address().into(), asset.asset_id)
+            .await
+            .unwrap(),
+        operator_balance
+    );
+
+    borrow_operations_abi::set_operator(
+        &borrow_operations_owner,
+        operator_identity,
+        asset.asset_id,
+        OperatorPermissions {
+            withdraw_coll: true,
+            borrow: true,
+            withdraw_to_operator: true,
+            ..no_permissions.clone()
+        },
+    )
+    .await
+    .unwrap();
+    let operator_usdm = provider
+        .get_asset_balance(operator.address().into(), contracts.usdm_asset_id)
+        .await
+        .unwrap();
+    borrow(100 * PRECISION).await.unwrap();
+    assert_eq!(
+        provider
+            .get_asset_balance(operator.address().into(), contracts.usdm_asset_id)
+            .await
+            .unwrap(),
+        operator_usdm + 100 * PRECISION
+    );
+    assert_reverts_with!(
+        add_coll(0, 100 * PRECISION).await,
+        ProtocolError::BorrowOperationsOperatorIsNotApproved
+    );
+
+    // Closing pays the debt from the operator and sends the collateral to the recipient
+    let debt = trove_manager_abi::get_trove_debt(&asset.trove_manager, owner_identity)
+        .await
+        .value;
+    let coll = trove_manager_abi::get_trove_coll(&asset.trove_manager, owner_identity)
+        .await
+        .value;
+    assert_reverts_with!(
+        borrow_operations_abi::close_trove_for(
+            &borrow_operations_operator,
+            &asset.asset,
+            &contracts.usdm,
+            owner_identity,
+            debt,
+        )
+        .await,
+        ProtocolError::BorrowOperationsOperatorIsNotApproved
+    );
+    borrow_operations_abi::set_operator(
+        &borrow_operations_owner,
+        operator_identity,
+        asset.asset_id,
+        OperatorPermissions {
+            close: true,
+            withdraw_to_operator: true,
+            ..no_permissions.clone()
+        },
+    )
+    .await
+    .unwrap();
+    let operator_balance = provider
+        .get_asset_balance(operator.address().into(), asset.asset_id)
+        .await
+        .unwrap();
+    borrow_operations_abi::close_trove_for(
+        &borrow_operations_operator,
+        &asset.asset,
+        &contracts.usdm,
+        owner_identity,
+        debt,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        trove_manager_abi::get_trove_status(&asset.trove_manager, owner_identity)
+            .await
+            .unwrap()
+            .value,
+        Status::ClosedByOwner
+    );
+    assert_eq!(
+        provider
+            .get_asset_balance(operator.address().into(), asset.asset_id)
+            .await
+            .unwrap(),
+        operator_balance + coll
+    );
+
+    // Approving no permissions revokes the operator
+    let response = borrow_operations_abi::set_operator(
+        &borrow_operations_owner,
+        operator_identity,
+        asset.asset_id,
+        no_permissions.clone(),
+    )
+    .await
+    .unwrap();
+    assert_emitted!(
+        response,
+        SetOperatorEvent {
+            owner: owner_identity,
+            operator: operator_identity,
+            asset_id: asset.asset_id,
+            permissions: no_permissions.clone(),
+        }
+    );
+    assert_eq!(
+        borrow_operations_abi::get_operator_permissions(
+            &contracts.borrow_operations,
+            owner_identity,
+            operator_identity,
+            asset.asset_id,
+        )
+        .await
+        .unwrap()
+        .value,
+        no_permissions
+    );
+}
@@ -1,7 +1,9 @@
 use fuels::{prelude::*, types::Identity};
 
 use test_utils::{
+    assert_reverts_with,
     data_structures::{ContractInstance, PRECISION},
+    errors::ProtocolError,
     interfaces::{
         borrow_operations::{borrow_operations_abi, BorrowOperations},
         oracle::oracle_abi,
@@ -331,3 +333,194 @@ async fn test_paused_operations() {
         "Should be able to withdraw USDM while unpaused"
     );
 }
+
+#[tokio::test]
+async fn test_paused_asset() {
+    let (contracts, admin, mut wallets) = setup_protocol(3, true, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    for asset in &contracts.asset_contracts {
+        token_abi::mint_to_id(
+            &asset.asset,
+            5_000 * PRECISION,
+            Identity::Address(admin.address().into()),
+        )
+        .await;
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    let open_trove = |index: usize| {
+        let asset = &contracts.asset_contracts[index];
+        borrow_operations_abi::open_trove(
+            &contracts.borrow_operations,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            1_200 * PRECISION,
+            600 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+    };
+    let withdraw_usdm = || {
+        borrow_operations_abi::withdraw_usdm(
+            &contracts.borrow_operations,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            100 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+    };
+
+    open_trove(0).await.unwrap();
+
+    // Only the pauser can pause an asset, and only a registered one
+    let unauthorized_wallet = wallets.pop().unwrap();
+    let unauthorized_borrow_operations = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            unauthorized_wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+    assert_reverts_with!(
+        borrow_operations_abi::set_asset_pause_status(
+            &unauthorized_borrow_operations,
+            asset.asset_id,
+            true
+        )
+        .await,
+        ProtocolError::BorrowOperationsCallerIsNotThePauser
+    );
+    assert_reverts_with!(
+        borrow_operations_abi::set_asset_pause_status(
+            &contracts.borrow_operations,
+            AssetId::zeroed(),
+            true
+        )
+        .await,
+        ProtocolError::BorrowOperationsInvalidAsset
+    );
+
+    borrow_operations_abi::set_asset_pause_status(
+        &contracts.borrow_operations,
+        asset.asset_id,
+        true,
+    )
+    .await
+    .unwrap();
+    assert!(
+        borrow_operations_abi::get_asset_is_paused(&contracts.borrow_operations, asset.asset_id)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(
+        !borrow_operations_abi::get_is_paused(&contracts.borrow_operations)
+            .await
+            .unwrap()
+            .value
+    );
+
+    // No new troves or debt against the paused asset
+    let wallet = wallets.pop().unwrap();
+    token_abi::mint_to_id(
+        &asset.asset,
+        5_000 * PRECISION,
+        Identity::Address(wallet.address().into()),
+    )
+    .await;
+    let borrow_operations_wallet = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+    assert_reverts_with!(
+        borrow_operations_abi::open_trove(
+            &borrow_operations_wallet,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            1_200 * PRECISION,
+            600 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await,
+        ProtocolError::BorrowOperationsAssetIsPaused
+    );
+    assert_reverts_with!(
+        withdraw_usdm().await,
+        ProtocolError::BorrowOperationsAssetIsPaused
+    );
+
+    // Debt can still be repaid and collateral added
+    borrow_operations_abi::repay_usdm(
+        &contracts.borrow_operations,
+        &asset.oracle,
+        &asset.mock_pyth_oracle,
+        &asset.mock_redstone_oracle,
+        &asset.asset,
+        &contracts.usdm,
+        &contracts.sorted_troves,
+        &asset.trove_manager,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        50 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+    borrow_operations_abi::add_coll(
+        &contracts.borrow_operations,
+        &asset.oracle,
+        &asset.mock_pyth_oracle,
+        &asset.mock_redstone_oracle,
+        &asset.asset,
+        &contracts.usdm,
+        &contracts.sorted_troves,
+        &asset.trove_manager,
+        &contracts.active_pool,
+        1_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // Other assets are not affected
+    open_trove(1).await.unwrap();
+
+    borrow_operations_abi::set_asset_pause_status(
+        &contracts.borrow_operations,
+        asset.asset_id,
+        false,
+    )
+    .await
+    .unwrap();
+    withdraw_usdm().await.unwrap();
+}
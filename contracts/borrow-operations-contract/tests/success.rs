@@ -1,18 +1,21 @@
 use fuels::{prelude::*, types::Identity};
 
 use test_utils::{
-    data_structures::{ContractInstance, PRECISION},
+    data_structures::{ContractInstance, MAX_FEE_PERCENTAGE, PRECISION},
     interfaces::{
         active_pool::active_pool_abi,
         borrow_operations::{borrow_operations_abi, BorrowOperations},
         oracle::oracle_abi,
-        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
         sorted_troves::sorted_troves_abi,
         token::token_abi,
         trove_manager::trove_manager_abi,
     },
     setup::common::setup_protocol,
-    utils::{calculate_icr, with_min_borrow_fee},
+    utils::{calculate_cr, calculate_icr, with_min_borrow_fee},
 };
 
 #[tokio::test]
@@ -1362,3 +1365,427 @@ async fn proper_creating_trove_with_2nd_asset() {
     .await;
     // print_response(&res);
 }
+
+#[tokio::test]
+async fn proper_adjust_trove_add_coll_and_increase_debt() {
+    let (contracts, admin, _) = setup_protocol(2, false, false).await;
+
+    let balance = 5000 * PRECISION;
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        balance,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+
+    let provider = admin.provider();
+
+    let usdm_asset_id = contracts.usdm_asset_id;
+
+    let deposit_amount = 1200 * PRECISION;
+    let borrow_amount = 600 * PRECISION;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        deposit_amount,
+        borrow_amount,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // Both changes in one call
+    borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        Some((contracts.asset_contracts[0].asset_id, 600 * PRECISION)),
+        MAX_FEE_PERCENTAGE,
+        0,
+        400 * PRECISION,
+        true,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let trove_col = trove_manager_abi::get_trove_coll(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+
+    let trove_debt = trove_manager_abi::get_trove_debt(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+
+    let expected_coll = 1800 * PRECISION;
+    let expected_debt = with_min_borrow_fee(1000 * PRECISION);
+
+    assert_eq!(trove_col, expected_coll, "Trove Collateral is wrong");
+    assert_eq!(trove_debt, expected_debt, "Trove Debt is wrong");
+
+    let nicr = trove_manager_abi::get_nominal_icr(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(
+        nicr,
+        calculate_icr(expected_coll, expected_debt),
+        "NICR is wrong"
+    );
+
+    let admin_balance = provider
+        .get_asset_balance(
+            admin.address().into(),
+            contracts.asset_contracts[0].asset_id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(admin_balance, 3200 * PRECISION, "Balance is wrong");
+
+    let usdm_balance = provider
+        .get_asset_balance(admin.address().into(), usdm_asset_id)
+        .await
+        .unwrap();
+    assert_eq!(usdm_balance, 1000 * PRECISION, "USDM Balance is wrong");
+
+    let active_pool_debt = active_pool_abi::get_usdm_debt(
+        &contracts.active_pool,
+        contracts.asset_contracts[0].asset_id,
+    )
+    .await
+    .value;
+    assert_eq!(active_pool_debt, expected_debt, "Active Pool Debt is wrong");
+
+    let active_pool_col = active_pool_abi::get_asset(
+        &contracts.active_pool,
+        contracts.asset_contracts[0].asset_id,
+    )
+    .await
+    .value;
+    assert_eq!(
+        active_pool_col, expected_coll,
+        "Active Pool Collateral is wrong"
+    );
+}
+
+#[tokio::test]
+async fn proper_adjust_trove_repay_and_withdraw_coll() {
+    let (contracts, admin, _) = setup_protocol(2, false, false).await;
+
+    let balance = 5000 * PRECISION;
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        balance,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+
+    let provider = admin.provider();
+
+    let usdm_asset_id = contracts.usdm_asset_id;
+
+    let deposit_amount = 1200 * PRECISION;
+    let borrow_amount = 800 * PRECISION;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        deposit_amount,
+        borrow_amount,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // Repaying alone or withdrawing alone would each need their own ICR check
+    borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        Some((usdm_asset_id, 200 * PRECISION)),
+        MAX_FEE_PERCENTAGE,
+        300 * PRECISION,
+        200 * PRECISION,
+        false,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let trove_col = trove_manager_abi::get_trove_coll(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+
+    let trove_debt = trove_manager_abi::get_trove_debt(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+
+    let expected_coll = 900 * PRECISION;
+    let expected_debt = with_min_borrow_fee(800 * PRECISION) - 200 * PRECISION;
+
+    assert_eq!(trove_col, expected_coll, "Trove Collateral is wrong");
+    assert_eq!(trove_debt, expected_debt, "Trove Debt is wrong");
+
+    let admin_balance = provider
+        .get_asset_balance(
+            admin.address().into(),
+            contracts.asset_contracts[0].asset_id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(admin_balance, 4100 * PRECISION, "Balance is wrong");
+
+    let usdm_balance = provider
+        .get_asset_balance(admin.address().into(), usdm_asset_id)
+        .await
+        .unwrap();
+    assert_eq!(usdm_balance, 600 * PRECISION, "USDM Balance is wrong");
+
+    let active_pool_debt = active_pool_abi::get_usdm_debt(
+        &contracts.active_pool,
+        contracts.asset_contracts[0].asset_id,
+    )
+    .await
+    .value;
+    assert_eq!(active_pool_debt, expected_debt, "Active Pool Debt is wrong");
+
+    let active_pool_col = active_pool_abi::get_asset(
+        &contracts.active_pool,
+        contracts.asset_contracts[0].asset_id,
+    )
+    .await
+    .value;
+    assert_eq!(
+        active_pool_col, expected_coll,
+        "Active Pool Collateral is wrong"
+    );
+}
+
+#[tokio::test]
+async fn proper_borrowing_that_improves_tcr_in_recovery_mode() {
+    let (contracts, admin, mut wallets) = setup_protocol(3, false, false).await;
+
+    let wallet1 = wallets.pop().unwrap();
+    let wallet2 = wallets.pop().unwrap();
+
+    for (wallet, balance) in [
+        (&admin, 5_000 * PRECISION),
+        (&wallet1, 5_000 * PRECISION),
+        (&wallet2, 2_000 * PRECISION),
+    ] {
+        token_abi::mint_to_id(
+            &contracts.asset_contracts[0].asset,
+            balance,
+            Identity::Address(wallet.address().into()),
+        )
+        .await;
+    }
+
+    let borrow_operations_wallet1 = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+
+    let borrow_operations_wallet2 = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet2.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_abi::open_trove(
+        &borrow_operations_wallet1,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        4_200 * PRECISION,
+        3_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        1_000 * PRECISION,
+        500 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // At a price of 1 the TCR is 5200 / 3517.5, under CCR
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    assert!(
+        trove_manager_abi::check_recovery_mode(
+            &contracts.asset_contracts[0].trove_manager,
+            PRECISION,
+        )
+        .await
+        .value
+    );
+
+    // A new trove at 151% is above CCR, the TCR stays under it
+    borrow_operations_abi::open_trove(
+        &borrow_operations_wallet2,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        1_520 * PRECISION,
+        1_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        trove_manager_abi::check_recovery_mode(
+            &contracts.asset_contracts[0].trove_manager,
+            PRECISION,
+        )
+        .await
+        .value
+    );
+
+    // Enough collateral comes with the new debt to raise the TCR above CCR
+    borrow_operations_abi::adjust_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].asset,
+        Some((contracts.asset_contracts[0].asset_id, 500 * PRECISION)),
+        MAX_FEE_PERCENTAGE,
+        0,
+        100 * PRECISION,
+        true,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let trove_coll = trove_manager_abi::get_trove_coll(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    let trove_debt = trove_manager_abi::get_trove_debt(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_coll, 1_500 * PRECISION, "Trove Collateral is wrong");
+    assert_eq!(
+        trove_debt,
+        with_min_borrow_fee(600 * PRECISION),
+        "Trove Debt is wrong"
+    );
+
+    let tcr = trove_manager_abi::get_tcr(&contracts.asset_contracts[0].trove_manager, PRECISION)
+        .await
+        .value;
+    assert_eq!(
+        tcr,
+        calculate_cr(
+            PRECISION,
+            7_220 * PRECISION,
+            with_min_borrow_fee(4_600 * PRECISION)
+        )
+    );
+    assert!(
+        !trove_manager_abi::check_recovery_mode(
+            &contracts.asset_contracts[0].trove_manager,
+            PRECISION,
+        )
+        .await
+        .value
+    );
+}
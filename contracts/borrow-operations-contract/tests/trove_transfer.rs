@@ -0,0 +1,458 @@
+use fuels::{prelude::*, types::Identity};
+use test_utils::{
+    assert_emitted, assert_reverts_with,
+    data_structures::{ContractInstance, PRECISION},
+    errors::ProtocolError,
+    events::TransferTroveEvent,
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        sorted_troves::sorted_troves_abi,
+        trove_manager::{trove_manager_abi, Status},
+    },
+    setup::common::setup_protocol,
+};
+
+#[tokio::test]
+async fn trove_moves_to_the_new_owner_once_accepted() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+
+    let owner = wallets.pop().unwrap();
+    let new_owner = wallets.pop().unwrap();
+    let holder = wallets.pop().unwrap();
+    let owner_identity = Identity::Address(owner.address().into());
+    let new_owner_identity = Identity::Address(new_owner.address().into());
+    let holder_identity = Identity::Address(holder.address().into());
+
+    // The owner's trove sits between the two other troves
+    for (wallet, coll) in [(&holder, 8_000), (&owner, 5_000), (&wallets[0], 3_000)] {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            asset,
+            &contracts.borrow_operations,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            coll * PRECISION,
+            1_000 * PRECISION,
+        )
+        .await;
+    }
+
+    let borrow_operations_for = |wallet: &Wallet| {
+        ContractInstance::new(
+            BorrowOperations::new(
+                contracts.borrow_operations.contract.contract_id().clone(),
+                wallet.clone(),
+            ),
+            contracts.borrow_operations.implementation_id,
+        )
+    };
+    let borrow_operations_owner = borrow_operations_for(&owner);
+    let borrow_operations_new_owner = borrow_operations_for(&new_owner);
+    let borrow_operations_holder = borrow_operations_for(&holder);
+
+    // Only active troves can be transferred, and only to the identity they were proposed to
+    assert_reverts_with!(
+        borrow_operations_abi::propose_trove_transfer(
+            &borrow_operations_new_owner,
+            &asset.trove_manager,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await,
+        ProtocolError::BorrowOperationsUserDoesNotHaveAnActiveTrove
+    );
+    assert_reverts_with!(
+        borrow_operations_abi::accept_trove_transfer(
+            &borrow_operations_new_owner,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await,
+        ProtocolError::BorrowOperationsNoPendingTroveTransfer
+    );
+
+    // Identities with a trove of the asset can't take another one
+    borrow_operations_abi::propose_trove_transfer(
+        &borrow_operations_owner,
+        &asset.trove_manager,
+        holder_identity,
+        asset.asset_id,
+    )
+    .await
+    .unwrap();
+    assert_reverts_with!(
+        borrow_operations_abi::accept_trove_transfer(
+            &borrow_operations_holder,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await,
+        ProtocolError::TroveManagerRecipientAlreadyHasAnActiveTrove
+    );
+
+    // A new proposal replaces the previous one, and proposals can be cancelled
+    borrow_operations_abi::propose_trove_transfer(
+        &borrow_operations_owner,
+        &asset.trove_manager,
+        new_owner_identity,
+        asset.asset_id,
+    )
+    .await
+    .unwrap();
+    assert_reverts_with!(
+        borrow_operations_abi::accept_trove_transfer(
+            &borrow_operations_holder,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await,
+        ProtocolError::BorrowOperationsNoPendingTroveTransfer
+    );
+    borrow_operations_abi::cancel_trove_transfer(&borrow_operations_owner, asset.asset_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        borrow_operations_abi::get_pending_trove_transfer(
+            &contracts.borrow_operations,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await
+        .unwrap()
+        .value,
+        None
+    );
+    assert_reverts_with!(
+        borrow_operations_abi::accept_trove_transfer(
+            &borrow_operations_new_owner,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await,
+        ProtocolError::BorrowOperationsNoPendingTroveTransfer
+    );
+
+    borrow_operations_abi::propose_trove_transfer(
+        &borrow_operations_owner,
+        &asset.trove_manager,
+        new_owner_identity,
+        asset.asset_id,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        borrow_operations_abi::get_pending_trove_transfer(
+            &contracts.borrow_operations,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await
+        .unwrap()
+        .value,
+        Some(new_owner_identity)
+    );
+
+    let coll = trove_manager_abi::get_trove_coll(&asset.trove_manager, owner_identity)
+        .await
+        .value;
+    let debt = trove_manager_abi::get_trove_debt(&asset.trove_manager, owner_identity)
+        .await
+        .value;
+    let stake = trove_manager_abi::get_trove_stake(&asset.trove_manager, owner_identity)
+        .await
+        .value;
+    let reward_snapshot =
+        trove_manager_abi::get_trove_rewards_snapshot(&asset.trove_manager, owner_identity)
+            .await
+            .value;
+    let prev =
+        sorted_troves_abi::get_prev(&contracts.sorted_troves, owner_identity, asset.asset_id)
+            .await
+            .value;
+    let next =
+        sorted_troves_abi::get_next(&contracts.sorted_troves, owner_identity, asset.asset_id)
+            .await
+            .value;
+    let owners_count = trove_manager_abi::get_trove_owners_count(&asset.trove_manager)
+        .await
+        .value;
+
+    let response = borrow_operations_abi::accept_trove_transfer(
+        &borrow_operations_new_owner,
+        owner_identity,
+        asset.asset_id,
+    )
+    .await
+    .unwrap();
+    assert_emitted!(
+        response,
+        TransferTroveEvent {
+            previous_owner: owner_identity,
+            new_owner: new_owner_identity,
+            asset_id: asset.asset_id,
+        }
+    );
+
+    // Everything moves as it was, the previous owner is left without a trove
+    assert_eq!(
+        trove_manager_abi::get_trove_status(&asset.trove_manager, owner_identity)
+            .await
+            .unwrap()
+            .value,
+        Status::NonExistent
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_status(&asset.trove_manager, new_owner_identity)
+            .await
+            .unwrap()
+            .value,
+        Status::Active
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_coll(&asset.trove_manager, new_owner_identity)
+            .await
+            .value,
+        coll
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&asset.trove_manager, new_owner_identity)
+            .await
+            .value,
+        debt
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_stake(&asset.trove_manager, new_owner_identity)
+            .await
+            .value,
+        stake
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_rewards_snapshot(&asset.trove_manager, new_owner_identity)
+            .await
+            .value,
+        reward_snapshot
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_owners_count(&asset.trove_manager)
+            .await
+            .value,
+        owners_count
+    );
+    let mut owners = Vec::new();
+    for index in 0..owners_count {
+        owners.push(
+            trove_manager_abi::get_trove_owner_by_index(&asset.trove_manager, index)
+                .await
+                .value,
+        );
+    }
+    assert!(owners.contains(&new_owner_identity));
+    assert!(!owners.contains(&owner_identity));
+
+    assert!(
+        !sorted_troves_abi::contains(&contracts.sorted_troves, owner_identity, asset.asset_id)
+            .await
+            .value
+    );
+    assert_eq!(
+        sorted_troves_abi::get_prev(&contracts.sorted_troves, new_owner_identity, asset.asset_id)
+            .await
+            .value,
+        prev
+    );
+    assert_eq!(
+        sorted_troves_abi::get_next(&contracts.sorted_troves, new_owner_identity, asset.asset_id)
+            .await
+            .value,
+        next
+    );
+    assert_eq!(
+        sorted_troves_abi::get_next(&contracts.sorted_troves, prev, asset.asset_id)
+            .await
+            .value,
+        new_owner_identity
+    );
+    assert_eq!(
+        sorted_troves_abi::get_prev(&contracts.sorted_troves, next, asset.asset_id)
+            .await
+            .value,
+        new_owner_identity
+    );
+
+    // The proposal is used up, the new owner manages the trove and the previous owner can open
+    // another one
+    assert_reverts_with!(
+        borrow_operations_abi::accept_trove_transfer(
+            &borrow_operations_new_owner,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await,
+        ProtocolError::BorrowOperationsNoPendingTroveTransfer
+    );
+    borrow_operations_abi::withdraw_coll(
+        &borrow_operations_new_owner,
+        &asset.oracle,
+        &asset.mock_pyth_oracle,
+        &asset.mock_redstone_oracle,
+        &asset.asset,
+        &contracts.sorted_troves,
+        &asset.trove_manager,
+        &contracts.active_pool,
+        500 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        trove_manager_abi::get_trove_coll(&asset.trove_manager, new_owner_identity)
+            .await
+            .value,
+        coll - 500 * PRECISION
+    );
+    borrow_operations_utils::mint_token_and_open_trove(
+        owner.clone(),
+        asset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        4_000 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+    assert_eq!(
+        sorted_troves_abi::get_size(&contracts.sorted_troves, asset.asset_id)
+            .await
+            .value,
+        4
+    );
+}
+
+#[tokio::test]
+async fn proposal_lapses_once_the_trove_closes() {
+    let (contracts, _admin, mut wallets) = setup_protocol(4, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+
+    let owner = wallets.pop().unwrap();
+    let new_owner = wallets.pop().unwrap();
+    let holder = wallets.pop().unwrap();
+    let owner_identity = Identity::Address(owner.address().into());
+    let new_owner_identity = Identity::Address(new_owner.address().into());
+
+    let open_trove = |wallet: &Wallet, coll: u64| {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            asset,
+            &contracts.borrow_operations,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            coll * PRECISION,
+            1_000 * PRECISION,
+        )
+    };
+    open_trove(&holder, 8_000).await;
+    open_trove(&owner, 5_000).await;
+
+    let borrow_operations_for = |wallet: &Wallet| {
+        ContractInstance::new(
+            BorrowOperations::new(
+                contracts.borrow_operations.contract.contract_id().clone(),
+                wallet.clone(),
+            ),
+            contracts.borrow_operations.implementation_id,
+        )
+    };
+    let borrow_operations_owner = borrow_operations_for(&owner);
+    let borrow_operations_new_owner = borrow_operations_for(&new_owner);
+
+    borrow_operations_abi::propose_trove_transfer(
+        &borrow_operations_owner,
+        &asset.trove_manager,
+        new_owner_identity,
+        asset.asset_id,
+    )
+    .await
+    .unwrap();
+
+    // The owner needs the holder's USDM to cover the borrowing fee
+    holder
+        .transfer(
+            owner.address(),
+            1_000 * PRECISION,
+            contracts.usdm_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+    let debt = trove_manager_abi::get_trove_debt(&asset.trove_manager, owner_identity)
+        .await
+        .value;
+    borrow_operations_abi::close_trove(
+        &borrow_operations_owner,
+        &asset.oracle,
+        &asset.mock_pyth_oracle,
+        &asset.mock_redstone_oracle,
+        &asset.asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &asset.trove_manager,
+        &contracts.active_pool,
+        debt,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        borrow_operations_abi::get_pending_trove_transfer(
+            &contracts.borrow_operations,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await
+        .unwrap()
+        .value,
+        None
+    );
+
+    // The reopened trove is not the one the proposal was made for
+    open_trove(&owner, 5_000).await;
+    assert_reverts_with!(
+        borrow_operations_abi::accept_trove_transfer(
+            &borrow_operations_new_owner,
+            owner_identity,
+            asset.asset_id,
+        )
+        .await,
+        ProtocolError::BorrowOperationsNoPendingTroveTransfer
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_status(&asset.trove_manager, owner_identity)
+            .await
+            .unwrap()
+            .value,
+        Status::Active
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_status(&asset.trove_manager, new_owner_identity)
+            .await
+            .unwrap()
+            .value,
+        Status::NonExistent
+    );
+}
@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 use fuels::{prelude::*, types::Identity};
 
 use test_utils::{
+    assert_reverts_with,
+    clock::SimClock,
     data_structures::{ContractInstance, PRECISION},
+    errors::ProtocolError,
     interfaces::{
         borrow_operations::{borrow_operations_abi, BorrowOperations},
         community_issuance::{community_issuance_abi, CommunityIssuance},
@@ -663,17 +668,7 @@ async fn test_only_owner_can_start_rewards_increase_transition() {
     .await;
 
     // Assert that the unauthorized call fails
-    assert!(
-        result.is_err(),
-        "Unauthorized user should not be able to start rewards increase transition"
-    );
-    if let Err(error) = result {
-        assert!(
-            error.to_string().contains("NotOwner"),
-            "Unexpected error message: {}",
-            error
-        );
-    }
+    assert_reverts_with!(result, ProtocolError::NotOwner);
 
     // Now try with the admin (owner)
     let result = community_issuance_abi::start_rewards_increase_transition(
@@ -688,3 +683,30 @@ async fn test_only_owner_can_start_rewards_increase_transition() {
         "Authorized user should be able to start rewards increase transition"
     );
 }
+
+#[tokio::test]
+async fn test_sim_clock_keeps_debug_clocks_in_sync() {
+    let (contracts, _admin, _wallets) = setup_protocol(4, false, false).await;
+
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    let mut clock = SimClock::start(&contracts, PYTH_TIMESTAMP).await;
+    clock.assert_in_sync().await;
+
+    // Well past the oracle timeout, the republished price must still be considered fresh
+    clock.advance(Duration::from_secs(60 * 60 * 24)).await;
+    clock.assert_in_sync().await;
+    assert_eq!(clock.now(), PYTH_TIMESTAMP + 60 * 60 * 24);
+
+    let price = oracle_abi::get_price(&contracts.asset_contracts[0].oracle).await;
+    assert_eq!(price, 1 * PRECISION);
+
+    let issuance_time = community_issuance_abi::get_current_time(&contracts.community_issuance)
+        .await
+        .value;
+    assert_eq!(issuance_time, clock.now());
+}
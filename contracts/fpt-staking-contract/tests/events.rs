@@ -1,7 +1,9 @@
 use fuels::{prelude::*, types::Identity};
 
 use test_utils::{
+    assert_emitted,
     data_structures::PRECISION,
+    events::{StakeEvent, UnstakeEvent},
     interfaces::{
         fpt_staking::fpt_staking_abi,
         token::{token_abi, Token},
@@ -35,22 +37,13 @@ async fn test_staking_events() {
             .await
             .unwrap();
 
-    let logs = response.decode_logs();
-    let stake_event = logs
-        .results
-        .iter()
-        .find(|log| log.as_ref().unwrap().contains("StakeEvent"))
-        .expect("StakeEvent not found")
-        .as_ref()
-        .unwrap();
-
-    assert!(
-        stake_event.contains(&admin.address().hash().to_string()),
-        "StakeEvent should contain user address"
-    );
-    assert!(
-        stake_event.contains(&stake_amount.to_string()),
-        "StakeEvent should contain stake amount"
+    let user = Identity::Address(admin.address().into());
+    assert_emitted!(
+        response,
+        StakeEvent {
+            user,
+            amount: stake_amount,
+        }
     );
 
     // Test UnstakeEvent
@@ -65,21 +58,11 @@ async fn test_staking_events() {
     .await
     .unwrap();
 
-    let logs = response.decode_logs();
-    let unstake_event = logs
-        .results
-        .iter()
-        .find(|log| log.as_ref().unwrap().contains("UnstakeEvent"))
-        .expect("UnstakeEvent not found")
-        .as_ref()
-        .unwrap();
-
-    assert!(
-        unstake_event.contains(&admin.address().hash().to_string()),
-        "UnstakeEvent should contain user address"
-    );
-    assert!(
-        unstake_event.contains(&unstake_amount.to_string()),
-        "UnstakeEvent should contain unstake amount"
+    assert_emitted!(
+        response,
+        UnstakeEvent {
+            user,
+            amount: unstake_amount,
+        }
     );
 }
@@ -1,5 +1,7 @@
 library;
 
+use libraries::protocol_manager_interface::data_structures::RiskParameters;
+
 pub struct RedemptionTotals {
     pub remaining_usdm: u64,
     pub total_usdm_to_redeem: u64,
@@ -32,6 +34,8 @@ pub struct AssetInfo {
     pub redemption_totals: Vec<RedemptionTotals>,
     pub current_borrowers: Vec<Identity>,
     pub current_crs: Vec<u64>,
+    pub risk_parameters: Vec<RiskParameters>,
+    pub is_sunset: Vec<bool>,
 }
 
 pub struct AssetContracts {
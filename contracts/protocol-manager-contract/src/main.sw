@@ -6,6 +6,9 @@ contract;
 // - Initializing the protocol by registering asset contracts and setting up necessary connections
 // - Administering the ownership and access control mechanisms
 // - Facilitating the redemption process for users
+// - Keeping the base rate that borrowing and redemption fees are charged on
+// - Storing the risk parameters and debt ceiling of every asset
+// - Sunsetting and delisting assets
 // - Interfacing with the Stability Pool for FPT issuance
 mod data_structures;
 use ::data_structures::{AssetContracts, AssetInfo, RedemptionTotals};
@@ -19,14 +22,16 @@ use libraries::default_pool_interface::DefaultPool;
 use libraries::coll_surplus_pool_interface::CollSurplusPool;
 use libraries::oracle_interface::Oracle;
 use libraries::protocol_manager_interface::ProtocolManager;
+use libraries::protocol_manager_interface::data_structures::{AssetStatus, RiskParameters};
 use libraries::usdm_token_interface::USDMToken;
 use libraries::fpt_staking_interface::FPTStaking;
 use libraries::fluid_math::*;
 use sway_libs::ownership::*;
-use standards::{src3::SRC3, src5::*,};
+use standards::{src20::SRC20, src3::SRC3, src5::*,};
 use std::{
     asset::transfer,
     auth::msg_sender,
+    block::timestamp,
     call_frames::{
         msg_asset_id,
     },
@@ -50,9 +55,17 @@ storage {
     active_pool_contract: ContractId = ContractId::zero(),
     sorted_troves_contract: ContractId = ContractId::zero(),
     asset_contracts: StorageMap<AssetId, AssetContracts> = StorageMap::<AssetId, AssetContracts> {},
+    risk_parameters: StorageMap<AssetId, RiskParameters> = StorageMap::<AssetId, RiskParameters> {},
+    debt_ceilings: StorageMap<AssetId, u64> = StorageMap::<AssetId, u64> {},
+    asset_statuses: StorageMap<AssetId, AssetStatus> = StorageMap::<AssetId, AssetStatus> {},
+    // Assets redeemed against, delisted assets are removed
     assets: StorageVec<AssetId> = StorageVec {},
     is_initialized: bool = false,
     lock_redeem_collateral: bool = false,
+    base_rate: u64 = 0,
+    last_fee_operation_time: u64 = 0,
+    debug: bool = false,
+    debug_timestamp: u64 = 0,
 }
 impl ProtocolManager for Contract {
     #[storage(read, write)]
@@ -66,6 +79,7 @@ impl ProtocolManager for Contract {
         active_pool: ContractId,
         sorted_troves: ContractId,
         initial_owner: Identity,
+        debugging: bool,
     ) {
         require(
             msg_sender()
@@ -87,6 +101,10 @@ impl ProtocolManager for Contract {
         storage.default_pool_contract.write(default_pool);
         storage.active_pool_contract.write(active_pool);
         storage.sorted_troves_contract.write(sorted_troves);
+        storage.debug.write(debugging);
+        storage
+            .last_fee_operation_time
+            .write(internal_get_current_time());
         storage.is_initialized.write(true);
     }
     #[storage(read, write)]
@@ -94,9 +112,11 @@ impl ProtocolManager for Contract {
         asset_address: AssetId,
         trove_manager: ContractId,
         oracle: ContractId,
+        risk_parameters: RiskParameters,
     ) {
         only_owner();
         require_asset_not_registered(asset_address);
+        require_valid_risk_parameters(risk_parameters);
         let stability_pool = abi(StabilityPool, storage.stability_pool_contract.read().bits());
         let borrow_operations = abi(BorrowOperations, storage.borrow_operations_contract.read().bits());
         let usdm_token = abi(USDMToken, storage.usdm_token_contract.read().bits());
@@ -115,6 +135,7 @@ impl ProtocolManager for Contract {
                     asset_address,
                 },
             );
+        storage.risk_parameters.insert(asset_address, risk_parameters);
         storage.assets.push(asset_address);
         borrow_operations.add_asset(asset_address, trove_manager, oracle);
         coll_surplus_pool.add_asset(asset_address, Identity::ContractId(trove_manager));
@@ -126,6 +147,91 @@ impl ProtocolManager for Contract {
         usdm_token.add_trove_manager(trove_manager);
     }
     #[storage(read, write)]
+    fn set_risk_parameters(asset: AssetId, risk_parameters: RiskParameters) {
+        only_owner();
+        require_asset_registered(asset);
+        require_valid_risk_parameters(risk_parameters);
+        storage.risk_parameters.insert(asset, risk_parameters);
+    }
+    #[storage(read)]
+    fn get_risk_parameters(asset: AssetId) -> RiskParameters {
+        require_asset_registered(asset);
+        internal_get_risk_parameters(asset)
+    }
+    // Caps the USDM minted against the asset, troves already above it can still repay
+    #[storage(read, write)]
+    fn set_debt_ceiling(asset: AssetId, debt_ceiling: u64) {
+        only_owner();
+        require_asset_registered(asset);
+        storage.debt_ceilings.insert(asset, debt_ceiling);
+    }
+    #[storage(read)]
+    fn get_debt_ceiling(asset: AssetId) -> u64 {
+        require_asset_registered(asset);
+        internal_get_debt_ceiling(asset)
+    }
+    // Debt that can still be opened or borrowed against the asset, active and default pool debt count
+    #[storage(read)]
+    fn get_remaining_debt_capacity(asset: AssetId) -> u64 {
+        require_asset_registered(asset);
+        let trove_manager = abi(
+            TroveManager,
+            storage
+                .asset_contracts
+                .get(asset)
+                .read()
+                .trove_manager
+                .bits(),
+        );
+        let debt_ceiling = internal_get_debt_ceiling(asset);
+        let system_debt = trove_manager.get_entire_system_debt();
+        if system_debt >= debt_ceiling {
+            return 0;
+        }
+        debt_ceiling - system_debt
+    }
+    // Stops new troves and new debt for the asset for good, its troves are redeemed first
+    #[storage(read, write)]
+    fn sunset_asset(asset: AssetId) {
+        only_owner();
+        require_asset_registered(asset);
+        require(
+            internal_get_asset_status(asset) == AssetStatus::Active,
+            "ProtocolManager: Asset is not active",
+        );
+        storage.asset_statuses.insert(asset, AssetStatus::Sunset);
+    }
+    // Removes a sunset asset without troves from redemptions, the stability pool and sorted troves.
+    // Collateral surplus and stability pool gains of the asset can still be claimed.
+    #[storage(read, write)]
+    fn delist_asset(asset: AssetId) {
+        only_owner();
+        require_asset_registered(asset);
+        require(
+            internal_get_asset_status(asset) == AssetStatus::Sunset,
+            "ProtocolManager: Asset is not sunset",
+        );
+        let stability_pool = abi(StabilityPool, storage.stability_pool_contract.read().bits());
+        let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
+        let length = storage.assets.len();
+        let mut i = 0;
+        while (i < length) {
+            if (storage.assets.get(i).unwrap().read() == asset) {
+                let _ = storage.assets.remove(i);
+                break;
+            }
+            i += 1;
+        }
+        storage.asset_statuses.insert(asset, AssetStatus::Delisted);
+        sorted_troves.remove_asset(asset);
+        stability_pool.remove_asset(asset);
+    }
+    #[storage(read)]
+    fn get_asset_status(asset: AssetId) -> AssetStatus {
+        require_asset_registered(asset);
+        internal_get_asset_status(asset)
+    }
+    #[storage(read, write)]
     fn renounce_admin() {
         only_owner();
         renounce_ownership();
@@ -136,176 +242,484 @@ impl ProtocolManager for Contract {
         partial_redemption_hint: u64,
         upper_partial_hint: Identity,
         lower_partial_hint: Identity,
+        max_fee_percentage: u64,
     ) {
+        internal_redeem_collateral(
+            internal_get_all_assets(),
+            max_iterations,
+            partial_redemption_hint,
+            upper_partial_hint,
+            lower_partial_hint,
+            max_fee_percentage,
+        );
+    }
+    // Same as `redeem_collateral`, skipping the troves of every asset not in `assets`
+    #[storage(read, write), payable]
+    fn redeem_collateral_from_assets(
+        assets: Vec<AssetId>,
+        max_iterations: u64,
+        partial_redemption_hint: u64,
+        upper_partial_hint: Identity,
+        lower_partial_hint: Identity,
+        max_fee_percentage: u64,
+    ) {
+        internal_redeem_collateral(
+            internal_get_redeemable_assets(assets),
+            max_iterations,
+            partial_redemption_hint,
+            upper_partial_hint,
+            lower_partial_hint,
+            max_fee_percentage,
+        );
+    }
+    #[storage(read, write)]
+    fn decay_base_rate_from_borrowing(asset: AssetId) -> u64 {
+        require_caller_is_borrow_operations();
+        let decayed_base_rate = internal_calc_decayed_base_rate();
+        storage.base_rate.write(decayed_base_rate);
+        internal_update_last_fee_operation_time();
+        fm_compute_borrowing_rate(
+            decayed_base_rate,
+            internal_get_risk_parameters(asset)
+                .borrowing_fee_floor,
+        )
+    }
+    #[storage(read)]
+    fn get_base_rate() -> u64 {
+        storage.base_rate.read()
+    }
+    #[storage(read)]
+    fn get_decayed_base_rate() -> u64 {
+        internal_calc_decayed_base_rate()
+    }
+    #[storage(read)]
+    fn get_borrowing_rate(asset: AssetId) -> u64 {
+        require_asset_registered(asset);
+        fm_compute_borrowing_rate(
+            internal_calc_decayed_base_rate(),
+            internal_get_risk_parameters(asset)
+                .borrowing_fee_floor,
+        )
+    }
+    #[storage(read)]
+    fn get_redemption_rate(asset: AssetId) -> u64 {
+        require_asset_registered(asset);
+        fm_compute_redemption_rate(
+            internal_calc_decayed_base_rate(),
+            internal_get_risk_parameters(asset)
+                .redemption_fee_floor,
+        )
+    }
+    #[storage(read)]
+    fn get_last_fee_operation_time() -> u64 {
+        storage.last_fee_operation_time.read()
+    }
+    #[storage(read)]
+    fn get_current_time() -> u64 {
+        internal_get_current_time()
+    }
+    #[storage(read, write)]
+    fn set_current_time(time: u64) {
         require(
             storage
-                .lock_redeem_collateral
-                .read() == false,
-            "ProtocolManager: Redeem collateral is locked",
+                .debug
+                .read(),
+            "ProtocolManager: Debugging must be enabled to set current time",
         );
-        storage.lock_redeem_collateral.write(true);
+        storage.debug_timestamp.write(time);
+    }
+    #[storage(read, write)]
+    fn transfer_owner(new_owner: Identity) {
+        only_owner();
+        transfer_ownership(new_owner);
+    }
+}
 
-        require_valid_usdm_id();
-        require(
-            msg_amount() > 0,
-            "ProtocolManager: Redemption amount must be greater than 0",
+impl SRC5 for Contract {
+    #[storage(read)]
+    fn owner() -> State {
+        _owner()
+    }
+}
+// --- Helper functions ---
+#[storage(read)]
+fn require_valid_usdm_id() {
+    require(
+        msg_asset_id() == AssetId::new(storage.usdm_token_contract.read(), SubId::zero()),
+        "ProtocolManager: Invalid asset being transfered",
+    );
+}
+
+#[storage(read)]
+fn require_caller_is_borrow_operations() {
+    require(
+        msg_sender()
+            .unwrap() == Identity::ContractId(storage.borrow_operations_contract.read()),
+        "ProtocolManager: Caller is not Borrow Operations",
+    );
+}
+
+fn require_valid_max_fee_percentage(max_fee_percentage: u64, redemption_fee_floor: u64) {
+    require(
+        max_fee_percentage >= redemption_fee_floor && max_fee_percentage <= DECIMAL_PRECISION,
+        "ProtocolManager: Max fee percentage must be between the redemption fee floor and 100%",
+    );
+}
+
+fn require_valid_risk_parameters(risk_parameters: RiskParameters) {
+    require(
+        risk_parameters.mcr >= MIN_MCR && risk_parameters.mcr < risk_parameters.ccr,
+        "ProtocolManager: MCR must be between 110% and CCR",
+    );
+    require(
+        risk_parameters.min_net_debt >= MIN_NET_DEBT_LOWER_BOUND && risk_parameters.min_net_debt <= MIN_NET_DEBT_UPPER_BOUND,
+        "ProtocolManager: Min net debt must be between 100 and 100000 USDM",
+    );
+    require(
+        risk_parameters.borrowing_fee_floor <= MAX_BORROWING_FEE,
+        "ProtocolManager: Borrowing fee floor must be at most 5%",
+    );
+    require(
+        risk_parameters.redemption_fee_floor <= MAX_REDEMPTION_FEE_FLOOR,
+        "ProtocolManager: Redemption fee floor must be at most 5%",
+    );
+    require(
+        risk_parameters.stability_pool_fee <= MAX_STABILITY_POOL_FEE,
+        "ProtocolManager: Stability pool fee must be at most 20%",
+    );
+    // Troves liquidated in recovery mode are under the asset's CCR, the liquidation math needs them under
+    // the post collateral ratio too. At CCR or above it also leaves room for the stability pool fee over 100%
+    require(
+        risk_parameters.post_collateral_ratio >= risk_parameters.ccr && risk_parameters.post_collateral_ratio <= MAX_POST_COLLATERAL_RATIO,
+        "ProtocolManager: Post collateral ratio must be between CCR and 300%",
+    );
+}
+
+// Assets registered before risk parameters were stored per asset keep the protocol wide values
+#[storage(read)]
+fn internal_get_risk_parameters(asset: AssetId) -> RiskParameters {
+    storage.risk_parameters.get(asset).try_read().unwrap_or(RiskParameters::default())
+}
+
+// Lowest redemption fee floor among `assets`, the least a redemption from them charges
+#[storage(read)]
+fn internal_get_min_redemption_fee_floor(assets: Vec<AssetId>) -> u64 {
+    let mut fee_floor = MAX_REDEMPTION_FEE_FLOOR;
+    let mut ind = 0;
+    while (ind < assets.len()) {
+        fee_floor = fm_min(
+            fee_floor,
+            internal_get_risk_parameters(assets.get(ind).unwrap())
+                .redemption_fee_floor,
         );
-        let usdm_contract_cache = storage.usdm_token_contract.read();
-        let fpt_staking_contract_cache = storage.fpt_staking_contract.read();
-        let usdm = abi(SRC3, usdm_contract_cache.bits());
-        let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
-        let active_pool = abi(ActivePool, storage.active_pool_contract.read().bits());
-        let fpt_staking = abi(FPTStaking, fpt_staking_contract_cache.bits());
-        let mut assets_info = get_all_assets_info();
-        let mut remaining_usdm = msg_amount();
-        let (mut current_borrower, mut index) = find_min_borrower(assets_info.current_borrowers, assets_info.current_crs);
-        let mut remaining_iterations = max_iterations;
-
-        // Iterate through troves, redeeming collateral until conditions are met
-        while (current_borrower != null_identity_address() && remaining_usdm > 0 && remaining_iterations > 0) {
-            let contracts_cache = assets_info.asset_contracts.get(index).unwrap();
-            let trove_manager_contract = abi(TroveManager, contracts_cache.trove_manager.bits());
-            let price = assets_info.prices.get(index).unwrap();
-            let mut totals = assets_info.redemption_totals.get(index).unwrap();
-            remaining_iterations -= 1;
-            let next_user_to_check = sorted_troves.get_prev(current_borrower, contracts_cache.asset_address);
-
-            // Apply pending rewards to ensure up-to-date trove state
-            trove_manager_contract.apply_pending_rewards(current_borrower);
-
-            // Attempt to redeem collateral from the current trove
-            let single_redemption = trove_manager_contract.redeem_collateral_from_trove(
-                current_borrower,
-                remaining_usdm,
-                price,
-                partial_redemption_hint,
-                upper_partial_hint,
-                lower_partial_hint,
-            );
+        ind += 1;
+    }
+    fee_floor
+}
 
-            // Break if partial redemption was cancelled
-            if (single_redemption.cancelled_partial) {
-                break;
-            }
+#[storage(read)]
+fn internal_get_asset_status(asset: AssetId) -> AssetStatus {
+    storage.asset_statuses.get(asset).try_read().unwrap_or(AssetStatus::Active)
+}
 
-            // Update totals and remaining USDM
-            totals.total_usdm_to_redeem += single_redemption.usdm_lot;
-            totals.total_asset_drawn += single_redemption.asset_lot;
-            remaining_usdm -= single_redemption.usdm_lot;
+// Assets without a ceiling set are uncapped
+#[storage(read)]
+fn internal_get_debt_ceiling(asset: AssetId) -> u64 {
+    storage.debt_ceilings.get(asset).try_read().unwrap_or(u64::max())
+}
 
-            let mut next_cr = u64::max();
-            if (next_user_to_check != null_identity_address()) {
-                next_cr = trove_manager_contract.get_current_icr(next_user_to_check, price);
-            }
-            assets_info.current_crs.set(index, next_cr);
-            assets_info.current_borrowers.set(index, next_user_to_check);
-            assets_info.redemption_totals.set(index, totals);
-            let next_borrower = find_min_borrower(assets_info.current_borrowers, assets_info.current_crs);
-            current_borrower = next_borrower.0;
-            index = next_borrower.1;
+#[storage(read)]
+fn internal_get_current_time() -> u64 {
+    if storage.debug.read() {
+        return storage.debug_timestamp.read();
+    } else {
+        return timestamp();
+    }
+}
+
+#[storage(read)]
+fn internal_minutes_passed_since_last_fee_operation() -> u64 {
+    (internal_get_current_time() - storage.last_fee_operation_time.read()) / SECONDS_IN_ONE_MINUTE
+}
+
+#[storage(read)]
+fn internal_calc_decayed_base_rate() -> u64 {
+    fm_decay_base_rate(
+        storage
+            .base_rate
+            .read(),
+        internal_minutes_passed_since_last_fee_operation(),
+    )
+}
+
+// Only whole minutes are consumed, so frequent fee operations can't prevent the decay
+#[storage(read, write)]
+fn internal_update_last_fee_operation_time() {
+    let minutes_passed = internal_minutes_passed_since_last_fee_operation();
+    if (minutes_passed > 0) {
+        storage
+            .last_fee_operation_time
+            .write(storage.last_fee_operation_time.read() + minutes_passed * SECONDS_IN_ONE_MINUTE);
+    }
+}
+
+#[storage(read, write)]
+fn internal_update_base_rate_from_redemption(usdm_redeemed: u64, usdm_supply: u64) -> u64 {
+    let new_base_rate = fm_compute_base_rate_from_redemption(internal_calc_decayed_base_rate(), usdm_redeemed, usdm_supply);
+    storage.base_rate.write(new_base_rate);
+    internal_update_last_fee_operation_time();
+    new_base_rate
+}
+
+// Redeems from the lowest ICR trove across `assets` until the USDM sent is used up
+#[storage(read, write)]
+fn internal_redeem_collateral(
+    assets: Vec<AssetId>,
+    max_iterations: u64,
+    partial_redemption_hint: u64,
+    upper_partial_hint: Identity,
+    lower_partial_hint: Identity,
+    max_fee_percentage: u64,
+) {
+    require(
+        storage
+            .lock_redeem_collateral
+            .read() == false,
+        "ProtocolManager: Redeem collateral is locked",
+    );
+    storage.lock_redeem_collateral.write(true);
+
+    require_valid_usdm_id();
+    require_valid_max_fee_percentage(
+        max_fee_percentage,
+        internal_get_min_redemption_fee_floor(assets),
+    );
+    require(
+        msg_amount() > 0,
+        "ProtocolManager: Redemption amount must be greater than 0",
+    );
+    let usdm_contract_cache = storage.usdm_token_contract.read();
+    let fpt_staking_contract_cache = storage.fpt_staking_contract.read();
+    let usdm = abi(SRC3, usdm_contract_cache.bits());
+    let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
+    let active_pool = abi(ActivePool, storage.active_pool_contract.read().bits());
+    let fpt_staking = abi(FPTStaking, fpt_staking_contract_cache.bits());
+    let mut assets_info = get_assets_info(assets);
+    let mut remaining_usdm = msg_amount();
+    let (mut current_borrower, mut index) = find_min_borrower(
+        assets_info.current_borrowers,
+        assets_info.current_crs,
+        assets_info.is_sunset,
+    );
+    let mut remaining_iterations = max_iterations;
+
+    // Iterate through troves, redeeming collateral until conditions are met
+    while (current_borrower != null_identity_address() && remaining_usdm > 0 && remaining_iterations > 0) {
+        let contracts_cache = assets_info.asset_contracts.get(index).unwrap();
+        let trove_manager_contract = abi(TroveManager, contracts_cache.trove_manager.bits());
+        let price = assets_info.prices.get(index).unwrap();
+        let mut totals = assets_info.redemption_totals.get(index).unwrap();
+        remaining_iterations -= 1;
+        let next_user_to_check = sorted_troves.get_prev(current_borrower, contracts_cache.asset_address);
+
+        // Apply pending rewards to ensure up-to-date trove state
+        trove_manager_contract.apply_pending_rewards(current_borrower);
+
+        // Attempt to redeem collateral from the current trove
+        let single_redemption = trove_manager_contract.redeem_collateral_from_trove(
+            current_borrower,
+            remaining_usdm,
+            price,
+            partial_redemption_hint,
+            upper_partial_hint,
+            lower_partial_hint,
+        );
+
+        // Break if partial redemption was cancelled
+        if (single_redemption.cancelled_partial) {
+            break;
         }
 
-        let mut total_usdm_redeemed = 0;
-        let mut ind = 0;
+        // Update totals and remaining USDM
+        totals.total_usdm_to_redeem += single_redemption.usdm_lot;
+        totals.total_asset_drawn += single_redemption.asset_lot;
+        remaining_usdm -= single_redemption.usdm_lot;
 
-        // Process redemptions for each asset
-        while (ind < assets_info.assets.len()) {
-            let contracts_cache = assets_info.asset_contracts.get(ind).unwrap();
+        let mut next_cr = u64::max();
+        if (next_user_to_check != null_identity_address()) {
+            next_cr = trove_manager_contract.get_current_icr(next_user_to_check, price);
+        }
+        assets_info.current_crs.set(index, next_cr);
+        assets_info.current_borrowers.set(index, next_user_to_check);
+        assets_info.redemption_totals.set(index, totals);
+        let next_borrower = find_min_borrower(
+            assets_info.current_borrowers,
+            assets_info.current_crs,
+            assets_info.is_sunset,
+        );
+        current_borrower = next_borrower.0;
+        index = next_borrower.1;
+    }
 
-            let mut totals = assets_info.redemption_totals.get(ind).unwrap();
+    let mut total_usdm_redeemed = 0;
+    let mut ind = 0;
+    while (ind < assets_info.assets.len()) {
+        total_usdm_redeemed += assets_info.redemption_totals.get(ind).unwrap().total_usdm_to_redeem;
+        ind += 1;
+    }
 
-            if (totals.total_usdm_to_redeem == 0) {
-                ind += 1;
-                continue;
-            }
+    // Raise the base rate by the redeemed fraction of the supply, before it is burnt
+    let mut base_rate = 0;
+    if (total_usdm_redeemed > 0) {
+        let usdm_supply = abi(SRC20, usdm_contract_cache.bits()).total_supply(AssetId::new(usdm_contract_cache, SubId::zero())).unwrap();
+        base_rate = internal_update_base_rate_from_redemption(total_usdm_redeemed, usdm_supply);
+    }
 
-            // Calculate redemption fee and amount to send to redeemer
-            totals.asset_fee = fm_compute_redemption_fee(totals.total_asset_drawn);
-            totals.asset_to_send_to_redeemer = totals.total_asset_drawn - totals.asset_fee;
-
-            // Send redemption fee to FPT stakers
-            active_pool.send_asset(
-                Identity::ContractId(fpt_staking_contract_cache),
-                totals
-                    .asset_fee,
-                contracts_cache
-                    .asset_address,
-            );
-            fpt_staking.increase_f_asset(totals.asset_fee, assets_info.assets.get(ind).unwrap());
-
-            // Update total USDM redeemed and decrease USDM debt
-            total_usdm_redeemed += totals.total_usdm_to_redeem;
-            active_pool.decrease_usdm_debt(totals.total_usdm_to_redeem, contracts_cache.asset_address);
-
-            // Send redeemed collateral to the user
-            active_pool.send_asset(
-                msg_sender()
-                    .unwrap(),
-                totals
-                    .asset_to_send_to_redeemer,
-                contracts_cache
-                    .asset_address,
-            );
+    ind = 0;
+
+    // Process redemptions for each asset
+    while (ind < assets_info.assets.len()) {
+        let contracts_cache = assets_info.asset_contracts.get(ind).unwrap();
+
+        let mut totals = assets_info.redemption_totals.get(ind).unwrap();
 
+        if (totals.total_usdm_to_redeem == 0) {
             ind += 1;
+            continue;
         }
 
-        // Burn the redeemed USDM
-        usdm
-            .burn {
-                coins: total_usdm_redeemed,
-                asset_id: AssetId::new(usdm_contract_cache, SubId::zero()).bits(),
-            }(SubId::zero(), total_usdm_redeemed);
-
-        // Return any remaining USDM to the redeemer
-        if (remaining_usdm > 0) {
-            transfer(
-                msg_sender()
-                    .unwrap(),
-                AssetId::new(usdm_contract_cache, SubId::zero()),
-                remaining_usdm,
-            );
-        }
+        // Each asset charges its own fee floor on top of the shared base rate
+        let redemption_rate = fm_compute_redemption_rate(
+            base_rate,
+            assets_info
+                .risk_parameters
+                .get(ind)
+                .unwrap()
+                .redemption_fee_floor,
+        );
+        require(
+            redemption_rate <= max_fee_percentage,
+            "ProtocolManager: Fee exceeded provided maximum",
+        );
+
+        // Calculate redemption fee and amount to send to redeemer
+        totals.asset_fee = fm_compute_fee(totals.total_asset_drawn, redemption_rate);
+        totals.asset_to_send_to_redeemer = totals.total_asset_drawn - totals.asset_fee;
 
-        storage.lock_redeem_collateral.write(false);
+        // Send redemption fee to FPT stakers
+        active_pool.send_asset(
+            Identity::ContractId(fpt_staking_contract_cache),
+            totals
+                .asset_fee,
+            contracts_cache
+                .asset_address,
+        );
+        fpt_staking.increase_f_asset(totals.asset_fee, assets_info.assets.get(ind).unwrap());
+
+        // Decrease USDM debt
+        active_pool.decrease_usdm_debt(totals.total_usdm_to_redeem, contracts_cache.asset_address);
+
+        // Send redeemed collateral to the user
+        active_pool.send_asset(
+            msg_sender()
+                .unwrap(),
+            totals
+                .asset_to_send_to_redeemer,
+            contracts_cache
+                .asset_address,
+        );
+
+        ind += 1;
     }
-    #[storage(read, write)]
-    fn transfer_owner(new_owner: Identity) {
-        only_owner();
-        transfer_ownership(new_owner);
+
+    // Burn the redeemed USDM
+    usdm
+        .burn {
+            coins: total_usdm_redeemed,
+            asset_id: AssetId::new(usdm_contract_cache, SubId::zero()).bits(),
+        }(SubId::zero(), total_usdm_redeemed);
+
+    // Return any remaining USDM to the redeemer
+    if (remaining_usdm > 0) {
+        transfer(
+            msg_sender()
+                .unwrap(),
+            AssetId::new(usdm_contract_cache, SubId::zero()),
+            remaining_usdm,
+        );
     }
+
+    storage.lock_redeem_collateral.write(false);
 }
 
-impl SRC5 for Contract {
-    #[storage(read)]
-    fn owner() -> State {
-        _owner()
+// Assets redeemed against, in registration order
+#[storage(read)]
+fn internal_get_all_assets() -> Vec<AssetId> {
+    let mut assets: Vec<AssetId> = Vec::new();
+    let mut ind = 0;
+    while (ind < storage.assets.len()) {
+        assets.push(storage.assets.get(ind).unwrap().read());
+        ind += 1;
     }
+    assets
 }
-// --- Helper functions ---
+
+// The requested assets in registration order, so ties between them go the same way as in
+// `redeem_collateral`
 #[storage(read)]
-fn require_valid_usdm_id() {
+fn internal_get_redeemable_assets(requested: Vec<AssetId>) -> Vec<AssetId> {
     require(
-        msg_asset_id() == AssetId::new(storage.usdm_token_contract.read(), SubId::zero()),
-        "ProtocolManager: Invalid asset being transfered",
+        requested
+            .len() > 0,
+        "ProtocolManager: No assets to redeem from",
     );
+    let all_assets = internal_get_all_assets();
+    let mut ind = 0;
+    while (ind < requested.len()) {
+        require(
+            vec_contains(all_assets, requested.get(ind).unwrap()),
+            "ProtocolManager: Asset is not redeemable",
+        );
+        ind += 1;
+    }
+    let mut assets: Vec<AssetId> = Vec::new();
+    ind = 0;
+    while (ind < all_assets.len()) {
+        let asset = all_assets.get(ind).unwrap();
+        if (vec_contains(requested, asset)) {
+            assets.push(asset);
+        }
+        ind += 1;
+    }
+    assets
+}
+
+fn vec_contains(assets: Vec<AssetId>, asset: AssetId) -> bool {
+    let mut ind = 0;
+    while (ind < assets.len()) {
+        if (assets.get(ind).unwrap() == asset) {
+            return true;
+        }
+        ind += 1;
+    }
+    false
 }
 
-// Get information about all assets in the system
+// Get information about the given assets
 #[storage(read)]
-fn get_all_assets_info() -> AssetInfo {
-    let mut assets: Vec<AssetId> = Vec::new();
+fn get_assets_info(assets: Vec<AssetId>) -> AssetInfo {
     let mut asset_contracts: Vec<AssetContracts> = Vec::new();
     let mut prices: Vec<u64> = Vec::new();
     let mut system_debt: Vec<u64> = Vec::new();
     let mut redemption_totals: Vec<RedemptionTotals> = Vec::new();
     let mut current_borrowers: Vec<Identity> = Vec::new();
     let mut current_crs: Vec<u64> = Vec::new();
+    let mut risk_parameters: Vec<RiskParameters> = Vec::new();
+    let mut is_sunset: Vec<bool> = Vec::new();
     let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
-    let length = storage.assets.len();
+    let length = assets.len();
     let mut ind = 0;
     while (ind < length) {
-        assets.push(storage.assets.get(ind).unwrap().read());
         asset_contracts.push(storage.asset_contracts.get(assets.get(ind).unwrap()).read());
         ind += 1;
     }
@@ -314,6 +728,7 @@ fn get_all_assets_info() -> AssetInfo {
         let oracle = abi(Oracle, asset_contracts.get(i).unwrap().oracle.into());
         let trove_manager = abi(TroveManager, asset_contracts.get(i).unwrap().trove_manager.into());
         let asset = assets.get(i).unwrap();
+        let asset_risk_parameters = internal_get_risk_parameters(asset);
         let price = oracle.get_price();
         let mut current_borrower = sorted_troves.get_last(asset);
         let mut current_cr = u64::max();
@@ -323,12 +738,14 @@ fn get_all_assets_info() -> AssetInfo {
         prices.push(price);
         system_debt.push(trove_manager.get_entire_system_debt());
         redemption_totals.push(RedemptionTotals::default());
-        while (current_borrower != null_identity_address() && current_cr < MCR) {
+        while (current_borrower != null_identity_address() && current_cr < asset_risk_parameters.mcr) {
             current_borrower = sorted_troves.get_prev(current_borrower, asset);
             current_cr = trove_manager.get_current_icr(current_borrower, price);
         }
         current_borrowers.push(current_borrower);
         current_crs.push(current_cr);
+        risk_parameters.push(asset_risk_parameters);
+        is_sunset.push(internal_get_asset_status(asset) == AssetStatus::Sunset);
         i += 1;
     }
     AssetInfo {
@@ -339,18 +756,29 @@ fn get_all_assets_info() -> AssetInfo {
         redemption_totals: redemption_totals,
         current_borrowers: current_borrowers,
         current_crs: current_crs,
+        risk_parameters: risk_parameters,
+        is_sunset: is_sunset,
     }
 }
-// Find the borrower with the lowest collateral ratio
-fn find_min_borrower(current_borrowers: Vec<Identity>, current_crs: Vec<u64>) -> (Identity, u64) {
+// Find the borrower with the lowest collateral ratio, troves of sunset assets come first
+fn find_min_borrower(
+    current_borrowers: Vec<Identity>,
+    current_crs: Vec<u64>,
+    is_sunset: Vec<bool>,
+) -> (Identity, u64) {
     let mut min_borrower = current_borrowers.get(0).unwrap();
     let mut min_cr = current_crs.get(0).unwrap();
+    let mut min_sunset = is_sunset.get(0).unwrap() && min_borrower != null_identity_address();
     let mut min_index = 0;
     let mut i = 1;
     while (i < current_borrowers.len()) {
-        if (current_crs.get(i).unwrap() < min_cr) {
-            min_borrower = current_borrowers.get(i).unwrap();
-            min_cr = current_crs.get(i).unwrap();
+        let borrower = current_borrowers.get(i).unwrap();
+        let cr = current_crs.get(i).unwrap();
+        let sunset = is_sunset.get(i).unwrap() && borrower != null_identity_address();
+        if ((sunset && !min_sunset) || (sunset == min_sunset && cr < min_cr)) {
+            min_borrower = borrower;
+            min_cr = cr;
+            min_sunset = sunset;
             min_index = i;
         }
         i += 1;
@@ -358,14 +786,22 @@ fn find_min_borrower(current_borrowers: Vec<Identity>, current_crs: Vec<u64>) ->
     (min_borrower, min_index)
 }
 
+// Delisted assets stay registered so they can't be added again
 #[storage(read)]
 fn require_asset_not_registered(asset_id: AssetId) {
-    let length = storage.assets.len();
-    let mut i = 0;
-    while (i < length) {
-        if (storage.assets.get(i).unwrap().read() == asset_id) {
-            revert(0);
-        }
-        i += 1;
+    if (storage.asset_contracts.get(asset_id).try_read().is_some()) {
+        revert(0);
     }
 }
+
+#[storage(read)]
+fn require_asset_registered(asset_id: AssetId) {
+    require(
+        storage
+            .asset_contracts
+            .get(asset_id)
+            .try_read()
+            .is_some(),
+        "ProtocolManager: Asset not registered",
+    );
+}
@@ -0,0 +1,489 @@
+use fuels::{prelude::*, types::Identity};
+use test_utils::{
+    assert_reverts_with,
+    data_structures::{default_risk_parameters, ContractInstance, PRECISION},
+    errors::ProtocolError,
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
+        oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, AssetStatus, ProtocolManager},
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
+        stability_pool::stability_pool_abi,
+        token::token_abi,
+        trove_manager::{trove_manager_abi, Status},
+    },
+    setup::common::setup_protocol,
+    utils::with_min_borrow_fee,
+};
+
+#[tokio::test]
+async fn sunset_asset_is_redeemed_first_and_delisted_once_empty() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, true, false).await;
+    let protocol_manager = &contracts.protocol_manager;
+    let sunset = &contracts.asset_contracts[0];
+    let active = &contracts.asset_contracts[1];
+
+    for asset in &contracts.asset_contracts {
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    // About 199% on the asset to sunset, 139% and 398% on the other one
+    let borrower = wallets.pop().unwrap();
+    borrow_operations_utils::mint_token_and_open_trove(
+        borrower.clone(),
+        sunset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+    let risky_borrower = wallets.pop().unwrap();
+    borrow_operations_utils::mint_token_and_open_trove(
+        risky_borrower.clone(),
+        active,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        7_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        active,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        20_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    assert_eq!(
+        protocol_manager_abi::get_asset_status(protocol_manager, sunset.asset_id)
+            .await
+            .unwrap()
+            .value,
+        AssetStatus::Active
+    );
+
+    // Only the owner sunsets assets, and only active ones can be delisted after a sunset
+    let attacker = wallets.pop().unwrap();
+    let protocol_manager_attacker = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            attacker.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    let result =
+        protocol_manager_abi::sunset_asset(&protocol_manager_attacker, sunset.asset_id).await;
+    assert!(
+        result.is_err(),
+        "Unauthorized user should not be able to sunset an asset"
+    );
+    if let Err(error) = result {
+        assert!(
+            error.to_string().contains("NotOwner"),
+            "Unexpected error message: {}",
+            error
+        );
+    }
+    assert_reverts_with!(
+        protocol_manager_abi::delist_asset(protocol_manager, sunset.asset_id).await,
+        ProtocolError::ProtocolManagerAssetIsNotSunset
+    );
+
+    protocol_manager_abi::sunset_asset(protocol_manager, sunset.asset_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        protocol_manager_abi::get_asset_status(protocol_manager, sunset.asset_id)
+            .await
+            .unwrap()
+            .value,
+        AssetStatus::Sunset
+    );
+    assert_reverts_with!(
+        protocol_manager_abi::sunset_asset(protocol_manager, sunset.asset_id).await,
+        ProtocolError::ProtocolManagerAssetIsNotActive
+    );
+
+    // No new troves or debt against the sunset asset
+    token_abi::mint_to_id(
+        &sunset.asset,
+        5_000 * PRECISION,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+    assert_reverts_with!(
+        borrow_operations_abi::open_trove(
+            &contracts.borrow_operations,
+            &sunset.oracle,
+            &sunset.mock_pyth_oracle,
+            &sunset.mock_redstone_oracle,
+            &sunset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &sunset.trove_manager,
+            &contracts.active_pool,
+            5_000 * PRECISION,
+            1_000 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await,
+        ProtocolError::BorrowOperationsAssetIsNotActive
+    );
+    let borrow_operations_borrower = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            borrower.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+    assert_reverts_with!(
+        borrow_operations_abi::withdraw_usdm(
+            &borrow_operations_borrower,
+            &sunset.oracle,
+            &sunset.mock_pyth_oracle,
+            &sunset.mock_redstone_oracle,
+            &sunset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &sunset.trove_manager,
+            &contracts.active_pool,
+            100 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await,
+        ProtocolError::BorrowOperationsAssetIsNotActive
+    );
+
+    // Troves are still listed
+    assert_reverts_with!(
+        protocol_manager_abi::delist_asset(protocol_manager, sunset.asset_id).await,
+        ProtocolError::SortedTrovesAssetStillHasTroves
+    );
+
+    // The 199% trove of the sunset asset is redeemed before the 139% one
+    let borrower_identity = Identity::Address(borrower.address().into());
+    let risky_identity = Identity::Address(risky_borrower.address().into());
+    let borrower_debt = trove_manager_abi::get_trove_debt(&sunset.trove_manager, borrower_identity)
+        .await
+        .value;
+    let risky_debt = trove_manager_abi::get_trove_debt(&active.trove_manager, risky_identity)
+        .await
+        .value;
+    protocol_manager_abi::redeem_collateral(
+        protocol_manager,
+        1_000 * PRECISION,
+        10,
+        0,
+        None,
+        None,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&sunset.trove_manager, borrower_identity)
+            .await
+            .value,
+        borrower_debt - 1_000 * PRECISION
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&active.trove_manager, risky_identity)
+            .await
+            .value,
+        risky_debt
+    );
+
+    // The last trove of a sunset asset can be closed
+    borrow_operations_abi::close_trove(
+        &borrow_operations_borrower,
+        &sunset.oracle,
+        &sunset.mock_pyth_oracle,
+        &sunset.mock_redstone_oracle,
+        &sunset.asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &sunset.trove_manager,
+        &contracts.active_pool,
+        borrower_debt - 1_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        trove_manager_abi::get_trove_status(&sunset.trove_manager, borrower_identity)
+            .await
+            .unwrap()
+            .value,
+        Status::ClosedByOwner
+    );
+
+    protocol_manager_abi::delist_asset(protocol_manager, sunset.asset_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        protocol_manager_abi::get_asset_status(protocol_manager, sunset.asset_id)
+            .await
+            .unwrap()
+            .value,
+        AssetStatus::Delisted
+    );
+
+    // Delisted assets can't be registered again
+    let result = protocol_manager_abi::register_asset(
+        protocol_manager,
+        sunset.asset_id,
+        sunset.trove_manager.contract.contract_id().into(),
+        sunset.oracle.contract.contract_id().into(),
+        default_risk_parameters(),
+        &contracts.borrow_operations,
+        &contracts.stability_pool,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+    )
+    .await;
+    assert!(
+        result.is_err(),
+        "A delisted asset should not be registered again"
+    );
+
+    // Redemptions go on against the remaining asset
+    protocol_manager_abi::redeem_collateral(
+        protocol_manager,
+        1_000 * PRECISION,
+        10,
+        0,
+        None,
+        None,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&active.trove_manager, risky_identity)
+            .await
+            .value,
+        risky_debt - 1_000 * PRECISION
+    );
+
+    // The stability pool no longer checks the delisted asset's troves
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdm,
+        &active.asset,
+        1_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+    stability_pool_abi::withdraw_from_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdm,
+        &active.asset,
+        &contracts.sorted_troves,
+        &active.oracle,
+        &active.mock_pyth_oracle,
+        &active.mock_redstone_oracle,
+        &active.trove_manager,
+        1_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn last_trove_of_a_sunset_asset_is_not_liquidated() {
+    let (contracts, _admin, mut wallets) = setup_protocol(2, false, false).await;
+    let sunset = &contracts.asset_contracts[0];
+
+    oracle_abi::set_debug_timestamp(&sunset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&sunset.mock_pyth_oracle, pyth_price_feed(10)).await;
+
+    let borrower = wallets.pop().unwrap();
+    let borrower_identity = Identity::Address(borrower.address().into());
+    borrow_operations_utils::mint_token_and_open_trove(
+        borrower.clone(),
+        sunset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+    protocol_manager_abi::sunset_asset(&contracts.protocol_manager, sunset.asset_id)
+        .await
+        .unwrap();
+
+    // At a price of 1 the trove is far under MCR, with no trove left to redistribute to
+    oracle_abi::set_debug_timestamp(&sunset.oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &sunset.mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+    assert_reverts_with!(
+        trove_manager_abi::liquidate(
+            &sunset.trove_manager,
+            &contracts.community_issuance,
+            &contracts.stability_pool,
+            &sunset.oracle,
+            &sunset.mock_pyth_oracle,
+            &sunset.mock_redstone_oracle,
+            &contracts.sorted_troves,
+            &contracts.active_pool,
+            &contracts.default_pool,
+            &contracts.coll_surplus_pool,
+            &contracts.usdm,
+            borrower_identity,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await,
+        ProtocolError::TroveManagerThereIsOnlyOneTroveInTheSystem
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_status(&sunset.trove_manager, borrower_identity)
+            .await
+            .unwrap()
+            .value,
+        Status::Active
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&sunset.trove_manager, borrower_identity)
+            .await
+            .value,
+        with_min_borrow_fee(5_000 * PRECISION)
+    );
+}
+
+#[tokio::test]
+async fn last_trove_of_a_sunset_asset_is_closed_by_redemption() {
+    let (contracts, admin, mut wallets) = setup_protocol(2, true, false).await;
+    let protocol_manager = &contracts.protocol_manager;
+    let sunset = &contracts.asset_contracts[0];
+    let active = &contracts.asset_contracts[1];
+
+    for asset in &contracts.asset_contracts {
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    let borrower = wallets.pop().unwrap();
+    let borrower_identity = Identity::Address(borrower.address().into());
+    let admin_identity = Identity::Address(admin.address().into());
+    borrow_operations_utils::mint_token_and_open_trove(
+        borrower.clone(),
+        sunset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        3_000 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        active,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        20_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+    protocol_manager_abi::sunset_asset(protocol_manager, sunset.asset_id)
+        .await
+        .unwrap();
+
+    // The redemption is larger than the only trove of the sunset asset, which it closes before
+    // moving on to the active asset
+    let borrower_debt = trove_manager_abi::get_trove_debt(&sunset.trove_manager, borrower_identity)
+        .await
+        .value;
+    let admin_debt = trove_manager_abi::get_trove_debt(&active.trove_manager, admin_identity)
+        .await
+        .value;
+    protocol_manager_abi::redeem_collateral(
+        protocol_manager,
+        2_000 * PRECISION,
+        10,
+        0,
+        None,
+        None,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert_eq!(
+        trove_manager_abi::get_trove_status(&sunset.trove_manager, borrower_identity)
+            .await
+            .unwrap()
+            .value,
+        Status::ClosedByRedemption
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&active.trove_manager, admin_identity)
+            .await
+            .value,
+        admin_debt - (2_000 * PRECISION - borrower_debt)
+    );
+
+    // With its last trove gone the asset can be delisted
+    protocol_manager_abi::delist_asset(protocol_manager, sunset.asset_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        protocol_manager_abi::get_asset_status(protocol_manager, sunset.asset_id)
+            .await
+            .unwrap()
+            .value,
+        AssetStatus::Delisted
+    );
+}
@@ -1,8 +1,12 @@
-use fuels::accounts::ViewOnlyAccount;
+use fuels::{
+    accounts::{impersonated_account::ImpersonatedAccount, ViewOnlyAccount},
+    prelude::*,
+    types::Identity,
+};
 use test_utils::{
     data_structures::{ContractInstance, ExistingAssetContracts},
-    interfaces::protocol_manager::{protocol_manager_abi, ProtocolManager},
-    setup::common::{deploy_asset_contracts, initialize_asset, setup_protocol},
+    interfaces::protocol_manager::{protocol_manager_abi, ProtocolManager, State},
+    setup::common::{deploy_asset_contracts, deploy_protocol, initialize_asset, setup_protocol},
 };
 
 #[tokio::test]
@@ -41,6 +45,7 @@ async fn test_authorizations() {
         stork_oracle: None,
         pyth_oracle: None,
         redstone_oracle: None,
+        risk_parameters: None,
     };
     let asset_contracts = deploy_asset_contracts(
         &protocol_manager_owner,
@@ -82,6 +87,7 @@ async fn test_authorizations() {
         stork_oracle: None,
         pyth_oracle: None,
         redstone_oracle: None,
+        risk_parameters: None,
     };
 
     let asset_contracts_owner = deploy_asset_contracts(
@@ -110,6 +116,7 @@ async fn test_authorizations() {
             .contract_id()
             .into(),
         asset_contracts_owner.oracle.contract.contract_id().into(),
+        asset_contracts_owner.risk_parameters.clone(),
         &contracts.borrow_operations,
         &contracts.stability_pool,
         &contracts.usdm,
@@ -160,6 +167,7 @@ async fn test_authorizations() {
         stork_oracle: None,
         pyth_oracle: None,
         redstone_oracle: None,
+        risk_parameters: None,
     };
     let asset_contracts = deploy_asset_contracts(
         &protocol_manager_owner,
@@ -205,6 +213,7 @@ async fn test_authorizations() {
         stork_oracle: None,
         pyth_oracle: None,
         redstone_oracle: None,
+        risk_parameters: None,
     };
     let unauthorized_asset_contracts = deploy_asset_contracts(
         &protocol_manager_owner,
@@ -229,3 +238,37 @@ async fn test_authorizations() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_admin_roles_with_non_wallet_account() {
+    // Without UTXO validation the node accepts unsigned transactions, so an account without a
+    // signer can own and administer the protocol
+    let node_config = NodeConfig {
+        utxo_validation: false,
+        ..NodeConfig::default()
+    };
+    let mut wallets = launch_custom_provider_and_get_wallets(
+        WalletsConfig::new(Some(1), Some(1), Some(1_000_000_000)),
+        Some(node_config),
+        None,
+    )
+    .await
+    .unwrap();
+    let wallet = wallets.pop().unwrap();
+    let admin = ImpersonatedAccount::new(
+        wallet.address().clone().into(),
+        Some(wallet.provider().clone()),
+    );
+
+    // Registering the second asset is restricted to the protocol manager owner
+    let contracts = deploy_protocol(&admin, true, false).await;
+    assert_eq!(contracts.asset_contracts.len(), 2);
+
+    let owner = protocol_manager_abi::owner(&contracts.protocol_manager)
+        .await
+        .value;
+    assert_eq!(
+        owner,
+        State::Initialized(Identity::Address(admin.address().clone().into()))
+    );
+}
@@ -0,0 +1,417 @@
+use std::time::Duration;
+
+use fuels::{prelude::*, types::Identity};
+use test_utils::{
+    assert_reverts_with,
+    clock::SimClock,
+    data_structures::{ContractInstance, PRECISION},
+    errors::ProtocolError,
+    fluid_math::{
+        fm_compute_base_rate_from_redemption, fm_compute_borrowing_rate, fm_compute_fee,
+        fm_compute_redemption_rate, fm_decay_base_rate, BORROWING_FEE_FLOOR, REDEMPTION_FEE_FLOOR,
+        SECONDS_IN_ONE_MINUTE,
+    },
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
+        oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, ProtocolManager},
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        trove_manager::trove_manager_abi,
+        usdm_token::usdm_token_abi,
+    },
+    invariants::check_invariants,
+    setup::common::setup_protocol,
+};
+
+#[tokio::test]
+async fn redemptions_raise_the_base_rate_and_it_decays_over_time() {
+    let (contracts, _admin, mut wallets) = setup_protocol(4, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    let mut clock = SimClock::start(&contracts, PYTH_TIMESTAMP).await;
+
+    let wallet1 = wallets.pop().unwrap();
+    let wallet2 = wallets.pop().unwrap();
+    let wallet3 = wallets.pop().unwrap();
+
+    for (wallet, coll) in [(&wallet1, 20_000), (&wallet2, 15_000), (&wallet3, 10_000)] {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            asset,
+            &contracts.borrow_operations,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            coll * PRECISION,
+            5_000 * PRECISION,
+        )
+        .await;
+    }
+
+    // Borrowing alone leaves the base rate at 0, both fees are at their floor
+    let protocol_manager = &contracts.protocol_manager;
+    assert_eq!(
+        protocol_manager_abi::get_base_rate(protocol_manager)
+            .await
+            .value,
+        0
+    );
+    assert_eq!(
+        protocol_manager_abi::get_borrowing_rate(protocol_manager, asset.asset_id)
+            .await
+            .value,
+        BORROWING_FEE_FLOOR
+    );
+    assert_eq!(
+        protocol_manager_abi::get_redemption_rate(protocol_manager, asset.asset_id)
+            .await
+            .value,
+        REDEMPTION_FEE_FLOOR
+    );
+
+    let protocol_manager_wallet1 = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    let redemption_amount = 1_000 * PRECISION;
+    let usdm_supply = usdm_token_abi::total_supply(&contracts.usdm)
+        .await
+        .value
+        .unwrap();
+    let base_rate = fm_compute_base_rate_from_redemption(0, redemption_amount, usdm_supply);
+    let redemption_rate = fm_compute_redemption_rate(base_rate, REDEMPTION_FEE_FLOOR);
+
+    let redeem = |max_fee_percentage: u64| {
+        protocol_manager_abi::redeem_collateral_with_max_fee(
+            &protocol_manager_wallet1,
+            redemption_amount,
+            10,
+            0,
+            None,
+            None,
+            max_fee_percentage,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.coll_surplus_pool,
+            &contracts.default_pool,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            &contracts.asset_contracts,
+        )
+    };
+
+    assert_reverts_with!(
+        redeem(REDEMPTION_FEE_FLOOR - 1).await,
+        ProtocolError::ProtocolManagerMaxFeePercentageMustBeBetweenTheRedemptionFeeFloorAnd100
+    );
+    // The fee is charged on the base rate the redemption itself raises
+    assert_reverts_with!(
+        redeem(redemption_rate - 1).await,
+        ProtocolError::ProtocolManagerFeeExceededProvidedMaximum
+    );
+    redeem(redemption_rate).await.unwrap();
+
+    assert_eq!(
+        protocol_manager_abi::get_base_rate(protocol_manager)
+            .await
+            .value,
+        base_rate
+    );
+    let fee_operation_time = protocol_manager_abi::get_last_fee_operation_time(protocol_manager)
+        .await
+        .value;
+    assert!(clock.now() - fee_operation_time < SECONDS_IN_ONE_MINUTE);
+
+    // Half of it is left after 12 hours
+    clock.advance(Duration::from_secs(12 * 60 * 60)).await;
+    let decayed_base_rate = protocol_manager_abi::get_decayed_base_rate(protocol_manager)
+        .await
+        .value;
+    assert_eq!(decayed_base_rate, fm_decay_base_rate(base_rate, 12 * 60));
+    assert!(decayed_base_rate.abs_diff(base_rate / 2) < base_rate / 1_000);
+
+    let borrowing_rate = protocol_manager_abi::get_borrowing_rate(protocol_manager, asset.asset_id)
+        .await
+        .value;
+    assert_eq!(
+        borrowing_rate,
+        fm_compute_borrowing_rate(decayed_base_rate, BORROWING_FEE_FLOOR)
+    );
+    assert!(borrowing_rate > BORROWING_FEE_FLOOR);
+
+    // Borrowing pays the raised fee, and only settles the decay
+    let borrow_operations_wallet2 = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet2.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+    let borrower = Identity::Address(wallet2.address().into());
+    let withdraw_usdm = |max_fee_percentage: u64| {
+        borrow_operations_abi::withdraw_usdm_with_max_fee(
+            &borrow_operations_wallet2,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            max_fee_percentage,
+            500 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+    };
+
+    assert_reverts_with!(
+        withdraw_usdm(BORROWING_FEE_FLOOR - 1).await,
+        ProtocolError::BorrowOperationsMaxFeePercentageMustBeBetweenTheBorrowingFeeFloorAnd100
+    );
+    assert_reverts_with!(
+        withdraw_usdm(borrowing_rate - 1).await,
+        ProtocolError::BorrowOperationsFeeExceededProvidedMaximum
+    );
+
+    let debt_before = trove_manager_abi::get_trove_debt(&asset.trove_manager, borrower)
+        .await
+        .value;
+    withdraw_usdm(borrowing_rate).await.unwrap();
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(&asset.trove_manager, borrower)
+            .await
+            .value,
+        debt_before + 500 * PRECISION + fm_compute_fee(500 * PRECISION, borrowing_rate)
+    );
+
+    assert_eq!(
+        protocol_manager_abi::get_base_rate(protocol_manager)
+            .await
+            .value,
+        decayed_base_rate
+    );
+    assert_eq!(
+        protocol_manager_abi::get_last_fee_operation_time(protocol_manager)
+            .await
+            .value,
+        fee_operation_time + 12 * 60 * 60
+    );
+
+    check_invariants(&contracts).await;
+}
+
+#[tokio::test]
+async fn fee_operations_only_consume_whole_minutes() {
+    let (contracts, _admin, mut wallets) = setup_protocol(4, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    let mut clock = SimClock::start(&contracts, PYTH_TIMESTAMP).await;
+
+    let wallet1 = wallets.pop().unwrap();
+    let wallet2 = wallets.pop().unwrap();
+    let wallet3 = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        wallet1.clone(),
+        asset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        20_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+    let opened_at = protocol_manager_abi::get_last_fee_operation_time(&contracts.protocol_manager)
+        .await
+        .value;
+    assert!(clock.now() - opened_at < SECONDS_IN_ONE_MINUTE);
+
+    // Less than a minute later the fee operation time stays put, so frequent operations can not
+    // hold off the decay
+    clock.set(opened_at + 59).await;
+    borrow_operations_utils::mint_token_and_open_trove(
+        wallet2.clone(),
+        asset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        20_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+    assert_eq!(
+        protocol_manager_abi::get_last_fee_operation_time(&contracts.protocol_manager)
+            .await
+            .value,
+        opened_at
+    );
+
+    // A minute and 58 seconds later only the whole minute is consumed, the 58 seconds carry over
+    clock.set(opened_at + 118).await;
+    borrow_operations_utils::mint_token_and_open_trove(
+        wallet3.clone(),
+        asset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        20_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+    assert_eq!(
+        protocol_manager_abi::get_last_fee_operation_time(&contracts.protocol_manager)
+            .await
+            .value,
+        opened_at + SECONDS_IN_ONE_MINUTE
+    );
+
+    // Only BorrowOperations charges a borrowing fee
+    let protocol_manager_wallet1 = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    let result = protocol_manager_wallet1
+        .contract
+        .methods()
+        .decay_base_rate_from_borrowing(asset.asset_id)
+        .with_contract_ids(&[
+            contracts.protocol_manager.contract.contract_id().into(),
+            contracts.protocol_manager.implementation_id.into(),
+        ])
+        .call()
+        .await;
+    assert_reverts_with!(
+        result,
+        ProtocolError::ProtocolManagerCallerIsNotBorrowOperations
+    );
+}
+
+#[tokio::test]
+async fn base_rate_decays_through_fee_operations_under_a_minute_apart() {
+    let (contracts, _admin, mut wallets) = setup_protocol(4, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    let mut clock = SimClock::start(&contracts, PYTH_TIMESTAMP).await;
+
+    let wallet1 = wallets.pop().unwrap();
+    let wallet2 = wallets.pop().unwrap();
+
+    for (wallet, coll) in [(&wallet1, 20_000), (&wallet2, 15_000)] {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            asset,
+            &contracts.borrow_operations,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            coll * PRECISION,
+            5_000 * PRECISION,
+        )
+        .await;
+    }
+
+    let protocol_manager = &contracts.protocol_manager;
+    let protocol_manager_wallet1 = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    protocol_manager_abi::redeem_collateral(
+        &protocol_manager_wallet1,
+        1_000 * PRECISION,
+        10,
+        0,
+        None,
+        None,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await;
+    let mut base_rate = protocol_manager_abi::get_base_rate(protocol_manager)
+        .await
+        .value;
+    assert!(base_rate > 0);
+    let redeemed_at = protocol_manager_abi::get_last_fee_operation_time(protocol_manager)
+        .await
+        .value;
+
+    // A borrow every 59 seconds, each one settles the whole minutes passed since the last one
+    let borrow_operations_wallet2 = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet2.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+    let mut fee_operation_time = redeemed_at;
+    for borrow in 1..=5 {
+        clock.set(redeemed_at + borrow * 59).await;
+        borrow_operations_abi::withdraw_usdm_with_max_fee(
+            &borrow_operations_wallet2,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            PRECISION,
+            100 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await
+        .unwrap();
+
+        let minutes_passed = (clock.now() - fee_operation_time) / SECONDS_IN_ONE_MINUTE;
+        base_rate = fm_decay_base_rate(base_rate, minutes_passed);
+        fee_operation_time += minutes_passed * SECONDS_IN_ONE_MINUTE;
+        assert_eq!(
+            protocol_manager_abi::get_base_rate(protocol_manager)
+                .await
+                .value,
+            base_rate
+        );
+        assert_eq!(
+            protocol_manager_abi::get_last_fee_operation_time(protocol_manager)
+                .await
+                .value,
+            fee_operation_time
+        );
+    }
+
+    // 295 seconds in, 4 whole minutes of decay are settled and the other 55 seconds are kept
+    assert_eq!(fee_operation_time, redeemed_at + 4 * SECONDS_IN_ONE_MINUTE);
+}
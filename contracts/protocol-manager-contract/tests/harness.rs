@@ -1,3 +1,7 @@
+pub mod asset_sunset;
 pub mod authorization;
+pub mod base_rate;
+pub mod risk_parameters;
+pub mod scenarios;
 pub mod success_redemptions;
 pub mod success_redemptions_many;
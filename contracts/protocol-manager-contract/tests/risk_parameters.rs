@@ -0,0 +1,490 @@
+use fuels::{prelude::*, types::Identity};
+use test_utils::{
+    assert_reverts_with,
+    data_structures::{default_risk_parameters, ContractInstance, PRECISION},
+    errors::ProtocolError,
+    fluid_math::{
+        fm_compute_base_rate_from_redemption, fm_compute_redemption_rate, CCR, MAX_BORROWING_FEE,
+        MAX_POST_COLLATERAL_RATIO, MAX_REDEMPTION_FEE_FLOOR, MAX_STABILITY_POOL_FEE, MIN_MCR,
+        MIN_NET_DEBT_LOWER_BOUND, MIN_NET_DEBT_UPPER_BOUND, REDEMPTION_FEE_FLOOR,
+    },
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, BorrowOperations},
+        oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, ProtocolManager, RiskParameters},
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        token::token_abi,
+        trove_manager::trove_manager_abi,
+        usdm_token::usdm_token_abi,
+    },
+    setup::common::setup_protocol,
+};
+#[tokio::test]
+async fn owner_sets_risk_parameters_within_bounds() {
+    let (contracts, _admin, mut wallets) = setup_protocol(2, false, false).await;
+    let protocol_manager = &contracts.protocol_manager;
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    // Assets registered without overrides get the protocol defaults
+    assert_eq!(
+        protocol_manager_abi::get_risk_parameters(protocol_manager, asset_id)
+            .await
+            .unwrap()
+            .value,
+        default_risk_parameters()
+    );
+
+    let out_of_bounds: Vec<(RiskParameters, ProtocolError)> = vec![
+        (
+            RiskParameters {
+                mcr: MIN_MCR - 1,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerMCRMustBeBetween110AndCCR,
+        ),
+        (
+            RiskParameters {
+                mcr: CCR,
+                post_collateral_ratio: CCR,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerMCRMustBeBetween110AndCCR,
+        ),
+        (
+            RiskParameters {
+                mcr: 1_700_000_000,
+                ccr: 1_600_000_000,
+                post_collateral_ratio: 2_000_000_000,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerMCRMustBeBetween110AndCCR,
+        ),
+        (
+            RiskParameters {
+                min_net_debt: MIN_NET_DEBT_LOWER_BOUND - 1,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerMinNetDebtMustBeBetween100And100000USDM,
+        ),
+        (
+            RiskParameters {
+                min_net_debt: MIN_NET_DEBT_UPPER_BOUND + 1,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerMinNetDebtMustBeBetween100And100000USDM,
+        ),
+        (
+            RiskParameters {
+                borrowing_fee_floor: MAX_BORROWING_FEE + 1,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerBorrowingFeeFloorMustBeAtMost5,
+        ),
+        (
+            RiskParameters {
+                redemption_fee_floor: MAX_REDEMPTION_FEE_FLOOR + 1,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerRedemptionFeeFloorMustBeAtMost5,
+        ),
+        (
+            RiskParameters {
+                stability_pool_fee: MAX_STABILITY_POOL_FEE + 1,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerStabilityPoolFeeMustBeAtMost20,
+        ),
+        (
+            RiskParameters {
+                post_collateral_ratio: CCR - 1,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerPostCollateralRatioMustBeBetweenCCRAnd300,
+        ),
+        (
+            RiskParameters {
+                post_collateral_ratio: MAX_POST_COLLATERAL_RATIO + 1,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerPostCollateralRatioMustBeBetweenCCRAnd300,
+        ),
+        (
+            RiskParameters {
+                ccr: 2_000_000_000,
+                post_collateral_ratio: 1_800_000_000,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerPostCollateralRatioMustBeBetweenCCRAnd300,
+        ),
+        // Even with a low MCR, troves liquidated in recovery mode have to end up above it
+        (
+            RiskParameters {
+                mcr: MIN_MCR,
+                post_collateral_ratio: 1_400_000_000,
+                ..default_risk_parameters()
+            },
+            ProtocolError::ProtocolManagerPostCollateralRatioMustBeBetweenCCRAnd300,
+        ),
+    ];
+    for (risk_parameters, error) in out_of_bounds {
+        assert_reverts_with!(
+            protocol_manager_abi::set_risk_parameters(protocol_manager, asset_id, risk_parameters)
+                .await,
+            error
+        );
+    }
+
+    // Only registered assets have risk parameters
+    assert_reverts_with!(
+        protocol_manager_abi::set_risk_parameters(
+            protocol_manager,
+            AssetId::zeroed(),
+            default_risk_parameters()
+        )
+        .await,
+        ProtocolError::ProtocolManagerAssetNotRegistered
+    );
+    assert_reverts_with!(
+        protocol_manager_abi::get_risk_parameters(protocol_manager, AssetId::zeroed()).await,
+        ProtocolError::ProtocolManagerAssetNotRegistered
+    );
+
+    // MCR and CCR can both go above the protocol wide CCR
+    let volatile = RiskParameters {
+        mcr: 1_600_000_000,
+        ccr: 2_000_000_000,
+        min_net_debt: MIN_NET_DEBT_LOWER_BOUND,
+        borrowing_fee_floor: MAX_BORROWING_FEE,
+        redemption_fee_floor: MAX_REDEMPTION_FEE_FLOOR,
+        post_collateral_ratio: MAX_POST_COLLATERAL_RATIO,
+        stability_pool_fee: MAX_STABILITY_POOL_FEE,
+    };
+
+    let attacker = wallets.pop().unwrap();
+    let protocol_manager_attacker = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            attacker.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    let result = protocol_manager_abi::set_risk_parameters(
+        &protocol_manager_attacker,
+        asset_id,
+        volatile.clone(),
+    )
+    .await;
+    assert!(
+        result.is_err(),
+        "Unauthorized user should not be able to set risk parameters"
+    );
+    if let Err(error) = result {
+        assert!(
+            error.to_string().contains("NotOwner"),
+            "Unexpected error message: {}",
+            error
+        );
+    }
+
+    protocol_manager_abi::set_risk_parameters(protocol_manager, asset_id, volatile.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        protocol_manager_abi::get_risk_parameters(protocol_manager, asset_id)
+            .await
+            .unwrap()
+            .value,
+        volatile
+    );
+    assert_eq!(
+        protocol_manager_abi::get_borrowing_rate(protocol_manager, asset_id)
+            .await
+            .value,
+        MAX_BORROWING_FEE
+    );
+    assert_eq!(
+        protocol_manager_abi::get_redemption_rate(protocol_manager, asset_id)
+            .await
+            .value,
+        MAX_REDEMPTION_FEE_FLOOR
+    );
+}
+
+#[tokio::test]
+async fn borrowing_follows_the_asset_risk_parameters() {
+    let (contracts, admin, _wallets) = setup_protocol(2, true, false).await;
+
+    for asset in &contracts.asset_contracts {
+        token_abi::mint_to_id(
+            &asset.asset,
+            5_000 * PRECISION,
+            Identity::Address(admin.address().into()),
+        )
+        .await;
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    // Asset 0 gets a higher MCR, a lower min net debt and a higher borrowing fee floor
+    let volatile = RiskParameters {
+        mcr: 1_450_000_000,
+        min_net_debt: MIN_NET_DEBT_LOWER_BOUND,
+        borrowing_fee_floor: 10_000_000,
+        ..default_risk_parameters()
+    };
+    protocol_manager_abi::set_risk_parameters(
+        &contracts.protocol_manager,
+        contracts.asset_contracts[0].asset_id,
+        volatile,
+    )
+    .await
+    .unwrap();
+
+    let open_trove = |index: usize, coll: u64, debt: u64| {
+        let asset = &contracts.asset_contracts[index];
+        borrow_operations_abi::open_trove(
+            &contracts.borrow_operations,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            coll * PRECISION,
+            debt * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+    };
+
+    // About 139% clears the default 135% MCR but not 145%
+    assert_reverts_with!(
+        open_trove(0, 1_400, 1_000).await,
+        ProtocolError::BorrowOperationsMinimumCollateralRatioNotMet
+    );
+    // 150 USDM is below the default 500 USDM min net debt
+    assert_reverts_with!(
+        open_trove(1, 300, 150).await,
+        ProtocolError::BorrowOperationsNetDebtMustBeGreaterThan0
+    );
+    open_trove(1, 1_400, 1_000).await.unwrap();
+    open_trove(0, 300, 150).await.unwrap();
+
+    // Charged the asset's 1% borrowing fee floor
+    let trove_debt = trove_manager_abi::get_trove_debt(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_debt, 150 * PRECISION + 150 * PRECISION / 100);
+}
+
+#[tokio::test]
+async fn recovery_mode_follows_the_asset_ccr() {
+    let (contracts, admin, mut wallets) = setup_protocol(2, true, false).await;
+    let wallet = wallets.pop().unwrap();
+
+    for asset in &contracts.asset_contracts {
+        for identity in [
+            Identity::Address(admin.address().into()),
+            Identity::Address(wallet.address().into()),
+        ] {
+            token_abi::mint_to_id(&asset.asset, 5_000 * PRECISION, identity).await;
+        }
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    // Asset 0 is in recovery mode below 200% instead of 150%
+    protocol_manager_abi::set_risk_parameters(
+        &contracts.protocol_manager,
+        contracts.asset_contracts[0].asset_id,
+        RiskParameters {
+            mcr: 1_600_000_000,
+            ccr: 2_000_000_000,
+            post_collateral_ratio: 2_500_000_000,
+            ..default_risk_parameters()
+        },
+    )
+    .await
+    .unwrap();
+
+    let borrow_operations_wallet = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+    let open_trove = |borrow_operations: &ContractInstance<BorrowOperations<Wallet>>,
+                      index: usize,
+                      coll: u64| {
+        let asset = &contracts.asset_contracts[index];
+        borrow_operations_abi::open_trove(
+            borrow_operations,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            coll * PRECISION,
+            1_000 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+    };
+
+    // The same 179% trove on both assets
+    for index in 0..2 {
+        open_trove(&contracts.borrow_operations, index, 1_800)
+            .await
+            .unwrap();
+    }
+    assert!(
+        trove_manager_abi::check_recovery_mode(
+            &contracts.asset_contracts[0].trove_manager,
+            PRECISION
+        )
+        .await
+        .value
+    );
+    assert!(
+        !trove_manager_abi::check_recovery_mode(
+            &contracts.asset_contracts[1].trove_manager,
+            PRECISION
+        )
+        .await
+        .value
+    );
+
+    // New troves of asset 0 have to open at its CCR
+    assert_reverts_with!(
+        open_trove(&borrow_operations_wallet, 0, 1_900).await,
+        ProtocolError::BorrowOperationsICRMustBeAtLeastCCRInRecoveryMode
+    );
+    open_trove(&borrow_operations_wallet, 0, 2_100)
+        .await
+        .unwrap();
+    open_trove(&borrow_operations_wallet, 1, 1_600)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn max_fee_bounds_follow_the_asset_fee_floors() {
+    let (contracts, admin, _wallets) = setup_protocol(2, true, false).await;
+
+    for asset in &contracts.asset_contracts {
+        token_abi::mint_to_id(
+            &asset.asset,
+            20_000 * PRECISION,
+            Identity::Address(admin.address().into()),
+        )
+        .await;
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    // Fee floors above the protocol wide ones on asset 0 and below them on asset 1
+    let (expensive, cheap) = (&contracts.asset_contracts[0], &contracts.asset_contracts[1]);
+    for (asset, borrowing_fee_floor, redemption_fee_floor) in [
+        (expensive, 20_000_000, 30_000_000),
+        (cheap, 2_000_000, 5_000_000),
+    ] {
+        protocol_manager_abi::set_risk_parameters(
+            &contracts.protocol_manager,
+            asset.asset_id,
+            RiskParameters {
+                borrowing_fee_floor,
+                redemption_fee_floor,
+                ..default_risk_parameters()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let open_trove = |index: usize, max_fee_percentage: u64, coll: u64| {
+        let asset = &contracts.asset_contracts[index];
+        borrow_operations_abi::open_trove_with_max_fee(
+            &contracts.borrow_operations,
+            &asset.oracle,
+            &asset.mock_pyth_oracle,
+            &asset.mock_redstone_oracle,
+            &asset.asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &asset.trove_manager,
+            &contracts.active_pool,
+            max_fee_percentage,
+            coll * PRECISION,
+            5_000 * PRECISION,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+    };
+
+    // A 1% max fee is above the protocol wide 0.5% floor but below the 2% charged on asset 0
+    assert_reverts_with!(
+        open_trove(0, 10_000_000, 10_000).await,
+        ProtocolError::BorrowOperationsMaxFeePercentageMustBeBetweenTheBorrowingFeeFloorAnd100
+    );
+    open_trove(0, 20_000_000, 10_000).await.unwrap();
+    // And 0.2% is enough on asset 1
+    open_trove(1, 2_000_000, 7_000).await.unwrap();
+
+    let redeem = |assets: Vec<AssetId>, amount: u64, max_fee_percentage: u64| {
+        protocol_manager_abi::redeem_collateral_from_assets_with_max_fee(
+            &contracts.protocol_manager,
+            amount,
+            assets,
+            10,
+            0,
+            None,
+            None,
+            max_fee_percentage,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.coll_surplus_pool,
+            &contracts.default_pool,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            &contracts.asset_contracts,
+        )
+    };
+    let all_assets = vec![expensive.asset_id, cheap.asset_id];
+
+    // Redeeming from both assets is bounded by the lowest floor among them, asset 1's 0.5%
+    assert_reverts_with!(
+        redeem(all_assets.clone(), 10 * PRECISION, 4_000_000).await,
+        ProtocolError::ProtocolManagerMaxFeePercentageMustBeBetweenTheRedemptionFeeFloorAnd100
+    );
+    // Redeeming from asset 0 alone is bounded by its 3%, even if the max fee clears the 1% default
+    assert_reverts_with!(
+        redeem(vec![expensive.asset_id], 10 * PRECISION, 20_000_000).await,
+        ProtocolError::ProtocolManagerMaxFeePercentageMustBeBetweenTheRedemptionFeeFloorAnd100
+    );
+
+    // Asset 1 holds the riskiest trove, so a redemption from both assets only pays its fee floor
+    let usdm_supply = usdm_token_abi::total_supply(&contracts.usdm)
+        .await
+        .value
+        .unwrap();
+    let redemption_rate = fm_compute_redemption_rate(
+        fm_compute_base_rate_from_redemption(0, 10 * PRECISION, usdm_supply),
+        5_000_000,
+    );
+    assert!(redemption_rate < REDEMPTION_FEE_FLOOR);
+    redeem(all_assets, 10 * PRECISION, redemption_rate)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,6 @@
+use test_utils::scenario::run_scenarios_in_dir;
+
+#[tokio::test]
+async fn yaml_scenarios() {
+    run_scenarios_in_dir("contracts/protocol-manager-contract/tests/scenarios").await;
+}
@@ -13,9 +13,11 @@ use test_utils::{
         pyth_oracle::{pyth_oracle_abi, pyth_price_feed},
         token::token_abi,
         trove_manager::{trove_manager_abi, trove_manager_utils, Status},
+        usdm_token::usdm_token_abi,
     },
+    invariants::{check_invariants, check_invariants_with_accounts},
     setup::common::setup_protocol,
-    utils::with_min_borrow_fee,
+    utils::{redemption_fee, with_min_borrow_fee},
 };
 
 #[tokio::test]
@@ -161,6 +163,11 @@ async fn proper_redemption_from_partially_closed() {
     )
     .await;
 
+    let usdm_supply = usdm_token_abi::total_supply(&contracts.usdm)
+        .await
+        .value
+        .unwrap();
+
     let res = protocol_manager_abi::redeem_collateral(
         &protocol_manager_health1,
         redemption_amount,
@@ -240,8 +247,8 @@ async fn proper_redemption_from_partially_closed() {
         .await
         .unwrap();
 
-    // here we need to calculate the fee and subtract it
-    let redemption_asset_fee = trove_manager_abi::get_redemption_fee(redemption_amount);
+    // The fee is charged at the base rate raised by this redemption
+    let redemption_asset_fee = redemption_fee(redemption_amount, redemption_amount, usdm_supply, 0);
 
     assert_eq!(staking_balance, redemption_asset_fee);
     assert_eq!(mock_balance, redemption_amount - redemption_asset_fee);
@@ -259,6 +266,8 @@ async fn proper_redemption_from_partially_closed() {
         with_min_borrow_fee(5_000 * PRECISION) - 3_000 * PRECISION,
     )
     .await;
+
+    check_invariants(&contracts).await;
 }
 
 #[tokio::test]
@@ -517,4 +526,14 @@ async fn proper_redemption_with_a_trove_closed_fully() {
     .value;
 
     assert_eq!(coll_surplus, coll3 - with_min_borrow_fee(debt3));
+
+    check_invariants_with_accounts(
+        &contracts,
+        &[
+            Identity::Address(healthy_wallet1.address().into()),
+            Identity::Address(healthy_wallet2.address().into()),
+            Identity::Address(healthy_wallet3.address().into()),
+        ],
+    )
+    .await;
 }
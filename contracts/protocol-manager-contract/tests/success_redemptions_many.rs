@@ -6,12 +6,16 @@ use test_utils::interfaces::oracle::oracle_abi;
 use test_utils::interfaces::protocol_manager::ProtocolManager;
 use test_utils::interfaces::pyth_oracle::PYTH_TIMESTAMP;
 use test_utils::{
+    assert_emitted, assert_reverts_with,
+    errors::ProtocolError,
+    events::RedemptionEvent,
     interfaces::{
         active_pool::active_pool_abi,
         protocol_manager::protocol_manager_abi,
         pyth_oracle::{pyth_oracle_abi, pyth_price_feed},
         trove_manager::trove_manager_utils,
     },
+    invariants::check_invariants,
     setup::common::setup_protocol,
     utils::with_min_borrow_fee,
 };
@@ -222,4 +226,197 @@ async fn proper_multi_collateral_redemption_from_partially_closed() {
         with_min_borrow_fee(5_000 * PRECISION) + st_mock_balance + fees2 - redemption_amount,
     )
     .await;
+
+    check_invariants(&contracts).await;
+}
+
+#[tokio::test]
+async fn targeted_redemption_only_walks_the_chosen_assets() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, true, false).await;
+    let untouched = &contracts.asset_contracts[0];
+    let targeted = &contracts.asset_contracts[1];
+
+    for asset in &contracts.asset_contracts {
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    // 6k / 5k on the first asset is the riskiest trove overall,
+    // 7k / 5k and 15k / 5k on the targeted asset
+    let healthy_wallet1 = wallets.pop().unwrap();
+    let healthy_wallet2 = wallets.pop().unwrap();
+    let healthy_wallet3 = wallets.pop().unwrap();
+    for (wallet, asset, coll) in [
+        (&healthy_wallet1, untouched, 6_000),
+        (&healthy_wallet2, targeted, 15_000),
+        (&healthy_wallet3, targeted, 7_000),
+    ] {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            asset,
+            &contracts.borrow_operations,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            coll * PRECISION,
+            5_000 * PRECISION,
+        )
+        .await;
+    }
+
+    let protocol_manager_health1 = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            healthy_wallet1.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    let provider = healthy_wallet1.provider();
+    let redeemer = Identity::Address(healthy_wallet1.address().into());
+    let redemption_amount: u64 = 3_000 * PRECISION;
+
+    let response = protocol_manager_abi::redeem_collateral_from_assets(
+        &protocol_manager_health1,
+        redemption_amount,
+        vec![targeted.asset_id],
+        20,
+        0,
+        None,
+        None,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await
+    .unwrap();
+    assert_emitted!(
+        response,
+        RedemptionEvent {
+            borrower: redeemer,
+            usdm_amount: redemption_amount,
+            ..
+        }
+    );
+
+    // The riskiest trove of the targeted asset absorbs the redemption
+    trove_manager_utils::assert_trove_coll(
+        &targeted.trove_manager,
+        Identity::Address(healthy_wallet3.address().into()),
+        7_000 * PRECISION - redemption_amount,
+    )
+    .await;
+    trove_manager_utils::assert_trove_debt(
+        &targeted.trove_manager,
+        Identity::Address(healthy_wallet3.address().into()),
+        with_min_borrow_fee(5_000 * PRECISION) - redemption_amount,
+    )
+    .await;
+    trove_manager_utils::assert_trove_coll(
+        &targeted.trove_manager,
+        Identity::Address(healthy_wallet2.address().into()),
+        15_000 * PRECISION,
+    )
+    .await;
+
+    // The riskier trove of the other asset is left alone
+    trove_manager_utils::assert_trove_coll(&untouched.trove_manager, redeemer, 6_000 * PRECISION)
+        .await;
+    trove_manager_utils::assert_trove_debt(
+        &untouched.trove_manager,
+        redeemer,
+        with_min_borrow_fee(5_000 * PRECISION),
+    )
+    .await;
+
+    // The redemption fee goes to FPT staking in the targeted asset only
+    let targeted_fee = provider
+        .get_contract_asset_balance(
+            &contracts.fpt_staking.contract.contract_id(),
+            targeted.asset_id,
+        )
+        .await
+        .unwrap();
+    let targeted_balance = provider
+        .get_asset_balance(healthy_wallet1.address(), targeted.asset_id)
+        .await
+        .unwrap();
+    assert!(targeted_fee > 0);
+    assert_eq!(targeted_balance + targeted_fee, redemption_amount);
+    assert_eq!(
+        provider
+            .get_contract_asset_balance(
+                &contracts.fpt_staking.contract.contract_id(),
+                untouched.asset_id,
+            )
+            .await
+            .unwrap(),
+        0
+    );
+
+    check_invariants(&contracts).await;
+}
+
+#[tokio::test]
+async fn targeted_redemption_requires_registered_assets() {
+    let (contracts, _admin, mut wallets) = setup_protocol(4, true, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+
+    let borrower = wallets.pop().unwrap();
+    borrow_operations_utils::mint_token_and_open_trove(
+        borrower.clone(),
+        asset,
+        &contracts.borrow_operations,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    let protocol_manager_borrower = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            borrower.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+
+    for (assets, error) in [
+        (vec![], ProtocolError::ProtocolManagerNoAssetsToRedeemFrom),
+        (
+            vec![asset.asset_id, contracts.usdm_asset_id],
+            ProtocolError::ProtocolManagerAssetIsNotRedeemable,
+        ),
+    ] {
+        assert_reverts_with!(
+            protocol_manager_abi::redeem_collateral_from_assets(
+                &protocol_manager_borrower,
+                1_000 * PRECISION,
+                assets,
+                10,
+                0,
+                None,
+                None,
+                &contracts.usdm,
+                &contracts.fpt_staking,
+                &contracts.coll_surplus_pool,
+                &contracts.default_pool,
+                &contracts.active_pool,
+                &contracts.sorted_troves,
+                &contracts.asset_contracts,
+            )
+            .await,
+            error
+        );
+    }
 }
@@ -86,11 +86,55 @@ impl SortedTroves for Contract {
             .valid_trove_manager
             .insert(Identity::ContractId(trove_manager), true);
     }
+    // Only empty lists can be removed, nothing can be inserted for the asset afterwards
+    #[storage(read, write)]
+    fn remove_asset(asset: AssetId) {
+        require_is_protocol_manager();
+        require(
+            internal_is_empty(asset),
+            "SortedTroves: Asset still has troves",
+        );
+        let trove_manager = storage.asset_trove_manager.get(asset).read();
+        storage
+            .valid_trove_manager
+            .insert(Identity::ContractId(trove_manager), false);
+        let _ = storage.asset_trove_manager.remove(asset);
+    }
     #[storage(read, write)]
     fn remove(id: Identity, asset: AssetId) {
         require_is_trove_manager();
         internal_remove(id, asset);
     }
+    // Gives a node a new id at the same position, for trove transfers
+    #[storage(read, write)]
+    fn replace(id: Identity, new_id: Identity, asset: AssetId) {
+        require_is_trove_manager();
+        require(
+            internal_contains(id, asset),
+            "SortedTroves: Id does not exist",
+        );
+        require(
+            !internal_contains(new_id, asset),
+            "SortedTroves: id already exists",
+        );
+        require(
+            null_identity_address() != new_id,
+            "SortedTroves: id must not be zero",
+        );
+        let node = storage.nodes.get((id, asset)).read();
+        if (node.prev_id == null_identity_address()) {
+            storage.head.insert(asset, new_id);
+        } else {
+            edit_node_neighbors(node.prev_id, Option::None, Option::Some(new_id), asset);
+        }
+        if (node.next_id == null_identity_address()) {
+            storage.tail.insert(asset, new_id);
+        } else {
+            edit_node_neighbors(node.next_id, Option::Some(new_id), Option::None, asset);
+        }
+        let _ = storage.nodes.remove((id, asset));
+        storage.nodes.insert((new_id, asset), node);
+    }
     #[storage(read, write)]
     fn re_insert(
         id: Identity,
@@ -2,9 +2,10 @@ use fuels::prelude::*;
 use fuels::programs::responses::CallResponse;
 use fuels::types::Identity;
 use rand::{self, Rng};
+use test_utils::artifacts::get_artifact_path;
 use test_utils::data_structures::ContractInstance;
 use test_utils::interfaces::sorted_troves::{sorted_troves_abi::initialize, SortedTroves};
-use test_utils::setup::common::{deploy_sorted_troves, get_absolute_path_from_relative};
+use test_utils::setup::common::deploy_sorted_troves;
 
 abigen!(Contract(
     name = "MockTroveManagerContract",
@@ -22,7 +23,7 @@ pub async fn deploy_mock_trove_manager_contract(
     let tx_parms = TxPolicies::default().with_tip(1);
 
     let id = Contract::load_from(
-        &get_absolute_path_from_relative(MOCK_TROVE_MANAGER_BINARY_PATH),
+        &get_artifact_path(MOCK_TROVE_MANAGER_BINARY_PATH),
         LoadConfiguration::default().with_salt(salt),
     )
     .unwrap()
@@ -33,8 +33,9 @@ use libraries::trove_manager_interface::TroveManager;
 use libraries::borrow_operations_interface::BorrowOperations;
 use libraries::community_issuance_interface::CommunityIssuance;
 use libraries::sorted_troves_interface::SortedTroves;
+use libraries::protocol_manager_interface::ProtocolManager;
 use libraries::fluid_math::numbers::*;
-use libraries::fluid_math::{DECIMAL_PRECISION, fm_min, MCR, null_contract, null_identity_address,};
+use libraries::fluid_math::{DECIMAL_PRECISION, fm_min, null_contract, null_identity_address,};
 use std::{
     asset::transfer,
     call_frames::{
@@ -63,6 +64,8 @@ storage {
     sorted_troves_contract: ContractId = ContractId::zero(),
     // List of assets tracked by the Stability Pool
     valid_assets: StorageVec<AssetId> = StorageVec {},
+    // Delisted assets stay in `valid_assets` so depositors can still claim their gains
+    delisted_assets: StorageMap<AssetId, bool> = StorageMap::<AssetId, bool> {},
     // Asset amounts held by the Stability Pool to be claimed
     asset: StorageMap<AssetId, u64> = StorageMap::<AssetId, u64> {},
     // Total amount of USDM held by the Stability Pool
@@ -164,6 +167,11 @@ impl StabilityPool for Contract {
                 },
             );
     }
+    #[storage(read, write)]
+    fn remove_asset(asset_contract: AssetId) {
+        require_is_protocol_manager();
+        storage.delisted_assets.insert(asset_contract, true);
+    }
     /*
     * - Triggers a FPT issuance, based on time passed since the last issuance. The FPT issuance is shared between *all* depositors
     * - Sends depositor's accumulated gains (FPT, Asset1, Asset2...) to depositor
@@ -519,7 +527,7 @@ fn require_caller_is_trove_manager() {
     while i < storage.valid_assets.len() {
         let asset = storage.valid_assets.get(i).unwrap().read();
         let trove_manager_contract = Identity::ContractId(storage.asset_contracts.get(asset).read().trove_manager);
-        if (msg_sender().unwrap() == trove_manager_contract) {
+        if (msg_sender().unwrap() == trove_manager_contract && !internal_is_delisted(asset)) {
             return;
         }
         i += 1;
@@ -527,11 +535,21 @@ fn require_caller_is_trove_manager() {
     require(false, "StabilityPool: Caller is not a trove manager");
 }
 #[storage(read)]
+fn internal_is_delisted(asset: AssetId) -> bool {
+    storage.delisted_assets.get(asset).try_read().unwrap_or(false)
+}
+#[storage(read)]
 fn require_no_undercollateralized_troves() {
     let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().into());
+    let protocol_manager = abi(ProtocolManager, storage.protocol_manager_address.read().into());
     let mut i = 0;
     while i < storage.valid_assets.len() {
         let asset = storage.valid_assets.get(i).unwrap().read();
+        // Delisted assets have no troves left and their oracle may no longer be maintained
+        if (internal_is_delisted(asset)) {
+            i += 1;
+            continue;
+        }
         let asset_contracts = storage.asset_contracts.get(asset).read();
         let trove_manager = abi(TroveManager, asset_contracts.trove_manager.into());
         let oracle = abi(Oracle, asset_contracts.oracle.into());
@@ -539,7 +557,9 @@ fn require_no_undercollateralized_troves() {
         let last = sorted_troves.get_last(asset);
         require(
             last == Identity::Address(Address::zero()) || trove_manager
-                .get_current_icr(last, price) > MCR,
+                .get_current_icr(last, price) > protocol_manager
+                .get_risk_parameters(asset)
+                .mcr,
             "StabilityPool: There are undercollateralized troves",
         );
         i += 1;
@@ -3,13 +3,12 @@ use rand::{self, Rng};
 
 use fuels::programs::responses::CallResponse;
 use fuels::types::Identity;
+use test_utils::artifacts::get_artifact_path;
 use test_utils::data_structures::ContractInstance;
 use test_utils::interfaces::sorted_troves::SortedTroves;
 use test_utils::interfaces::stability_pool::{stability_pool_abi, StabilityPool};
 use test_utils::interfaces::token::{token_abi, Token};
-use test_utils::setup::common::{
-    deploy_active_pool, deploy_stability_pool, deploy_token, get_absolute_path_from_relative,
-};
+use test_utils::setup::common::{deploy_active_pool, deploy_stability_pool, deploy_token};
 
 abigen!(Contract(
     name = "MockTroveManagerContract",
@@ -33,7 +32,7 @@ pub async fn deploy_mock_trove_manager_contract(
     let tx_parms = TxPolicies::default().with_tip(1);
 
     let id = Contract::load_from(
-        &get_absolute_path_from_relative(MOCK_TROVE_MANAGER_BINARY_PATH),
+        &get_artifact_path(MOCK_TROVE_MANAGER_BINARY_PATH),
         LoadConfiguration::default().with_salt(salt),
     )
     .unwrap()
@@ -0,0 +1,81 @@
+contract;
+// Flash mint receiver for tests, repays what it is told to and can try to flash mint again from the
+// callback
+
+use libraries::borrow_operations_interface::BorrowOperations;
+use libraries::flash_mint_receiver_interface::FlashMintReceiver;
+use std::asset::transfer;
+
+storage {
+    borrow_operations_contract: ContractId = ContractId::zero(),
+    usdm_asset_id: AssetId = AssetId::zero(),
+    // Subtracted from the amount and fee sent back
+    shortfall: u64 = 0,
+    reenter: bool = false,
+    last_initiator: Identity = Identity::Address(Address::zero()),
+    last_amount: u64 = 0,
+    last_fee: u64 = 0,
+}
+
+abi MockFlashMintReceiver {
+    #[storage(read, write)]
+    fn initialize(borrow_operations: ContractId, usdm_asset_id: AssetId);
+    #[storage(read, write)]
+    fn set_shortfall(shortfall: u64);
+    #[storage(read, write)]
+    fn set_reenter(reenter: bool);
+    #[storage(read)]
+    fn get_last_flash_mint() -> (Identity, u64, u64);
+}
+
+impl MockFlashMintReceiver for Contract {
+    #[storage(read, write)]
+    fn initialize(borrow_operations: ContractId, usdm_asset_id: AssetId) {
+        storage.borrow_operations_contract.write(borrow_operations);
+        storage.usdm_asset_id.write(usdm_asset_id);
+    }
+    #[storage(read, write)]
+    fn set_shortfall(shortfall: u64) {
+        storage.shortfall.write(shortfall);
+    }
+    #[storage(read, write)]
+    fn set_reenter(reenter: bool) {
+        storage.reenter.write(reenter);
+    }
+    #[storage(read)]
+    fn get_last_flash_mint() -> (Identity, u64, u64) {
+        (
+            storage.last_initiator.read(),
+            storage.last_amount.read(),
+            storage.last_fee.read(),
+        )
+    }
+}
+
+impl FlashMintReceiver for Contract {
+    #[storage(read, write)]
+    fn on_flash_mint(initiator: Identity, amount: u64, fee: u64) {
+        let borrow_operations_contract = storage.borrow_operations_contract.read();
+        require(
+            msg_sender()
+                .unwrap() == Identity::ContractId(borrow_operations_contract),
+            "TestsArtifactsFlashMintReceiverContract: Caller is not the Borrow Operations contract",
+        );
+        storage.last_initiator.write(initiator);
+        storage.last_amount.write(amount);
+        storage.last_fee.write(fee);
+
+        if storage.reenter.read() {
+            let borrow_operations = abi(BorrowOperations, borrow_operations_contract.bits());
+            borrow_operations.flash_mint(amount, ContractId::this());
+        }
+
+        transfer(
+            Identity::ContractId(borrow_operations_contract),
+            storage
+                .usdm_asset_id
+                .read(),
+            amount + fee - storage.shortfall.read(),
+        );
+    }
+}
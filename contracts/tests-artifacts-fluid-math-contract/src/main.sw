@@ -0,0 +1,88 @@
+contract;
+
+use libraries::fluid_math::*;
+use std::u128::U128;
+
+// Exposes the fluid_math library so the Rust mirror in test-utils can be checked against it
+abi FluidMath {
+    fn convert_precision(price: u64, current_precision: u32) -> u64;
+    fn convert_precision_u256_and_downcast(price: u256, current_precision: u32) -> u64;
+    fn fm_compute_borrow_fee(debt: u64) -> u64;
+    fn fm_compute_redemption_fee(debt: u64) -> u64;
+    fn fm_compute_borrowing_rate(base_rate: u64, fee_floor: u64) -> u64;
+    fn fm_compute_redemption_rate(base_rate: u64, fee_floor: u64) -> u64;
+    fn fm_compute_fee(amount: u64, rate: u64) -> u64;
+    fn fm_decay_base_rate(base_rate: u64, minutes_passed: u64) -> u64;
+    fn fm_compute_base_rate_from_redemption(
+        decayed_base_rate: u64,
+        usdm_redeemed: u64,
+        usdm_supply: u64,
+    ) -> u64;
+    fn fm_compute_nominal_cr(coll: u64, debt: u64) -> u64;
+    fn fm_multiply_ratio(value: u64, numerator: u64, denominator: u64) -> u64;
+    fn fm_compute_cr(coll: u64, debt: u64, price: u64) -> u64;
+    fn dec_mul(a: U128, b: U128) -> U128;
+    fn dec_pow(base: u64, minutes: u64) -> U128;
+}
+
+impl FluidMath for Contract {
+    fn convert_precision(price: u64, current_precision: u32) -> u64 {
+        convert_precision(price, current_precision)
+    }
+
+    fn convert_precision_u256_and_downcast(price: u256, current_precision: u32) -> u64 {
+        convert_precision_u256_and_downcast(price, current_precision)
+    }
+
+    fn fm_compute_borrow_fee(debt: u64) -> u64 {
+        fm_compute_borrow_fee(debt)
+    }
+
+    fn fm_compute_redemption_fee(debt: u64) -> u64 {
+        fm_compute_redemption_fee(debt)
+    }
+
+    fn fm_compute_borrowing_rate(base_rate: u64, fee_floor: u64) -> u64 {
+        fm_compute_borrowing_rate(base_rate, fee_floor)
+    }
+
+    fn fm_compute_redemption_rate(base_rate: u64, fee_floor: u64) -> u64 {
+        fm_compute_redemption_rate(base_rate, fee_floor)
+    }
+
+    fn fm_compute_fee(amount: u64, rate: u64) -> u64 {
+        fm_compute_fee(amount, rate)
+    }
+
+    fn fm_decay_base_rate(base_rate: u64, minutes_passed: u64) -> u64 {
+        fm_decay_base_rate(base_rate, minutes_passed)
+    }
+
+    fn fm_compute_base_rate_from_redemption(
+        decayed_base_rate: u64,
+        usdm_redeemed: u64,
+        usdm_supply: u64,
+    ) -> u64 {
+        fm_compute_base_rate_from_redemption(decayed_base_rate, usdm_redeemed, usdm_supply)
+    }
+
+    fn fm_compute_nominal_cr(coll: u64, debt: u64) -> u64 {
+        fm_compute_nominal_cr(coll, debt)
+    }
+
+    fn fm_multiply_ratio(value: u64, numerator: u64, denominator: u64) -> u64 {
+        fm_multiply_ratio(value, numerator, denominator)
+    }
+
+    fn fm_compute_cr(coll: u64, debt: u64, price: u64) -> u64 {
+        fm_compute_cr(coll, debt, price)
+    }
+
+    fn dec_mul(a: U128, b: U128) -> U128 {
+        dec_mul(a, b)
+    }
+
+    fn dec_pow(base: u64, minutes: u64) -> U128 {
+        dec_pow(base, minutes)
+    }
+}
@@ -0,0 +1,328 @@
+use fuels::{prelude::*, programs::calls::Execution, types::U256};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use test_utils::{
+    artifacts::get_artifact_path,
+    fluid_math::{self, DECIMAL_PRECISION, DEC_POW_MAX_MINUTES, MAX_BORROWING_FEE},
+};
+
+abigen!(Contract(
+    name = "FluidMath",
+    abi = "contracts/tests-artifacts-fluid-math-contract/out/debug/tests-artifacts-fluid-math-contract-abi.json"
+));
+
+const FLUID_MATH_BINARY_PATH: &str =
+    "contracts/tests-artifacts-fluid-math-contract/out/debug/tests-artifacts-fluid-math-contract.bin";
+
+const CASES: usize = 64;
+
+async fn setup() -> FluidMath<Wallet> {
+    let wallet = launch_provider_and_get_wallet().await.unwrap();
+
+    let id = Contract::load_from(
+        &get_artifact_path(FLUID_MATH_BINARY_PATH),
+        LoadConfiguration::default(),
+    )
+    .unwrap()
+    .deploy(&wallet, TxPolicies::default().with_tip(1))
+    .await
+    .unwrap()
+    .contract_id;
+
+    FluidMath::new(id, wallet)
+}
+
+// Boundary values mixed with random ones, so both reverting and non reverting paths are hit
+fn sample_u64(rng: &mut StdRng) -> u64 {
+    match rng.gen_range(0..6) {
+        0 => [
+            0,
+            1,
+            DECIMAL_PRECISION,
+            u64::MAX,
+            u64::MAX - 1,
+            u64::MAX / 2,
+        ][rng.gen_range(0..6)],
+        1 => 10u64.pow(rng.gen_range(0..20)),
+        2 => rng.gen_range(0..1_000_000),
+        3 => rng.gen_range(0..1_000_000) * DECIMAL_PRECISION,
+        _ => rng.gen(),
+    }
+}
+
+fn sample_u128(rng: &mut StdRng) -> u128 {
+    match rng.gen_range(0..4) {
+        0 => sample_u64(rng) as u128,
+        1 => rng.gen_range(0..1_000_000_000_000u128) * DECIMAL_PRECISION as u128,
+        2 => u128::MAX - rng.gen_range(0..DECIMAL_PRECISION as u128),
+        _ => rng.gen(),
+    }
+}
+
+// Same value when the Sway function succeeds, a revert when the mirror reports one
+fn assert_matches<V: PartialEq + std::fmt::Debug>(
+    name: &str,
+    inputs: impl std::fmt::Debug,
+    expected: Option<V>,
+    actual: Result<V, Error>,
+) {
+    match (expected, actual) {
+        (Some(expected), Ok(actual)) => {
+            assert_eq!(expected, actual, "{} mismatch for {:?}", name, inputs)
+        }
+        (None, Err(_)) => {}
+        (expected, actual) => panic!(
+            "{} revert mismatch for {:?}: rust {:?}, sway {:?}",
+            name, inputs, expected, actual
+        ),
+    }
+}
+
+#[tokio::test]
+async fn convert_precision_matches_sway() {
+    let contract = setup().await;
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for _ in 0..CASES {
+        let price = sample_u64(&mut rng);
+        let precision = rng.gen_range(0..32);
+        let actual = contract
+            .methods()
+            .convert_precision(price, precision)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "convert_precision",
+            (price, precision),
+            fluid_math::checked_convert_precision(price, precision),
+            actual,
+        );
+
+        let price = U256::from(sample_u128(&mut rng)) * U256::from(sample_u64(&mut rng));
+        let actual = contract
+            .methods()
+            .convert_precision_u256_and_downcast(price, precision)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "convert_precision_u256_and_downcast",
+            (price, precision),
+            fluid_math::checked_convert_precision_u256_and_downcast(price, precision),
+            actual,
+        );
+    }
+}
+
+#[tokio::test]
+async fn fees_match_sway() {
+    let contract = setup().await;
+    let mut rng = StdRng::seed_from_u64(2);
+
+    for _ in 0..CASES {
+        let debt = sample_u64(&mut rng);
+
+        let actual = contract
+            .methods()
+            .fm_compute_borrow_fee(debt)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_compute_borrow_fee",
+            debt,
+            Some(fluid_math::fm_compute_borrow_fee(debt)),
+            actual,
+        );
+
+        let actual = contract
+            .methods()
+            .fm_compute_redemption_fee(debt)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_compute_redemption_fee",
+            debt,
+            Some(fluid_math::fm_compute_redemption_fee(debt)),
+            actual,
+        );
+    }
+}
+
+#[tokio::test]
+async fn base_rate_fees_match_sway() {
+    let contract = setup().await;
+    let mut rng = StdRng::seed_from_u64(5);
+
+    for _ in 0..CASES {
+        // Base rates stay within 100% in the protocol, larger ones exercise the overflow paths
+        let base_rate = match rng.gen_range(0..2) {
+            0 => rng.gen_range(0..=DECIMAL_PRECISION),
+            _ => sample_u64(&mut rng),
+        };
+        let (amount, supply) = (sample_u64(&mut rng), sample_u64(&mut rng));
+        let minutes = rng.gen_range(0..100_000);
+        // Fee floors are bounded by the ProtocolManager, the rates only overflow on the sum
+        let fee_floor = rng.gen_range(0..=MAX_BORROWING_FEE);
+
+        let actual = contract
+            .methods()
+            .fm_compute_borrowing_rate(base_rate, fee_floor)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_compute_borrowing_rate",
+            (base_rate, fee_floor),
+            fluid_math::checked_fm_compute_borrowing_rate(base_rate, fee_floor),
+            actual,
+        );
+
+        let actual = contract
+            .methods()
+            .fm_compute_redemption_rate(base_rate, fee_floor)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_compute_redemption_rate",
+            (base_rate, fee_floor),
+            fluid_math::checked_fm_compute_redemption_rate(base_rate, fee_floor),
+            actual,
+        );
+
+        let actual = contract
+            .methods()
+            .fm_compute_fee(amount, base_rate)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_compute_fee",
+            (amount, base_rate),
+            fluid_math::checked_fm_compute_fee(amount, base_rate),
+            actual,
+        );
+
+        let actual = contract
+            .methods()
+            .fm_decay_base_rate(base_rate, minutes)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_decay_base_rate",
+            (base_rate, minutes),
+            Some(fluid_math::fm_decay_base_rate(base_rate, minutes)),
+            actual,
+        );
+
+        let actual = contract
+            .methods()
+            .fm_compute_base_rate_from_redemption(base_rate, amount, supply)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_compute_base_rate_from_redemption",
+            (base_rate, amount, supply),
+            fluid_math::checked_fm_compute_base_rate_from_redemption(base_rate, amount, supply),
+            actual,
+        );
+    }
+}
+
+#[tokio::test]
+async fn ratios_match_sway() {
+    let contract = setup().await;
+    let mut rng = StdRng::seed_from_u64(3);
+
+    for _ in 0..CASES {
+        let (coll, debt, price) = (
+            sample_u64(&mut rng),
+            sample_u64(&mut rng),
+            sample_u64(&mut rng),
+        );
+
+        let actual = contract
+            .methods()
+            .fm_compute_nominal_cr(coll, debt)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_compute_nominal_cr",
+            (coll, debt),
+            fluid_math::checked_fm_compute_nominal_cr(coll, debt),
+            actual,
+        );
+
+        let actual = contract
+            .methods()
+            .fm_compute_cr(coll, debt, price)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_compute_cr",
+            (coll, debt, price),
+            fluid_math::checked_fm_compute_cr(coll, debt, price),
+            actual,
+        );
+
+        let actual = contract
+            .methods()
+            .fm_multiply_ratio(coll, price, debt)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "fm_multiply_ratio",
+            (coll, price, debt),
+            fluid_math::checked_fm_multiply_ratio(coll, price, debt),
+            actual,
+        );
+    }
+}
+
+#[tokio::test]
+async fn dec_mul_and_dec_pow_match_sway() {
+    let contract = setup().await;
+    let mut rng = StdRng::seed_from_u64(4);
+
+    for _ in 0..CASES {
+        let (a, b) = (sample_u128(&mut rng), sample_u128(&mut rng));
+        let actual = contract
+            .methods()
+            .dec_mul(a, b)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches("dec_mul", (a, b), fluid_math::checked_dec_mul(a, b), actual);
+
+        // Decay factors close to ONE are what the protocol actually feeds into dec_pow
+        let base = match rng.gen_range(0..3) {
+            0 => DECIMAL_PRECISION - rng.gen_range(0..DECIMAL_PRECISION / 100),
+            1 => rng.gen_range(0..3 * DECIMAL_PRECISION),
+            _ => sample_u64(&mut rng),
+        };
+        let minutes = match rng.gen_range(0..3) {
+            0 => rng.gen_range(0..100),
+            1 => DEC_POW_MAX_MINUTES + rng.gen_range(0..2),
+            _ => sample_u64(&mut rng),
+        };
+        let actual = contract
+            .methods()
+            .dec_pow(base, minutes)
+            .simulate(Execution::state_read_only())
+            .await
+            .map(|r| r.value);
+        assert_matches(
+            "dec_pow",
+            (base, minutes),
+            fluid_math::checked_dec_pow(base, minutes),
+            actual,
+        );
+    }
+}
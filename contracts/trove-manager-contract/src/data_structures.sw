@@ -40,6 +40,9 @@ pub struct LocalVariablesLiquidationSequence {
     pub i: u64,
     pub icr: u64,
     pub borrower: Identity,
+    pub entire_system_coll: u64,
+    pub entire_system_debt: u64,
+    pub recovery_mode: bool,
 }
 impl LocalVariablesLiquidationSequence {
     pub fn default() -> Self {
@@ -48,6 +51,9 @@ impl LocalVariablesLiquidationSequence {
             i: 0,
             icr: 0,
             borrower: Identity::Address(Address::zero()),
+            entire_system_coll: 0,
+            entire_system_debt: 0,
+            recovery_mode: false,
         }
     }
 }
@@ -25,6 +25,8 @@ use libraries::default_pool_interface::DefaultPool;
 use libraries::active_pool_interface::ActivePool;
 use libraries::coll_surplus_pool_interface::CollSurplusPool;
 use libraries::oracle_interface::Oracle;
+use libraries::protocol_manager_interface::ProtocolManager;
+use libraries::protocol_manager_interface::data_structures::{AssetStatus, RiskParameters};
 use libraries::trove_manager_interface::data_structures::{
     RewardSnapshot,
     SingleRedemptionValues,
@@ -168,6 +170,18 @@ impl TroveManager for Contract {
         internal_get_entire_system_debt()
     }
     #[storage(read)]
+    fn get_entire_system_coll() -> u64 {
+        internal_get_entire_system_coll()
+    }
+    #[storage(read)]
+    fn get_tcr(price: u64) -> u64 {
+        internal_get_tcr(price)
+    }
+    #[storage(read)]
+    fn check_recovery_mode(price: u64) -> bool {
+        internal_get_tcr(price) < internal_get_risk_parameters().ccr
+    }
+    #[storage(read)]
     fn get_entire_debt_and_coll(id: Identity) -> (u64, u64, u64, u64) {
         let res = internal_get_entire_debt_and_coll(id);
         return (
@@ -247,6 +261,31 @@ impl TroveManager for Contract {
         require_caller_is_borrow_operations_contract();
         internal_close_trove(id, Status::ClosedByOwner);
     }
+    // Moves the trove with its stake, reward snapshot, owners array entry and sorted troves node
+    #[storage(read, write)]
+    fn transfer_trove(from: Identity, to: Identity) {
+        require_caller_is_borrow_operations_contract();
+        require_trove_is_active(from);
+        require(
+            internal_get_trove_status(to) != Status::Active,
+            "TroveManager: Recipient already has an active trove",
+        );
+        let trove = storage.troves.get(from).read();
+        storage.troves.insert(to, trove);
+        storage.troves.insert(from, Trove::default());
+        let reward_snapshot = storage.reward_snapshots.get(from).read();
+        storage.reward_snapshots.insert(to, reward_snapshot);
+        storage.reward_snapshots.insert(
+            from,
+            RewardSnapshot {
+                asset: 0,
+                usdm_debt: 0,
+            },
+        );
+        storage.trove_owners.set(trove.array_index, to);
+        let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().into());
+        sorted_troves.replace(from, to, storage.asset_contract.read());
+    }
     #[storage(read, write)]
     fn remove_stake(id: Identity) {
         require_caller_is_borrow_operations_contract();
@@ -254,10 +293,7 @@ impl TroveManager for Contract {
     }
     #[storage(read)]
     fn get_trove_status(id: Identity) -> Status {
-        match storage.troves.get(id).try_read() {
-            Some(trove) => return trove.status,
-            None => return Status::NonExistent,
-        }
+        internal_get_trove_status(id)
     }
     #[storage(read, write)]
     fn batch_liquidate_troves(
@@ -331,11 +367,17 @@ fn internal_close_trove(id: Identity, close_status: Status) {
     let trove_owner_array_length = storage.trove_owners.len();
     let sorted_troves_contract_cache = storage.sorted_troves_contract.read();
     let sorted_troves = abi(SortedTroves, sorted_troves_contract_cache.into());
-    require_more_than_one_trove_in_system(
-        trove_owner_array_length,
-        asset_contract_cache,
-        sorted_troves_contract_cache,
-    );
+    // The owner or a redemption can close the last trove of a sunset asset so that the asset can be
+    // delisted, liquidations still need another trove to redistribute to
+    let protocol_manager = abi(ProtocolManager, storage.protocol_manager_contract.read().into());
+    let closes_without_redistribution = close_status == Status::ClosedByOwner || close_status == Status::ClosedByRedemption;
+    if (!closes_without_redistribution || protocol_manager.get_asset_status(asset_contract_cache) != AssetStatus::Sunset) {
+        require_more_than_one_trove_in_system(
+            trove_owner_array_length,
+            asset_contract_cache,
+            sorted_troves_contract_cache,
+        );
+    }
     let mut trove = storage.troves.get(id).read();
     trove.status = close_status;
     trove.coll = 0;
@@ -367,6 +409,13 @@ fn internal_remove_trove_owner(_borrower: Identity, _trove_array_owner_length: u
     let _ = storage.trove_owners.swap_remove(index);
 }
 #[storage(read)]
+fn internal_get_trove_status(id: Identity) -> Status {
+    match storage.troves.get(id).try_read() {
+        Some(trove) => return trove.status,
+        None => return Status::NonExistent,
+    }
+}
+#[storage(read)]
 fn require_trove_is_active(id: Identity) {
     let trove = storage.troves.get(id).read();
     require(
@@ -516,6 +565,10 @@ fn internal_decrease_trove_debt(id: Identity, debt: u64) -> u64 {
     storage.troves.insert(id, trove);
     return trove.debt;
 }
+#[storage(read)]
+fn internal_get_risk_parameters() -> RiskParameters {
+    abi(ProtocolManager, storage.protocol_manager_contract.read().into()).get_risk_parameters(storage.asset_contract.read())
+}
 #[storage(read, write)]
 fn internal_get_totals_from_batch_liquidate(
     price: u64,
@@ -530,15 +583,25 @@ fn internal_get_totals_from_batch_liquidate(
     let mut single_liquidation = LiquidationValues::default();
     let mut i = 0;
     let mut totals = LiquidationTotals::default();
+    vars.entire_system_coll = internal_get_entire_system_coll();
+    vars.entire_system_debt = internal_get_entire_system_debt();
+    let risk_parameters = internal_get_risk_parameters();
     // Iterate through the list of borrowers
     while i < borrowers.len() {
         vars.borrower = borrowers.get(i).unwrap();
         // Calculate the Individual Collateralization Ratio (ICR) for the current borrower
         vars.icr = internal_get_current_icr(vars.borrower, price);
-        // If the trove is undercollateralized (ICR < Minimum Collateralization Ratio), liquidate it
-        if vars.icr < MCR {
+        // Recovery mode is checked again after every liquidation, as each one changes the TCR
+        vars.recovery_mode = fm_compute_cr(vars.entire_system_coll, vars.entire_system_debt, price) < risk_parameters.ccr;
+        // Liquidate below the Minimum Collateralization Ratio, or below the Critical Collateralization Ratio in recovery mode
+        if vars.icr < risk_parameters.mcr || (vars.recovery_mode && vars.icr < risk_parameters.ccr) {
             // Get the entire debt and collateral for the trove
             let position = internal_get_entire_debt_and_coll(vars.borrower);
+            // At or above the MCR a trove is only liquidated if the Stability Pool can absorb all of its debt,
+            // so none of it is redistributed to other troves
+            if vars.icr >= risk_parameters.mcr && position.entire_trove_debt > vars.remaining_usdm_in_stability_pool {
+                break;
+            }
             // Move any pending rewards to the active pool before liquidation
             internal_move_pending_trove_rewards_to_active_pool(position.pending_coll_rewards, position.pending_debt_rewards);
             // Calculate the values for offsetting debt and redistributing collateral
@@ -549,6 +612,7 @@ fn internal_get_totals_from_batch_liquidate(
                     .entire_trove_debt,
                 vars.remaining_usdm_in_stability_pool,
                 price,
+                risk_parameters,
             );
             // Apply the liquidation to the trove
             internal_apply_liquidation(
@@ -559,10 +623,13 @@ fn internal_get_totals_from_batch_liquidate(
             );
             // Update the remaining USDM in the stability pool
             vars.remaining_usdm_in_stability_pool -= single_liquidation.debt_to_offset;
+            // Offset debt and collateral leaving the system, redistributed amounts stay in it
+            vars.entire_system_debt -= single_liquidation.debt_to_offset;
+            vars.entire_system_coll -= single_liquidation.coll_to_send_to_sp + single_liquidation.coll_gas_compensation + single_liquidation.coll_surplus;
             // Add the results of this liquidation to the running totals
             totals = add_liquidation_vals_to_totals(totals, single_liquidation);
         } else {
-            // If we've reached a trove that's not undercollateralized, we can stop the liquidation process
+            // If we've reached a trove that can't be liquidated, we can stop the liquidation process
             break;
         }
         i += 1;
@@ -680,6 +747,12 @@ fn internal_redistribute_debt_and_coll(debt: u64, coll: u64) {
     if (debt == 0) {
         return;
     }
+    require(
+        storage
+            .total_stakes
+            .read() > 0,
+        "TroveManager: No stakes to redistribute to",
+    );
     let asset_numerator: U128 = U128::from(coll) * U128::from(DECIMAL_PRECISION) + U128::from(storage.last_asset_error_redistribution.read());
     let usdm_numerator: U128 = U128::from(debt) * U128::from(DECIMAL_PRECISION) + U128::from(storage.last_usdm_error_redistribution.read());
     let asset_reward_per_unit_staked = asset_numerator / U128::from(storage.total_stakes.read());
@@ -776,6 +849,21 @@ fn internal_get_entire_system_debt() -> u64 {
     let asset_contract_cache = storage.asset_contract.read();
     return active_pool.get_usdm_debt(asset_contract_cache) + default_pool.get_usdm_debt(asset_contract_cache);
 }
+#[storage(read)]
+fn internal_get_entire_system_coll() -> u64 {
+    let active_pool = abi(ActivePool, storage.active_pool_contract.read().into());
+    let default_pool = abi(DefaultPool, storage.default_pool_contract.read().into());
+    let asset_contract_cache = storage.asset_contract.read();
+    return active_pool.get_asset(asset_contract_cache) + default_pool.get_asset(asset_contract_cache);
+}
+#[storage(read)]
+fn internal_get_tcr(price: u64) -> u64 {
+    fm_compute_cr(
+        internal_get_entire_system_coll(),
+        internal_get_entire_system_debt(),
+        price,
+    )
+}
 #[storage(read, write)]
 fn internal_redeem_collateral_from_trove(
     borrower: Identity,
@@ -816,7 +904,7 @@ fn internal_redeem_collateral_from_trove(
         // Calculate the new nominal collateralization ratio
         let new_nicr = fm_compute_nominal_cr(new_coll, new_debt);
         // If the new debt is below the minimum allowed, cancel the partial redemption
-        if (new_debt < MIN_NET_DEBT) {
+        if (new_debt < internal_get_risk_parameters().min_net_debt) {
             single_redemption_values.cancelled_partial = true;
             return single_redemption_values;
         }
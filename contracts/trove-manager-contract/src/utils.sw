@@ -3,11 +3,13 @@ library;
 use ::data_structures::{LiquidatedTroveValsInner, LiquidationTotals, LiquidationValues};
 
 use libraries::fluid_math::*;
+use libraries::protocol_manager_interface::data_structures::RiskParameters;
 use std::u128::U128;
 pub fn calculate_liqudated_trove_values(
     coll: u64,
     debt: u64,
     price: u64,
+    risk_parameters: RiskParameters,
 ) -> LiquidatedTroveValsInner {
     // If bad debt
     if fm_multiply_ratio(coll, price, DECIMAL_PRECISION) < debt
@@ -18,8 +20,8 @@ pub fn calculate_liqudated_trove_values(
             is_partial_liquidation: false,
         }
     }
-    let trove_debt_numerator: U128 = U128::from(debt) * U128::from(POST_COLLATERAL_RATIO) - U128::from(coll) * U128::from(price);
-    let trove_debt_denominator: U128 = U128::from(POST_COLLATERAL_RATIO - ONE - STABILITY_POOL_FEE);
+    let trove_debt_numerator: U128 = U128::from(debt) * U128::from(risk_parameters.post_collateral_ratio) - U128::from(coll) * U128::from(price);
+    let trove_debt_denominator: U128 = U128::from(risk_parameters.post_collateral_ratio - ONE - risk_parameters.stability_pool_fee);
     let trove_debt_to_repay = (trove_debt_numerator / trove_debt_denominator).as_u64().unwrap();
     let trove_debt_to_repay = fm_min(trove_debt_to_repay, debt);
     // This calculation is derived from the desired post-liquidation collateral ratio
@@ -28,13 +30,13 @@ pub fn calculate_liqudated_trove_values(
 
     // Numerator: (debt * POST_COLLATERAL_RATIO) - (coll * price)
     // This represents the difference between the desired collateral value and the actual collateral value
-    let mut trove_coll_liquidated = fm_multiply_ratio(trove_debt_to_repay, ONE + STABILITY_POOL_FEE, price);
+    let mut trove_coll_liquidated = fm_multiply_ratio(trove_debt_to_repay, ONE + risk_parameters.stability_pool_fee, price);
 
     // Denominator: POST_COLLATERAL_RATIO - 100% - STABILITY_POOL_FEE
     // This factor adjusts for the desired collateral ratio and the stability pool fee
-    if debt - trove_debt_to_repay < MIN_NET_DEBT {
+    if debt - trove_debt_to_repay < risk_parameters.min_net_debt {
         // Calculate the debt to repay
-        trove_coll_liquidated = fm_multiply_ratio(debt, ONE + STABILITY_POOL_FEE, price);
+        trove_coll_liquidated = fm_multiply_ratio(debt, ONE + risk_parameters.stability_pool_fee, price);
 
         // Ensure we don't repay more than the total debt
         return LiquidatedTroveValsInner {
@@ -55,11 +57,12 @@ pub fn get_offset_and_redistribution_vals(
     debt: u64,
     usdm_in_stab_pool: u64,
     price: u64,
+    risk_parameters: RiskParameters,
 ) -> LiquidationValues {
     let mut vars: LiquidationValues = LiquidationValues::default();
     vars.entire_trove_coll = coll;
     vars.entire_trove_debt = debt;
-    let liquidated_position_vals = calculate_liqudated_trove_values(coll, debt, price);
+    let liquidated_position_vals = calculate_liqudated_trove_values(coll, debt, price, risk_parameters);
     if (liquidated_position_vals.is_partial_liquidation) {
         vars.is_partial_liquidation = true;
         vars.remaining_trove_coll = coll - liquidated_position_vals.trove_coll_liquidated;
@@ -115,7 +118,7 @@ fn test_calculate_liqudated_trove_values() {
     let starting_coll = 550 * DECIMAL_PRECISION;
     let starting_debt = 500 * DECIMAL_PRECISION;
     let price = DECIMAL_PRECISION;
-    let liquidation_vals = calculate_liqudated_trove_values(starting_coll, starting_debt, price);
+    let liquidation_vals = calculate_liqudated_trove_values(starting_coll, starting_debt, price, RiskParameters::default());
     // Value of debt + 5% stability fee
     let coll_liquidated = U128::from(starting_debt) * U128::from(ONE + STABILITY_POOL_FEE) / U128::from(price);
     assert(liquidation_vals.trove_coll_liquidated == coll_liquidated.as_u64().unwrap());
@@ -125,7 +128,7 @@ fn test_calculate_liqudated_trove_values() {
     // Test passes but runs into sway issue of 'TransactionScriptLength'
     // let starting_coll = 12_000 * DECIMAL_PRECISION;
     // let starting_debt = 10_000 * DECIMAL_PRECISION;
-    // let liquidation_vals = calculate_liqudated_trove_values(starting_coll, starting_debt, price);
+    // let liquidation_vals = calculate_liqudated_trove_values(starting_coll, starting_debt, price, RiskParameters::default());
     // let ending_coll = starting_coll - liquidation_vals.trove_coll_liquidated;
     // let ending_debt = starting_debt - liquidation_vals.trove_debt_to_repay;
     // let pcr = fm_compute_cr(ending_coll, ending_debt, price);
@@ -136,7 +139,7 @@ fn test_calculate_liqudated_trove_values_bad_debt() {
     // Full liquidation bad debt
     let starting_coll = 900 * DECIMAL_PRECISION;
     let starting_debt = 1_000 * DECIMAL_PRECISION;
-    let liquidation_vals = calculate_liqudated_trove_values(starting_coll, starting_debt, 1_000_000_000);
+    let liquidation_vals = calculate_liqudated_trove_values(starting_coll, starting_debt, 1_000_000_000, RiskParameters::default());
     assert(liquidation_vals.trove_coll_liquidated == starting_coll);
     assert(liquidation_vals.trove_debt_to_repay == starting_debt);
     assert(liquidation_vals.is_partial_liquidation == false);
@@ -147,7 +150,7 @@ fn test_get_offset_and_redistribution_vals_full_liquidation_empty_pool() {
     let starting_coll = 1_100 * DECIMAL_PRECISION;
     let starting_debt = 1_000 * DECIMAL_PRECISION;
     let price = DECIMAL_PRECISION;
-    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, 0, price);
+    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, 0, price, RiskParameters::default());
     let coll_liquidated = fm_multiply_ratio(starting_debt, ONE + STABILITY_POOL_FEE, price);
     let coll_gas_compensation = coll_liquidated / 200;
     assert(liquidation_vals.entire_trove_coll == starting_coll);
@@ -172,7 +175,7 @@ fn test_get_offset_and_redistribution_vals_full_liquidation_enough_pool() {
     let starting_debt = 1_000 * DECIMAL_PRECISION;
     let amount_in_pool = 2_000 * DECIMAL_PRECISION;
     let price = DECIMAL_PRECISION;
-    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, amount_in_pool, price);
+    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, amount_in_pool, price, RiskParameters::default());
     let coll_liquidated = fm_multiply_ratio(starting_debt, ONE + STABILITY_POOL_FEE, price);
     let coll_gas_compensation = coll_liquidated / 200;
     assert(liquidation_vals.entire_trove_coll == starting_coll);
@@ -197,7 +200,7 @@ fn test_get_offset_and_redistribution_vals_full_liquidation_partial_pool() {
     let starting_debt = 1_000 * DECIMAL_PRECISION;
     let amount_in_pool = 500 * DECIMAL_PRECISION;
     let price = DECIMAL_PRECISION;
-    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, amount_in_pool, price);
+    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, amount_in_pool, price, RiskParameters::default());
     let coll_liquidated = fm_multiply_ratio(starting_debt, ONE + STABILITY_POOL_FEE, price);
     let coll_gas_compensation = coll_liquidated / 200;
     assert(liquidation_vals.entire_trove_coll == starting_coll);
@@ -223,7 +226,7 @@ fn test_get_offset_and_redistribution_vals_partial_liquidation_empty_pool() {
     let starting_coll = 12_000 * DECIMAL_PRECISION;
     let starting_debt = 10_000 * DECIMAL_PRECISION;
     let price = DECIMAL_PRECISION;
-    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, 0, price);
+    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, 0, price, RiskParameters::default());
     let icr = fm_compute_cr(
         liquidation_vals
             .remaining_trove_coll,
@@ -257,7 +260,7 @@ fn test_get_offset_and_redistribution_vals_partial_liquidation_enough_pool() {
     let starting_debt = 10_000 * DECIMAL_PRECISION;
     let amount_in_pool = 20_000 * DECIMAL_PRECISION;
     let price = DECIMAL_PRECISION;
-    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, amount_in_pool, price);
+    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, amount_in_pool, price, RiskParameters::default());
     let icr = fm_compute_cr(
         liquidation_vals
             .remaining_trove_coll,
@@ -291,7 +294,7 @@ fn test_get_offset_and_redistribution_vals_partial_liquidation_partial_pool() {
     let starting_debt = 10_000 * DECIMAL_PRECISION;
     let total_usdm = 1_000 * DECIMAL_PRECISION;
     let price = DECIMAL_PRECISION;
-    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, total_usdm, price);
+    let liquidation_vals = get_offset_and_redistribution_vals(starting_coll, starting_debt, total_usdm, price, RiskParameters::default());
     let icr = fm_compute_cr(
         liquidation_vals
             .remaining_trove_coll,
@@ -1,4 +1,7 @@
 pub mod failure;
+pub mod price_path_liquidations;
+pub mod recovery_mode;
+pub mod scenarios;
 pub mod success_batch_liquidations;
 pub mod success_full_liquidations;
 pub mod success_partial_liquidations;
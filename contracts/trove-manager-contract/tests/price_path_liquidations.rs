@@ -0,0 +1,213 @@
+use fuels::prelude::*;
+use fuels::types::Identity;
+use test_utils::{
+    data_structures::{ContractInstance, PRECISION},
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, BorrowOperations},
+        oracle::{oracle_abi, ORACLE_TIMEOUT},
+        pyth_oracle::PYTH_TIMESTAMP,
+        stability_pool::{stability_pool_abi, StabilityPool},
+        token::token_abi,
+        trove_manager::{trove_manager_abi, Status},
+    },
+    invariants::check_invariants,
+    price_path::{PriceDriver, PricePath, PriceSource},
+    setup::common::setup_protocol,
+};
+
+#[tokio::test]
+async fn liquidates_trove_once_scripted_crash_breaks_mcr() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    // Pyth crashes from 10 to 1 over steps 2..=4, Stork tracks it 1% lower but stops
+    // publishing at step 2, after which the oracle has to fall back to Pyth
+    let pyth_path = PricePath::crash(10 * PRECISION, 1 * PRECISION, 2, 3, 6);
+    let stork_path = pyth_path.deviated(-100).stale_from(2);
+    let expected_prices: Vec<u64> = (0..6)
+        .map(|step| {
+            stork_path
+                .price_at(step)
+                .unwrap_or_else(|| pyth_path.price_at(step).unwrap())
+        })
+        .collect();
+
+    // Each step is longer than the oracle timeout, so a feed that skips a step is stale
+    let mut driver = PriceDriver::new(&contracts, PYTH_TIMESTAMP, ORACLE_TIMEOUT + 1)
+        .with_path(0, PriceSource::Pyth, pyth_path)
+        .with_path(0, PriceSource::Stork, stork_path);
+    assert_eq!(driver.steps(), 6);
+
+    let first_step = driver.step().await.unwrap();
+    assert_eq!(first_step.timestamp, PYTH_TIMESTAMP);
+
+    let liquidated_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+
+    for wallet in [&liquidated_wallet, &healthy_wallet] {
+        token_abi::mint_to_id(
+            &contracts.asset_contracts[0].asset,
+            25_000 * PRECISION,
+            Identity::Address(wallet.address().into()),
+        )
+        .await;
+    }
+
+    let borrow_operations_liquidated_wallet = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            liquidated_wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+
+    let borrow_operations_healthy_wallet = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+
+    borrow_operations_abi::open_trove(
+        &borrow_operations_liquidated_wallet,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        1_100 * PRECISION,
+        1_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    borrow_operations_abi::open_trove(
+        &borrow_operations_healthy_wallet,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let stability_pool_healthy_wallet = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_healthy_wallet,
+        &contracts.community_issuance,
+        &contracts.usdm,
+        &contracts.asset_contracts[0].asset,
+        5_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    let mut liquidated_at = None;
+    let mut step = Some(first_step);
+    while let Some(current) = step {
+        let price = oracle_abi::get_price(&contracts.asset_contracts[0].oracle).await;
+        assert_eq!(
+            price, expected_prices[current.index],
+            "Unexpected oracle price at step {}",
+            current.index
+        );
+
+        if liquidated_at.is_none() {
+            let res = trove_manager_abi::liquidate(
+                &contracts.asset_contracts[0].trove_manager,
+                &contracts.community_issuance,
+                &contracts.stability_pool,
+                &contracts.asset_contracts[0].oracle,
+                &contracts.asset_contracts[0].mock_pyth_oracle,
+                &contracts.asset_contracts[0].mock_redstone_oracle,
+                &contracts.sorted_troves,
+                &contracts.active_pool,
+                &contracts.default_pool,
+                &contracts.coll_surplus_pool,
+                &contracts.usdm,
+                Identity::Address(liquidated_wallet.address().into()),
+                Identity::Address(Address::zeroed()),
+                Identity::Address(Address::zeroed()),
+            )
+            .await;
+
+            if res.is_ok() {
+                liquidated_at = Some(current.index);
+            }
+        }
+
+        step = driver.step().await;
+    }
+
+    // 1_100 collateral against ~1_005 debt only drops below the MCR once the price reaches 1
+    assert_eq!(liquidated_at, Some(4));
+
+    let status = trove_manager_abi::get_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(liquidated_wallet.address().into()),
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_eq!(status, Status::ClosedByLiquidation);
+
+    let status = trove_manager_abi::get_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(healthy_wallet.address().into()),
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_eq!(status, Status::Active);
+
+    check_invariants(&contracts).await;
+}
+
+#[test]
+fn price_path_builders() {
+    let crash = PricePath::crash(100, 10, 1, 3, 6);
+    assert_eq!(
+        (0..6).map(|step| crash.price_at(step)).collect::<Vec<_>>(),
+        vec![Some(100), Some(70), Some(40), Some(10), Some(10), Some(10)]
+    );
+
+    let gapped = PricePath::constant(5, 4).with_gap(1, 3);
+    assert_eq!(
+        (0..4).map(|step| gapped.price_at(step)).collect::<Vec<_>>(),
+        vec![Some(5), None, None, Some(5)]
+    );
+    assert_eq!(gapped.price_at(4), None);
+
+    assert_eq!(
+        PricePath::constant(10_000, 1).deviated(-250).price_at(0),
+        Some(9_750)
+    );
+
+    // Same seed, same path
+    let walk = PricePath::random_walk(1_000 * PRECISION, 50, 200, 7);
+    assert_eq!(walk, PricePath::random_walk(1_000 * PRECISION, 50, 200, 7));
+    assert_eq!(walk.len(), 50);
+    assert_eq!(walk.price_at(0), Some(1_000 * PRECISION));
+}
@@ -0,0 +1,389 @@
+use fuels::prelude::*;
+use fuels::programs::responses::CallResponse;
+use fuels::types::Identity;
+use test_utils::{
+    assert_reverts_with,
+    data_structures::{
+        default_risk_parameters, ContractInstance, ProtocolContracts,
+        POST_LIQUIDATION_COLLATERAL_RATIO, PRECISION,
+    },
+    errors::ProtocolError,
+    fluid_math::{CCR, MIN_MCR},
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, BorrowOperations},
+        oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, RiskParameters},
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
+        stability_pool::{stability_pool_abi, StabilityPool},
+        token::token_abi,
+        trove_manager::{trove_manager_abi, Status},
+    },
+    invariants::check_invariants,
+    setup::common::setup_protocol,
+    utils::{assert_within_threshold, calculate_cr, with_min_borrow_fee},
+};
+
+// Opens a 4400/3000 trove for wallet1, which deposits 2000 USDM into the Stability Pool, and a
+// 1450/1000 trove for wallet2 at a price of 10, then drops the price to 1. Both troves are above MCR
+// but the TCR is 145%, under CCR.
+async fn setup_recovery_mode() -> (ProtocolContracts<Wallet>, Wallet, Wallet) {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let wallet1 = wallets.pop().unwrap();
+    let wallet2 = wallets.pop().unwrap();
+
+    for wallet in [&wallet1, &wallet2] {
+        token_abi::mint_to_id(
+            &contracts.asset_contracts[0].asset,
+            10_000 * PRECISION,
+            Identity::Address(wallet.address().into()),
+        )
+        .await;
+    }
+
+    for (wallet, coll, debt) in [
+        (&wallet1, 4_400 * PRECISION, 3_000 * PRECISION),
+        (&wallet2, 1_450 * PRECISION, 1_000 * PRECISION),
+    ] {
+        let borrow_operations_wallet = ContractInstance::new(
+            BorrowOperations::new(
+                contracts.borrow_operations.contract.contract_id().clone(),
+                wallet.clone(),
+            ),
+            contracts.borrow_operations.implementation_id.clone(),
+        );
+
+        borrow_operations_abi::open_trove(
+            &borrow_operations_wallet,
+            &contracts.asset_contracts[0].oracle,
+            &contracts.asset_contracts[0].mock_pyth_oracle,
+            &contracts.asset_contracts[0].mock_redstone_oracle,
+            &contracts.asset_contracts[0].asset,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.sorted_troves,
+            &contracts.asset_contracts[0].trove_manager,
+            &contracts.active_pool,
+            coll,
+            debt,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await
+        .unwrap();
+    }
+
+    let stability_pool_wallet1 = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_wallet1,
+        &contracts.community_issuance,
+        &contracts.usdm,
+        &contracts.asset_contracts[0].asset,
+        2_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        !trove_manager_abi::check_recovery_mode(
+            &contracts.asset_contracts[0].trove_manager,
+            10 * PRECISION,
+        )
+        .await
+        .value
+    );
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    (contracts, wallet1, wallet2)
+}
+
+async fn liquidate(
+    contracts: &ProtocolContracts<Wallet>,
+    id: Identity,
+) -> Result<CallResponse<()>, Error> {
+    trove_manager_abi::liquidate(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdm,
+        id,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn proper_liquidation_below_ccr_in_recovery_mode() {
+    let (contracts, _wallet1, wallet2) = setup_recovery_mode().await;
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+
+    let system_coll = trove_manager_abi::get_entire_system_coll(trove_manager)
+        .await
+        .value;
+    let system_debt = trove_manager_abi::get_entire_system_debt(trove_manager)
+        .await
+        .value;
+    assert_eq!(system_coll, 5_850 * PRECISION);
+    assert_eq!(system_debt, with_min_borrow_fee(4_000 * PRECISION));
+
+    let tcr = trove_manager_abi::get_tcr(trove_manager, PRECISION)
+        .await
+        .value;
+    assert_eq!(tcr, calculate_cr(PRECISION, system_coll, system_debt));
+    assert!(
+        trove_manager_abi::check_recovery_mode(trove_manager, PRECISION)
+            .await
+            .value
+    );
+
+    // Wallet 2 is at 144%, above MCR but under CCR, so it is brought back to CCR
+    liquidate(&contracts, Identity::Address(wallet2.address().into()))
+        .await
+        .unwrap();
+
+    let status = trove_manager_abi::get_trove_status(
+        trove_manager,
+        Identity::Address(wallet2.address().into()),
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_eq!(status, Status::Active);
+
+    let coll = trove_manager_abi::get_trove_coll(
+        trove_manager,
+        Identity::Address(wallet2.address().into()),
+    )
+    .await
+    .value;
+    let debt = trove_manager_abi::get_trove_debt(
+        trove_manager,
+        Identity::Address(wallet2.address().into()),
+    )
+    .await
+    .value;
+    assert_within_threshold(
+        calculate_cr(PRECISION, coll, debt),
+        POST_LIQUIDATION_COLLATERAL_RATIO,
+        "Partially liquidated trove should be back at CCR",
+    );
+
+    let debt_repaid = with_min_borrow_fee(1_000 * PRECISION) - debt;
+    let usdm_in_sp = stability_pool_abi::get_total_usdm_deposits(&contracts.stability_pool)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(usdm_in_sp, 2_000 * PRECISION - debt_repaid);
+
+    check_invariants(&contracts).await;
+}
+
+#[tokio::test]
+async fn recovery_mode_ends_when_the_tcr_recovers() {
+    let (contracts, wallet1, wallet2) = setup_recovery_mode().await;
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+
+    assert!(
+        trove_manager_abi::check_recovery_mode(trove_manager, PRECISION)
+            .await
+            .value
+    );
+
+    // Adding collateral is always allowed and takes the TCR to 170%
+    let borrow_operations_wallet1 = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+    borrow_operations_abi::add_coll(
+        &borrow_operations_wallet1,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdm,
+        &contracts.sorted_troves,
+        trove_manager,
+        &contracts.active_pool,
+        1_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        !trove_manager_abi::check_recovery_mode(trove_manager, PRECISION)
+            .await
+            .value
+    );
+
+    // Outside recovery mode wallet 2 is above MCR and can't be liquidated
+    let res = liquidate(&contracts, Identity::Address(wallet2.address().into())).await;
+    assert_reverts_with!(res, ProtocolError::TroveManagerNoDebtToLiquidate);
+
+    check_invariants(&contracts).await;
+}
+
+#[tokio::test]
+async fn recovery_mode_liquidation_with_the_lowest_post_collateral_ratio() {
+    let (contracts, _wallet1, wallet2) = setup_recovery_mode().await;
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+    let asset_id = contracts.asset_contracts[0].asset_id;
+    let borrower = Identity::Address(wallet2.address().into());
+
+    // Wallet 2 at 144% would already be above a 140% post collateral ratio while still under CCR
+    let icr = calculate_cr(
+        PRECISION,
+        trove_manager_abi::get_trove_coll(trove_manager, borrower)
+            .await
+            .value,
+        trove_manager_abi::get_trove_debt(trove_manager, borrower)
+            .await
+            .value,
+    );
+    assert!(icr > 1_400_000_000 && icr < CCR);
+    assert_reverts_with!(
+        protocol_manager_abi::set_risk_parameters(
+            &contracts.protocol_manager,
+            asset_id,
+            RiskParameters {
+                mcr: MIN_MCR,
+                post_collateral_ratio: 1_400_000_000,
+                ..default_risk_parameters()
+            },
+        )
+        .await,
+        ProtocolError::ProtocolManagerPostCollateralRatioMustBeBetweenCCRAnd300
+    );
+
+    // The lowest one allowed is CCR, which any trove liquidated in recovery mode is under
+    protocol_manager_abi::set_risk_parameters(
+        &contracts.protocol_manager,
+        asset_id,
+        RiskParameters {
+            mcr: MIN_MCR,
+            post_collateral_ratio: CCR,
+            ..default_risk_parameters()
+        },
+    )
+    .await
+    .unwrap();
+    liquidate(&contracts, borrower).await.unwrap();
+
+    let coll = trove_manager_abi::get_trove_coll(trove_manager, borrower)
+        .await
+        .value;
+    let debt = trove_manager_abi::get_trove_debt(trove_manager, borrower)
+        .await
+        .value;
+    assert_within_threshold(
+        calculate_cr(PRECISION, coll, debt),
+        CCR,
+        "Partially liquidated trove should be back at CCR",
+    );
+
+    check_invariants(&contracts).await;
+}
+
+#[tokio::test]
+async fn recovery_mode_liquidation_above_mcr_needs_the_stability_pool_to_cover_the_debt() {
+    let (contracts, wallet1, wallet2) = setup_recovery_mode().await;
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+    let borrower = Identity::Address(wallet2.address().into());
+
+    // Wallet 1 withdraws down to 500 USDM, less than wallet 2's debt
+    let stability_pool_wallet1 = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+    stability_pool_abi::withdraw_from_stability_pool(
+        &stability_pool_wallet1,
+        &contracts.community_issuance,
+        &contracts.usdm,
+        &contracts.asset_contracts[0].asset,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        trove_manager,
+        1_500 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    let debt = trove_manager_abi::get_trove_debt(trove_manager, borrower)
+        .await
+        .value;
+    assert!(
+        trove_manager_abi::check_recovery_mode(trove_manager, PRECISION)
+            .await
+            .value
+    );
+
+    // Wallet 2 is above MCR, so none of its debt may be redistributed to wallet 1
+    assert_reverts_with!(
+        liquidate(&contracts, borrower).await,
+        ProtocolError::TroveManagerNoDebtToLiquidate
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_status(trove_manager, borrower)
+            .await
+            .unwrap()
+            .value,
+        Status::Active
+    );
+    assert_eq!(
+        trove_manager_abi::get_trove_debt(trove_manager, borrower)
+            .await
+            .value,
+        debt
+    );
+    assert_eq!(
+        trove_manager_abi::get_pending_usdm_reward(
+            trove_manager,
+            Identity::Address(wallet1.address().into()),
+        )
+        .await
+        .value,
+        0
+    );
+
+    check_invariants(&contracts).await;
+}
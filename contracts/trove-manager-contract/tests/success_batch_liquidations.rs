@@ -15,6 +15,7 @@ use test_utils::{
         stability_pool::{stability_pool_abi, StabilityPool},
         trove_manager::{trove_manager_abi, trove_manager_utils, Status},
     },
+    invariants::check_invariants_with_accounts,
     setup::common::setup_protocol,
     utils::with_min_borrow_fee,
 };
@@ -238,4 +239,14 @@ async fn proper_batch_liquidations_enough_usdm_in_sp() {
         liq_coll_surplus, 0,
         "Liquidated wallet collateral surplus was not 0"
     );
+
+    check_invariants_with_accounts(
+        &contracts,
+        &[
+            Identity::Address(liquidated_wallet.address().into()),
+            Identity::Address(liquidated_wallet2.address().into()),
+            Identity::Address(healthy_wallet1.address().into()),
+        ],
+    )
+    .await;
 }
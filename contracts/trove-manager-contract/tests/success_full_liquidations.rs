@@ -19,6 +19,7 @@ use test_utils::{
         token::token_abi,
         trove_manager::{trove_manager_abi, trove_manager_utils, Status},
     },
+    invariants::{check_invariants, check_invariants_with_accounts},
     setup::common::{deploy_multi_trove_getter, setup_protocol},
     utils::{assert_within_threshold, with_liquidation_penalty, with_min_borrow_fee},
 };
@@ -278,6 +279,15 @@ async fn proper_full_liquidation_enough_usdm_in_sp() {
         liq_coll_surplus, 0,
         "Liquidated wallet collateral surplus was not 0"
     );
+
+    check_invariants_with_accounts(
+        &contracts,
+        &[
+            Identity::Address(liquidated_wallet.address().into()),
+            Identity::Address(healthy_wallet1.address().into()),
+        ],
+    )
+    .await;
 }
 
 #[tokio::test]
@@ -586,6 +596,16 @@ async fn proper_full_liquidation_partial_usdm_in_sp() {
         liq_coll_surplus, 0,
         "Liquidated wallet collateral surplus was not 0"
     );
+
+    check_invariants_with_accounts(
+        &contracts,
+        &[
+            Identity::Address(liquidated_wallet.address().into()),
+            Identity::Address(healthy_wallet1.address().into()),
+            Identity::Address(healthy_wallet2.address().into()),
+        ],
+    )
+    .await;
 }
 
 #[tokio::test]
@@ -868,6 +888,16 @@ async fn proper_full_liquidation_empty_sp() {
         1_100 * PRECISION - expected_default_pool_asset - gas_compensation,
         "Liquidated wallet collateral surplus was not 50_000"
     );
+
+    check_invariants_with_accounts(
+        &contracts,
+        &[
+            Identity::Address(liquidated_wallet.address().into()),
+            Identity::Address(healthy_wallet1.address().into()),
+            Identity::Address(healthy_wallet2.address().into()),
+        ],
+    )
+    .await;
 }
 
 #[tokio::test]
@@ -1096,4 +1126,6 @@ async fn test_trove_sorting_after_liquidation_and_rewards() {
         &contracts.asset_contracts[0].asset_id,
     )
     .await;
+
+    check_invariants(&contracts).await;
 }
@@ -16,6 +16,7 @@ use test_utils::{
         token::token_abi,
         trove_manager::{trove_manager_abi, trove_manager_utils, Status},
     },
+    invariants::check_invariants,
     setup::common::setup_protocol,
     utils::{assert_within_threshold, calculate_cr, with_min_borrow_fee},
 };
@@ -241,6 +242,8 @@ async fn proper_partial_liquidation_enough_usdm_in_sp() {
         liq_coll_surplus, 0,
         "Liquidated wallet collateral surplus was not 0"
     );
+
+    check_invariants(&contracts).await;
 }
 
 #[tokio::test]
@@ -595,6 +598,8 @@ async fn proper_partial_liquidation_partial_usdm_in_sp() {
         liq_coll_surplus, 0,
         "Liquidated wallet collateral surplus was not 0"
     );
+
+    check_invariants(&contracts).await;
 }
 
 #[tokio::test]
@@ -913,4 +918,6 @@ async fn proper_partial_liquidation_empty_sp() {
         liq_coll_surplus, 0,
         "Liquidated wallet collateral surplus was not 0"
     );
+
+    check_invariants(&contracts).await;
 }
@@ -17,6 +17,7 @@ pub async fn add_asset(symbol: &str) {
     println!("🔑 Wallet address: {}", address);
     println!("Network name: {}", network_name);
     println!("Is testnet: {}", is_testnet);
+    ensure_deployable_artifacts(is_testnet);
 
     let core_contracts = load_core_contracts(wallet.clone(), is_testnet);
 
@@ -75,6 +76,7 @@ pub async fn add_asset(symbol: &str) {
             None
         },
         redstone_oracle: None, // TODO: Add redstone oracle when it's ready
+        risk_parameters: asset_constants.risk_parameters.clone(),
     };
 
     // Redstone oracle is not required for initialization
@@ -0,0 +1,111 @@
+use crate::utils::utils::{is_testnet, load_asset_id, load_core_contracts, setup_wallet};
+use dotenv::dotenv;
+
+use fuels::accounts::ViewOnlyAccount;
+use test_utils::interfaces::{
+    borrow_operations::borrow_operations_abi, protocol_manager::protocol_manager_abi,
+};
+
+pub async fn pause_asset(symbol: &str) {
+    set_asset_pause_status(symbol, true).await
+}
+
+pub async fn unpause_asset(symbol: &str) {
+    set_asset_pause_status(symbol, false).await
+}
+
+// Pausing an asset only blocks new troves and new debt against it, like pausing the protocol
+async fn set_asset_pause_status(symbol: &str, is_paused: bool) {
+    dotenv().ok();
+
+    let wallet = setup_wallet().await;
+    let address = wallet.address();
+    println!("🔑 Wallet address: {}", address);
+
+    let is_testnet = is_testnet(wallet.clone()).await;
+    let core_contracts = load_core_contracts(wallet.clone(), is_testnet);
+    let asset_id = load_asset_id(symbol, is_testnet);
+    let action = if is_paused { "pause" } else { "unpause" };
+
+    if !confirm(&format!(
+        "Are you sure you want to {} {} ({})? (y/n)",
+        action, symbol, asset_id
+    )) {
+        return;
+    }
+
+    let _ = borrow_operations_abi::set_asset_pause_status(
+        &core_contracts.borrow_operations,
+        asset_id,
+        is_paused,
+    )
+    .await
+    .unwrap();
+
+    println!("{} {}d successfully", symbol, action);
+}
+
+// Sunsetting can't be undone, the asset takes no new troves or debt and is redeemed first
+pub async fn sunset_asset(symbol: &str) {
+    dotenv().ok();
+
+    let wallet = setup_wallet().await;
+    let address = wallet.address();
+    println!("🔑 Wallet address: {}", address);
+
+    let is_testnet = is_testnet(wallet.clone()).await;
+    let core_contracts = load_core_contracts(wallet.clone(), is_testnet);
+    let asset_id = load_asset_id(symbol, is_testnet);
+
+    if !confirm(&format!(
+        "Are you sure you want to sunset {} ({})? This can't be undone (y/n)",
+        symbol, asset_id
+    )) {
+        return;
+    }
+
+    let _ = protocol_manager_abi::sunset_asset(&core_contracts.protocol_manager, asset_id)
+        .await
+        .unwrap();
+
+    println!("{} sunset successfully", symbol);
+}
+
+// Only sunset assets whose last trove is closed can be delisted
+pub async fn delist_asset(symbol: &str) {
+    dotenv().ok();
+
+    let wallet = setup_wallet().await;
+    let address = wallet.address();
+    println!("🔑 Wallet address: {}", address);
+
+    let is_testnet = is_testnet(wallet.clone()).await;
+    let core_contracts = load_core_contracts(wallet.clone(), is_testnet);
+    let asset_id = load_asset_id(symbol, is_testnet);
+
+    if !confirm(&format!(
+        "Are you sure you want to delist {} ({})? (y/n)",
+        symbol, asset_id
+    )) {
+        return;
+    }
+
+    let _ = protocol_manager_abi::delist_asset(&core_contracts.protocol_manager, asset_id)
+        .await
+        .unwrap();
+
+    println!("{} delisted successfully", symbol);
+}
+
+fn confirm(prompt: &str) -> bool {
+    println!("{}", prompt);
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read line");
+    if input.trim().to_lowercase() != "y" {
+        println!("Operation cancelled.");
+        return false;
+    }
+    true
+}
@@ -1,3 +1,5 @@
+use test_utils::interfaces::protocol_manager::RiskParameters;
+
 // References:
 // https://docs.pyth.network/price-feeds/contract-addresses/fuel
 // https://github.com/FuelLabs/verified-assets/blob/main/ASSETS.md
@@ -89,6 +91,8 @@ pub struct AssetConstants {
     pub stork_contract_id: Option<&'static str>,
     pub stork_price_id: Option<&'static str>,
     pub decimals: u32,
+    // The protocol defaults are registered when not set
+    pub risk_parameters: Option<RiskParameters>,
 }
 
 // Asset-specific constants
@@ -102,6 +106,7 @@ pub const TESTNET_FUEL_CONSTANTS: AssetConstants = AssetConstants {
     decimals: 9,
     stork_contract_id: Some(TESTNET_STORK_CONTRACT_ID),
     stork_price_id: Some(STORK_FUEL_PRICE_ID),
+    risk_parameters: None,
 };
 
 pub const TESTNET_STFUEL_CONSTANTS: AssetConstants = AssetConstants {
@@ -113,6 +118,7 @@ pub const TESTNET_STFUEL_CONSTANTS: AssetConstants = AssetConstants {
     decimals: 9,
     stork_contract_id: Some(TESTNET_STORK_CONTRACT_ID),
     stork_price_id: Some(STORK_STFUEL_PRICE_ID),
+    risk_parameters: None,
 };
 
 pub const TESTNET_ETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -124,6 +130,7 @@ pub const TESTNET_ETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: 9,
     stork_contract_id: Some(TESTNET_STORK_CONTRACT_ID),
     stork_price_id: Some(STORK_ETH_PRICE_ID),
+    risk_parameters: None,
 };
 
 pub const TESTNET_WSTETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -135,6 +142,7 @@ pub const TESTNET_WSTETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: 9,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const TESTNET_EZETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -146,6 +154,7 @@ pub const TESTNET_EZETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: 9,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const TESTNET_WEETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -157,6 +166,7 @@ pub const TESTNET_WEETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: 9,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const TESTNET_RSETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -168,6 +178,7 @@ pub const TESTNET_RSETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: 9,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const TESTNET_METH_CONSTANTS: AssetConstants = AssetConstants {
@@ -179,6 +190,7 @@ pub const TESTNET_METH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: 9,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 // Mainnet
@@ -192,6 +204,7 @@ pub const MAINNET_FUEL_CONSTANTS: AssetConstants = AssetConstants {
     decimals: MAINNET_FUEL_DECIMALS,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const MAINNET_ETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -203,6 +216,7 @@ pub const MAINNET_ETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: MAINNET_ETH_DECIMALS,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const MAINNET_WSTETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -214,6 +228,7 @@ pub const MAINNET_WSTETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: MAINNET_WSTETH_DECIMALS,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const MAINNET_EZETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -225,6 +240,7 @@ pub const MAINNET_EZETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: MAINNET_EZETH_DECIMALS,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const MAINNET_WEETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -236,6 +252,7 @@ pub const MAINNET_WEETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: MAINNET_WEETH_DECIMALS,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const MAINNET_RSETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -247,6 +264,7 @@ pub const MAINNET_RSETH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: MAINNET_RSETH_DECIMALS,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 pub const MAINNET_METH_CONSTANTS: AssetConstants = AssetConstants {
@@ -258,6 +276,7 @@ pub const MAINNET_METH_CONSTANTS: AssetConstants = AssetConstants {
     decimals: MAINNET_METH_DECIMALS,
     stork_contract_id: None,
     stork_price_id: None,
+    risk_parameters: None,
 };
 
 // pub const MAINNET_PZETH_CONSTANTS: AssetConstants = AssetConstants {
@@ -9,7 +9,7 @@ use test_utils::interfaces::hint_helper::HintHelper;
 use test_utils::interfaces::multi_trove_getter::MultiTroveGetter;
 
 use crate::constants::{MAINNET_CONTRACTS_FILE, TESTNET_CONTRACTS_FILE};
-use crate::utils::utils::{is_testnet, setup_wallet};
+use crate::utils::utils::{ensure_deployable_artifacts, is_testnet, setup_wallet};
 
 use test_utils::setup::common::{
     deploy_core_contracts, deploy_hint_helper, deploy_multi_trove_getter, initialize_core_contracts,
@@ -32,6 +32,7 @@ pub mod deployment {
         println!("🔑 Wallet address: 0x{}", address);
         println!("🔑 Is testnet: {}", is_testnet);
         println!("🔑 Network name: {}", network_name);
+        ensure_deployable_artifacts(is_testnet);
         //--------------- Deploy ---------------
         let core_contracts =
             deploy_and_initialize_all_core_contracts(wallet.clone(), is_testnet).await;
@@ -42,20 +43,20 @@ pub mod deployment {
         write_contracts_to_file(core_contracts, hint_helper, multi_trove_getter, is_testnet);
     }
 
-    pub async fn deploy_and_initialize_all_core_contracts(
-        wallet: Wallet,
+    pub async fn deploy_and_initialize_all_core_contracts<T: Account + Clone>(
+        wallet: T,
         _is_testnet: bool,
-    ) -> ProtocolContracts<Wallet> {
+    ) -> ProtocolContracts<T> {
         let mut core_contracts = deploy_core_contracts(&wallet, false, true).await;
         initialize_core_contracts(&mut core_contracts, &wallet, false, false, true).await;
 
         return core_contracts;
     }
 
-    pub async fn deploy_frontend_helper_contracts(
-        wallet: Wallet,
-        core_contracts: &ProtocolContracts<Wallet>,
-    ) -> (HintHelper<Wallet>, MultiTroveGetter<Wallet>) {
+    pub async fn deploy_frontend_helper_contracts<T: Account + Clone>(
+        wallet: T,
+        core_contracts: &ProtocolContracts<T>,
+    ) -> (HintHelper<T>, MultiTroveGetter<T>) {
         let hint_helper = deploy_hint_helper(&wallet).await;
         let multi_trove_getter = deploy_multi_trove_getter(
             &wallet,
@@ -66,10 +67,10 @@ pub mod deployment {
         return (hint_helper, multi_trove_getter);
     }
 
-    fn write_contracts_to_file(
-        contracts: ProtocolContracts<Wallet>,
-        hint_helper: HintHelper<Wallet>,
-        multi_trove_getter: MultiTroveGetter<Wallet>,
+    fn write_contracts_to_file<T: Account>(
+        contracts: ProtocolContracts<T>,
+        hint_helper: HintHelper<T>,
+        multi_trove_getter: MultiTroveGetter<T>,
         is_testnet: bool,
     ) {
         let mut file = File::create(match is_testnet {
@@ -1,4 +1,5 @@
 pub mod add_asset;
+pub mod asset_status;
 pub mod constants;
 pub mod deploy;
 pub mod pause;
@@ -1,5 +1,6 @@
 use deploy_scripts::{
     add_asset::add_asset,
+    asset_status::{delist_asset, pause_asset, sunset_asset, unpause_asset},
     deploy::deployment::deploy,
     pause::{pause_protocol, unpause_protocol},
     sanity_check::sanity_check,
@@ -11,7 +12,7 @@ pub async fn main() {
     let args: Vec<String> = std::env::args().collect();
     if args.len() < 2 {
         println!(
-            "Please specify 'deploy', 'add-asset <symbol>', 'pause', 'unpause', 'sanity-check', or 'transfer-owner <address>'"
+            "Please specify 'deploy', 'add-asset <symbol>', 'pause', 'unpause', 'pause-asset <symbol>', 'unpause-asset <symbol>', 'sunset-asset <symbol>', 'delist-asset <symbol>', 'sanity-check', or 'transfer-owner <address>'"
         );
         return;
     }
@@ -27,6 +28,18 @@ pub async fn main() {
         },
         "pause" => pause_protocol().await,
         "unpause" => unpause_protocol().await,
+        "pause-asset" | "unpause-asset" | "sunset-asset" | "delist-asset" => {
+            if args.len() < 3 {
+                println!("Please specify an asset symbol (e.g., '{} ETH')", args[1]);
+                return;
+            }
+            match args[1].as_str() {
+                "pause-asset" => pause_asset(&args[2]).await,
+                "unpause-asset" => unpause_asset(&args[2]).await,
+                "sunset-asset" => sunset_asset(&args[2]).await,
+                _ => delist_asset(&args[2]).await,
+            }
+        },
         "sanity-check" => sanity_check().await,
         "transfer-owner" => {
             if args.len() < 3 {
@@ -36,7 +49,7 @@ pub async fn main() {
             transfer_owner(&args[2]).await
         },
         _ => println!(
-            "Invalid argument. Use 'deploy', 'add-asset <symbol>', 'pause', 'unpause', 'sanity-check', or 'transfer-owner <address>'"
+            "Invalid argument. Use 'deploy', 'add-asset <symbol>', 'pause', 'unpause', 'pause-asset <symbol>', 'unpause-asset <symbol>', 'sunset-asset <symbol>', 'delist-asset <symbol>', 'sanity-check', or 'transfer-owner <address>'"
         ),
     }
 }
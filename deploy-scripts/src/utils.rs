@@ -9,6 +9,7 @@ pub mod utils {
     use std::fs::File;
     use std::io::Write;
     use std::str::FromStr;
+    use test_utils::artifacts::check_artifact;
     use test_utils::data_structures::{
         AssetContracts, AssetContractsOptionalOracles, ContractInstance, PRECISION,
     };
@@ -17,6 +18,7 @@ pub mod utils {
     use test_utils::interfaces::redstone_oracle::redstone_oracle_abi;
     use test_utils::interfaces::stork_oracle::{stork_oracle_abi, StorkCore, I128};
     use test_utils::interfaces::vesting::{VestingSchedule, TOTAL_AMOUNT_VESTED};
+    use test_utils::paths::*;
     use test_utils::setup::common::get_absolute_path_from_relative;
     use test_utils::{
         data_structures::ProtocolContracts,
@@ -362,6 +364,66 @@ pub mod utils {
         protocol_contracts
     }
 
+    // Every artifact `deploy` and `add-asset` may send on chain
+    const DEPLOYED_ARTIFACTS: &[&str] = &[
+        PROXY_CONTRACT_BINARY_PATH,
+        BORROW_OPERATIONS_CONTRACT_BINARY_PATH,
+        USDM_TOKEN_CONTRACT_BINARY_PATH,
+        STABILITY_POOL_CONTRACT_BINARY_PATH,
+        FPT_STAKING_CONTRACT_BINARY_PATH,
+        COMMUNITY_ISSUANCE_CONTRACT_BINARY_PATH,
+        FPT_TOKEN_CONTRACT_BINARY_PATH,
+        PROTCOL_MANAGER_CONTRACT_BINARY_PATH,
+        COLL_SURPLUS_POOL_CONTRACT_BINARY_PATH,
+        DEFAULT_POOL_CONTRACT_BINARY_PATH,
+        ACTIVE_POOL_CONTRACT_BINARY_PATH,
+        SORTED_TROVES_CONTRACT_BINARY_PATH,
+        VESTING_CONTRACT_BINARY_PATH,
+        TROVE_MANAGER_CONTRACT_BINARY_PATH,
+        ORACLE_CONTRACT_BINARY_PATH,
+        HINT_HELPER_CONTRACT_BINARY_PATH,
+        MULTI_TROVE_GETTER_CONTRACT_BINARY_PATH,
+    ];
+
+    // Checked before the first transaction, so a stale artifact can't leave a half deployed protocol
+    pub fn ensure_deployable_artifacts(is_testnet: bool) {
+        if !is_testnet && ARTIFACT_PROFILE != "release" {
+            panic!(
+                "❌ Refusing to deploy {} artifacts to mainnet, build with `forc build --release` and run with `--features release-artifacts`",
+                ARTIFACT_PROFILE
+            );
+        }
+
+        for path in DEPLOYED_ARTIFACTS {
+            if let Err(message) = check_artifact(path) {
+                panic!("❌ {}", message);
+            }
+        }
+    }
+
+    // Asset id of a deployed asset by the symbol it was added with
+    pub fn load_asset_id(symbol: &str, is_testnet: bool) -> AssetId {
+        let json = std::fs::read_to_string(match is_testnet {
+            true => TESTNET_CONTRACTS_FILE,
+            false => MAINNET_CONTRACTS_FILE,
+        })
+        .unwrap();
+        let contracts: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let asset_contract = contracts["asset_contracts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|asset_contract| {
+                asset_contract["symbol"]
+                    .as_str()
+                    .is_some_and(|deployed| deployed.eq_ignore_ascii_case(symbol))
+            })
+            .unwrap_or_else(|| panic!("Asset '{}' is not deployed", symbol));
+
+        AssetId::from_str(asset_contract["asset_id"].as_str().unwrap()).unwrap()
+    }
+
     pub async fn is_testnet(wallet: Wallet) -> bool {
         let network_name = wallet.provider().chain_info().await.unwrap().name;
         network_name.to_lowercase().contains("testnet")
@@ -424,6 +486,15 @@ pub mod utils {
                     None => json!(null),
                 },
                 "fuel_vm_decimals": asset_contract.fuel_vm_decimals,
+                "risk_parameters": {
+                    "mcr": asset_contract.risk_parameters.mcr,
+                    "ccr": asset_contract.risk_parameters.ccr,
+                    "min_net_debt": asset_contract.risk_parameters.min_net_debt,
+                    "borrowing_fee_floor": asset_contract.risk_parameters.borrowing_fee_floor,
+                    "redemption_fee_floor": asset_contract.risk_parameters.redemption_fee_floor,
+                    "post_collateral_ratio": asset_contract.risk_parameters.post_collateral_ratio,
+                    "stability_pool_fee": asset_contract.risk_parameters.stability_pool_fee,
+                },
             }));
         }
 
@@ -0,0 +1,163 @@
+use fuels::{
+    prelude::{Account, Error},
+    tx::Receipt,
+    types::{AssetId, Identity},
+};
+use moor_sdk::{
+    abi::{
+        borrow_operations::{
+            AdjustTroveEvent, CloseTroveEvent, OpenTroveEvent, TransferTroveEvent,
+        },
+        trove_manager::{RedemptionEvent, TroveFullLiquidationEvent, TrovePartialLiquidationEvent},
+    },
+    MoorClient,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::json;
+
+// Trove changes that move a trove in its sorted list, as logged by BorrowOperations and the asset's
+// TroveManager. Collateral and debt are the trove's entire amounts after the change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TroveEvent {
+    Opened {
+        #[serde(with = "json::asset_id")]
+        asset_id: AssetId,
+        #[serde(with = "json::identity")]
+        borrower: Identity,
+        coll: u64,
+        debt: u64,
+    },
+    Adjusted {
+        #[serde(with = "json::asset_id")]
+        asset_id: AssetId,
+        #[serde(with = "json::identity")]
+        borrower: Identity,
+        coll: u64,
+        debt: u64,
+    },
+    Closed {
+        #[serde(with = "json::asset_id")]
+        asset_id: AssetId,
+        #[serde(with = "json::identity")]
+        borrower: Identity,
+    },
+    // The trove keeps its position under the new owner
+    Transferred {
+        #[serde(with = "json::asset_id")]
+        asset_id: AssetId,
+        #[serde(with = "json::identity")]
+        previous_owner: Identity,
+        #[serde(with = "json::identity")]
+        new_owner: Identity,
+    },
+    // Amounts taken from the trove, which closes once its debt is gone
+    Redeemed {
+        #[serde(with = "json::asset_id")]
+        asset_id: AssetId,
+        #[serde(with = "json::identity")]
+        borrower: Identity,
+        usdm_amount: u64,
+        collateral_amount: u64,
+    },
+    PartiallyLiquidated {
+        #[serde(with = "json::asset_id")]
+        asset_id: AssetId,
+        #[serde(with = "json::identity")]
+        borrower: Identity,
+        coll: u64,
+        debt: u64,
+    },
+    Liquidated {
+        #[serde(with = "json::asset_id")]
+        asset_id: AssetId,
+        #[serde(with = "json::identity")]
+        borrower: Identity,
+    },
+}
+
+impl TroveEvent {
+    pub fn asset_id(&self) -> AssetId {
+        match self {
+            TroveEvent::Opened { asset_id, .. }
+            | TroveEvent::Adjusted { asset_id, .. }
+            | TroveEvent::Closed { asset_id, .. }
+            | TroveEvent::Transferred { asset_id, .. }
+            | TroveEvent::Redeemed { asset_id, .. }
+            | TroveEvent::PartiallyLiquidated { asset_id, .. }
+            | TroveEvent::Liquidated { asset_id, .. } => *asset_id,
+        }
+    }
+}
+
+// Trove events in the receipts of a transaction. TroveManager events do not carry the asset, it is
+// taken from the TroveManager that logged them.
+pub fn events_from_receipts<A: Account + Clone>(
+    client: &MoorClient<A>,
+    receipts: &[Receipt],
+) -> Result<Vec<TroveEvent>, Error> {
+    let mut events = Vec::new();
+
+    let borrow_operations = client.borrow_operations().log_decoder();
+    for event in borrow_operations.decode_logs_with_type::<OpenTroveEvent>(receipts)? {
+        events.push(TroveEvent::Opened {
+            asset_id: event.asset_id,
+            borrower: event.user,
+            coll: event.collateral,
+            debt: event.debt,
+        });
+    }
+    for event in borrow_operations.decode_logs_with_type::<AdjustTroveEvent>(receipts)? {
+        events.push(TroveEvent::Adjusted {
+            asset_id: event.asset_id,
+            borrower: event.user,
+            coll: event.total_collateral,
+            debt: event.total_debt,
+        });
+    }
+    for event in borrow_operations.decode_logs_with_type::<CloseTroveEvent>(receipts)? {
+        events.push(TroveEvent::Closed {
+            asset_id: event.asset_id,
+            borrower: event.user,
+        });
+    }
+    for event in borrow_operations.decode_logs_with_type::<TransferTroveEvent>(receipts)? {
+        events.push(TroveEvent::Transferred {
+            asset_id: event.asset_id,
+            previous_owner: event.previous_owner,
+            new_owner: event.new_owner,
+        });
+    }
+
+    for asset in client.deployment().asset_contracts.iter() {
+        let asset_id = asset.asset_id;
+        let trove_manager = client.trove_manager(asset_id)?.log_decoder();
+        for event in trove_manager.decode_logs_with_type::<RedemptionEvent>(receipts)? {
+            events.push(TroveEvent::Redeemed {
+                asset_id,
+                borrower: event.borrower,
+                usdm_amount: event.usdm_amount,
+                collateral_amount: event.collateral_amount,
+            });
+        }
+        for event in
+            trove_manager.decode_logs_with_type::<TrovePartialLiquidationEvent>(receipts)?
+        {
+            events.push(TroveEvent::PartiallyLiquidated {
+                asset_id,
+                borrower: event.borrower,
+                coll: event.remaining_collateral,
+                debt: event.remaining_debt,
+            });
+        }
+        for event in trove_manager.decode_logs_with_type::<TroveFullLiquidationEvent>(receipts)? {
+            events.push(TroveEvent::Liquidated {
+                asset_id,
+                borrower: event.borrower,
+            });
+        }
+    }
+
+    Ok(events)
+}
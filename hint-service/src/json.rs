@@ -0,0 +1,70 @@
+// JSON forms of the Fuel types in the API: asset ids as 0x-prefixed hex, identities as
+// `{"address": "0x…"}` or `{"contract_id": "0x…"}`
+
+pub mod asset_id {
+    use std::str::FromStr;
+
+    use fuels::types::AssetId;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(asset_id: &AssetId, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", asset_id))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<AssetId, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        AssetId::from_str(&value)
+            .map_err(|e| serde::de::Error::custom(format!("invalid asset id {}: {}", value, e)))
+    }
+}
+
+pub mod identity {
+    use std::str::FromStr;
+
+    use fuels::types::{Address, ContractId, Identity};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum IdentityJson {
+        Address(String),
+        ContractId(String),
+    }
+
+    pub fn serialize<S: Serializer>(identity: &Identity, serializer: S) -> Result<S::Ok, S::Error> {
+        match identity {
+            Identity::Address(address) => IdentityJson::Address(format!("0x{}", address)),
+            Identity::ContractId(contract_id) => {
+                IdentityJson::ContractId(format!("0x{}", contract_id))
+            }
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Identity, D::Error> {
+        match IdentityJson::deserialize(deserializer)? {
+            IdentityJson::Address(value) => Address::from_str(&value)
+                .map(Identity::Address)
+                .map_err(|e| serde::de::Error::custom(format!("invalid address {}: {}", value, e))),
+            IdentityJson::ContractId(value) => ContractId::from_str(&value)
+                .map(Identity::ContractId)
+                .map_err(|e| {
+                    serde::de::Error::custom(format!("invalid contract id {}: {}", value, e))
+                }),
+        }
+    }
+
+    pub mod option {
+        use fuels::types::Identity;
+        use serde::{Deserialize, Deserializer};
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Identity>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] Identity);
+
+            Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(identity)| identity))
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod events;
+pub mod json;
+pub mod mirror;
+pub mod server;
+pub mod service;
+
+pub use service::HintService;
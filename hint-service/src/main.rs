@@ -0,0 +1,62 @@
+use std::{sync::Arc, time::Duration};
+
+use dotenv::dotenv;
+use fuels::prelude::Provider;
+use hint_service::{server::serve, HintService};
+use moor_sdk::{Deployment, MoorClient};
+use tokio::net::TcpListener;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8090";
+
+#[tokio::main]
+pub async fn main() {
+    dotenv().ok();
+
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 {
+        println!(
+            "Please specify the deployment manifest (e.g., 'hint-service mainnet.contracts.json')"
+        );
+        return;
+    }
+
+    let deployment = match Deployment::from_file(&args[1]) {
+        Ok(deployment) => deployment,
+        Err(error) => panic!("❌ Cannot read deployment manifest: {:#?}", error),
+    };
+    let rpc = std::env::var("RPC").expect("RPC must be set to the node's GraphQL endpoint");
+    let provider = match Provider::connect(rpc).await {
+        Ok(p) => p,
+        Err(error) => panic!("❌ Problem creating provider: {:#?}", error),
+    };
+
+    let service = match HintService::bootstrap(MoorClient::read_only(deployment, provider)).await {
+        Ok(service) => Arc::new(service),
+        Err(error) => panic!("❌ Cannot read sorted troves: {}", error),
+    };
+
+    // Without an indexer posting to /events the mirror only follows the chain through resyncs
+    if let Ok(secs) = std::env::var("HINT_SERVICE_RESYNC_SECS") {
+        let secs: u64 = secs
+            .parse()
+            .expect("HINT_SERVICE_RESYNC_SECS must be a number of seconds");
+        let service = service.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(secs));
+            loop {
+                interval.tick().await;
+                if let Err(error) = service.resync_all().await {
+                    eprintln!("Resync failed: {}", error);
+                }
+            }
+        });
+    }
+
+    let addr = std::env::var("HINT_SERVICE_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+    let listener = TcpListener::bind(&addr)
+        .await
+        .unwrap_or_else(|error| panic!("❌ Cannot listen on {}: {}", addr, error));
+    println!("Serving hints on {}", addr);
+
+    serve(service, listener).await.unwrap();
+}
@@ -0,0 +1,254 @@
+use std::fmt;
+
+use fuels::types::{AssetId, Identity};
+use moor_sdk::{
+    abi::protocol_manager::AssetStatus,
+    hints::{find_insert_position, ListedTrove, SortedTrove},
+    redemption::{quote_redemption, AssetTroves, RedemptionError, RedemptionQuote},
+    risk_parameters::RiskParameters,
+};
+
+use crate::events::TroveEvent;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MirrorError {
+    UnknownAsset(AssetId),
+    Redemption(RedemptionError),
+}
+
+impl fmt::Display for MirrorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MirrorError::UnknownAsset(asset_id) => {
+                write!(f, "Asset 0x{} is not mirrored", asset_id)
+            }
+            MirrorError::Redemption(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for MirrorError {}
+
+#[derive(Debug, Clone)]
+struct MirroredAsset {
+    asset_id: AssetId,
+    // Head to tail
+    troves: Vec<ListedTrove>,
+    // Set when an event may have changed troves it does not name, the asset has to be read again
+    stale: bool,
+}
+
+// In-memory copy of every asset's sorted troves. Troves keep their entire collateral and debt, so the
+// NICRs are the ones SortedTroves compares against through `get_nominal_icr`.
+#[derive(Debug, Clone, Default)]
+pub struct SortedTrovesMirror {
+    // In the order the assets were registered with the ProtocolManager
+    assets: Vec<MirroredAsset>,
+}
+
+impl SortedTrovesMirror {
+    pub fn new(asset_ids: &[AssetId]) -> Self {
+        Self {
+            assets: asset_ids
+                .iter()
+                .map(|asset_id| MirroredAsset {
+                    asset_id: *asset_id,
+                    troves: Vec::new(),
+                    stale: true,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn asset_ids(&self) -> Vec<AssetId> {
+        self.assets.iter().map(|asset| asset.asset_id).collect()
+    }
+
+    // Replaces the asset's list with a fresh read, head to tail
+    pub fn load(&mut self, asset_id: AssetId, troves: Vec<ListedTrove>) -> Result<(), MirrorError> {
+        let asset = self.asset_mut(asset_id)?;
+        asset.troves = troves;
+        asset.stale = false;
+        Ok(())
+    }
+
+    pub fn troves(&self, asset_id: AssetId) -> Result<&[ListedTrove], MirrorError> {
+        Ok(&self.asset(asset_id)?.troves)
+    }
+
+    pub fn stale_assets(&self) -> Vec<AssetId> {
+        self.assets
+            .iter()
+            .filter(|asset| asset.stale)
+            .map(|asset| asset.asset_id)
+            .collect()
+    }
+
+    pub fn apply(&mut self, event: &TroveEvent) -> Result<(), MirrorError> {
+        let asset = self.asset_mut(event.asset_id())?;
+        match *event {
+            TroveEvent::Opened {
+                borrower,
+                coll,
+                debt,
+                ..
+            }
+            | TroveEvent::Adjusted {
+                borrower,
+                coll,
+                debt,
+                ..
+            } => asset.upsert(ListedTrove {
+                id: borrower,
+                coll,
+                debt,
+            }),
+            TroveEvent::Closed { borrower, .. } => {
+                asset.remove(borrower);
+            }
+            TroveEvent::Transferred {
+                previous_owner,
+                new_owner,
+                ..
+            } => match asset
+                .troves
+                .iter_mut()
+                .find(|trove| trove.id == previous_owner)
+            {
+                Some(trove) => trove.id = new_owner,
+                None => asset.stale = true,
+            },
+            TroveEvent::Redeemed {
+                borrower,
+                usdm_amount,
+                collateral_amount,
+                ..
+            } => {
+                let redeemed = asset.remove(borrower).and_then(|trove| {
+                    Some(ListedTrove {
+                        id: trove.id,
+                        coll: trove.coll.checked_sub(collateral_amount)?,
+                        debt: trove.debt.checked_sub(usdm_amount)?,
+                    })
+                });
+                match redeemed {
+                    Some(trove) if trove.debt > 0 => asset.upsert(trove),
+                    Some(_) => {}
+                    None => asset.stale = true,
+                }
+            }
+            // Whatever the Stability Pool does not offset is redistributed to every other trove
+            TroveEvent::PartiallyLiquidated {
+                borrower,
+                coll,
+                debt,
+                ..
+            } => {
+                asset.upsert(ListedTrove {
+                    id: borrower,
+                    coll,
+                    debt,
+                });
+                asset.stale = true;
+            }
+            TroveEvent::Liquidated { borrower, .. } => {
+                asset.remove(borrower);
+                asset.stale = true;
+            }
+        }
+        Ok(())
+    }
+
+    // The exact `(upper_hint, lower_hint)` for a trove at `nicr`, leaving out `borrower` when it is
+    // re-inserted
+    pub fn insert_position(
+        &self,
+        asset_id: AssetId,
+        nicr: u64,
+        borrower: Option<Identity>,
+    ) -> Result<(Identity, Identity), MirrorError> {
+        let troves: Vec<SortedTrove> = self
+            .asset(asset_id)?
+            .troves
+            .iter()
+            .filter(|trove| Some(trove.id) != borrower)
+            .map(ListedTrove::sorted)
+            .collect();
+        Ok(find_insert_position(&troves, nicr))
+    }
+
+    // `prices` has to cover every mirrored asset, each with its risk parameters and status.
+    // Delisted assets are left out as the ProtocolManager no longer redeems against them.
+    pub fn quote_redemption(
+        &self,
+        usdm_amount: u64,
+        max_iterations: u64,
+        decayed_base_rate: u64,
+        usdm_supply: u64,
+        prices: &[(AssetId, u64, RiskParameters, AssetStatus)],
+    ) -> Result<RedemptionQuote, MirrorError> {
+        let mut assets = Vec::with_capacity(self.assets.len());
+        for asset in self.assets.iter() {
+            let (price, risk_parameters, status) = prices
+                .iter()
+                .find(|(asset_id, _, _, _)| *asset_id == asset.asset_id)
+                .map(|(_, price, risk_parameters, status)| {
+                    (*price, risk_parameters.clone(), status.clone())
+                })
+                .ok_or(MirrorError::UnknownAsset(asset.asset_id))?;
+            if status == AssetStatus::Delisted {
+                continue;
+            }
+            assets.push(AssetTroves {
+                asset_id: asset.asset_id,
+                price,
+                risk_parameters,
+                is_sunset: status == AssetStatus::Sunset,
+                troves: asset.troves.clone(),
+            });
+        }
+
+        quote_redemption(
+            usdm_amount,
+            max_iterations,
+            decayed_base_rate,
+            usdm_supply,
+            &assets,
+        )
+        .map_err(MirrorError::Redemption)
+    }
+
+    fn asset(&self, asset_id: AssetId) -> Result<&MirroredAsset, MirrorError> {
+        self.assets
+            .iter()
+            .find(|asset| asset.asset_id == asset_id)
+            .ok_or(MirrorError::UnknownAsset(asset_id))
+    }
+
+    fn asset_mut(&mut self, asset_id: AssetId) -> Result<&mut MirroredAsset, MirrorError> {
+        self.assets
+            .iter_mut()
+            .find(|asset| asset.asset_id == asset_id)
+            .ok_or(MirrorError::UnknownAsset(asset_id))
+    }
+}
+
+impl MirroredAsset {
+    // Inserts in front of troves with the same NICR, as SortedTroves does
+    fn upsert(&mut self, trove: ListedTrove) {
+        self.remove(trove.id);
+        let sorted: Vec<SortedTrove> = self.troves.iter().map(ListedTrove::sorted).collect();
+        let (_, next) = find_insert_position(&sorted, trove.sorted().nicr);
+        let position = self
+            .troves
+            .iter()
+            .position(|other| other.id == next)
+            .unwrap_or(self.troves.len());
+        self.troves.insert(position, trove);
+    }
+
+    fn remove(&mut self, borrower: Identity) -> Option<ListedTrove> {
+        let position = self.troves.iter().position(|trove| trove.id == borrower)?;
+        Some(self.troves.remove(position))
+    }
+}
@@ -0,0 +1,233 @@
+use std::sync::Arc;
+
+use fuels::{
+    prelude::Account,
+    types::{AssetId, Identity},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{
+    events::TroveEvent,
+    json,
+    mirror::MirrorError,
+    service::{HintService, ServiceError},
+};
+
+// Requests larger than this are refused, a batch of events stays well below it
+const MAX_BODY_SIZE: usize = 1 << 20;
+
+#[derive(Deserialize)]
+struct InsertPositionRequest {
+    #[serde(with = "json::asset_id")]
+    asset_id: AssetId,
+    nicr: u64,
+    // The trove being re-inserted, left out of the list
+    #[serde(default, deserialize_with = "json::identity::option::deserialize")]
+    borrower: Option<Identity>,
+}
+
+#[derive(Serialize)]
+struct InsertPositionResponse {
+    #[serde(with = "json::identity")]
+    upper_hint: Identity,
+    #[serde(with = "json::identity")]
+    lower_hint: Identity,
+}
+
+#[derive(Deserialize)]
+struct RedemptionHintsRequest {
+    usdm_amount: u64,
+    max_iterations: u64,
+}
+
+#[derive(Serialize)]
+struct RedemptionHintsResponse {
+    partial_redemption_hint: u64,
+    #[serde(with = "json::identity")]
+    upper_partial_hint: Identity,
+    #[serde(with = "json::identity")]
+    lower_partial_hint: Identity,
+    usdm_redeemed: u64,
+    remaining_usdm: u64,
+    redemption_rate: u64,
+}
+
+struct Response {
+    status: u16,
+    body: serde_json::Value,
+}
+
+impl Response {
+    fn ok(body: impl Serialize) -> Self {
+        Self {
+            status: 200,
+            body: serde_json::to_value(body).expect("Responses serialize to JSON"),
+        }
+    }
+
+    fn error(status: u16, message: impl ToString) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.to_string() }),
+        }
+    }
+}
+
+impl From<ServiceError> for Response {
+    fn from(e: ServiceError) -> Self {
+        let status = match e {
+            ServiceError::Mirror(MirrorError::UnknownAsset(_)) => 404,
+            ServiceError::Mirror(MirrorError::Redemption(_)) => 400,
+            ServiceError::Fuel(_) => 500,
+        };
+        Response::error(status, e)
+    }
+}
+
+// JSON over HTTP/1.1, one request per connection:
+//   GET  /health
+//   POST /insert-position    {"asset_id", "nicr", "borrower"?} -> {"upper_hint", "lower_hint"}
+//   POST /redemption-hints   {"usdm_amount", "max_iterations"} -> hints for `redeem_collateral`
+//   POST /events             [TroveEvent] from an indexer
+//   POST /resync             reads every asset again
+pub async fn serve<A>(service: Arc<HintService<A>>, listener: TcpListener) -> std::io::Result<()>
+where
+    A: Account + Clone + Send + Sync + 'static,
+{
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let service = service.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(&service, stream).await {
+                eprintln!("Connection failed: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection<A: Account + Clone>(
+    service: &HintService<A>,
+    stream: TcpStream,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let response = if content_length > MAX_BODY_SIZE {
+        Response::error(413, "Request body too large")
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await?;
+        route(service, &method, &path, &body).await
+    };
+
+    let body = response.body.to_string();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    let mut stream = reader.into_inner();
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn route<A: Account + Clone>(
+    service: &HintService<A>,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> Response {
+    match (method, path) {
+        ("GET", "/health") => Response::ok(json!({ "status": "ok" })),
+        ("POST", "/insert-position") => {
+            let request: InsertPositionRequest = match parse(body) {
+                Ok(request) => request,
+                Err(response) => return response,
+            };
+            match service
+                .insert_position(request.asset_id, request.nicr, request.borrower)
+                .await
+            {
+                Ok((upper_hint, lower_hint)) => Response::ok(InsertPositionResponse {
+                    upper_hint,
+                    lower_hint,
+                }),
+                Err(e) => e.into(),
+            }
+        }
+        ("POST", "/redemption-hints") => {
+            let request: RedemptionHintsRequest = match parse(body) {
+                Ok(request) => request,
+                Err(response) => return response,
+            };
+            match service
+                .quote_redemption(request.usdm_amount, request.max_iterations)
+                .await
+            {
+                Ok(quote) => Response::ok(RedemptionHintsResponse {
+                    partial_redemption_hint: quote.partial_redemption_hint,
+                    upper_partial_hint: quote.upper_partial_hint,
+                    lower_partial_hint: quote.lower_partial_hint,
+                    usdm_redeemed: quote.usdm_redeemed,
+                    remaining_usdm: quote.remaining_usdm,
+                    redemption_rate: quote.redemption_rate,
+                }),
+                Err(e) => e.into(),
+            }
+        }
+        ("POST", "/events") => {
+            let events: Vec<TroveEvent> = match parse(body) {
+                Ok(events) => events,
+                Err(response) => return response,
+            };
+            match service.apply_events(&events).await {
+                Ok(()) => Response::ok(json!({ "applied": events.len() })),
+                Err(e) => e.into(),
+            }
+        }
+        ("POST", "/resync") => match service.resync_all().await {
+            Ok(()) => Response::ok(json!({ "status": "ok" })),
+            Err(e) => e.into(),
+        },
+        _ => Response::error(404, format!("No route for {} {}", method, path)),
+    }
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> Result<T, Response> {
+    serde_json::from_slice(body).map_err(|e| Response::error(400, e))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+
+use fuels::{
+    prelude::{Account, Error},
+    tx::Receipt,
+    types::{AssetId, Identity},
+};
+use moor_sdk::{redemption::RedemptionQuote, MoorClient};
+use tokio::sync::RwLock;
+
+use crate::{
+    events::{events_from_receipts, TroveEvent},
+    mirror::{MirrorError, SortedTrovesMirror},
+};
+
+#[derive(Debug)]
+pub enum ServiceError {
+    Mirror(MirrorError),
+    Fuel(Error),
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::Mirror(e) => write!(f, "{}", e),
+            ServiceError::Fuel(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+impl From<MirrorError> for ServiceError {
+    fn from(e: MirrorError) -> Self {
+        ServiceError::Mirror(e)
+    }
+}
+
+impl From<Error> for ServiceError {
+    fn from(e: Error) -> Self {
+        ServiceError::Fuel(e)
+    }
+}
+
+// Answers hint queries from the mirror instead of walking SortedTroves on-chain. Assets are read again
+// through MultiTroveGetter on bootstrap and after liquidations, every other trove event is applied in
+// place.
+pub struct HintService<A: Account + Clone> {
+    client: MoorClient<A>,
+    mirror: RwLock<SortedTrovesMirror>,
+}
+
+impl<A: Account + Clone> HintService<A> {
+    pub fn new(client: MoorClient<A>) -> Self {
+        let asset_ids: Vec<AssetId> = client
+            .deployment()
+            .asset_contracts
+            .iter()
+            .map(|asset| asset.asset_id)
+            .collect();
+        Self {
+            client,
+            mirror: RwLock::new(SortedTrovesMirror::new(&asset_ids)),
+        }
+    }
+
+    pub async fn bootstrap(client: MoorClient<A>) -> Result<Self, ServiceError> {
+        let service = Self::new(client);
+        service.resync_all().await?;
+        Ok(service)
+    }
+
+    pub fn client(&self) -> &MoorClient<A> {
+        &self.client
+    }
+
+    pub async fn resync(&self, asset_id: AssetId) -> Result<(), ServiceError> {
+        let troves = self.client.listed_troves(asset_id).await?;
+        self.mirror.write().await.load(asset_id, troves)?;
+        Ok(())
+    }
+
+    pub async fn resync_all(&self) -> Result<(), ServiceError> {
+        let asset_ids = self.mirror.read().await.asset_ids();
+        for asset_id in asset_ids {
+            self.resync(asset_id).await?;
+        }
+        Ok(())
+    }
+
+    // Events have to come in the order they were logged
+    pub async fn apply_events(&self, events: &[TroveEvent]) -> Result<(), ServiceError> {
+        let stale_assets = {
+            let mut mirror = self.mirror.write().await;
+            for event in events {
+                mirror.apply(event)?;
+            }
+            mirror.stale_assets()
+        };
+        for asset_id in stale_assets {
+            self.resync(asset_id).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn apply_receipts(&self, receipts: &[Receipt]) -> Result<(), ServiceError> {
+        let events = events_from_receipts(&self.client, receipts)?;
+        self.apply_events(&events).await
+    }
+
+    pub async fn snapshot(&self) -> SortedTrovesMirror {
+        self.mirror.read().await.clone()
+    }
+
+    pub async fn insert_position(
+        &self,
+        asset_id: AssetId,
+        nicr: u64,
+        borrower: Option<Identity>,
+    ) -> Result<(Identity, Identity), ServiceError> {
+        Ok(self
+            .mirror
+            .read()
+            .await
+            .insert_position(asset_id, nicr, borrower)?)
+    }
+
+    // Prices, risk parameters, asset statuses, the base rate and the USDM supply are read from the
+    // contracts, the troves come from the mirror
+    pub async fn quote_redemption(
+        &self,
+        usdm_amount: u64,
+        max_iterations: u64,
+    ) -> Result<RedemptionQuote, ServiceError> {
+        let asset_ids = self.mirror.read().await.asset_ids();
+        let mut prices = Vec::with_capacity(asset_ids.len());
+        for asset_id in asset_ids {
+            prices.push((
+                asset_id,
+                self.client.price(asset_id).await?,
+                self.client.risk_parameters(asset_id).await?,
+                self.client.asset_status(asset_id).await?,
+            ));
+        }
+        let decayed_base_rate = self.client.decayed_base_rate().await?;
+        let usdm_supply = self.client.usdm_supply().await?;
+
+        Ok(self.mirror.read().await.quote_redemption(
+            usdm_amount,
+            max_iterations,
+            decayed_base_rate,
+            usdm_supply,
+            &prices,
+        )?)
+    }
+}
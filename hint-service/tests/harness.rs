@@ -0,0 +1,2 @@
+mod mirror;
+mod service;
@@ -0,0 +1,185 @@
+use fuels::types::{Address, AssetId, Identity};
+use hint_service::{events::TroveEvent, mirror::SortedTrovesMirror};
+use moor_sdk::hints::{null_hint, ListedTrove};
+use test_utils::data_structures::PRECISION;
+
+fn id(byte: u8) -> Identity {
+    Identity::Address(Address::new([byte; 32]))
+}
+
+fn trove(byte: u8, coll: u64, debt: u64) -> ListedTrove {
+    ListedTrove {
+        id: id(byte),
+        coll: coll * PRECISION,
+        debt: debt * PRECISION,
+    }
+}
+
+fn ids(mirror: &SortedTrovesMirror, asset_id: AssetId) -> Vec<Identity> {
+    mirror
+        .troves(asset_id)
+        .unwrap()
+        .iter()
+        .map(|trove| trove.id)
+        .collect()
+}
+
+#[test]
+fn events_keep_the_list_sorted() {
+    let asset_id = AssetId::zeroed();
+    let mut mirror = SortedTrovesMirror::new(&[asset_id]);
+    assert_eq!(mirror.stale_assets(), vec![asset_id]);
+
+    mirror
+        .load(
+            asset_id,
+            vec![
+                trove(1, 4_000, 1_000),
+                trove(2, 3_000, 1_000),
+                trove(3, 2_000, 1_000),
+            ],
+        )
+        .unwrap();
+    assert!(mirror.stale_assets().is_empty());
+
+    // A trove with the same NICR as another goes in front of it
+    mirror
+        .apply(&TroveEvent::Opened {
+            asset_id,
+            borrower: id(4),
+            coll: 6_000 * PRECISION,
+            debt: 2_000 * PRECISION,
+        })
+        .unwrap();
+    assert_eq!(ids(&mirror, asset_id), vec![id(1), id(4), id(2), id(3)]);
+
+    mirror
+        .apply(&TroveEvent::Adjusted {
+            asset_id,
+            borrower: id(1),
+            coll: 4_000 * PRECISION,
+            debt: 1_600 * PRECISION,
+        })
+        .unwrap();
+    assert_eq!(ids(&mirror, asset_id), vec![id(4), id(2), id(1), id(3)]);
+
+    // Redemptions take amounts off the trove, and close it once the debt is gone
+    mirror
+        .apply(&TroveEvent::Redeemed {
+            asset_id,
+            borrower: id(3),
+            usdm_amount: 1_000 * PRECISION,
+            collateral_amount: 1_000 * PRECISION,
+        })
+        .unwrap();
+    assert_eq!(ids(&mirror, asset_id), vec![id(4), id(2), id(1)]);
+    mirror
+        .apply(&TroveEvent::Redeemed {
+            asset_id,
+            borrower: id(2),
+            usdm_amount: 500 * PRECISION,
+            collateral_amount: 500 * PRECISION,
+        })
+        .unwrap();
+    assert_eq!(ids(&mirror, asset_id), vec![id(2), id(4), id(1)]);
+    assert_eq!(mirror.troves(asset_id).unwrap()[0], trove(2, 2_500, 500));
+
+    mirror
+        .apply(&TroveEvent::Closed {
+            asset_id,
+            borrower: id(4),
+        })
+        .unwrap();
+    assert_eq!(ids(&mirror, asset_id), vec![id(2), id(1)]);
+    assert!(mirror.stale_assets().is_empty());
+
+    // Re-inserting a trove ignores its current position
+    let nicr = trove(1, 4_000, 1_600).sorted().nicr;
+    assert_eq!(
+        mirror.insert_position(asset_id, nicr, None).unwrap(),
+        (id(2), id(1))
+    );
+    assert_eq!(
+        mirror.insert_position(asset_id, nicr, Some(id(1))).unwrap(),
+        (id(2), null_hint())
+    );
+
+    // Redistribution touches every trove, the asset has to be read again
+    mirror
+        .apply(&TroveEvent::Liquidated {
+            asset_id,
+            borrower: id(1),
+        })
+        .unwrap();
+    assert_eq!(ids(&mirror, asset_id), vec![id(2)]);
+    assert_eq!(mirror.stale_assets(), vec![asset_id]);
+
+    let other_asset = AssetId::new([1; 32]);
+    assert!(mirror
+        .apply(&TroveEvent::Closed {
+            asset_id: other_asset,
+            borrower: id(2),
+        })
+        .is_err());
+}
+
+#[test]
+fn transferred_troves_keep_their_position() {
+    let asset_id = AssetId::zeroed();
+    let mut mirror = SortedTrovesMirror::new(&[asset_id]);
+    mirror
+        .load(
+            asset_id,
+            vec![
+                trove(1, 4_000, 1_000),
+                trove(2, 3_000, 1_000),
+                trove(3, 2_000, 1_000),
+            ],
+        )
+        .unwrap();
+
+    mirror
+        .apply(&TroveEvent::Transferred {
+            asset_id,
+            previous_owner: id(2),
+            new_owner: id(4),
+        })
+        .unwrap();
+    assert_eq!(ids(&mirror, asset_id), vec![id(1), id(4), id(3)]);
+    assert_eq!(
+        mirror.troves(asset_id).unwrap()[1],
+        ListedTrove {
+            id: id(4),
+            ..trove(2, 3_000, 1_000)
+        }
+    );
+    assert!(mirror.stale_assets().is_empty());
+
+    // A transfer of a trove the mirror does not know means it missed events
+    mirror
+        .apply(&TroveEvent::Transferred {
+            asset_id,
+            previous_owner: id(2),
+            new_owner: id(5),
+        })
+        .unwrap();
+    assert_eq!(mirror.stale_assets(), vec![asset_id]);
+}
+
+#[test]
+fn events_round_trip_through_json() {
+    let event = TroveEvent::Redeemed {
+        asset_id: AssetId::new([7; 32]),
+        borrower: id(1),
+        usdm_amount: 10,
+        collateral_amount: 20,
+    };
+    let value = serde_json::to_value(event).unwrap();
+    assert_eq!(value["type"], "redeemed");
+    assert_eq!(value["asset_id"], format!("0x{}", "07".repeat(32)));
+    assert_eq!(
+        value["borrower"]["address"],
+        format!("0x{}", "01".repeat(32))
+    );
+    assert_eq!(serde_json::from_value::<TroveEvent>(value).unwrap(), event);
+}
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use fuels::{prelude::*, types::Identity};
+use hint_service::{server::serve, HintService};
+use moor_sdk::{
+    fluid_math::fm_compute_nominal_cr, hints::null_hint, preview::TroveAdjustment, MoorClient,
+};
+use test_utils::{
+    data_structures::{MAX_FEE_PERCENTAGE, PRECISION},
+    interfaces::{
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        sorted_troves::sorted_troves_abi,
+        token::token_abi,
+    },
+    setup::common::{deploy_multi_trove_getter, deployment_of, setup_protocol},
+    utils::with_min_borrow_fee,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+async fn request(addr: std::net::SocketAddr, method: &str, path: &str, body: &str) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+fn json_body(response: &str) -> serde_json::Value {
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    serde_json::from_str(body).unwrap()
+}
+
+#[tokio::test]
+async fn mirror_follows_trove_events() {
+    let (contracts, admin, mut wallets) = setup_protocol(6, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    let multi_trove_getter = deploy_multi_trove_getter(
+        &admin,
+        &contracts.sorted_troves.contract.contract_id().into(),
+    )
+    .await;
+    let mut deployment = deployment_of(&contracts);
+    deployment.multi_trove_getter = Some(multi_trove_getter.contract_id().into());
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+
+    let service = HintService::bootstrap(MoorClient::read_only(
+        deployment.clone(),
+        admin.provider().clone(),
+    ))
+    .await
+    .unwrap();
+    assert!(service
+        .snapshot()
+        .await
+        .troves(asset.asset_id)
+        .unwrap()
+        .is_empty());
+
+    // Every trove opens at the position SortedTroves finds searching from the head
+    let mut clients = Vec::new();
+    for coll in [2_000, 3_000, 4_000, 10_000] {
+        let wallet = wallets.pop().unwrap();
+        let owner = Identity::Address(wallet.address().into());
+        token_abi::mint_to_id(&asset.asset, coll * PRECISION, owner).await;
+
+        let nicr = fm_compute_nominal_cr(coll * PRECISION, with_min_borrow_fee(1_000 * PRECISION));
+        let hints = service
+            .insert_position(asset.asset_id, nicr, None)
+            .await
+            .unwrap();
+        let searched = sorted_troves_abi::find_insert_position(
+            &contracts.sorted_troves,
+            &asset.trove_manager.contract,
+            nicr,
+            null_hint(),
+            null_hint(),
+            asset.asset_id,
+        )
+        .await
+        .value;
+        assert_eq!(hints, searched);
+
+        let client = MoorClient::new(deployment.clone(), wallet);
+        let response = client
+            .open_trove(
+                asset.asset_id,
+                MAX_FEE_PERCENTAGE,
+                coll * PRECISION,
+                1_000 * PRECISION,
+                hints.0,
+                hints.1,
+            )
+            .await
+            .unwrap();
+        service
+            .apply_receipts(&response.tx_status.receipts)
+            .await
+            .unwrap();
+        clients.push(client);
+    }
+    assert_eq!(
+        service.snapshot().await.troves(asset.asset_id).unwrap(),
+        service
+            .client()
+            .listed_troves(asset.asset_id)
+            .await
+            .unwrap()
+    );
+
+    // Borrowing more moves the 3000 collateral trove to the tail
+    let borrower = &clients[1];
+    let withdraw = borrower
+        .preview_adjustment(
+            asset.asset_id,
+            borrower.identity(),
+            TroveAdjustment::WithdrawUsdm(800 * PRECISION),
+        )
+        .await
+        .unwrap();
+    let hints = service
+        .insert_position(asset.asset_id, withdraw.nicr, Some(borrower.identity()))
+        .await
+        .unwrap();
+    assert_eq!(hints, (withdraw.upper_hint, withdraw.lower_hint));
+    let response = borrower
+        .withdraw_usdm(
+            asset.asset_id,
+            MAX_FEE_PERCENTAGE,
+            800 * PRECISION,
+            hints.0,
+            hints.1,
+        )
+        .await
+        .unwrap();
+    service
+        .apply_receipts(&response.tx_status.receipts)
+        .await
+        .unwrap();
+    let troves = service
+        .client()
+        .listed_troves(asset.asset_id)
+        .await
+        .unwrap();
+    assert_eq!(troves.last().unwrap().id, borrower.identity());
+    assert_eq!(
+        service.snapshot().await.troves(asset.asset_id).unwrap(),
+        troves
+    );
+
+    // Redemption hints come from the mirror and leave it in step with the chain
+    let redeemer = &clients[3];
+    let quote = service.quote_redemption(300 * PRECISION, 10).await.unwrap();
+    assert_eq!(
+        quote,
+        redeemer
+            .quote_redemption(300 * PRECISION, 10)
+            .await
+            .unwrap()
+    );
+    let response = redeemer
+        .redeem_collateral(
+            300 * PRECISION,
+            10,
+            quote.partial_redemption_hint,
+            quote.upper_partial_hint,
+            quote.lower_partial_hint,
+            quote.redemption_rate,
+        )
+        .await
+        .unwrap();
+    service
+        .apply_receipts(&response.tx_status.receipts)
+        .await
+        .unwrap();
+    assert_eq!(
+        service.snapshot().await.troves(asset.asset_id).unwrap(),
+        service
+            .client()
+            .listed_troves(asset.asset_id)
+            .await
+            .unwrap()
+    );
+
+    // The same answers over HTTP
+    let service = Arc::new(service);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(serve(service.clone(), listener));
+
+    let health = request(addr, "GET", "/health", "").await;
+    assert!(health.starts_with("HTTP/1.1 200"));
+
+    let nicr = fm_compute_nominal_cr(5_000 * PRECISION, 1_000 * PRECISION);
+    let (upper_hint, lower_hint) = service
+        .insert_position(asset.asset_id, nicr, None)
+        .await
+        .unwrap();
+    let body = serde_json::json!({
+        "asset_id": format!("0x{}", asset.asset_id),
+        "nicr": nicr,
+    });
+    let response = request(addr, "POST", "/insert-position", &body.to_string()).await;
+    assert!(response.starts_with("HTTP/1.1 200"));
+    let hints = json_body(&response);
+    let hex = |identity: Identity| match identity {
+        Identity::Address(address) => format!("0x{}", address),
+        Identity::ContractId(contract_id) => format!("0x{}", contract_id),
+    };
+    assert_eq!(hints["upper_hint"]["address"], hex(upper_hint));
+    assert_eq!(hints["lower_hint"]["address"], hex(lower_hint));
+
+    let quote = service.quote_redemption(200 * PRECISION, 10).await.unwrap();
+    let body = serde_json::json!({ "usdm_amount": 200 * PRECISION, "max_iterations": 10 });
+    let response = request(addr, "POST", "/redemption-hints", &body.to_string()).await;
+    assert!(response.starts_with("HTTP/1.1 200"));
+    let hints = json_body(&response);
+    assert_eq!(
+        hints["partial_redemption_hint"],
+        quote.partial_redemption_hint
+    );
+    assert_eq!(
+        hints["upper_partial_hint"]["address"],
+        hex(quote.upper_partial_hint)
+    );
+    assert_eq!(hints["usdm_redeemed"], quote.usdm_redeemed);
+    assert_eq!(hints["redemption_rate"], quote.redemption_rate);
+
+    let body = serde_json::json!({
+        "asset_id": format!("0x{}", AssetId::zeroed()),
+        "nicr": nicr,
+    });
+    let response = request(addr, "POST", "/insert-position", &body.to_string()).await;
+    assert!(response.starts_with("HTTP/1.1 404"));
+    assert!(json_body(&response)["error"].is_string());
+
+    let response = request(addr, "POST", "/insert-position", "{").await;
+    assert!(response.starts_with("HTTP/1.1 400"));
+}
@@ -1,5 +1,8 @@
 library;
 
+pub mod data_structures;
+use data_structures::OperatorPermissions;
+
 abi BorrowOperations {
     #[storage(read, write)]
     fn initialize(
@@ -27,8 +30,13 @@ abi BorrowOperations {
     #[storage(read, write)]
     fn renounce_owner();
 
-    #[storage(read), payable]
-    fn open_trove(usdm_amount: u64, upper_hint: Identity, lower_hint: Identity);
+    #[storage(read, write), payable]
+    fn open_trove(
+        max_fee_percentage: u64,
+        usdm_amount: u64,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    );
 
     #[storage(read, write), payable]
     fn add_coll(upper_hint: Identity, lower_hint: Identity);
@@ -43,6 +51,7 @@ abi BorrowOperations {
 
     #[storage(read, write)]
     fn withdraw_usdm(
+        max_fee_percentage: u64,
         amount: u64,
         upper_hint: Identity,
         lower_hint: Identity,
@@ -52,9 +61,62 @@ abi BorrowOperations {
     #[storage(read, write), payable]
     fn repay_usdm(upper_hint: Identity, lower_hint: Identity, asset: AssetId);
 
+    #[storage(read, write), payable]
+    fn adjust_trove(
+        max_fee_percentage: u64,
+        coll_withdrawal: u64,
+        usdm_change: u64,
+        is_debt_increase: bool,
+        upper_hint: Identity,
+        lower_hint: Identity,
+        asset: AssetId,
+    );
+
     #[storage(read, write), payable]
     fn close_trove(asset: AssetId);
 
+    #[storage(read, write)]
+    fn set_operator(operator: Identity, asset: AssetId, permissions: OperatorPermissions);
+
+    #[storage(read)]
+    fn get_operator_permissions(
+        owner: Identity,
+        operator: Identity,
+        asset: AssetId,
+    ) -> OperatorPermissions;
+
+    #[storage(read, write), payable]
+    fn adjust_trove_for(
+        owner: Identity,
+        max_fee_percentage: u64,
+        coll_withdrawal: u64,
+        usdm_change: u64,
+        is_debt_increase: bool,
+        upper_hint: Identity,
+        lower_hint: Identity,
+        asset: AssetId,
+    );
+
+    #[storage(read, write), payable]
+    fn close_trove_for(owner: Identity, asset: AssetId);
+
+    #[storage(read, write)]
+    fn propose_trove_transfer(new_owner: Identity, asset: AssetId);
+
+    #[storage(read, write)]
+    fn cancel_trove_transfer(asset: AssetId);
+
+    #[storage(read, write)]
+    fn accept_trove_transfer(previous_owner: Identity, asset: AssetId);
+
+    #[storage(read)]
+    fn get_pending_trove_transfer(owner: Identity, asset: AssetId) -> Option<Identity>;
+
+    #[storage(read, write)]
+    fn flash_mint(amount: u64, receiver: ContractId);
+
+    fn get_flash_mint_fee(amount: u64) -> u64;
+
     #[storage(read)]
     fn claim_collateral(asset: AssetId);
 
@@ -69,4 +131,10 @@ abi BorrowOperations {
 
     #[storage(read)]
     fn get_is_paused() -> bool;
+
+    #[storage(read, write)]
+    fn set_asset_pause_status(asset: AssetId, is_paused: bool);
+
+    #[storage(read)]
+    fn get_asset_is_paused(asset: AssetId) -> bool;
 }
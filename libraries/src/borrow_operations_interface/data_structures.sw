@@ -0,0 +1,25 @@
+library;
+
+// What an operator may do with the trove of the owner who approved it
+pub struct OperatorPermissions {
+    pub add_coll: bool,
+    pub repay: bool,
+    pub withdraw_coll: bool,
+    pub borrow: bool,
+    pub close: bool,
+    // Withdrawn collateral and borrowed USDM go to the operator instead of the trove owner
+    pub withdraw_to_operator: bool,
+}
+
+impl OperatorPermissions {
+    pub fn none() -> Self {
+        OperatorPermissions {
+            add_coll: false,
+            repay: false,
+            withdraw_coll: false,
+            borrow: false,
+            close: false,
+            withdraw_to_operator: false,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+library;
+
+// Implemented by contracts taking USDM flash mints from BorrowOperations
+abi FlashMintReceiver {
+    // Called once `amount` USDM is minted to the receiver, which has to send `amount + fee` USDM
+    // back to BorrowOperations before returning
+    #[storage(read, write)]
+    fn on_flash_mint(initiator: Identity, amount: u64, fee: u64);
+}
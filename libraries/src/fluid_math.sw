@@ -16,7 +16,19 @@ pub const REDEMPTION_FEE_FLOOR: u64 = 10_000_000;
 // Min borrowing fee is 0.5%
 pub const BORROWING_FEE_FLOOR: u64 = 5_000_000;
 
+// Max borrowing fee is 5%
+pub const MAX_BORROWING_FEE: u64 = 50_000_000;
+
+// Flash mint fee is 0.1%
+pub const FLASH_MINT_FEE: u64 = 1_000_000;
+
+// Half-life of 12h, 0.5 = MINUTE_DECAY_FACTOR ^ 720
+pub const MINUTE_DECAY_FACTOR: u64 = 999_037_758;
+
 pub const MCR: u64 = 1_350_000_000;
+
+// Default critical collateral ratio, below it the asset is in recovery mode
+pub const CCR: u64 = 1_500_000_000;
 // 10 USDM 
 pub const USDM_GAS_COMPENSATION: u64 = 10_000_000;
 
@@ -34,6 +46,14 @@ pub const LIQUIDATOR_EXECUTION_GAS_FEE: u64 = 5_000_000;
 pub const ONE: u64 = 1_000_000_000;
 pub const BETA: u64 = 2;
 
+// Hard bounds the owner can set the per-asset risk parameters within
+pub const MIN_MCR: u64 = 1_100_000_000;
+pub const MIN_NET_DEBT_LOWER_BOUND: u64 = 100_000_000_000;
+pub const MIN_NET_DEBT_UPPER_BOUND: u64 = 100_000_000_000_000;
+pub const MAX_REDEMPTION_FEE_FLOOR: u64 = 50_000_000;
+pub const MAX_STABILITY_POOL_FEE: u64 = 200_000_000;
+pub const MAX_POST_COLLATERAL_RATIO: u64 = 3_000_000_000;
+
 pub fn convert_precision(price: u64, current_precision: u32) -> u64 {
     let mut adjusted_price = 0;
     if current_precision > 9 {
@@ -80,6 +100,42 @@ pub fn fm_compute_redemption_fee(debt: u64) -> u64 {
     return fee.as_u64().unwrap();
 }
 
+// Floor plus base rate, capped at 5%
+pub fn fm_compute_borrowing_rate(base_rate: u64, fee_floor: u64) -> u64 {
+    fm_min(fee_floor + base_rate, MAX_BORROWING_FEE)
+}
+
+// Floor plus base rate, capped at 100%
+pub fn fm_compute_redemption_rate(base_rate: u64, fee_floor: u64) -> u64 {
+    fm_min(fee_floor + base_rate, DECIMAL_PRECISION)
+}
+
+pub fn fm_compute_fee(amount: u64, rate: u64) -> u64 {
+    let fee = U128::from(amount) * U128::from(rate) / U128::from(DECIMAL_PRECISION);
+    return fee.as_u64().unwrap();
+}
+
+// dec_pow returns the base for zero minutes, so the base rate is only decayed once a minute has passed
+pub fn fm_decay_base_rate(base_rate: u64, minutes_passed: u64) -> u64 {
+    if minutes_passed == 0 {
+        return base_rate;
+    }
+    let decay_factor = dec_pow(MINUTE_DECAY_FACTOR, minutes_passed);
+    let decayed = U128::from(base_rate) * decay_factor / U128::from(DECIMAL_PRECISION);
+    return decayed.as_u64().unwrap();
+}
+
+// Increases the decayed base rate by half the redeemed fraction of the USDM supply, capped at 100%
+pub fn fm_compute_base_rate_from_redemption(
+    decayed_base_rate: u64,
+    usdm_redeemed: u64,
+    usdm_supply: u64,
+) -> u64 {
+    let redeemed_fraction = U128::from(usdm_redeemed) * U128::from(DECIMAL_PRECISION) / U128::from(usdm_supply);
+    let new_base_rate = U128::from(decayed_base_rate) + redeemed_fraction / U128::from(BETA);
+    return fm_min_u128(new_base_rate, U128::from(DECIMAL_PRECISION)).as_u64().unwrap();
+}
+
 pub fn fm_compute_nominal_cr(coll: u64, debt: u64) -> u64 {
     if (debt > 0) {
         let ncr: U128 = U128::from(coll) * U128::from(DECIMAL_PRECISION) / U128::from(debt);
@@ -197,6 +253,13 @@ fn test_dec_pow_two() {
     assert(9_000_000_000 == result.as_u64().unwrap());
 }
 
+#[test]
+fn test_decay_base_rate_half_life() {
+    let base_rate = 100_000_000;
+    assert(fm_decay_base_rate(base_rate, 0) == base_rate);
+    assert_within_percent_tolerance(fm_decay_base_rate(base_rate, 720), base_rate / 2, 1_000_000);
+}
+
 #[test]
 fn test_precision_less_than_current() {
     let price = 1_000_000_000_000;
@@ -3,6 +3,7 @@ library;
 pub mod sorted_troves_interface;
 pub mod trove_manager_interface;
 pub mod borrow_operations_interface;
+pub mod flash_mint_receiver_interface;
 pub mod fluid_math;
 pub mod token_interface;
 pub mod active_pool_interface;
@@ -1,5 +1,7 @@
 library;
 
+pub mod data_structures;
+use data_structures::{AssetStatus, RiskParameters};
 use standards::src5::State;
 abi ProtocolManager {
     #[storage(read, write)]
@@ -13,14 +15,32 @@ abi ProtocolManager {
         active_pool: ContractId,
         sorted_troves: ContractId,
         admin: Identity,
+        debugging: bool,
     );
     #[storage(read, write)]
     fn register_asset(
         asset_address: AssetId,
         trove_manager: ContractId,
         oracle: ContractId,
+        risk_parameters: RiskParameters,
     );
     #[storage(read, write)]
+    fn set_risk_parameters(asset: AssetId, risk_parameters: RiskParameters);
+    #[storage(read)]
+    fn get_risk_parameters(asset: AssetId) -> RiskParameters;
+    #[storage(read, write)]
+    fn set_debt_ceiling(asset: AssetId, debt_ceiling: u64);
+    #[storage(read)]
+    fn get_debt_ceiling(asset: AssetId) -> u64;
+    #[storage(read)]
+    fn get_remaining_debt_capacity(asset: AssetId) -> u64;
+    #[storage(read, write)]
+    fn sunset_asset(asset: AssetId);
+    #[storage(read, write)]
+    fn delist_asset(asset: AssetId);
+    #[storage(read)]
+    fn get_asset_status(asset: AssetId) -> AssetStatus;
+    #[storage(read, write)]
     fn renounce_admin();
     #[storage(read, write), payable]
     fn redeem_collateral(
@@ -28,7 +48,33 @@ abi ProtocolManager {
         partial_redemption_hint: u64,
         upper_partial_hint: Identity,
         lower_partial_hint: Identity,
+        max_fee_percentage: u64,
+    );
+    #[storage(read, write), payable]
+    fn redeem_collateral_from_assets(
+        assets: Vec<AssetId>,
+        max_iterations: u64,
+        partial_redemption_hint: u64,
+        upper_partial_hint: Identity,
+        lower_partial_hint: Identity,
+        max_fee_percentage: u64,
     );
     #[storage(read, write)]
+    fn decay_base_rate_from_borrowing(asset: AssetId) -> u64;
+    #[storage(read)]
+    fn get_base_rate() -> u64;
+    #[storage(read)]
+    fn get_decayed_base_rate() -> u64;
+    #[storage(read)]
+    fn get_borrowing_rate(asset: AssetId) -> u64;
+    #[storage(read)]
+    fn get_redemption_rate(asset: AssetId) -> u64;
+    #[storage(read)]
+    fn get_last_fee_operation_time() -> u64;
+    #[storage(read)]
+    fn get_current_time() -> u64;
+    #[storage(read, write)]
+    fn set_current_time(time: u64);
+    #[storage(read, write)]
     fn transfer_owner(new_owner: Identity);
 }
@@ -0,0 +1,60 @@
+library;
+
+use ::fluid_math::{
+    BORROWING_FEE_FLOOR,
+    CCR,
+    MCR,
+    MIN_NET_DEBT,
+    POST_COLLATERAL_RATIO,
+    REDEMPTION_FEE_FLOOR,
+    STABILITY_POOL_FEE,
+};
+
+pub struct RiskParameters {
+    pub mcr: u64,
+    // Below this total collateral ratio the asset is in recovery mode
+    pub ccr: u64,
+    pub min_net_debt: u64,
+    pub borrowing_fee_floor: u64,
+    pub redemption_fee_floor: u64,
+    pub post_collateral_ratio: u64,
+    pub stability_pool_fee: u64,
+}
+
+impl RiskParameters {
+    // The protocol wide values every asset used before risk parameters were set per asset
+    pub fn default() -> Self {
+        RiskParameters {
+            mcr: MCR,
+            ccr: CCR,
+            min_net_debt: MIN_NET_DEBT,
+            borrowing_fee_floor: BORROWING_FEE_FLOOR,
+            redemption_fee_floor: REDEMPTION_FEE_FLOOR,
+            post_collateral_ratio: POST_COLLATERAL_RATIO,
+            stability_pool_fee: STABILITY_POOL_FEE,
+        }
+    }
+}
+
+pub enum AssetStatus {
+    Active: (),
+    // No new troves or debt, redeemed before active assets
+    Sunset: (),
+    // Removed from redemptions, the stability pool and sorted troves once the last trove is gone
+    Delisted: (),
+}
+
+impl AssetStatus {
+    pub fn eq(self, other: AssetStatus) -> bool {
+        match (self, other) {
+            (AssetStatus::Active, AssetStatus::Active) => true,
+            (AssetStatus::Sunset, AssetStatus::Sunset) => true,
+            (AssetStatus::Delisted, AssetStatus::Delisted) => true,
+            _ => false,
+        }
+    }
+
+    pub fn neq(self, other: AssetStatus) -> bool {
+        !self.eq(other)
+    }
+}
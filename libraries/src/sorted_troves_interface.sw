@@ -11,6 +11,9 @@ abi SortedTroves {
     #[storage(read, write)]
     fn add_asset(asset: AssetId, trove_manager: ContractId);
 
+    #[storage(read, write)]
+    fn remove_asset(asset: AssetId);
+
     #[storage(read, write)]
     fn insert(
         id: Identity,
@@ -23,6 +26,9 @@ abi SortedTroves {
     #[storage(read, write)]
     fn remove(id: Identity, asset: AssetId);
 
+    #[storage(read, write)]
+    fn replace(id: Identity, new_id: Identity, asset: AssetId);
+
     #[storage(read, write)]
     fn re_insert(
         id: Identity,
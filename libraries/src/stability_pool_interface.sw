@@ -17,6 +17,9 @@ abi StabilityPool {
         oracle_address: ContractId,
     );
 
+    #[storage(read, write)]
+    fn remove_asset(asset_address: AssetId);
+
     #[storage(read, write), payable]
     fn provide_to_stability_pool();
 
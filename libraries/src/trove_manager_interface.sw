@@ -31,6 +31,15 @@ abi TroveManager {
     #[storage(read)]
     fn get_entire_system_debt() -> u64;
 
+    #[storage(read)]
+    fn get_entire_system_coll() -> u64;
+
+    #[storage(read)]
+    fn get_tcr(price: u64) -> u64;
+
+    #[storage(read)]
+    fn check_recovery_mode(price: u64) -> bool;
+
     #[storage(read)]
     fn get_trove_owners_count() -> u64;
 
@@ -87,6 +96,9 @@ abi TroveManager {
     #[storage(read, write)]
     fn close_trove(id: Identity);
 
+    #[storage(read, write)]
+    fn transfer_trove(from: Identity, to: Identity);
+
     #[storage(read, write)]
     fn remove_stake(id: Identity);
 
@@ -0,0 +1,170 @@
+// Bindings for the contracts integrators talk to, one module per contract so that types sharing a
+// name across ABIs do not collide
+
+pub mod borrow_operations {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "BorrowOperations",
+        abi = "contracts/borrow-operations-contract/out/debug/borrow-operations-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "BorrowOperations",
+        abi =
+            "contracts/borrow-operations-contract/out/release/borrow-operations-contract-abi.json"
+    ));
+}
+
+pub mod trove_manager {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "TroveManagerContract",
+        abi = "contracts/trove-manager-contract/out/debug/trove-manager-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "TroveManagerContract",
+        abi = "contracts/trove-manager-contract/out/release/trove-manager-contract-abi.json"
+    ));
+}
+
+pub mod stability_pool {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "StabilityPool",
+        abi = "contracts/stability-pool-contract/out/debug/stability-pool-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "StabilityPool",
+        abi = "contracts/stability-pool-contract/out/release/stability-pool-contract-abi.json"
+    ));
+}
+
+pub mod fpt_staking {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "FPTStaking",
+        abi = "contracts/fpt-staking-contract/out/debug/fpt-staking-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "FPTStaking",
+        abi = "contracts/fpt-staking-contract/out/release/fpt-staking-contract-abi.json"
+    ));
+}
+
+pub mod oracle {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "Oracle",
+        abi = "contracts/oracle-contract/out/debug/oracle-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "Oracle",
+        abi = "contracts/oracle-contract/out/release/oracle-contract-abi.json"
+    ));
+}
+
+pub mod active_pool {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "ActivePool",
+        abi = "contracts/active-pool-contract/out/debug/active-pool-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "ActivePool",
+        abi = "contracts/active-pool-contract/out/release/active-pool-contract-abi.json"
+    ));
+}
+
+pub mod default_pool {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "DefaultPool",
+        abi = "contracts/default-pool-contract/out/debug/default-pool-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "DefaultPool",
+        abi = "contracts/default-pool-contract/out/release/default-pool-contract-abi.json"
+    ));
+}
+
+pub mod coll_surplus_pool {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "CollSurplusPool",
+        abi = "contracts/coll-surplus-pool-contract/out/debug/coll-surplus-pool-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "CollSurplusPool",
+        abi =
+            "contracts/coll-surplus-pool-contract/out/release/coll-surplus-pool-contract-abi.json"
+    ));
+}
+
+pub mod protocol_manager {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "ProtocolManager",
+        abi = "contracts/protocol-manager-contract/out/debug/protocol-manager-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "ProtocolManager",
+        abi = "contracts/protocol-manager-contract/out/release/protocol-manager-contract-abi.json"
+    ));
+}
+
+pub mod usdm_token {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "USDMToken",
+        abi = "contracts/usdm-token-contract/out/debug/usdm-token-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "USDMToken",
+        abi = "contracts/usdm-token-contract/out/release/usdm-token-contract-abi.json"
+    ));
+}
+
+pub mod multi_trove_getter {
+    use fuels::prelude::abigen;
+
+    #[cfg(not(feature = "release-artifacts"))]
+    abigen!(Contract(
+        name = "MultiTroveGetter",
+        abi =
+            "contracts/multi-trove-getter-contract/out/debug/multi-trove-getter-contract-abi.json"
+    ));
+    #[cfg(feature = "release-artifacts")]
+    abigen!(Contract(
+        name = "MultiTroveGetter",
+        abi = "contracts/multi-trove-getter-contract/out/release/multi-trove-getter-contract-abi.json"
+    ));
+}
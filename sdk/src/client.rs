@@ -0,0 +1,1397 @@
+use fuels::{
+    accounts::impersonated_account::ImpersonatedAccount,
+    prelude::{Account, CallParameters, Error, Execution, Provider, TxPolicies, ViewOnlyAccount},
+    programs::responses::CallResponse,
+    types::{
+        bech32::Bech32ContractId, transaction_builders::VariableOutputPolicy, Address, AssetId,
+        ContractId, Identity,
+    },
+};
+
+use crate::{
+    abi::{
+        active_pool::ActivePool,
+        borrow_operations::{BorrowOperations, OperatorPermissions},
+        coll_surplus_pool::CollSurplusPool,
+        default_pool::DefaultPool,
+        fpt_staking::FPTStaking,
+        multi_trove_getter::MultiTroveGetter,
+        oracle::Oracle,
+        protocol_manager::{AssetStatus, ProtocolManager},
+        stability_pool::StabilityPool,
+        trove_manager::{Status, TroveManagerContract},
+        usdm_token::USDMToken,
+    },
+    deployment::{AssetDeployment, Deployment},
+    hints::{ListedTrove, SortedTrove},
+    liquidation::{estimate_liquidations, LiquidationSequence},
+    preview::{preview_adjustment, AdjustmentPreview, TroveAdjustment, TroveState},
+    redemption::{quote_redemption, AssetTroves, RedemptionQuote},
+    risk_parameters::RiskParameters,
+    views::{AssetSystemView, StabilityDepositView, StakeView, TroveView},
+};
+
+// Variable outputs per call, matching what the contracts transfer out
+const OPEN_TROVE_OUTPUTS: usize = 3;
+const ADJUST_TROVE_OUTPUTS: usize = 1;
+const COMBINED_ADJUST_TROVE_OUTPUTS: usize = 2;
+const CLOSE_TROVE_OUTPUTS: usize = 3;
+const CLAIM_COLLATERAL_OUTPUTS: usize = 1;
+const PROVIDE_TO_SP_OUTPUTS: usize = 2;
+const MIN_MULTI_ASSET_OUTPUTS: usize = 10;
+
+const SCRIPT_GAS_LIMIT: u64 = 2_000_000;
+
+const SORTED_TROVES_PAGE_SIZE: u8 = u8::MAX;
+
+// Typed entry point for integrators, every contract is resolved from a deployment manifest and calls
+// carry the contract ids and variable outputs they need
+pub struct MoorClient<A: Account + Clone> {
+    deployment: Deployment,
+    account: A,
+}
+
+impl MoorClient<ImpersonatedAccount> {
+    // Client without a signer, only the read methods can be used
+    pub fn read_only(deployment: Deployment, provider: Provider) -> Self {
+        Self::new(
+            deployment,
+            ImpersonatedAccount::new(Address::zeroed().into(), Some(provider)),
+        )
+    }
+}
+
+impl<A: Account + Clone> MoorClient<A> {
+    pub fn new(deployment: Deployment, account: A) -> Self {
+        Self {
+            deployment,
+            account,
+        }
+    }
+
+    pub fn deployment(&self) -> &Deployment {
+        &self.deployment
+    }
+
+    pub fn account(&self) -> &A {
+        &self.account
+    }
+
+    pub fn identity(&self) -> Identity {
+        Identity::Address(self.account.address().into())
+    }
+
+    pub fn borrow_operations(&self) -> BorrowOperations<A> {
+        BorrowOperations::new(self.deployment.borrow_operations, self.account.clone())
+    }
+
+    pub fn stability_pool(&self) -> StabilityPool<A> {
+        StabilityPool::new(self.deployment.stability_pool, self.account.clone())
+    }
+
+    pub fn fpt_staking(&self) -> FPTStaking<A> {
+        FPTStaking::new(self.deployment.fpt_staking, self.account.clone())
+    }
+
+    pub fn protocol_manager(&self) -> ProtocolManager<A> {
+        ProtocolManager::new(self.deployment.protocol_manager, self.account.clone())
+    }
+
+    pub fn usdm(&self) -> USDMToken<A> {
+        USDMToken::new(self.deployment.usdm, self.account.clone())
+    }
+
+    pub fn active_pool(&self) -> ActivePool<A> {
+        ActivePool::new(self.deployment.active_pool, self.account.clone())
+    }
+
+    pub fn default_pool(&self) -> DefaultPool<A> {
+        DefaultPool::new(self.deployment.default_pool, self.account.clone())
+    }
+
+    pub fn coll_surplus_pool(&self) -> CollSurplusPool<A> {
+        CollSurplusPool::new(self.deployment.coll_surplus_pool, self.account.clone())
+    }
+
+    pub fn trove_manager(&self, asset_id: AssetId) -> Result<TroveManagerContract<A>, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+        Ok(TroveManagerContract::new(
+            asset.trove_manager,
+            self.account.clone(),
+        ))
+    }
+
+    pub fn oracle(&self, asset_id: AssetId) -> Result<Oracle<A>, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+        Ok(Oracle::new(asset.oracle, self.account.clone()))
+    }
+
+    pub fn multi_trove_getter(&self) -> Result<MultiTroveGetter<A>, Error> {
+        let multi_trove_getter = self
+            .deployment
+            .multi_trove_getter
+            .ok_or_else(|| Error::Other("The deployment has no MultiTroveGetter".to_string()))?;
+        Ok(MultiTroveGetter::new(
+            multi_trove_getter,
+            self.account.clone(),
+        ))
+    }
+
+    // Reads
+
+    pub async fn price(&self, asset_id: AssetId) -> Result<u64, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+
+        Ok(self
+            .oracle(asset_id)?
+            .methods()
+            .get_price()
+            .with_contract_ids(&to_bech32(&self.oracle_ids(asset)))
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    pub async fn trove(&self, asset_id: AssetId, owner: Identity) -> Result<TroveView, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+        let trove_manager = self.trove_manager(asset_id)?;
+        let trove_manager_ids =
+            to_bech32(&[asset.trove_manager, asset.trove_manager_implementation_id]);
+        let price = self.price(asset_id).await?;
+
+        let status = trove_manager
+            .methods()
+            .get_trove_status(owner)
+            .with_contract_ids(&trove_manager_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        // The other getters revert for an address that never had a trove
+        if status == Status::NonExistent {
+            return Ok(TroveView {
+                owner,
+                asset_id,
+                status,
+                coll: 0,
+                debt: 0,
+                pending_coll_rewards: 0,
+                pending_debt_rewards: 0,
+                stake: 0,
+                price,
+                icr: u64::MAX,
+                nominal_icr: u64::MAX,
+            });
+        }
+
+        let (debt, coll, pending_debt_rewards, pending_coll_rewards) = trove_manager
+            .methods()
+            .get_entire_debt_and_coll(owner)
+            .with_contract_ids(&trove_manager_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let stake = trove_manager
+            .methods()
+            .get_trove_stake(owner)
+            .with_contract_ids(&trove_manager_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let icr = trove_manager
+            .methods()
+            .get_current_icr(owner, price)
+            .with_contract_ids(&trove_manager_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let nominal_icr = trove_manager
+            .methods()
+            .get_nominal_icr(owner)
+            .with_contract_ids(&trove_manager_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        Ok(TroveView {
+            owner,
+            asset_id,
+            status,
+            coll,
+            debt,
+            pending_coll_rewards,
+            pending_debt_rewards,
+            stake,
+            price,
+            icr,
+            nominal_icr,
+        })
+    }
+
+    pub async fn asset_system(&self, asset_id: AssetId) -> Result<AssetSystemView, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+        let price = self.price(asset_id).await?;
+
+        let trove_count = self
+            .trove_manager(asset_id)?
+            .methods()
+            .get_trove_owners_count()
+            .with_contract_ids(&to_bech32(&[
+                asset.trove_manager,
+                asset.trove_manager_implementation_id,
+            ]))
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        let active_pool = self.active_pool();
+        let active_pool_ids = to_bech32(&[
+            self.deployment.active_pool,
+            self.deployment.active_pool_implementation_id,
+        ]);
+        let active_pool_coll = active_pool
+            .methods()
+            .get_asset(asset_id)
+            .with_contract_ids(&active_pool_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let active_pool_debt = active_pool
+            .methods()
+            .get_usdm_debt(asset_id)
+            .with_contract_ids(&active_pool_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        let default_pool = self.default_pool();
+        let default_pool_ids = to_bech32(&[
+            self.deployment.default_pool,
+            self.deployment.default_pool_implementation_id,
+        ]);
+        let default_pool_coll = default_pool
+            .methods()
+            .get_asset(asset_id)
+            .with_contract_ids(&default_pool_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let default_pool_debt = default_pool
+            .methods()
+            .get_usdm_debt(asset_id)
+            .with_contract_ids(&default_pool_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        let coll_surplus = self
+            .coll_surplus_pool()
+            .methods()
+            .get_asset(asset_id)
+            .with_contract_ids(&to_bech32(&[
+                self.deployment.coll_surplus_pool,
+                self.deployment.coll_surplus_pool_implementation_id,
+            ]))
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        let stability_pool_coll = self
+            .stability_pool()
+            .methods()
+            .get_asset(asset_id)
+            .with_contract_ids(&self.stability_pool_ids())
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        Ok(AssetSystemView {
+            asset_id,
+            symbol: asset.symbol.clone(),
+            price,
+            ccr: self.risk_parameters(asset_id).await?.ccr,
+            trove_count,
+            active_pool_coll,
+            active_pool_debt,
+            default_pool_coll,
+            default_pool_debt,
+            coll_surplus,
+            stability_pool_coll,
+        })
+    }
+
+    pub async fn stability_deposit(
+        &self,
+        depositor: Identity,
+    ) -> Result<StabilityDepositView, Error> {
+        let stability_pool = self.stability_pool();
+        let stability_pool_ids = self.stability_pool_ids();
+
+        let compounded_usdm_deposit = stability_pool
+            .methods()
+            .get_compounded_usdm_deposit(depositor)
+            .with_contract_ids(&stability_pool_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let fpt_gain = stability_pool
+            .methods()
+            .get_depositor_fpt_gain(depositor)
+            .with_contract_ids(&stability_pool_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let total_usdm_deposits = stability_pool
+            .methods()
+            .get_total_usdm_deposits()
+            .with_contract_ids(&stability_pool_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        let mut asset_gains = Vec::with_capacity(self.deployment.asset_contracts.len());
+        for asset in self.deployment.asset_contracts.iter() {
+            let gain = stability_pool
+                .methods()
+                .get_depositor_asset_gain(depositor, asset.asset_id)
+                .with_contract_ids(&stability_pool_ids)
+                .simulate(Execution::state_read_only())
+                .await?
+                .value;
+            asset_gains.push((asset.asset_id, gain));
+        }
+
+        Ok(StabilityDepositView {
+            depositor,
+            compounded_usdm_deposit,
+            fpt_gain,
+            asset_gains,
+            total_usdm_deposits,
+        })
+    }
+
+    pub async fn stake(&self, staker: Identity) -> Result<StakeView, Error> {
+        let fpt_staking = self.fpt_staking();
+        let fpt_staking_ids = to_bech32(&[
+            self.deployment.fpt_staking,
+            self.deployment.fpt_staking_implementation_id,
+        ]);
+
+        let staked_fpt = fpt_staking
+            .methods()
+            .get_staking_balance(staker)
+            .with_contract_ids(&fpt_staking_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let total_fpt_staked = fpt_staking
+            .methods()
+            .get_storage()
+            .with_contract_ids(&fpt_staking_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value
+            .total_fpt_staked;
+        let pending_usdm_gain = fpt_staking
+            .methods()
+            .get_pending_usdm_gain(staker)
+            .with_contract_ids(&fpt_staking_ids)
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+
+        let mut pending_asset_gains = Vec::with_capacity(self.deployment.asset_contracts.len());
+        for asset in self.deployment.asset_contracts.iter() {
+            let gain = fpt_staking
+                .methods()
+                .get_pending_asset_gain(staker, asset.asset_id)
+                .with_contract_ids(&fpt_staking_ids)
+                .simulate(Execution::state_read_only())
+                .await?
+                .value;
+            pending_asset_gains.push((asset.asset_id, gain));
+        }
+
+        Ok(StakeView {
+            staker,
+            staked_fpt,
+            total_fpt_staked,
+            pending_usdm_gain,
+            pending_asset_gains,
+        })
+    }
+
+    // The asset's troves from head to tail with their entire collateral and debt, read through
+    // MultiTroveGetter
+    pub async fn listed_troves(&self, asset_id: AssetId) -> Result<Vec<ListedTrove>, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+        let multi_trove_getter = self.multi_trove_getter()?;
+        let ids = to_bech32(&[
+            self.deployment.sorted_troves,
+            self.deployment.sorted_troves_implementation_id,
+            asset.trove_manager,
+            asset.trove_manager_implementation_id,
+        ]);
+
+        // Pages start from the tail
+        let mut troves = Vec::new();
+        loop {
+            let page = multi_trove_getter
+                .methods()
+                .get_multiple_sorted_troves(
+                    asset.trove_manager,
+                    asset_id,
+                    troves.len() as u64,
+                    SORTED_TROVES_PAGE_SIZE,
+                )
+                .with_contract_ids(&ids)
+                .simulate(Execution::state_read_only())
+                .await?
+                .value;
+            let page_len = page.len();
+
+            troves.extend(page.into_iter().map(|trove| ListedTrove {
+                id: trove.address,
+                coll: trove.collateral,
+                debt: trove.debt,
+            }));
+            if page_len < SORTED_TROVES_PAGE_SIZE as usize {
+                break;
+            }
+        }
+        troves.reverse();
+
+        Ok(troves)
+    }
+
+    // The asset's sorted troves from head to tail
+    pub async fn sorted_troves(&self, asset_id: AssetId) -> Result<Vec<SortedTrove>, Error> {
+        Ok(self
+            .listed_troves(asset_id)
+            .await?
+            .iter()
+            .map(ListedTrove::sorted)
+            .collect())
+    }
+
+    pub async fn preview_adjustment(
+        &self,
+        asset_id: AssetId,
+        borrower: Identity,
+        adjustment: TroveAdjustment,
+    ) -> Result<AdjustmentPreview, Error> {
+        let trove = self.trove(asset_id, borrower).await?;
+        let current = (trove.status == Status::Active).then(|| TroveState::from(&trove));
+        let sorted_troves = self.sorted_troves(asset_id).await?;
+
+        let borrowing_rate = self.borrowing_rate(asset_id).await?;
+        let risk_parameters = self.risk_parameters(asset_id).await?;
+
+        preview_adjustment(
+            borrower,
+            current,
+            adjustment,
+            trove.price,
+            borrowing_rate,
+            &risk_parameters,
+            &sorted_troves,
+        )
+        .map_err(|e| Error::Other(e.to_string()))
+    }
+
+    // What `redeem_collateral` would do with the current prices and troves, the deployment lists the
+    // assets in the order they were registered with the ProtocolManager
+    pub async fn quote_redemption(
+        &self,
+        usdm_amount: u64,
+        max_iterations: u64,
+    ) -> Result<RedemptionQuote, Error> {
+        let asset_ids: Vec<AssetId> = self
+            .deployment
+            .asset_contracts
+            .iter()
+            .map(|asset| asset.asset_id)
+            .collect();
+        self.quote_redemption_from_assets(usdm_amount, max_iterations, &asset_ids)
+            .await
+    }
+
+    // Quote for `redeem_collateral_from_assets`, the walk only sees the troves of `asset_ids`
+    pub async fn quote_redemption_from_assets(
+        &self,
+        usdm_amount: u64,
+        max_iterations: u64,
+        asset_ids: &[AssetId],
+    ) -> Result<RedemptionQuote, Error> {
+        let mut assets = Vec::with_capacity(asset_ids.len());
+        for asset in self.deployment.asset_contracts.iter() {
+            if !asset_ids.contains(&asset.asset_id) {
+                continue;
+            }
+            let status = self.asset_status(asset.asset_id).await?;
+            if status == AssetStatus::Delisted {
+                continue;
+            }
+            assets.push(AssetTroves {
+                asset_id: asset.asset_id,
+                price: self.price(asset.asset_id).await?,
+                risk_parameters: self.risk_parameters(asset.asset_id).await?,
+                is_sunset: status == AssetStatus::Sunset,
+                troves: self.listed_troves(asset.asset_id).await?,
+            });
+        }
+
+        let decayed_base_rate = self.decayed_base_rate().await?;
+        let usdm_supply = self.usdm_supply().await?;
+
+        quote_redemption(
+            usdm_amount,
+            max_iterations,
+            decayed_base_rate,
+            usdm_supply,
+            &assets,
+        )
+        .map_err(|e| Error::Other(e.to_string()))
+    }
+
+    // Base rate decayed to the current time, what the next borrowing or redemption starts from
+    pub async fn decayed_base_rate(&self) -> Result<u64, Error> {
+        Ok(self
+            .protocol_manager()
+            .methods()
+            .get_decayed_base_rate()
+            .with_contract_ids(&self.protocol_manager_ids())
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    // Fee rate charged on USDM drawn against the asset, `max_fee_percentage` has to be at least this
+    pub async fn borrowing_rate(&self, asset_id: AssetId) -> Result<u64, Error> {
+        Ok(self
+            .protocol_manager()
+            .methods()
+            .get_borrowing_rate(asset_id)
+            .with_contract_ids(&self.protocol_manager_ids())
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    // MCR, min net debt, fee floors and liquidation terms the ProtocolManager keeps for the asset
+    pub async fn risk_parameters(&self, asset_id: AssetId) -> Result<RiskParameters, Error> {
+        Ok(self
+            .protocol_manager()
+            .methods()
+            .get_risk_parameters(asset_id)
+            .with_contract_ids(&self.protocol_manager_ids())
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    // Sunset assets take no new debt and are redeemed first, delisted ones are no longer redeemed
+    pub async fn asset_status(&self, asset_id: AssetId) -> Result<AssetStatus, Error> {
+        Ok(self
+            .protocol_manager()
+            .methods()
+            .get_asset_status(asset_id)
+            .with_contract_ids(&self.protocol_manager_ids())
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    // `u64::MAX` for assets without a ceiling
+    pub async fn debt_ceiling(&self, asset_id: AssetId) -> Result<u64, Error> {
+        Ok(self
+            .protocol_manager()
+            .methods()
+            .get_debt_ceiling(asset_id)
+            .with_contract_ids(&self.protocol_manager_ids())
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    // Debt, fees included, that troves of the asset can still open or borrow before its ceiling
+    pub async fn remaining_debt_capacity(&self, asset_id: AssetId) -> Result<u64, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+        let mut ids = vec![
+            self.deployment.protocol_manager,
+            self.deployment.protocol_manager_implementation_id,
+        ];
+        ids.extend(self.core_ids());
+        ids.extend(self.asset_ids(asset));
+
+        Ok(self
+            .protocol_manager()
+            .methods()
+            .get_remaining_debt_capacity(asset_id)
+            .with_contract_ids(&to_bech32(&ids))
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    pub async fn usdm_supply(&self) -> Result<u64, Error> {
+        Ok(self
+            .usdm()
+            .methods()
+            .total_supply(self.deployment.usdm_asset_id)
+            .with_contract_ids(&to_bech32(&[
+                self.deployment.usdm,
+                self.deployment.usdm_implementation_id,
+            ]))
+            .simulate(Execution::state_read_only())
+            .await?
+            .value
+            .unwrap_or_default())
+    }
+
+    // The troves `batch_liquidate_troves` would liquidate now, with the Stability Pool offset,
+    // redistribution and liquidator reward of each
+    pub async fn estimate_liquidations(
+        &self,
+        asset_id: AssetId,
+    ) -> Result<LiquidationSequence, Error> {
+        let system = self.asset_system(asset_id).await?;
+        let usdm_in_stability_pool = self
+            .stability_pool()
+            .methods()
+            .get_total_usdm_deposits()
+            .with_contract_ids(&self.stability_pool_ids())
+            .simulate(Execution::state_read_only())
+            .await?
+            .value;
+        let troves = self.listed_troves(asset_id).await?;
+        let risk_parameters = self.risk_parameters(asset_id).await?;
+
+        Ok(estimate_liquidations(
+            asset_id,
+            system.price,
+            &risk_parameters,
+            usdm_in_stability_pool,
+            system.total_coll(),
+            system.total_debt(),
+            &troves,
+        ))
+    }
+
+    // Borrower operations
+
+    pub async fn open_trove(
+        &self,
+        asset_id: AssetId,
+        max_fee_percentage: u64,
+        coll_amount: u64,
+        usdm_amount: u64,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(coll_amount)
+            .with_asset_id(asset_id);
+
+        self.borrow_operations()
+            .methods()
+            .open_trove(max_fee_percentage, usdm_amount, upper_hint, lower_hint)
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(OPEN_TROVE_OUTPUTS))
+            .call()
+            .await
+    }
+
+    pub async fn add_coll(
+        &self,
+        asset_id: AssetId,
+        amount: u64,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(asset_id);
+
+        self.borrow_operations()
+            .methods()
+            .add_coll(upper_hint, lower_hint)
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(ADJUST_TROVE_OUTPUTS))
+            .call()
+            .await
+    }
+
+    pub async fn withdraw_coll(
+        &self,
+        asset_id: AssetId,
+        amount: u64,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        self.borrow_operations()
+            .methods()
+            .withdraw_coll(amount, upper_hint, lower_hint, asset_id)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(ADJUST_TROVE_OUTPUTS))
+            .call()
+            .await
+    }
+
+    pub async fn withdraw_usdm(
+        &self,
+        asset_id: AssetId,
+        max_fee_percentage: u64,
+        amount: u64,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        self.borrow_operations()
+            .methods()
+            .withdraw_usdm(max_fee_percentage, amount, upper_hint, lower_hint, asset_id)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(ADJUST_TROVE_OUTPUTS))
+            .call()
+            .await
+    }
+
+    pub async fn repay_usdm(
+        &self,
+        asset_id: AssetId,
+        amount: u64,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(self.deployment.usdm_asset_id);
+
+        self.borrow_operations()
+            .methods()
+            .repay_usdm(upper_hint, lower_hint, asset_id)
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(ADJUST_TROVE_OUTPUTS))
+            .call()
+            .await
+    }
+
+    // Collateral and debt change together with one ICR check. `coll_added` is sent as collateral,
+    // otherwise a repayment sends `usdm_change` USDM, the contract takes one or the other
+    #[allow(clippy::too_many_arguments)]
+    pub async fn adjust_trove(
+        &self,
+        asset_id: AssetId,
+        max_fee_percentage: u64,
+        coll_added: u64,
+        coll_withdrawal: u64,
+        usdm_change: u64,
+        is_debt_increase: bool,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = if coll_added > 0 {
+            CallParameters::default()
+                .with_amount(coll_added)
+                .with_asset_id(asset_id)
+        } else if !is_debt_increase && usdm_change > 0 {
+            CallParameters::default()
+                .with_amount(usdm_change)
+                .with_asset_id(self.deployment.usdm_asset_id)
+        } else {
+            CallParameters::default()
+        };
+
+        self.borrow_operations()
+            .methods()
+            .adjust_trove(
+                max_fee_percentage,
+                coll_withdrawal,
+                usdm_change,
+                is_debt_increase,
+                upper_hint,
+                lower_hint,
+                asset_id,
+            )
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(
+                COMBINED_ADJUST_TROVE_OUTPUTS,
+            ))
+            .call()
+            .await
+    }
+
+    // `usdm_amount` has to cover the trove debt minus the gas compensation
+    pub async fn close_trove(
+        &self,
+        asset_id: AssetId,
+        usdm_amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(usdm_amount)
+            .with_asset_id(self.deployment.usdm_asset_id);
+
+        self.borrow_operations()
+            .methods()
+            .close_trove(asset_id)
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(CLOSE_TROVE_OUTPUTS))
+            .call()
+            .await
+    }
+
+    // Lets `operator` act on the caller's trove of the asset, approving no permissions revokes it
+    pub async fn set_operator(
+        &self,
+        asset_id: AssetId,
+        operator: Identity,
+        permissions: OperatorPermissions,
+    ) -> Result<CallResponse<()>, Error> {
+        self.borrow_operations()
+            .methods()
+            .set_operator(operator, asset_id, permissions)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&to_bech32(&[
+                self.deployment.borrow_operations,
+                self.deployment.borrow_operations_implementation_id,
+            ]))
+            .call()
+            .await
+    }
+
+    pub async fn operator_permissions(
+        &self,
+        asset_id: AssetId,
+        owner: Identity,
+        operator: Identity,
+    ) -> Result<OperatorPermissions, Error> {
+        Ok(self
+            .borrow_operations()
+            .methods()
+            .get_operator_permissions(owner, operator, asset_id)
+            .with_contract_ids(&to_bech32(&[
+                self.deployment.borrow_operations,
+                self.deployment.borrow_operations_implementation_id,
+            ]))
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    // `adjust_trove` on the trove of `owner`, the caller pays and needs a permission for every part
+    // of the adjustment
+    #[allow(clippy::too_many_arguments)]
+    pub async fn adjust_trove_for(
+        &self,
+        owner: Identity,
+        asset_id: AssetId,
+        max_fee_percentage: u64,
+        coll_added: u64,
+        coll_withdrawal: u64,
+        usdm_change: u64,
+        is_debt_increase: bool,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = if coll_added > 0 {
+            CallParameters::default()
+                .with_amount(coll_added)
+                .with_asset_id(asset_id)
+        } else if !is_debt_increase && usdm_change > 0 {
+            CallParameters::default()
+                .with_amount(usdm_change)
+                .with_asset_id(self.deployment.usdm_asset_id)
+        } else {
+            CallParameters::default()
+        };
+
+        self.borrow_operations()
+            .methods()
+            .adjust_trove_for(
+                owner,
+                max_fee_percentage,
+                coll_withdrawal,
+                usdm_change,
+                is_debt_increase,
+                upper_hint,
+                lower_hint,
+                asset_id,
+            )
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(
+                COMBINED_ADJUST_TROVE_OUTPUTS,
+            ))
+            .call()
+            .await
+    }
+
+    // The caller pays the debt of `owner`'s trove, excess USDM comes back to the caller
+    pub async fn close_trove_for(
+        &self,
+        owner: Identity,
+        asset_id: AssetId,
+        usdm_amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(usdm_amount)
+            .with_asset_id(self.deployment.usdm_asset_id);
+
+        self.borrow_operations()
+            .methods()
+            .close_trove_for(owner, asset_id)
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(CLOSE_TROVE_OUTPUTS))
+            .call()
+            .await
+    }
+
+    // The trove moves once `new_owner` accepts, a new proposal replaces the pending one
+    pub async fn propose_trove_transfer(
+        &self,
+        asset_id: AssetId,
+        new_owner: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        self.borrow_operations()
+            .methods()
+            .propose_trove_transfer(new_owner, asset_id)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .call()
+            .await
+    }
+
+    pub async fn cancel_trove_transfer(
+        &self,
+        asset_id: AssetId,
+    ) -> Result<CallResponse<()>, Error> {
+        self.borrow_operations()
+            .methods()
+            .cancel_trove_transfer(asset_id)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&to_bech32(&[
+                self.deployment.borrow_operations,
+                self.deployment.borrow_operations_implementation_id,
+            ]))
+            .call()
+            .await
+    }
+
+    pub async fn accept_trove_transfer(
+        &self,
+        asset_id: AssetId,
+        previous_owner: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        self.borrow_operations()
+            .methods()
+            .accept_trove_transfer(previous_owner, asset_id)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .call()
+            .await
+    }
+
+    pub async fn pending_trove_transfer(
+        &self,
+        asset_id: AssetId,
+        owner: Identity,
+    ) -> Result<Option<Identity>, Error> {
+        Ok(self
+            .borrow_operations()
+            .methods()
+            .get_pending_trove_transfer(owner, asset_id)
+            .with_contract_ids(&to_bech32(&[
+                self.deployment.borrow_operations,
+                self.deployment.borrow_operations_implementation_id,
+            ]))
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    // `receiver` implements `FlashMintReceiver` and has to send the amount and the fee back to
+    // BorrowOperations, `callback_ids` are the contracts its callback calls
+    pub async fn flash_mint(
+        &self,
+        amount: u64,
+        receiver: ContractId,
+        callback_ids: &[ContractId],
+    ) -> Result<CallResponse<()>, Error> {
+        let mut ids = vec![
+            self.deployment.borrow_operations,
+            self.deployment.borrow_operations_implementation_id,
+            receiver,
+        ];
+        ids.extend(self.core_ids());
+        ids.extend_from_slice(callback_ids);
+
+        self.borrow_operations()
+            .methods()
+            .flash_mint(amount, receiver)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&to_bech32(&ids))
+            .call()
+            .await
+    }
+
+    pub async fn flash_mint_fee(&self, amount: u64) -> Result<u64, Error> {
+        Ok(self
+            .borrow_operations()
+            .methods()
+            .get_flash_mint_fee(amount)
+            .with_contract_ids(&to_bech32(&[
+                self.deployment.borrow_operations,
+                self.deployment.borrow_operations_implementation_id,
+            ]))
+            .simulate(Execution::state_read_only())
+            .await?
+            .value)
+    }
+
+    pub async fn claim_collateral(&self, asset_id: AssetId) -> Result<CallResponse<()>, Error> {
+        self.borrow_operations()
+            .methods()
+            .claim_collateral(asset_id)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.borrow_operations_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(CLAIM_COLLATERAL_OUTPUTS))
+            .call()
+            .await
+    }
+
+    // Stability pool
+
+    pub async fn provide_to_stability_pool(&self, amount: u64) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(self.deployment.usdm_asset_id);
+
+        self.stability_pool()
+            .methods()
+            .provide_to_stability_pool()
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.stability_pool_call_ids())
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(PROVIDE_TO_SP_OUTPUTS))
+            .call()
+            .await
+    }
+
+    // Withdrawing pays out the gains of every asset, and checks no trove is below MCR
+    pub async fn withdraw_from_stability_pool(
+        &self,
+        amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        self.stability_pool()
+            .methods()
+            .withdraw_from_stability_pool(amount)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.stability_pool_call_ids())
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(
+                PROVIDE_TO_SP_OUTPUTS + self.deployment.asset_contracts.len(),
+            ))
+            .call()
+            .await
+    }
+
+    // FPT staking
+
+    pub async fn stake_fpt(&self, amount: u64) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(self.deployment.fpt_asset_id);
+
+        self.fpt_staking()
+            .methods()
+            .stake()
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.fpt_staking_call_ids())
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(self.multi_asset_outputs()))
+            .call()
+            .await
+    }
+
+    pub async fn unstake_fpt(&self, amount: u64) -> Result<CallResponse<()>, Error> {
+        self.fpt_staking()
+            .methods()
+            .unstake(amount)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.fpt_staking_call_ids())
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(self.multi_asset_outputs()))
+            .call()
+            .await
+    }
+
+    // Redemptions and liquidations
+
+    pub async fn redeem_collateral(
+        &self,
+        usdm_amount: u64,
+        max_iterations: u64,
+        partial_redemption_hint: u64,
+        upper_partial_hint: Identity,
+        lower_partial_hint: Identity,
+        max_fee_percentage: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(usdm_amount)
+            .with_asset_id(self.deployment.usdm_asset_id);
+
+        let mut ids = vec![
+            self.deployment.protocol_manager,
+            self.deployment.protocol_manager_implementation_id,
+        ];
+        ids.extend(self.core_ids());
+        for asset in self.deployment.asset_contracts.iter() {
+            ids.extend(self.asset_ids(asset));
+        }
+
+        self.protocol_manager()
+            .methods()
+            .redeem_collateral(
+                max_iterations,
+                partial_redemption_hint,
+                upper_partial_hint,
+                lower_partial_hint,
+                max_fee_percentage,
+            )
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&to_bech32(&ids))
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(self.multi_asset_outputs()))
+            .call()
+            .await
+    }
+
+    // Same as `redeem_collateral`, but only from the troves of `asset_ids`
+    pub async fn redeem_collateral_from_assets(
+        &self,
+        usdm_amount: u64,
+        asset_ids: Vec<AssetId>,
+        max_iterations: u64,
+        partial_redemption_hint: u64,
+        upper_partial_hint: Identity,
+        lower_partial_hint: Identity,
+        max_fee_percentage: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(usdm_amount)
+            .with_asset_id(self.deployment.usdm_asset_id);
+
+        let mut ids = vec![
+            self.deployment.protocol_manager,
+            self.deployment.protocol_manager_implementation_id,
+        ];
+        ids.extend(self.core_ids());
+        for asset_id in asset_ids.iter() {
+            ids.extend(self.asset_ids(self.deployment.asset(*asset_id)?));
+        }
+
+        self.protocol_manager()
+            .methods()
+            .redeem_collateral_from_assets(
+                asset_ids,
+                max_iterations,
+                partial_redemption_hint,
+                upper_partial_hint,
+                lower_partial_hint,
+                max_fee_percentage,
+            )
+            .call_params(call_params)?
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&to_bech32(&ids))
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(self.multi_asset_outputs()))
+            .call()
+            .await
+    }
+
+    pub async fn liquidate(
+        &self,
+        asset_id: AssetId,
+        id: Identity,
+        upper_partial_hint: Identity,
+        lower_partial_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        self.trove_manager(asset_id)?
+            .methods()
+            .liquidate(id, upper_partial_hint, lower_partial_hint)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.liquidation_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(self.multi_asset_outputs()))
+            .call()
+            .await
+    }
+
+    pub async fn batch_liquidate_troves(
+        &self,
+        asset_id: AssetId,
+        ids: Vec<Identity>,
+        upper_partial_hint: Identity,
+        lower_partial_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        self.trove_manager(asset_id)?
+            .methods()
+            .batch_liquidate_troves(ids, upper_partial_hint, lower_partial_hint)
+            .with_tx_policies(tx_policies())
+            .with_contract_ids(&self.liquidation_ids(asset_id)?)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(self.multi_asset_outputs()))
+            .call()
+            .await
+    }
+
+    // Dependency lists, proxied contracts are listed with their implementation
+
+    fn oracle_ids(&self, asset: &AssetDeployment) -> Vec<ContractId> {
+        let mut ids = vec![asset.oracle, asset.oracle_implementation_id];
+        ids.extend(asset.price_feeds());
+        ids
+    }
+
+    fn asset_ids(&self, asset: &AssetDeployment) -> Vec<ContractId> {
+        let mut ids = vec![
+            asset.trove_manager,
+            asset.trove_manager_implementation_id,
+            asset.asset_contract,
+        ];
+        ids.extend(self.oracle_ids(asset));
+        ids
+    }
+
+    // Pools and tokens shared by every asset
+    fn core_ids(&self) -> Vec<ContractId> {
+        let deployment = &self.deployment;
+        vec![
+            deployment.sorted_troves,
+            deployment.sorted_troves_implementation_id,
+            deployment.active_pool,
+            deployment.active_pool_implementation_id,
+            deployment.default_pool,
+            deployment.default_pool_implementation_id,
+            deployment.coll_surplus_pool,
+            deployment.coll_surplus_pool_implementation_id,
+            deployment.usdm,
+            deployment.usdm_implementation_id,
+            deployment.fpt_staking,
+            deployment.fpt_staking_implementation_id,
+        ]
+    }
+
+    fn borrow_operations_ids(&self, asset_id: AssetId) -> Result<Vec<Bech32ContractId>, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+
+        let mut ids = vec![
+            self.deployment.borrow_operations,
+            self.deployment.borrow_operations_implementation_id,
+        ];
+        ids.extend([
+            self.deployment.protocol_manager,
+            self.deployment.protocol_manager_implementation_id,
+        ]);
+        ids.extend(self.core_ids());
+        ids.extend(self.asset_ids(asset));
+
+        Ok(to_bech32(&ids))
+    }
+
+    fn protocol_manager_ids(&self) -> Vec<Bech32ContractId> {
+        to_bech32(&[
+            self.deployment.protocol_manager,
+            self.deployment.protocol_manager_implementation_id,
+        ])
+    }
+
+    // Liquidations read the asset's risk parameters from the ProtocolManager
+    fn liquidation_ids(&self, asset_id: AssetId) -> Result<Vec<Bech32ContractId>, Error> {
+        let asset = self.deployment.asset(asset_id)?;
+
+        let mut ids = vec![
+            self.deployment.stability_pool,
+            self.deployment.stability_pool_implementation_id,
+            self.deployment.community_issuance,
+            self.deployment.community_issuance_implementation_id,
+            self.deployment.protocol_manager,
+            self.deployment.protocol_manager_implementation_id,
+        ];
+        ids.extend(self.core_ids());
+        ids.extend(self.asset_ids(asset));
+
+        Ok(to_bech32(&ids))
+    }
+
+    fn stability_pool_ids(&self) -> Vec<Bech32ContractId> {
+        to_bech32(&[
+            self.deployment.stability_pool,
+            self.deployment.stability_pool_implementation_id,
+        ])
+    }
+
+    // Deposits and withdrawals pay out FPT from community issuance and check every asset for
+    // troves under its MCR, which the ProtocolManager keeps
+    fn stability_pool_call_ids(&self) -> Vec<Bech32ContractId> {
+        let mut ids = vec![
+            self.deployment.stability_pool,
+            self.deployment.stability_pool_implementation_id,
+            self.deployment.community_issuance,
+            self.deployment.community_issuance_implementation_id,
+            self.deployment.fpt_token,
+            self.deployment.fpt_token_implementation_id,
+            self.deployment.protocol_manager,
+            self.deployment.protocol_manager_implementation_id,
+        ];
+        ids.extend(self.core_ids());
+        for asset in self.deployment.asset_contracts.iter() {
+            ids.extend(self.asset_ids(asset));
+        }
+
+        to_bech32(&ids)
+    }
+
+    fn fpt_staking_call_ids(&self) -> Vec<Bech32ContractId> {
+        let mut ids = vec![
+            self.deployment.fpt_staking,
+            self.deployment.fpt_staking_implementation_id,
+            self.deployment.fpt_token,
+            self.deployment.fpt_token_implementation_id,
+            self.deployment.usdm,
+            self.deployment.usdm_implementation_id,
+        ];
+        ids.extend(
+            self.deployment
+                .asset_contracts
+                .iter()
+                .map(|asset| asset.asset_contract),
+        );
+
+        to_bech32(&ids)
+    }
+
+    // Calls paying out one output per asset on top of USDM and FPT
+    fn multi_asset_outputs(&self) -> usize {
+        MIN_MULTI_ASSET_OUTPUTS.max(self.deployment.asset_contracts.len() + 2)
+    }
+}
+
+fn tx_policies() -> TxPolicies {
+    TxPolicies::default()
+        .with_tip(1)
+        .with_script_gas_limit(SCRIPT_GAS_LIMIT)
+}
+
+fn to_bech32(ids: &[ContractId]) -> Vec<Bech32ContractId> {
+    ids.iter().map(|id| (*id).into()).collect()
+}
@@ -0,0 +1,184 @@
+use std::{path::Path, str::FromStr};
+
+use fuels::{
+    prelude::Error,
+    types::{bech32::Bech32ContractId, AssetId, ContractId},
+};
+use serde::{Deserialize, Deserializer};
+
+// Deployment manifest as written by `deploy-scripts` (`mainnet.contracts.json`, `testnet.contracts.json`).
+// Proxied contracts come with the id of the implementation they currently point to, calls through a
+// proxy must list both.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Deployment {
+    #[serde(deserialize_with = "contract_id")]
+    pub borrow_operations: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub borrow_operations_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub usdm: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub usdm_implementation_id: ContractId,
+    #[serde(deserialize_with = "asset_id")]
+    pub usdm_asset_id: AssetId,
+    #[serde(deserialize_with = "contract_id")]
+    pub stability_pool: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub stability_pool_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub protocol_manager: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub protocol_manager_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub fpt_staking: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub fpt_staking_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub fpt_token: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub fpt_token_implementation_id: ContractId,
+    #[serde(deserialize_with = "asset_id")]
+    pub fpt_asset_id: AssetId,
+    #[serde(deserialize_with = "contract_id")]
+    pub community_issuance: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub community_issuance_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub coll_surplus_pool: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub coll_surplus_pool_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub default_pool: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub default_pool_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub active_pool: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub active_pool_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub sorted_troves: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub sorted_troves_implementation_id: ContractId,
+    #[serde(default, deserialize_with = "optional_contract_id")]
+    pub hint_helper: Option<ContractId>,
+    #[serde(default, deserialize_with = "optional_contract_id")]
+    pub multi_trove_getter: Option<ContractId>,
+    #[serde(default)]
+    pub asset_contracts: Vec<AssetDeployment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssetDeployment {
+    // Only written by `add-asset`, assets deployed alongside the core contracts have none
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(deserialize_with = "asset_id")]
+    pub asset_id: AssetId,
+    #[serde(deserialize_with = "contract_id")]
+    pub asset_contract: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub oracle: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub oracle_implementation_id: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub trove_manager: ContractId,
+    #[serde(deserialize_with = "contract_id")]
+    pub trove_manager_implementation_id: ContractId,
+    #[serde(default, deserialize_with = "optional_contract_id")]
+    pub pyth_contract: Option<ContractId>,
+    #[serde(default, deserialize_with = "optional_contract_id")]
+    pub stork_contract: Option<ContractId>,
+    #[serde(default)]
+    pub redstone: Option<RedstoneDeployment>,
+    #[serde(default)]
+    pub fuel_vm_decimals: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RedstoneDeployment {
+    #[serde(deserialize_with = "contract_id")]
+    pub redstone_contract: ContractId,
+}
+
+impl Deployment {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| Error::Other(format!("Failed to read {}: {}", path.display(), e)))?;
+
+        Self::from_json(&json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json)
+            .map_err(|e| Error::Other(format!("Invalid deployment manifest: {}", e)))
+    }
+
+    pub fn asset(&self, asset_id: AssetId) -> Result<&AssetDeployment, Error> {
+        self.asset_contracts
+            .iter()
+            .find(|asset| asset.asset_id == asset_id)
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "Asset 0x{} is not part of the deployment",
+                    asset_id
+                ))
+            })
+    }
+
+    pub fn asset_by_symbol(&self, symbol: &str) -> Result<&AssetDeployment, Error> {
+        self.asset_contracts
+            .iter()
+            .find(|asset| {
+                asset
+                    .symbol
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(symbol))
+            })
+            .ok_or_else(|| Error::Other(format!("Asset {} is not part of the deployment", symbol)))
+    }
+}
+
+impl AssetDeployment {
+    // Price feeds the oracle may read from, absent sources are skipped
+    pub fn price_feeds(&self) -> Vec<ContractId> {
+        [
+            self.stork_contract,
+            self.pyth_contract,
+            self.redstone.as_ref().map(|r| r.redstone_contract),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+// Older manifests store contract ids in bech32, newer ones as 0x-prefixed hex
+fn parse_contract_id(value: &str) -> Result<ContractId, String> {
+    if value.starts_with("fuel") {
+        Bech32ContractId::from_str(value)
+            .map(|id| ContractId::from(&id))
+            .map_err(|e| format!("invalid contract id {}: {}", value, e))
+    } else {
+        ContractId::from_str(value).map_err(|e| format!("invalid contract id {}: {}", value, e))
+    }
+}
+
+fn contract_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ContractId, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse_contract_id(&value).map_err(serde::de::Error::custom)
+}
+
+fn optional_contract_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ContractId>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| parse_contract_id(&value).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+fn asset_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<AssetId, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    AssetId::from_str(&value)
+        .map_err(|e| serde::de::Error::custom(format!("invalid asset id {}: {}", value, e)))
+}
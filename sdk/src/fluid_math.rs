@@ -0,0 +1,217 @@
+use fuels::types::U256;
+
+// Mirror of `libraries/src/fluid_math.sw`, results match the Sway functions bit for bit.
+//
+// Every function has a `checked_` variant returning `None` exactly where the Sway version
+// reverts (u64/U128/u256 overflow, division by zero or a failed downcast), the plain variant
+// panics in the same cases.
+
+pub const SECONDS_IN_ONE_MINUTE: u64 = 60;
+
+pub const DECIMAL_PRECISION: u64 = 1_000_000_000;
+pub const ONE: u64 = 1_000_000_000;
+
+pub const REDEMPTION_FEE_FLOOR: u64 = 10_000_000;
+pub const BORROWING_FEE_FLOOR: u64 = 5_000_000;
+pub const MAX_BORROWING_FEE: u64 = 50_000_000;
+pub const FLASH_MINT_FEE: u64 = 1_000_000;
+pub const MINUTE_DECAY_FACTOR: u64 = 999_037_758;
+pub const BETA: u64 = 2;
+
+pub const MCR: u64 = 1_350_000_000;
+pub const CCR: u64 = 1_500_000_000;
+pub const POST_COLLATERAL_RATIO: u64 = 1_500_000_000;
+pub const USDM_GAS_COMPENSATION: u64 = 10_000_000;
+pub const MIN_NET_DEBT: u64 = 500_000_000_000;
+
+pub const STABILITY_POOL_FEE: u64 = 100_000_000;
+pub const LIQUIDATOR_EXECUTION_GAS_FEE: u64 = 5_000_000;
+
+pub const MIN_MCR: u64 = 1_100_000_000;
+pub const MIN_NET_DEBT_LOWER_BOUND: u64 = 100_000_000_000;
+pub const MIN_NET_DEBT_UPPER_BOUND: u64 = 100_000_000_000_000;
+pub const MAX_REDEMPTION_FEE_FLOOR: u64 = 50_000_000;
+pub const MAX_STABILITY_POOL_FEE: u64 = 200_000_000;
+pub const MAX_POST_COLLATERAL_RATIO: u64 = 3_000_000_000;
+
+// `dec_pow` caps the exponent at 1000 years of minutes
+pub const DEC_POW_MAX_MINUTES: u64 = 525_600_000;
+
+const FUEL_VM_DECIMALS: u32 = 9;
+
+pub fn checked_convert_precision(price: u64, current_precision: u32) -> Option<u64> {
+    if current_precision > FUEL_VM_DECIMALS {
+        let magnitude = 10u64.checked_pow(current_precision - FUEL_VM_DECIMALS)?;
+        Some(price / magnitude)
+    } else if current_precision < FUEL_VM_DECIMALS {
+        let magnitude = 10u64.checked_pow(FUEL_VM_DECIMALS - current_precision)?;
+        price.checked_mul(magnitude)
+    } else {
+        Some(price)
+    }
+}
+
+pub fn convert_precision(price: u64, current_precision: u32) -> u64 {
+    checked_convert_precision(price, current_precision).expect("convert_precision reverts")
+}
+
+// The magnitude is computed as a u64 before being widened, as in Sway
+pub fn checked_convert_precision_u256_and_downcast(
+    price: U256,
+    current_precision: u32,
+) -> Option<u64> {
+    let adjusted_price = if current_precision > FUEL_VM_DECIMALS {
+        let magnitude = 10u64.checked_pow(current_precision - FUEL_VM_DECIMALS)?;
+        price / U256::from(magnitude)
+    } else if current_precision < FUEL_VM_DECIMALS {
+        let magnitude = 10u64.checked_pow(FUEL_VM_DECIMALS - current_precision)?;
+        price.checked_mul(U256::from(magnitude))?
+    } else {
+        price
+    };
+
+    u64::try_from(adjusted_price).ok()
+}
+
+pub fn convert_precision_u256_and_downcast(price: U256, current_precision: u32) -> u64 {
+    checked_convert_precision_u256_and_downcast(price, current_precision)
+        .expect("convert_precision_u256_and_downcast reverts")
+}
+
+// Never reverts, the fee is always below the debt
+pub fn fm_compute_borrow_fee(debt: u64) -> u64 {
+    (debt as u128 * BORROWING_FEE_FLOOR as u128 / DECIMAL_PRECISION as u128) as u64
+}
+
+// Never reverts, the fee is always below the debt
+pub fn fm_compute_redemption_fee(debt: u64) -> u64 {
+    (debt as u128 * REDEMPTION_FEE_FLOOR as u128 / DECIMAL_PRECISION as u128) as u64
+}
+
+// Reverts only if the floor plus the base rate overflows
+pub fn checked_fm_compute_borrowing_rate(base_rate: u64, fee_floor: u64) -> Option<u64> {
+    Some(fee_floor.checked_add(base_rate)?.min(MAX_BORROWING_FEE))
+}
+
+pub fn fm_compute_borrowing_rate(base_rate: u64, fee_floor: u64) -> u64 {
+    checked_fm_compute_borrowing_rate(base_rate, fee_floor)
+        .expect("fm_compute_borrowing_rate reverts")
+}
+
+// Reverts only if the floor plus the base rate overflows
+pub fn checked_fm_compute_redemption_rate(base_rate: u64, fee_floor: u64) -> Option<u64> {
+    Some(fee_floor.checked_add(base_rate)?.min(DECIMAL_PRECISION))
+}
+
+pub fn fm_compute_redemption_rate(base_rate: u64, fee_floor: u64) -> u64 {
+    checked_fm_compute_redemption_rate(base_rate, fee_floor)
+        .expect("fm_compute_redemption_rate reverts")
+}
+
+// Reverts only for rates above 100% whose fee does not fit in a u64
+pub fn checked_fm_compute_fee(amount: u64, rate: u64) -> Option<u64> {
+    u64::try_from(amount as u128 * rate as u128 / DECIMAL_PRECISION as u128).ok()
+}
+
+pub fn fm_compute_fee(amount: u64, rate: u64) -> u64 {
+    checked_fm_compute_fee(amount, rate).expect("fm_compute_fee reverts")
+}
+
+// Never reverts, the decay factor is below ONE so the decayed rate is at most the base rate
+pub fn fm_decay_base_rate(base_rate: u64, minutes_passed: u64) -> u64 {
+    if minutes_passed == 0 {
+        return base_rate;
+    }
+    let decay_factor = dec_pow(MINUTE_DECAY_FACTOR, minutes_passed);
+    (base_rate as u128 * decay_factor / DECIMAL_PRECISION as u128) as u64
+}
+
+// Reverts only for an empty USDM supply, the result is capped at 100%
+pub fn checked_fm_compute_base_rate_from_redemption(
+    decayed_base_rate: u64,
+    usdm_redeemed: u64,
+    usdm_supply: u64,
+) -> Option<u64> {
+    let redeemed_fraction =
+        (usdm_redeemed as u128 * DECIMAL_PRECISION as u128).checked_div(usdm_supply as u128)?;
+    let new_base_rate = decayed_base_rate as u128 + redeemed_fraction / BETA as u128;
+    Some(new_base_rate.min(DECIMAL_PRECISION as u128) as u64)
+}
+
+pub fn fm_compute_base_rate_from_redemption(
+    decayed_base_rate: u64,
+    usdm_redeemed: u64,
+    usdm_supply: u64,
+) -> u64 {
+    checked_fm_compute_base_rate_from_redemption(decayed_base_rate, usdm_redeemed, usdm_supply)
+        .expect("fm_compute_base_rate_from_redemption reverts")
+}
+
+pub fn checked_fm_compute_nominal_cr(coll: u64, debt: u64) -> Option<u64> {
+    if debt > 0 {
+        u64::try_from(coll as u128 * DECIMAL_PRECISION as u128 / debt as u128).ok()
+    } else {
+        Some(u64::MAX)
+    }
+}
+
+pub fn fm_compute_nominal_cr(coll: u64, debt: u64) -> u64 {
+    checked_fm_compute_nominal_cr(coll, debt).expect("fm_compute_nominal_cr reverts")
+}
+
+pub fn checked_fm_multiply_ratio(value: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    let ratio = (value as u128 * numerator as u128).checked_div(denominator as u128)?;
+    u64::try_from(ratio).ok()
+}
+
+pub fn fm_multiply_ratio(value: u64, numerator: u64, denominator: u64) -> u64 {
+    checked_fm_multiply_ratio(value, numerator, denominator).expect("fm_multiply_ratio reverts")
+}
+
+pub fn checked_fm_compute_cr(coll: u64, debt: u64, price: u64) -> Option<u64> {
+    if debt > 0 {
+        u64::try_from(coll as u128 * price as u128 / debt as u128).ok()
+    } else {
+        Some(u64::MAX)
+    }
+}
+
+pub fn fm_compute_cr(coll: u64, debt: u64, price: u64) -> u64 {
+    checked_fm_compute_cr(coll, debt, price).expect("fm_compute_cr reverts")
+}
+
+// Rounds half up, reverts if the U128 product or the rounding overflows
+pub fn checked_dec_mul(a: u128, b: u128) -> Option<u128> {
+    let prod = a.checked_mul(b)?;
+    let rounded = prod.checked_add(DECIMAL_PRECISION as u128 / 2)?;
+    Some(rounded / DECIMAL_PRECISION as u128)
+}
+
+pub fn dec_mul(a: u128, b: u128) -> u128 {
+    checked_dec_mul(a, b).expect("dec_mul reverts")
+}
+
+// Exponentiation by squaring with `dec_mul`. Unlike Liquity's `decPow` the Sway version returns
+// `base` rather than `DECIMAL_PRECISION` for `minutes == 0`, and this mirror keeps that behaviour.
+pub fn checked_dec_pow(base: u64, minutes: u64) -> Option<u128> {
+    let mut n = minutes.min(DEC_POW_MAX_MINUTES);
+    let mut y = DECIMAL_PRECISION as u128;
+    let mut x = base as u128;
+
+    while n > 1 {
+        if n % 2 == 0 {
+            x = checked_dec_mul(x, x)?;
+            n /= 2;
+        } else {
+            y = checked_dec_mul(x, y)?;
+            x = checked_dec_mul(x, x)?;
+            n = (n - 1) / 2;
+        }
+    }
+
+    checked_dec_mul(x, y)
+}
+
+pub fn dec_pow(base: u64, minutes: u64) -> u128 {
+    checked_dec_pow(base, minutes).expect("dec_pow reverts")
+}
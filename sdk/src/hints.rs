@@ -0,0 +1,54 @@
+use fuels::types::{Address, Identity};
+
+use crate::fluid_math::fm_compute_nominal_cr;
+
+// A node of an asset's sorted troves list, ordered from the highest NICR (head) to the lowest (tail)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortedTrove {
+    pub id: Identity,
+    pub nicr: u64,
+}
+
+// A listed trove with its entire collateral and debt, pending redistribution rewards included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListedTrove {
+    pub id: Identity,
+    pub coll: u64,
+    pub debt: u64,
+}
+
+impl ListedTrove {
+    pub fn sorted(&self) -> SortedTrove {
+        SortedTrove {
+            id: self.id,
+            nicr: fm_compute_nominal_cr(self.coll, self.debt),
+        }
+    }
+}
+
+pub fn null_hint() -> Identity {
+    Identity::Address(Address::zeroed())
+}
+
+// The `(prev, next)` pair `SortedTroves` would settle on when descending from the head, passing it as
+// `(upper_hint, lower_hint)` makes `insert` and `re_insert` skip the search
+pub fn find_insert_position(troves: &[SortedTrove], nicr: u64) -> (Identity, Identity) {
+    let head = match troves.first() {
+        Some(head) => head,
+        None => return (null_hint(), null_hint()),
+    };
+    if nicr >= head.nicr {
+        return (null_hint(), head.id);
+    }
+
+    for (index, prev) in troves.iter().enumerate() {
+        match troves.get(index + 1) {
+            Some(next) if prev.nicr >= nicr && nicr >= next.nicr => return (prev.id, next.id),
+            None if nicr <= prev.nicr => return (prev.id, null_hint()),
+            _ => {}
+        }
+    }
+
+    // Unreachable for a list sorted by descending NICR
+    (null_hint(), null_hint())
+}
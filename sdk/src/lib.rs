@@ -0,0 +1,13 @@
+pub mod abi;
+pub mod client;
+pub mod deployment;
+pub mod fluid_math;
+pub mod hints;
+pub mod liquidation;
+pub mod preview;
+pub mod redemption;
+pub mod risk_parameters;
+pub mod views;
+
+pub use client::MoorClient;
+pub use deployment::{AssetDeployment, Deployment};
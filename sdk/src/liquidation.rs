@@ -0,0 +1,291 @@
+use fuels::types::{AssetId, Identity};
+
+use crate::{
+    fluid_math::{
+        fm_compute_cr, fm_compute_nominal_cr, fm_multiply_ratio, DECIMAL_PRECISION,
+        LIQUIDATOR_EXECUTION_GAS_FEE, ONE,
+    },
+    hints::{find_insert_position, null_hint, ListedTrove, SortedTrove},
+    risk_parameters::RiskParameters,
+};
+
+// Mirror of `LiquidatedTroveValsInner` in trove-manager-contract/src/data_structures.sw
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LiquidatedTroveValues {
+    pub trove_debt_to_repay: u64,
+    pub trove_coll_liquidated: u64,
+    pub is_partial_liquidation: bool,
+}
+
+// Mirror of `LiquidationValues` in trove-manager-contract/src/data_structures.sw
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LiquidationValues {
+    pub entire_trove_debt: u64,
+    pub entire_trove_coll: u64,
+    pub debt_to_offset: u64,
+    pub coll_to_send_to_sp: u64,
+    pub debt_to_redistribute: u64,
+    pub coll_to_redistribute: u64,
+    pub coll_surplus: u64,
+    pub coll_gas_compensation: u64,
+    pub is_partial_liquidation: bool,
+    pub remaining_trove_coll: u64,
+    pub remaining_trove_debt: u64,
+}
+
+// Mirror of `calculate_liqudated_trove_values` in trove-manager-contract/src/utils.sw, reverts where
+// the contract does. A partial liquidation repays just enough debt to bring the trove back to the
+// asset's post collateral ratio, unless that leaves it below its min net debt.
+pub fn calculate_liquidated_trove_values(
+    coll: u64,
+    debt: u64,
+    price: u64,
+    risk_parameters: &RiskParameters,
+) -> LiquidatedTroveValues {
+    let stability_pool_fee = risk_parameters.stability_pool_fee;
+    // Bad debt
+    if fm_multiply_ratio(coll, price, DECIMAL_PRECISION) < debt {
+        return LiquidatedTroveValues {
+            trove_coll_liquidated: coll,
+            trove_debt_to_repay: debt,
+            is_partial_liquidation: false,
+        };
+    }
+
+    let trove_debt_numerator = (debt as u128 * risk_parameters.post_collateral_ratio as u128)
+        .checked_sub(coll as u128 * price as u128)
+        .expect("calculate_liquidated_trove_values reverts above the post collateral ratio");
+    let trove_debt_denominator =
+        (risk_parameters.post_collateral_ratio - ONE - stability_pool_fee) as u128;
+    let trove_debt_to_repay = u64::try_from(trove_debt_numerator / trove_debt_denominator)
+        .expect("calculate_liquidated_trove_values reverts");
+    let trove_debt_to_repay = trove_debt_to_repay.min(debt);
+
+    if debt - trove_debt_to_repay < risk_parameters.min_net_debt {
+        let trove_coll_liquidated = fm_multiply_ratio(debt, ONE + stability_pool_fee, price);
+        return LiquidatedTroveValues {
+            trove_coll_liquidated: trove_coll_liquidated.min(coll),
+            trove_debt_to_repay: debt,
+            is_partial_liquidation: false,
+        };
+    }
+
+    let trove_coll_liquidated =
+        fm_multiply_ratio(trove_debt_to_repay, ONE + stability_pool_fee, price);
+    LiquidatedTroveValues {
+        trove_coll_liquidated: trove_coll_liquidated.min(coll),
+        trove_debt_to_repay,
+        is_partial_liquidation: true,
+    }
+}
+
+// Mirror of `get_offset_and_redistribution_vals` in trove-manager-contract/src/utils.sw
+pub fn get_offset_and_redistribution_vals(
+    coll: u64,
+    debt: u64,
+    usdm_in_stab_pool: u64,
+    price: u64,
+    risk_parameters: &RiskParameters,
+) -> LiquidationValues {
+    let mut vars = LiquidationValues {
+        entire_trove_coll: coll,
+        entire_trove_debt: debt,
+        ..Default::default()
+    };
+    let liquidated = calculate_liquidated_trove_values(coll, debt, price, risk_parameters);
+    if liquidated.is_partial_liquidation {
+        vars.is_partial_liquidation = true;
+        vars.remaining_trove_coll = coll - liquidated.trove_coll_liquidated;
+        vars.remaining_trove_debt = debt - liquidated.trove_debt_to_repay;
+    } else {
+        vars.coll_surplus = coll - liquidated.trove_coll_liquidated;
+    }
+
+    // The contract divides by 200, the same 0.5% as LIQUIDATOR_EXECUTION_GAS_FEE
+    vars.coll_gas_compensation =
+        liquidated.trove_coll_liquidated / (DECIMAL_PRECISION / LIQUIDATOR_EXECUTION_GAS_FEE);
+    let pending_liquidated_coll = liquidated.trove_coll_liquidated - vars.coll_gas_compensation;
+    if usdm_in_stab_pool > 0 {
+        vars.debt_to_offset = liquidated.trove_debt_to_repay.min(usdm_in_stab_pool);
+        vars.coll_to_send_to_sp = fm_multiply_ratio(
+            pending_liquidated_coll,
+            vars.debt_to_offset,
+            liquidated.trove_debt_to_repay,
+        );
+        vars.debt_to_redistribute = liquidated.trove_debt_to_repay - vars.debt_to_offset;
+        vars.coll_to_redistribute = pending_liquidated_coll - vars.coll_to_send_to_sp;
+    } else {
+        vars.debt_to_redistribute = liquidated.trove_debt_to_repay;
+        vars.coll_to_redistribute = pending_liquidated_coll;
+    }
+
+    vars
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiquidationEstimate {
+    pub borrower: Identity,
+    pub icr: u64,
+    pub values: LiquidationValues,
+    // Where a partially liquidated trove is re-inserted, once the troves before it are liquidated
+    pub upper_partial_hint: Identity,
+    pub lower_partial_hint: Identity,
+}
+
+impl LiquidationEstimate {
+    pub fn is_partial(&self) -> bool {
+        self.values.is_partial_liquidation
+    }
+
+    // Collateral sent to the liquidator
+    pub fn liquidator_reward(&self) -> u64 {
+        self.values.coll_gas_compensation
+    }
+}
+
+// What `batch_liquidate_troves` does to one asset at the given price and Stability Pool size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiquidationSequence {
+    pub asset_id: AssetId,
+    pub price: u64,
+    pub usdm_in_stability_pool: u64,
+    // Liquidated troves, lowest NICR first
+    pub troves: Vec<LiquidationEstimate>,
+}
+
+impl LiquidationSequence {
+    pub fn is_empty(&self) -> bool {
+        self.troves.is_empty()
+    }
+
+    pub fn borrowers(&self) -> Vec<Identity> {
+        self.troves.iter().map(|trove| trove.borrower).collect()
+    }
+
+    pub fn liquidator_reward(&self) -> u64 {
+        self.sum(|values| values.coll_gas_compensation)
+    }
+
+    // The liquidator reward valued in USDM at the oracle price, to weigh against the transaction cost
+    pub fn liquidator_reward_value(&self) -> u64 {
+        fm_multiply_ratio(self.liquidator_reward(), self.price, DECIMAL_PRECISION)
+    }
+
+    // USDM burned from the Stability Pool deposits
+    pub fn stability_pool_usdm_loss(&self) -> u64 {
+        self.sum(|values| values.debt_to_offset)
+    }
+
+    // Collateral shared between the Stability Pool depositors
+    pub fn stability_pool_coll_gain(&self) -> u64 {
+        self.sum(|values| values.coll_to_send_to_sp)
+    }
+
+    pub fn debt_to_redistribute(&self) -> u64 {
+        self.sum(|values| values.debt_to_redistribute)
+    }
+
+    pub fn coll_to_redistribute(&self) -> u64 {
+        self.sum(|values| values.coll_to_redistribute)
+    }
+
+    pub fn coll_surplus(&self) -> u64 {
+        self.sum(|values| values.coll_surplus)
+    }
+
+    // Pro-rata `(usdm_loss, coll_gain)` of one deposit, the pool's running product and sums round
+    // slightly differently
+    pub fn deposit_impact(&self, deposit: u64) -> (u64, u64) {
+        if self.usdm_in_stability_pool == 0 {
+            return (0, 0);
+        }
+        (
+            fm_multiply_ratio(
+                deposit,
+                self.stability_pool_usdm_loss(),
+                self.usdm_in_stability_pool,
+            ),
+            fm_multiply_ratio(
+                deposit,
+                self.stability_pool_coll_gain(),
+                self.usdm_in_stability_pool,
+            ),
+        )
+    }
+
+    fn sum(&self, value: impl Fn(&LiquidationValues) -> u64) -> u64 {
+        self.troves.iter().map(|trove| value(&trove.values)).sum()
+    }
+}
+
+// Mirrors `internal_get_totals_from_batch_liquidate`: walks the troves from the tail and stops at the
+// first one at or above the asset's MCR, or CCR while the asset is in recovery mode, each offset
+// shrinking the Stability Pool and the system totals for the next. In recovery mode a trove at or
+// above the MCR also stops the walk if the Stability Pool can't absorb all of its debt. `troves` is
+// the asset's list from head to tail, pending rewards included.
+pub fn estimate_liquidations(
+    asset_id: AssetId,
+    price: u64,
+    risk_parameters: &RiskParameters,
+    usdm_in_stability_pool: u64,
+    mut entire_system_coll: u64,
+    mut entire_system_debt: u64,
+    troves: &[ListedTrove],
+) -> LiquidationSequence {
+    let mut remaining_usdm_in_stability_pool = usdm_in_stability_pool;
+    let mut sorted: Vec<SortedTrove> = troves.iter().map(ListedTrove::sorted).collect();
+    let mut estimates = Vec::new();
+
+    for trove in troves.iter().rev() {
+        let icr = fm_compute_cr(trove.coll, trove.debt, price);
+        let recovery_mode =
+            fm_compute_cr(entire_system_coll, entire_system_debt, price) < risk_parameters.ccr;
+        let liquidatable =
+            icr < risk_parameters.mcr || (recovery_mode && icr < risk_parameters.ccr);
+        if !liquidatable
+            || (icr >= risk_parameters.mcr && trove.debt > remaining_usdm_in_stability_pool)
+        {
+            break;
+        }
+
+        let values = get_offset_and_redistribution_vals(
+            trove.coll,
+            trove.debt,
+            remaining_usdm_in_stability_pool,
+            price,
+            risk_parameters,
+        );
+        remaining_usdm_in_stability_pool -= values.debt_to_offset;
+        entire_system_debt -= values.debt_to_offset;
+        entire_system_coll -=
+            values.coll_to_send_to_sp + values.coll_gas_compensation + values.coll_surplus;
+
+        sorted.retain(|other| other.id != trove.id);
+        let (mut upper_partial_hint, mut lower_partial_hint) = (null_hint(), null_hint());
+        if values.is_partial_liquidation {
+            let nicr =
+                fm_compute_nominal_cr(values.remaining_trove_coll, values.remaining_trove_debt);
+            (upper_partial_hint, lower_partial_hint) = find_insert_position(&sorted, nicr);
+            let position = sorted
+                .iter()
+                .position(|other| other.id == lower_partial_hint)
+                .unwrap_or(sorted.len());
+            sorted.insert(position, SortedTrove { id: trove.id, nicr });
+        }
+
+        estimates.push(LiquidationEstimate {
+            borrower: trove.id,
+            icr,
+            values,
+            upper_partial_hint,
+            lower_partial_hint,
+        });
+    }
+
+    LiquidationSequence {
+        asset_id,
+        price,
+        usdm_in_stability_pool,
+        troves: estimates,
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt;
+
+use fuels::types::Identity;
+
+use crate::{
+    fluid_math::{checked_fm_compute_cr, checked_fm_compute_fee, checked_fm_compute_nominal_cr},
+    hints::{find_insert_position, SortedTrove},
+    risk_parameters::RiskParameters,
+    views::TroveView,
+};
+
+// Entire collateral and debt of a trove, pending redistribution rewards included
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TroveState {
+    pub coll: u64,
+    pub debt: u64,
+}
+
+impl From<&TroveView> for TroveState {
+    fn from(trove: &TroveView) -> Self {
+        Self {
+            coll: trove.coll,
+            debt: trove.debt,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TroveAdjustment {
+    Open { coll: u64, usdm_amount: u64 },
+    AddColl(u64),
+    WithdrawColl(u64),
+    WithdrawUsdm(u64),
+    RepayUsdm(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdjustmentPreview {
+    pub coll: u64,
+    pub debt: u64,
+    // Minted to FPT staking and added to the debt, only charged when borrowing
+    pub borrow_fee: u64,
+    pub icr: u64,
+    pub nicr: u64,
+    // Price below which the trove falls under MCR
+    pub liquidation_price: u64,
+    pub meets_mcr: bool,
+    // Only checked on open and repayment, always true otherwise
+    pub meets_min_net_debt: bool,
+    pub upper_hint: Identity,
+    pub lower_hint: Identity,
+}
+
+impl AdjustmentPreview {
+    pub fn is_valid(&self) -> bool {
+        self.meets_mcr && self.meets_min_net_debt
+    }
+}
+
+// Reverts of `BorrowOperations` that do not depend on the resulting position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewError {
+    TroveAlreadyActive,
+    TroveNotActive,
+    ZeroAdjustment,
+    WithdrawalExceedsCollateral,
+    RepaymentExceedsDebt,
+    Overflow,
+}
+
+impl fmt::Display for PreviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            PreviewError::TroveAlreadyActive => "User already has an active Trove",
+            PreviewError::TroveNotActive => "User does not have an active Trove",
+            PreviewError::ZeroAdjustment => "Collateral or debt change must be greater than 0",
+            PreviewError::WithdrawalExceedsCollateral => {
+                "Cannot withdraw more than the Trove's collateral"
+            }
+            PreviewError::RepaymentExceedsDebt => "Cannot repay more than the Trove's debt",
+            PreviewError::Overflow => "Resulting position overflows",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for PreviewError {}
+
+// Mirrors `open_trove` and `internal_adjust_trove` of BorrowOperations. `current` is the borrower's
+// trove with pending rewards applied (None when opening), `sorted_troves` the asset's list from head
+// to tail, which may still contain the borrower. `borrowing_rate` is the one the ProtocolManager
+// reports for the current decayed base rate, `risk_parameters` those of the asset.
+pub fn preview_adjustment(
+    borrower: Identity,
+    current: Option<TroveState>,
+    adjustment: TroveAdjustment,
+    price: u64,
+    borrowing_rate: u64,
+    risk_parameters: &RiskParameters,
+    sorted_troves: &[SortedTrove],
+) -> Result<AdjustmentPreview, PreviewError> {
+    let (coll, debt, borrow_fee, checks_min_net_debt) = match (current, adjustment) {
+        (Some(_), TroveAdjustment::Open { .. }) => return Err(PreviewError::TroveAlreadyActive),
+        (None, TroveAdjustment::Open { coll, usdm_amount }) => {
+            let fee = checked_fm_compute_fee(usdm_amount, borrowing_rate)
+                .ok_or(PreviewError::Overflow)?;
+            let debt = usdm_amount.checked_add(fee).ok_or(PreviewError::Overflow)?;
+            (coll, debt, fee, true)
+        }
+        (None, _) => return Err(PreviewError::TroveNotActive),
+        (Some(trove), adjustment) => match adjustment {
+            TroveAdjustment::AddColl(0)
+            | TroveAdjustment::WithdrawColl(0)
+            | TroveAdjustment::WithdrawUsdm(0)
+            | TroveAdjustment::RepayUsdm(0) => return Err(PreviewError::ZeroAdjustment),
+            TroveAdjustment::AddColl(amount) => (
+                trove
+                    .coll
+                    .checked_add(amount)
+                    .ok_or(PreviewError::Overflow)?,
+                trove.debt,
+                0,
+                false,
+            ),
+            TroveAdjustment::WithdrawColl(amount) => (
+                trove
+                    .coll
+                    .checked_sub(amount)
+                    .ok_or(PreviewError::WithdrawalExceedsCollateral)?,
+                trove.debt,
+                0,
+                false,
+            ),
+            TroveAdjustment::WithdrawUsdm(amount) => {
+                let fee =
+                    checked_fm_compute_fee(amount, borrowing_rate).ok_or(PreviewError::Overflow)?;
+                let debt = amount
+                    .checked_add(fee)
+                    .and_then(|change| trove.debt.checked_add(change))
+                    .ok_or(PreviewError::Overflow)?;
+                (trove.coll, debt, fee, false)
+            }
+            TroveAdjustment::RepayUsdm(amount) => (
+                trove.coll,
+                trove
+                    .debt
+                    .checked_sub(amount)
+                    .ok_or(PreviewError::RepaymentExceedsDebt)?,
+                0,
+                true,
+            ),
+            TroveAdjustment::Open { .. } => unreachable!(),
+        },
+    };
+
+    let icr = checked_fm_compute_cr(coll, debt, price).ok_or(PreviewError::Overflow)?;
+    let nicr = checked_fm_compute_nominal_cr(coll, debt).ok_or(PreviewError::Overflow)?;
+
+    // `re_insert` removes the borrower before searching for the new position
+    let others: Vec<SortedTrove> = sorted_troves
+        .iter()
+        .filter(|trove| trove.id != borrower)
+        .copied()
+        .collect();
+    let (upper_hint, lower_hint) = find_insert_position(&others, nicr);
+
+    Ok(AdjustmentPreview {
+        coll,
+        debt,
+        borrow_fee,
+        icr,
+        nicr,
+        liquidation_price: liquidation_price(coll, debt, risk_parameters.mcr),
+        meets_mcr: icr >= risk_parameters.mcr,
+        meets_min_net_debt: !checks_min_net_debt || debt >= risk_parameters.min_net_debt,
+        upper_hint,
+        lower_hint,
+    })
+}
+
+// Lowest price keeping the trove at or above `mcr`
+pub fn liquidation_price(coll: u64, debt: u64, mcr: u64) -> u64 {
+    if debt == 0 {
+        return 0;
+    }
+    if coll == 0 {
+        return u64::MAX;
+    }
+
+    let price = (mcr as u128 * debt as u128).div_ceil(coll as u128);
+    u64::try_from(price).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,265 @@
+use std::fmt;
+
+use fuels::types::{AssetId, Identity};
+
+use crate::{
+    fluid_math::{
+        checked_fm_compute_base_rate_from_redemption, checked_fm_compute_cr,
+        checked_fm_multiply_ratio, fm_compute_fee, fm_compute_nominal_cr,
+        fm_compute_redemption_rate, DECIMAL_PRECISION,
+    },
+    hints::{find_insert_position, null_hint, ListedTrove, SortedTrove},
+    risk_parameters::RiskParameters,
+};
+
+// One registered asset as `redeem_collateral` reads it, troves from head to tail
+#[derive(Debug, Clone)]
+pub struct AssetTroves {
+    pub asset_id: AssetId,
+    pub price: u64,
+    pub risk_parameters: RiskParameters,
+    // Troves of sunset assets are redeemed before those of active assets
+    pub is_sunset: bool,
+    pub troves: Vec<ListedTrove>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RedeemedTrove {
+    pub asset_id: AssetId,
+    pub borrower: Identity,
+    pub usdm_lot: u64,
+    pub asset_lot: u64,
+    // Fully redeemed troves are closed and their leftover collateral goes to the CollSurplusPool
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetRedemption {
+    pub asset_id: AssetId,
+    pub usdm_redeemed: u64,
+    pub asset_drawn: u64,
+    // The asset's redemption fee floor on top of the base rate raised by this redemption
+    pub redemption_rate: u64,
+    // Sent to FPT staking
+    pub fee: u64,
+    pub asset_to_redeemer: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedemptionQuote {
+    // In the order they are redeemed
+    pub troves: Vec<RedeemedTrove>,
+    // Only the assets something was redeemed from
+    pub assets: Vec<AssetRedemption>,
+    pub usdm_redeemed: u64,
+    // Highest rate charged on any asset drawn. 0 when nothing is redeemed, `max_fee_percentage`
+    // has to be at least this
+    pub redemption_rate: u64,
+    // Returned to the redeemer
+    pub remaining_usdm: u64,
+    // The trove the walk stopped at because a partial redemption would leave it below its min net debt
+    pub cancelled_partial: Option<Identity>,
+    // Arguments for `redeem_collateral`, exact neighbours of the partially redeemed trove if any
+    pub partial_redemption_hint: u64,
+    pub upper_partial_hint: Identity,
+    pub lower_partial_hint: Identity,
+}
+
+impl RedemptionQuote {
+    pub fn asset(&self, asset_id: AssetId) -> Option<&AssetRedemption> {
+        self.assets.iter().find(|asset| asset.asset_id == asset_id)
+    }
+}
+
+// Reverts of `redeem_collateral` the quote can tell in advance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedemptionError {
+    ZeroAmount,
+    InsufficientCollateral,
+    Overflow,
+}
+
+impl fmt::Display for RedemptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            RedemptionError::ZeroAmount => "Redemption amount must be greater than 0",
+            RedemptionError::InsufficientCollateral => {
+                "A redeemed Trove has less collateral than the redeemed USDM is worth"
+            }
+            RedemptionError::Overflow => "Redemption amounts overflow",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for RedemptionError {}
+
+// Replays `ProtocolManager::redeem_collateral`: every asset starts at its tail, skipping troves below
+// its MCR, and each iteration redeems from the lowest ICR across sunset assets, then across all
+// assets once no sunset asset has a trove left, ties going to the asset registered first. `assets`
+// must be the assets that are not delisted in registration order, or the targeted ones for
+// `redeem_collateral_from_assets`, `decayed_base_rate` and `usdm_supply` are read before the
+// redemption.
+pub fn quote_redemption(
+    usdm_amount: u64,
+    max_iterations: u64,
+    decayed_base_rate: u64,
+    usdm_supply: u64,
+    assets: &[AssetTroves],
+) -> Result<RedemptionQuote, RedemptionError> {
+    if usdm_amount == 0 {
+        return Err(RedemptionError::ZeroAmount);
+    }
+
+    let mut cursors = Vec::with_capacity(assets.len());
+    for asset in assets {
+        let mut cursor = asset.troves.len().checked_sub(1);
+        while let Some(position) = cursor {
+            if current_icr(asset, Some(position))? >= asset.risk_parameters.mcr {
+                break;
+            }
+            cursor = position.checked_sub(1);
+        }
+        cursors.push(cursor);
+    }
+
+    let mut totals: Vec<(u64, u64)> = vec![(0, 0); assets.len()];
+    let mut troves = Vec::new();
+    let mut remaining_usdm = usdm_amount;
+    let mut remaining_iterations = max_iterations;
+    let mut cancelled_partial = None;
+    let mut partial_redemption_hint = 0;
+    let mut upper_partial_hint = null_hint();
+    let mut lower_partial_hint = null_hint();
+
+    while remaining_usdm > 0 && remaining_iterations > 0 {
+        let (index, position) = match find_min_borrower(assets, &cursors)? {
+            (index, Some(position)) => (index, position),
+            (_, None) => break,
+        };
+        let asset = &assets[index];
+        let trove = asset.troves[position];
+        remaining_iterations -= 1;
+
+        let usdm_lot = remaining_usdm.min(trove.debt);
+        let asset_lot = checked_fm_multiply_ratio(usdm_lot, DECIMAL_PRECISION, asset.price)
+            .ok_or(RedemptionError::Overflow)?;
+        let new_debt = trove.debt - usdm_lot;
+        let new_coll = trove
+            .coll
+            .checked_sub(asset_lot)
+            .ok_or(RedemptionError::InsufficientCollateral)?;
+
+        if new_debt > 0 {
+            if new_debt < asset.risk_parameters.min_net_debt {
+                cancelled_partial = Some(trove.id);
+                break;
+            }
+
+            // Troves redeemed before a partial redemption were all closed
+            let others: Vec<SortedTrove> = asset
+                .troves
+                .iter()
+                .filter(|other| {
+                    other.id != trove.id
+                        && !troves.iter().any(|redeemed: &RedeemedTrove| {
+                            redeemed.asset_id == asset.asset_id && redeemed.borrower == other.id
+                        })
+                })
+                .map(ListedTrove::sorted)
+                .collect();
+            partial_redemption_hint = fm_compute_nominal_cr(new_coll, new_debt);
+            (upper_partial_hint, lower_partial_hint) =
+                find_insert_position(&others, partial_redemption_hint);
+        }
+
+        totals[index].0 += usdm_lot;
+        totals[index].1 += asset_lot;
+        remaining_usdm -= usdm_lot;
+        troves.push(RedeemedTrove {
+            asset_id: asset.asset_id,
+            borrower: trove.id,
+            usdm_lot,
+            asset_lot,
+            closed: new_debt == 0,
+        });
+
+        cursors[index] = position.checked_sub(1);
+    }
+
+    let usdm_redeemed = usdm_amount - remaining_usdm;
+    let base_rate = if usdm_redeemed > 0 {
+        checked_fm_compute_base_rate_from_redemption(decayed_base_rate, usdm_redeemed, usdm_supply)
+            .ok_or(RedemptionError::Overflow)?
+    } else {
+        0
+    };
+
+    let assets: Vec<AssetRedemption> = assets
+        .iter()
+        .zip(totals)
+        .filter(|(_, (usdm_redeemed, _))| *usdm_redeemed > 0)
+        .map(|(asset, (usdm_redeemed, asset_drawn))| {
+            let redemption_rate =
+                fm_compute_redemption_rate(base_rate, asset.risk_parameters.redemption_fee_floor);
+            let fee = fm_compute_fee(asset_drawn, redemption_rate);
+            AssetRedemption {
+                asset_id: asset.asset_id,
+                usdm_redeemed,
+                asset_drawn,
+                redemption_rate,
+                fee,
+                asset_to_redeemer: asset_drawn - fee,
+            }
+        })
+        .collect();
+    let redemption_rate = assets
+        .iter()
+        .map(|asset| asset.redemption_rate)
+        .max()
+        .unwrap_or(0);
+
+    Ok(RedemptionQuote {
+        troves,
+        assets,
+        usdm_redeemed,
+        redemption_rate,
+        remaining_usdm,
+        cancelled_partial,
+        partial_redemption_hint,
+        upper_partial_hint,
+        lower_partial_hint,
+    })
+}
+
+// `get_current_icr` of the trove at `position`, `u64::MAX` past the head as for the null identity
+fn current_icr(asset: &AssetTroves, position: Option<usize>) -> Result<u64, RedemptionError> {
+    match position {
+        Some(position) => {
+            let trove = asset.troves[position];
+            checked_fm_compute_cr(trove.coll, trove.debt, asset.price)
+                .ok_or(RedemptionError::Overflow)
+        }
+        None => Ok(u64::MAX),
+    }
+}
+
+fn find_min_borrower(
+    assets: &[AssetTroves],
+    cursors: &[Option<usize>],
+) -> Result<(usize, Option<usize>), RedemptionError> {
+    let mut min_index = 0;
+    let mut min_cr = u64::MAX;
+    let mut min_sunset = false;
+    for (index, (asset, cursor)) in assets.iter().zip(cursors).enumerate() {
+        let cr = current_icr(asset, *cursor)?;
+        let sunset = asset.is_sunset && cursor.is_some();
+        if index == 0 || (sunset && !min_sunset) || (sunset == min_sunset && cr < min_cr) {
+            min_index = index;
+            min_cr = cr;
+            min_sunset = sunset;
+        }
+    }
+
+    Ok((min_index, cursors.get(min_index).copied().flatten()))
+}
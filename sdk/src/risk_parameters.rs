@@ -0,0 +1,20 @@
+pub use crate::abi::protocol_manager::RiskParameters;
+
+use crate::fluid_math::{
+    BORROWING_FEE_FLOOR, CCR, MCR, MIN_NET_DEBT, POST_COLLATERAL_RATIO, REDEMPTION_FEE_FLOOR,
+    STABILITY_POOL_FEE,
+};
+
+// Mirror of `RiskParameters::default()`, kept by assets registered before the ProtocolManager stored
+// risk parameters per asset
+pub fn default_risk_parameters() -> RiskParameters {
+    RiskParameters {
+        mcr: MCR,
+        ccr: CCR,
+        min_net_debt: MIN_NET_DEBT,
+        borrowing_fee_floor: BORROWING_FEE_FLOOR,
+        redemption_fee_floor: REDEMPTION_FEE_FLOOR,
+        post_collateral_ratio: POST_COLLATERAL_RATIO,
+        stability_pool_fee: STABILITY_POOL_FEE,
+    }
+}
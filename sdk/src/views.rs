@@ -0,0 +1,74 @@
+use fuels::types::{AssetId, Identity};
+
+use crate::{abi::trove_manager::Status, fluid_math::checked_fm_compute_cr};
+
+// A trove with its pending redistribution rewards applied, as the next borrower operation would see it
+#[derive(Debug, Clone)]
+pub struct TroveView {
+    pub owner: Identity,
+    pub asset_id: AssetId,
+    pub status: Status,
+    pub coll: u64,
+    pub debt: u64,
+    pub pending_coll_rewards: u64,
+    pub pending_debt_rewards: u64,
+    pub stake: u64,
+    pub price: u64,
+    pub icr: u64,
+    pub nominal_icr: u64,
+}
+
+// Where the collateral and debt of one asset currently sit
+#[derive(Debug, Clone)]
+pub struct AssetSystemView {
+    pub asset_id: AssetId,
+    pub symbol: Option<String>,
+    pub price: u64,
+    // The asset's critical collateral ratio
+    pub ccr: u64,
+    pub trove_count: u64,
+    pub active_pool_coll: u64,
+    pub active_pool_debt: u64,
+    pub default_pool_coll: u64,
+    pub default_pool_debt: u64,
+    pub coll_surplus: u64,
+    pub stability_pool_coll: u64,
+}
+
+impl AssetSystemView {
+    pub fn total_coll(&self) -> u64 {
+        self.active_pool_coll + self.default_pool_coll
+    }
+
+    pub fn total_debt(&self) -> u64 {
+        self.active_pool_debt + self.default_pool_debt
+    }
+
+    // Total collateral ratio of the asset, `u64::MAX` without debt as in `fm_compute_cr`
+    pub fn tcr(&self) -> u64 {
+        checked_fm_compute_cr(self.total_coll(), self.total_debt(), self.price).unwrap_or(u64::MAX)
+    }
+
+    // Below the CCR borrowing has to raise the TCR and troves under it can be liquidated
+    pub fn is_recovery_mode(&self) -> bool {
+        self.tcr() < self.ccr
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StabilityDepositView {
+    pub depositor: Identity,
+    pub compounded_usdm_deposit: u64,
+    pub fpt_gain: u64,
+    pub asset_gains: Vec<(AssetId, u64)>,
+    pub total_usdm_deposits: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct StakeView {
+    pub staker: Identity,
+    pub staked_fpt: u64,
+    pub total_fpt_staked: u64,
+    pub pending_usdm_gain: u64,
+    pub pending_asset_gains: Vec<(AssetId, u64)>,
+}
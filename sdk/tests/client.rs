@@ -0,0 +1,187 @@
+use fuels::{prelude::*, types::Identity};
+use moor_sdk::{
+    abi::{protocol_manager::AssetStatus, trove_manager::Status},
+    fluid_math::BORROWING_FEE_FLOOR,
+    MoorClient,
+};
+use test_utils::{
+    data_structures::{MAX_FEE_PERCENTAGE, PRECISION},
+    interfaces::{
+        active_pool::active_pool_abi,
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        token::token_abi,
+        trove_manager::trove_manager_abi,
+    },
+    setup::common::{deployment_of, setup_protocol},
+};
+
+#[tokio::test]
+async fn views_match_contract_reads() {
+    let (contracts, admin, _) = setup_protocol(2, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+    let owner = Identity::Address(admin.address().into());
+
+    token_abi::mint_to_id(&asset.asset, 5000 * PRECISION, owner).await;
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+
+    let client = MoorClient::new(deployment_of(&contracts), admin.clone());
+    let deposit_amount = 1200 * PRECISION;
+    let borrow_amount = 600 * PRECISION;
+
+    client
+        .open_trove(
+            asset.asset_id,
+            MAX_FEE_PERCENTAGE,
+            deposit_amount,
+            borrow_amount,
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await
+        .unwrap();
+
+    let trove = client.trove(asset.asset_id, owner).await.unwrap();
+    assert_eq!(trove.status, Status::Active);
+    assert_eq!(trove.price, PRECISION);
+    assert_eq!(
+        trove.coll,
+        trove_manager_abi::get_trove_coll(&asset.trove_manager, owner)
+            .await
+            .value
+    );
+    assert_eq!(
+        trove.debt,
+        trove_manager_abi::get_trove_debt(&asset.trove_manager, owner)
+            .await
+            .value
+    );
+    assert_eq!(
+        trove.nominal_icr,
+        trove_manager_abi::get_nominal_icr(&asset.trove_manager, owner)
+            .await
+            .value
+    );
+    assert_eq!(
+        trove.icr as u128,
+        trove.coll as u128 * PRECISION as u128 / trove.debt as u128
+    );
+    assert_eq!(trove.pending_coll_rewards, 0);
+    assert_eq!(trove.pending_debt_rewards, 0);
+
+    let system = client.asset_system(asset.asset_id).await.unwrap();
+    assert_eq!(system.trove_count, 1);
+    assert_eq!(
+        system.active_pool_coll,
+        active_pool_abi::get_asset(&contracts.active_pool, asset.asset_id)
+            .await
+            .value
+    );
+    assert_eq!(system.total_coll(), deposit_amount);
+    assert_eq!(system.total_debt(), trove.debt);
+    assert_eq!(system.tcr(), trove.icr);
+
+    // Borrowing alone never raises the base rate
+    assert_eq!(client.decayed_base_rate().await.unwrap(), 0);
+    assert_eq!(
+        client.borrowing_rate(asset.asset_id).await.unwrap(),
+        BORROWING_FEE_FLOOR
+    );
+    assert_eq!(client.usdm_supply().await.unwrap(), trove.debt);
+
+    // No debt ceiling until the owner sets one
+    assert_eq!(
+        client.asset_status(asset.asset_id).await.unwrap(),
+        AssetStatus::Active
+    );
+    assert_eq!(client.debt_ceiling(asset.asset_id).await.unwrap(), u64::MAX);
+    assert_eq!(
+        client
+            .remaining_debt_capacity(asset.asset_id)
+            .await
+            .unwrap(),
+        u64::MAX - trove.debt
+    );
+
+    let sp_deposit = 200 * PRECISION;
+    client.provide_to_stability_pool(sp_deposit).await.unwrap();
+
+    let deposit = client.stability_deposit(owner).await.unwrap();
+    assert_eq!(deposit.compounded_usdm_deposit, sp_deposit);
+    assert_eq!(deposit.total_usdm_deposits, sp_deposit);
+    assert_eq!(deposit.asset_gains, vec![(asset.asset_id, 0)]);
+
+    let stake = client.stake(owner).await.unwrap();
+    assert_eq!(stake.staked_fpt, 0);
+    assert_eq!(stake.total_fpt_staked, 0);
+
+    // A client without a signer sees the same state
+    let reader = MoorClient::read_only(deployment_of(&contracts), admin.provider().clone());
+    let read_only_trove = reader.trove(asset.asset_id, owner).await.unwrap();
+    assert_eq!(read_only_trove.coll, trove.coll);
+    assert_eq!(read_only_trove.debt, trove.debt);
+}
+
+#[tokio::test]
+async fn write_methods_carry_their_dependencies() {
+    let (contracts, admin, _) = setup_protocol(2, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+    let owner = Identity::Address(admin.address().into());
+    let no_hint = Identity::Address(Address::zeroed());
+
+    token_abi::mint_to_id(&asset.asset, 5000 * PRECISION, owner).await;
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+
+    let client = MoorClient::new(deployment_of(&contracts), admin.clone());
+
+    client
+        .open_trove(
+            asset.asset_id,
+            MAX_FEE_PERCENTAGE,
+            1200 * PRECISION,
+            600 * PRECISION,
+            no_hint,
+            no_hint,
+        )
+        .await
+        .unwrap();
+    client
+        .add_coll(asset.asset_id, 100 * PRECISION, no_hint, no_hint)
+        .await
+        .unwrap();
+    client
+        .withdraw_coll(asset.asset_id, 50 * PRECISION, no_hint, no_hint)
+        .await
+        .unwrap();
+    client
+        .withdraw_usdm(
+            asset.asset_id,
+            MAX_FEE_PERCENTAGE,
+            100 * PRECISION,
+            no_hint,
+            no_hint,
+        )
+        .await
+        .unwrap();
+    client
+        .repay_usdm(asset.asset_id, 100 * PRECISION, no_hint, no_hint)
+        .await
+        .unwrap();
+
+    let trove = client.trove(asset.asset_id, owner).await.unwrap();
+    assert_eq!(trove.coll, 1250 * PRECISION);
+
+    client
+        .provide_to_stability_pool(100 * PRECISION)
+        .await
+        .unwrap();
+    client
+        .withdraw_from_stability_pool(100 * PRECISION)
+        .await
+        .unwrap();
+
+    let deposit = client.stability_deposit(owner).await.unwrap();
+    assert_eq!(deposit.compounded_usdm_deposit, 0);
+}
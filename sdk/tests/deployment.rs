@@ -0,0 +1,27 @@
+use moor_sdk::Deployment;
+use test_utils::setup::common::get_absolute_path_from_relative;
+
+#[test]
+fn parses_published_testnet_manifest() {
+    let deployment =
+        Deployment::from_file(get_absolute_path_from_relative("deployments/testnet.json")).unwrap();
+
+    assert!(!deployment.asset_contracts.is_empty());
+
+    let stfuel = deployment.asset_by_symbol("stfuel").unwrap();
+    assert!(stfuel.pyth_contract.is_none());
+    assert_eq!(stfuel.price_feeds(), vec![stfuel.stork_contract.unwrap()]);
+    assert_eq!(
+        deployment.asset(stfuel.asset_id).unwrap().trove_manager,
+        stfuel.trove_manager
+    );
+}
+
+#[test]
+fn rejects_unknown_assets() {
+    let deployment =
+        Deployment::from_file(get_absolute_path_from_relative("deployments/testnet.json")).unwrap();
+
+    assert!(deployment.asset_by_symbol("DOGE").is_err());
+    assert!(deployment.asset(Default::default()).is_err());
+}
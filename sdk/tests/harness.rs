@@ -0,0 +1,5 @@
+mod client;
+mod deployment;
+mod liquidation;
+mod preview;
+mod redemption;
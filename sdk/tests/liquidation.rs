@@ -0,0 +1,390 @@
+use fuels::{prelude::*, types::Identity};
+use moor_sdk::{
+    abi::trove_manager::Status,
+    fluid_math::{
+        fm_compute_cr, fm_multiply_ratio, ONE, POST_COLLATERAL_RATIO, STABILITY_POOL_FEE,
+    },
+    hints::{null_hint, ListedTrove},
+    liquidation::{
+        calculate_liquidated_trove_values, estimate_liquidations,
+        get_offset_and_redistribution_vals,
+    },
+    risk_parameters::default_risk_parameters,
+    MoorClient,
+};
+use test_utils::{
+    data_structures::{MAX_FEE_PERCENTAGE, PRECISION},
+    interfaces::{
+        oracle::oracle_abi,
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
+        token::token_abi,
+    },
+    invariants::check_invariants,
+    setup::common::{deploy_multi_trove_getter, deployment_of, setup_protocol},
+    utils::{assert_within_threshold, with_min_borrow_fee},
+};
+
+fn id(byte: u8) -> Identity {
+    Identity::Address(Address::new([byte; 32]))
+}
+
+#[test]
+fn liquidated_values_match_trove_manager_utils() {
+    let risk_parameters = default_risk_parameters();
+
+    // Full liquidation repays the debt plus the Stability Pool fee in collateral
+    let full = calculate_liquidated_trove_values(
+        550 * PRECISION,
+        500 * PRECISION,
+        PRECISION,
+        &risk_parameters,
+    );
+    assert!(!full.is_partial_liquidation);
+    assert_eq!(full.trove_debt_to_repay, 500 * PRECISION);
+    assert_eq!(
+        full.trove_coll_liquidated,
+        fm_multiply_ratio(500 * PRECISION, ONE + STABILITY_POOL_FEE, PRECISION)
+    );
+
+    let bad_debt = calculate_liquidated_trove_values(
+        900 * PRECISION,
+        1_000 * PRECISION,
+        PRECISION,
+        &risk_parameters,
+    );
+    assert!(!bad_debt.is_partial_liquidation);
+    assert_eq!(bad_debt.trove_coll_liquidated, 900 * PRECISION);
+
+    // Partial liquidation brings the trove back to POST_COLLATERAL_RATIO
+    let usdm_in_pool = 1_000 * PRECISION;
+    let partial = get_offset_and_redistribution_vals(
+        12_000 * PRECISION,
+        10_000 * PRECISION,
+        usdm_in_pool,
+        PRECISION,
+        &risk_parameters,
+    );
+    assert!(partial.is_partial_liquidation);
+    let icr = fm_compute_cr(
+        partial.remaining_trove_coll,
+        partial.remaining_trove_debt,
+        PRECISION,
+    );
+    assert!(icr.abs_diff(POST_COLLATERAL_RATIO) <= PRECISION / 100);
+    assert_eq!(partial.debt_to_offset, usdm_in_pool);
+    assert_eq!(
+        partial.debt_to_offset + partial.debt_to_redistribute + partial.remaining_trove_debt,
+        10_000 * PRECISION
+    );
+    assert_eq!(
+        partial.coll_to_send_to_sp
+            + partial.coll_to_redistribute
+            + partial.coll_gas_compensation
+            + partial.remaining_trove_coll,
+        12_000 * PRECISION
+    );
+    assert_eq!(
+        partial.coll_gas_compensation,
+        (12_000 * PRECISION - partial.remaining_trove_coll) / 200
+    );
+
+    // An asset with a higher post collateral ratio and Stability Pool fee is taken further back
+    let mut volatile = risk_parameters;
+    volatile.post_collateral_ratio = 2 * ONE;
+    volatile.stability_pool_fee = 15 * PRECISION / 100;
+    let partial = get_offset_and_redistribution_vals(
+        12_000 * PRECISION,
+        10_000 * PRECISION,
+        usdm_in_pool,
+        PRECISION,
+        &volatile,
+    );
+    assert!(partial.is_partial_liquidation);
+    let icr = fm_compute_cr(
+        partial.remaining_trove_coll,
+        partial.remaining_trove_debt,
+        PRECISION,
+    );
+    assert!(icr.abs_diff(2 * ONE) <= PRECISION / 100);
+    assert_eq!(
+        partial.coll_to_send_to_sp + partial.coll_to_redistribute + partial.coll_gas_compensation,
+        fm_multiply_ratio(
+            10_000 * PRECISION - partial.remaining_trove_debt,
+            ONE + volatile.stability_pool_fee,
+            PRECISION
+        )
+    );
+}
+
+#[test]
+fn sequence_stops_at_mcr_and_drains_the_pool() {
+    let troves = [
+        ListedTrove {
+            id: id(1),
+            coll: 3_000 * PRECISION,
+            debt: 1_000 * PRECISION,
+        },
+        ListedTrove {
+            id: id(2),
+            coll: 12_000 * PRECISION,
+            debt: 10_000 * PRECISION,
+        },
+        ListedTrove {
+            id: id(3),
+            coll: 1_100 * PRECISION,
+            debt: 1_000 * PRECISION,
+        },
+    ];
+    let usdm_in_pool = 2_000 * PRECISION;
+
+    let sequence = estimate_liquidations(
+        AssetId::zeroed(),
+        PRECISION,
+        &default_risk_parameters(),
+        usdm_in_pool,
+        16_100 * PRECISION,
+        12_000 * PRECISION,
+        &troves,
+    );
+    assert_eq!(sequence.borrowers(), vec![id(3), id(2)]);
+
+    // The full liquidation takes 1000 of the pool, the partial one the rest
+    let full = &sequence.troves[0];
+    assert!(!full.is_partial());
+    assert_eq!(full.values.debt_to_offset, 1_000 * PRECISION);
+    assert_eq!(full.upper_partial_hint, null_hint());
+    let partial = &sequence.troves[1];
+    assert!(partial.is_partial());
+    assert_eq!(partial.values.debt_to_offset, 1_000 * PRECISION);
+    assert_eq!(sequence.stability_pool_usdm_loss(), usdm_in_pool);
+    assert_eq!(
+        sequence.debt_to_redistribute(),
+        partial.values.debt_to_redistribute
+    );
+
+    // Back at POST_COLLATERAL_RATIO the trove stays below the 300% one
+    assert_eq!(
+        (partial.upper_partial_hint, partial.lower_partial_hint),
+        (id(1), null_hint())
+    );
+
+    assert_eq!(
+        sequence.liquidator_reward(),
+        full.values.coll_gas_compensation + partial.values.coll_gas_compensation
+    );
+    assert_eq!(
+        sequence.deposit_impact(usdm_in_pool / 2),
+        (usdm_in_pool / 2, sequence.stability_pool_coll_gain() / 2)
+    );
+}
+
+#[test]
+fn recovery_mode_liquidates_below_ccr_until_the_tcr_recovers() {
+    let troves = [
+        ListedTrove {
+            id: id(1),
+            coll: 3_100 * PRECISION,
+            debt: 2_000 * PRECISION,
+        },
+        ListedTrove {
+            id: id(2),
+            coll: 1_450 * PRECISION,
+            debt: 1_000 * PRECISION,
+        },
+        ListedTrove {
+            id: id(3),
+            coll: 1_040 * PRECISION,
+            debt: 800 * PRECISION,
+        },
+    ];
+    let usdm_in_pool = 2_000 * PRECISION;
+    let estimate = |system_coll: u64, system_debt: u64| {
+        estimate_liquidations(
+            AssetId::zeroed(),
+            PRECISION,
+            &default_risk_parameters(),
+            usdm_in_pool,
+            system_coll * PRECISION,
+            system_debt * PRECISION,
+            &troves,
+        )
+    };
+
+    // Outside recovery mode only the trove under MCR goes
+    assert_eq!(estimate(10_000, 3_800).borrowers(), vec![id(3)]);
+
+    // At a TCR of 147% the first liquidation takes the asset back above CCR
+    let sequence = estimate(5_590, 3_800);
+    assert_eq!(sequence.borrowers(), vec![id(3)]);
+    assert_eq!(sequence.troves[0].values.coll_surplus, 160 * PRECISION);
+
+    // With more debt in the system recovery mode holds, and the 145% trove is brought back to CCR
+    let sequence = estimate(5_590, 4_800);
+    assert_eq!(sequence.borrowers(), vec![id(3), id(2)]);
+    let partial = &sequence.troves[1];
+    assert!(partial.is_partial());
+    assert_eq!(
+        partial.values.entire_trove_debt - partial.values.remaining_trove_debt,
+        125 * PRECISION
+    );
+    assert_eq!(
+        fm_compute_cr(
+            partial.values.remaining_trove_coll,
+            partial.values.remaining_trove_debt,
+            PRECISION,
+        ),
+        POST_COLLATERAL_RATIO
+    );
+
+    // Unless the Stability Pool can't absorb all of its debt once the first trove is offset
+    let sequence = estimate_liquidations(
+        AssetId::zeroed(),
+        PRECISION,
+        &default_risk_parameters(),
+        1_500 * PRECISION,
+        5_590 * PRECISION,
+        4_800 * PRECISION,
+        &troves,
+    );
+    assert_eq!(sequence.borrowers(), vec![id(3)]);
+}
+
+#[tokio::test]
+async fn estimate_matches_batch_liquidation() {
+    let (contracts, admin, mut wallets) = setup_protocol(6, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    let multi_trove_getter = deploy_multi_trove_getter(
+        &admin,
+        &contracts.sorted_troves.contract.contract_id().into(),
+    )
+    .await;
+    let mut deployment = deployment_of(&contracts);
+    deployment.multi_trove_getter = Some(multi_trove_getter.contract_id().into());
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(10)).await;
+
+    let partial_wallet = wallets.pop().unwrap();
+    let depositor = wallets.pop().unwrap();
+    let full_wallet = wallets.pop().unwrap();
+    let liquidator = wallets.pop().unwrap();
+
+    let troves = [
+        (&partial_wallet, 12_000, 10_100),
+        (&depositor, 30_000, 15_000),
+        (&full_wallet, 700, 600),
+    ];
+    for (wallet, coll, debt) in troves {
+        let owner = Identity::Address(wallet.address().into());
+        token_abi::mint_to_id(&asset.asset, coll * PRECISION, owner).await;
+        MoorClient::new(deployment.clone(), wallet.clone())
+            .open_trove(
+                asset.asset_id,
+                MAX_FEE_PERCENTAGE,
+                coll * PRECISION,
+                debt * PRECISION,
+                null_hint(),
+                null_hint(),
+            )
+            .await
+            .unwrap();
+    }
+    MoorClient::new(deployment.clone(), depositor.clone())
+        .provide_to_stability_pool(15_000 * PRECISION)
+        .await
+        .unwrap();
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &asset.mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    // 700 / 603 is fully liquidated, 12000 / 10150.5 partially, the pool covers both
+    let client = MoorClient::new(deployment, liquidator.clone());
+    let sequence = client.estimate_liquidations(asset.asset_id).await.unwrap();
+    let full_id = Identity::Address(full_wallet.address().into());
+    let partial_id = Identity::Address(partial_wallet.address().into());
+    assert_eq!(sequence.borrowers(), vec![full_id, partial_id]);
+    assert!(!sequence.troves[0].is_partial());
+    assert!(sequence.troves[1].is_partial());
+    assert_eq!(
+        sequence.troves[0].values.entire_trove_debt,
+        with_min_borrow_fee(600 * PRECISION)
+    );
+    assert_eq!(sequence.debt_to_redistribute(), 0);
+
+    let provider = liquidator.provider();
+    let liquidator_balance = provider
+        .get_asset_balance(liquidator.address(), asset.asset_id)
+        .await
+        .unwrap();
+    let system = client.asset_system(asset.asset_id).await.unwrap();
+    let depositor_id = Identity::Address(depositor.address().into());
+    let deposit = client.stability_deposit(depositor_id).await.unwrap();
+
+    let partial = sequence.troves[1];
+    client
+        .batch_liquidate_troves(
+            asset.asset_id,
+            sequence.borrowers(),
+            partial.upper_partial_hint,
+            partial.lower_partial_hint,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        provider
+            .get_asset_balance(liquidator.address(), asset.asset_id)
+            .await
+            .unwrap(),
+        liquidator_balance + sequence.liquidator_reward()
+    );
+
+    let after = client.asset_system(asset.asset_id).await.unwrap();
+    assert_eq!(
+        after.stability_pool_coll,
+        system.stability_pool_coll + sequence.stability_pool_coll_gain()
+    );
+    assert_eq!(
+        after.default_pool_coll,
+        system.default_pool_coll + sequence.coll_to_redistribute()
+    );
+    assert_eq!(
+        after.coll_surplus,
+        system.coll_surplus + sequence.coll_surplus()
+    );
+
+    let (usdm_loss, coll_gain) = sequence.deposit_impact(deposit.compounded_usdm_deposit);
+    let deposit_after = client.stability_deposit(depositor_id).await.unwrap();
+    assert_eq!(
+        deposit_after.total_usdm_deposits,
+        deposit.total_usdm_deposits - sequence.stability_pool_usdm_loss()
+    );
+    assert_within_threshold(
+        deposit_after.compounded_usdm_deposit,
+        deposit.compounded_usdm_deposit - usdm_loss,
+        "Compounded deposit differs from the estimated loss",
+    );
+    assert_within_threshold(
+        deposit_after.asset_gains[0].1,
+        coll_gain,
+        "Depositor gain differs from the estimated gain",
+    );
+
+    let full_trove = client.trove(asset.asset_id, full_id).await.unwrap();
+    assert_eq!(full_trove.status, Status::ClosedByLiquidation);
+
+    let partial_trove = client.trove(asset.asset_id, partial_id).await.unwrap();
+    assert_eq!(partial_trove.status, Status::Active);
+    assert_eq!(partial_trove.coll, partial.values.remaining_trove_coll);
+    assert_eq!(partial_trove.debt, partial.values.remaining_trove_debt);
+
+    check_invariants(&contracts).await;
+}
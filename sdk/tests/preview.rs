@@ -0,0 +1,309 @@
+use fuels::{prelude::*, types::Identity};
+use moor_sdk::{
+    fluid_math::{
+        fm_compute_borrowing_rate, fm_compute_cr, fm_compute_fee, BORROWING_FEE_FLOOR, MCR,
+    },
+    hints::{find_insert_position, null_hint, SortedTrove},
+    preview::{
+        liquidation_price, preview_adjustment, AdjustmentPreview, PreviewError, TroveAdjustment,
+        TroveState,
+    },
+    risk_parameters::default_risk_parameters,
+    MoorClient,
+};
+use test_utils::{
+    data_structures::{ProtocolContracts, MAX_FEE_PERCENTAGE, PRECISION},
+    interfaces::{
+        borrow_operations::borrow_operations_utils,
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        sorted_troves::sorted_troves_abi,
+        token::token_abi,
+    },
+    setup::common::{deploy_multi_trove_getter, deployment_of, setup_protocol},
+};
+
+fn id(byte: u8) -> Identity {
+    Identity::Address(Address::new([byte; 32]))
+}
+
+#[test]
+fn insert_position_matches_descending_walk() {
+    let troves = [
+        SortedTrove {
+            id: id(1),
+            nicr: 300,
+        },
+        SortedTrove {
+            id: id(2),
+            nicr: 200,
+        },
+        SortedTrove {
+            id: id(3),
+            nicr: 200,
+        },
+        SortedTrove {
+            id: id(4),
+            nicr: 100,
+        },
+    ];
+
+    assert_eq!(find_insert_position(&[], 5), (null_hint(), null_hint()));
+    assert_eq!(find_insert_position(&troves, 300), (null_hint(), id(1)));
+    assert_eq!(find_insert_position(&troves, 250), (id(1), id(2)));
+    // Equal NICRs go in front of the existing nodes
+    assert_eq!(find_insert_position(&troves, 200), (id(1), id(2)));
+    assert_eq!(find_insert_position(&troves, 150), (id(3), id(4)));
+    assert_eq!(find_insert_position(&troves, 100), (id(3), id(4)));
+    assert_eq!(find_insert_position(&troves, 50), (id(4), null_hint()));
+}
+
+#[test]
+fn preview_rejects_what_borrow_operations_reverts_on() {
+    let trove = TroveState {
+        coll: 1_000 * PRECISION,
+        debt: 600 * PRECISION,
+    };
+    let risk_parameters = default_risk_parameters();
+
+    assert_eq!(
+        preview_adjustment(
+            id(1),
+            Some(trove),
+            TroveAdjustment::AddColl(0),
+            PRECISION,
+            BORROWING_FEE_FLOOR,
+            &risk_parameters,
+            &[]
+        ),
+        Err(PreviewError::ZeroAdjustment)
+    );
+    assert_eq!(
+        preview_adjustment(
+            id(1),
+            Some(trove),
+            TroveAdjustment::WithdrawColl(trove.coll + 1),
+            PRECISION,
+            BORROWING_FEE_FLOOR,
+            &risk_parameters,
+            &[]
+        ),
+        Err(PreviewError::WithdrawalExceedsCollateral)
+    );
+    assert_eq!(
+        preview_adjustment(
+            id(1),
+            None,
+            TroveAdjustment::RepayUsdm(1),
+            PRECISION,
+            BORROWING_FEE_FLOOR,
+            &risk_parameters,
+            &[]
+        ),
+        Err(PreviewError::TroveNotActive)
+    );
+
+    // A 1% base rate on top of the 0.5% floor
+    let borrowing_rate = fm_compute_borrowing_rate(PRECISION / 100, BORROWING_FEE_FLOOR);
+    let preview = preview_adjustment(
+        id(1),
+        Some(trove),
+        TroveAdjustment::WithdrawUsdm(100 * PRECISION),
+        PRECISION,
+        borrowing_rate,
+        &risk_parameters,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        preview.borrow_fee,
+        fm_compute_fee(100 * PRECISION, borrowing_rate)
+    );
+    assert_eq!(preview.borrow_fee, 3 * PRECISION / 2);
+    assert_eq!(preview.debt, 700 * PRECISION + preview.borrow_fee);
+
+    // At the liquidation price the trove is exactly at MCR, one unit lower it is below
+    let price = liquidation_price(preview.coll, preview.debt, MCR);
+    assert!(fm_compute_cr(preview.coll, preview.debt, price) >= MCR);
+    assert!(fm_compute_cr(preview.coll, preview.debt, price - 1) < MCR);
+}
+
+#[tokio::test]
+async fn preview_matches_borrow_operations() {
+    let (contracts, admin, mut wallets) = setup_protocol(6, false, false).await;
+    let asset = &contracts.asset_contracts[0];
+
+    let multi_trove_getter = deploy_multi_trove_getter(
+        &admin,
+        &contracts.sorted_troves.contract.contract_id().into(),
+    )
+    .await;
+    let mut deployment = deployment_of(&contracts);
+    deployment.multi_trove_getter = Some(multi_trove_getter.contract_id().into());
+
+    oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+
+    for i in 1..=4 {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallets.pop().unwrap(),
+            asset,
+            &contracts.borrow_operations,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            i * 1_000 * PRECISION,
+            600 * PRECISION,
+        )
+        .await;
+    }
+
+    // Opening lands exactly where SortedTroves would search from the head
+    let borrower = wallets.pop().unwrap();
+    let borrower_id = Identity::Address(borrower.address().into());
+    let client = MoorClient::new(deployment, borrower.clone());
+    token_abi::mint_to_id(&asset.asset, 10_000 * PRECISION, borrower_id).await;
+
+    let open = client
+        .preview_adjustment(
+            asset.asset_id,
+            borrower_id,
+            TroveAdjustment::Open {
+                coll: 2_500 * PRECISION,
+                usdm_amount: 600 * PRECISION,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(open.is_valid());
+    let searched = sorted_troves_abi::find_insert_position(
+        &contracts.sorted_troves,
+        &asset.trove_manager.contract,
+        open.nicr,
+        null_hint(),
+        null_hint(),
+        asset.asset_id,
+    )
+    .await
+    .value;
+    assert_eq!((open.upper_hint, open.lower_hint), searched);
+
+    client
+        .open_trove(
+            asset.asset_id,
+            MAX_FEE_PERCENTAGE,
+            2_500 * PRECISION,
+            600 * PRECISION,
+            open.upper_hint,
+            open.lower_hint,
+        )
+        .await
+        .unwrap();
+    assert_trove_matches(&client, &contracts, asset.asset_id, borrower_id, &open).await;
+
+    // Borrowing more moves the trove towards the tail, past the 2000 collateral trove
+    let withdraw = client
+        .preview_adjustment(
+            asset.asset_id,
+            borrower_id,
+            TroveAdjustment::WithdrawUsdm(400 * PRECISION),
+        )
+        .await
+        .unwrap();
+    assert!(withdraw.is_valid());
+    client
+        .withdraw_usdm(
+            asset.asset_id,
+            MAX_FEE_PERCENTAGE,
+            400 * PRECISION,
+            withdraw.upper_hint,
+            withdraw.lower_hint,
+        )
+        .await
+        .unwrap();
+    assert_trove_matches(&client, &contracts, asset.asset_id, borrower_id, &withdraw).await;
+
+    let add = client
+        .preview_adjustment(
+            asset.asset_id,
+            borrower_id,
+            TroveAdjustment::AddColl(5_000 * PRECISION),
+        )
+        .await
+        .unwrap();
+    client
+        .add_coll(
+            asset.asset_id,
+            5_000 * PRECISION,
+            add.upper_hint,
+            add.lower_hint,
+        )
+        .await
+        .unwrap();
+    assert_trove_matches(&client, &contracts, asset.asset_id, borrower_id, &add).await;
+
+    // Failed checks are reported and the contract reverts on them
+    let below_min_debt = client
+        .preview_adjustment(
+            asset.asset_id,
+            borrower_id,
+            TroveAdjustment::RepayUsdm(600 * PRECISION),
+        )
+        .await
+        .unwrap();
+    assert!(below_min_debt.meets_mcr);
+    assert!(!below_min_debt.meets_min_net_debt);
+    assert!(client
+        .repay_usdm(
+            asset.asset_id,
+            600 * PRECISION,
+            below_min_debt.upper_hint,
+            below_min_debt.lower_hint,
+        )
+        .await
+        .is_err());
+
+    let below_mcr = client
+        .preview_adjustment(
+            asset.asset_id,
+            borrower_id,
+            TroveAdjustment::WithdrawColl(7_000 * PRECISION),
+        )
+        .await
+        .unwrap();
+    assert!(!below_mcr.meets_mcr);
+    assert!(client
+        .withdraw_coll(
+            asset.asset_id,
+            7_000 * PRECISION,
+            below_mcr.upper_hint,
+            below_mcr.lower_hint,
+        )
+        .await
+        .is_err());
+}
+
+async fn assert_trove_matches(
+    client: &MoorClient<Wallet>,
+    contracts: &ProtocolContracts<Wallet>,
+    asset_id: AssetId,
+    borrower: Identity,
+    preview: &AdjustmentPreview,
+) {
+    let trove = client.trove(asset_id, borrower).await.unwrap();
+    assert_eq!(trove.coll, preview.coll);
+    assert_eq!(trove.debt, preview.debt);
+    assert_eq!(trove.icr, preview.icr);
+    assert_eq!(trove.nominal_icr, preview.nicr);
+
+    // The hints were the exact neighbours, so the trove sits between them
+    let prev = sorted_troves_abi::get_prev(&contracts.sorted_troves, borrower, asset_id)
+        .await
+        .value;
+    let next = sorted_troves_abi::get_next(&contracts.sorted_troves, borrower, asset_id)
+        .await
+        .value;
+    assert_eq!(prev, preview.upper_hint);
+    assert_eq!(next, preview.lower_hint);
+}
@@ -0,0 +1,403 @@
+use fuels::{prelude::*, types::Identity};
+use moor_sdk::{
+    abi::trove_manager::Status,
+    fluid_math::{
+        fm_compute_base_rate_from_redemption, fm_compute_fee, fm_compute_redemption_rate,
+        DECIMAL_PRECISION, REDEMPTION_FEE_FLOOR,
+    },
+    hints::{null_hint, ListedTrove},
+    redemption::{quote_redemption, AssetTroves, RedemptionError, RedemptionQuote},
+    risk_parameters::default_risk_parameters,
+    Deployment, MoorClient,
+};
+use test_utils::{
+    data_structures::{ProtocolContracts, PRECISION},
+    interfaces::{
+        borrow_operations::borrow_operations_utils,
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        sorted_troves::sorted_troves_abi,
+    },
+    invariants::check_invariants,
+    setup::common::{deploy_multi_trove_getter, deployment_of, setup_protocol},
+    utils::with_min_borrow_fee,
+};
+
+fn id(byte: u8) -> Identity {
+    Identity::Address(Address::new([byte; 32]))
+}
+
+// The debt of every trove in `assets`
+const USDM_SUPPLY: u64 = 30_650 * PRECISION;
+
+fn trove(byte: u8, coll: u64, debt: u64) -> ListedTrove {
+    ListedTrove {
+        id: id(byte),
+        coll: coll * PRECISION,
+        debt: debt * PRECISION,
+    }
+}
+
+// The troves of `proper_multi_collateral_redemption_from_partially_closed`, plus one below MCR at
+// the tail of the first asset
+fn assets() -> Vec<AssetTroves> {
+    vec![
+        AssetTroves {
+            asset_id: AssetId::new([1; 32]),
+            price: PRECISION,
+            risk_parameters: default_risk_parameters(),
+            is_sunset: false,
+            troves: vec![
+                trove(1, 20_000, 10_050),
+                trove(2, 9_000, 5_025),
+                trove(3, 8_000, 5_025),
+                trove(4, 600, 500),
+            ],
+        },
+        AssetTroves {
+            asset_id: AssetId::new([2; 32]),
+            price: PRECISION,
+            risk_parameters: default_risk_parameters(),
+            is_sunset: false,
+            troves: vec![trove(5, 15_000, 5_025), trove(6, 7_000, 5_025)],
+        },
+    ]
+}
+
+#[test]
+fn walk_follows_lowest_icr_across_assets() {
+    let assets = assets();
+
+    let quote = quote_redemption(8_000 * PRECISION, 20, 0, USDM_SUPPLY, &assets).unwrap();
+    assert_eq!(
+        quote
+            .troves
+            .iter()
+            .map(|redeemed| (redeemed.borrower, redeemed.usdm_lot, redeemed.closed))
+            .collect::<Vec<_>>(),
+        vec![
+            (id(6), 5_025 * PRECISION, true),
+            (id(3), 2_975 * PRECISION, false)
+        ]
+    );
+    assert_eq!(quote.remaining_usdm, 0);
+    assert_eq!(quote.cancelled_partial, None);
+
+    let first = quote.asset(assets[0].asset_id).unwrap();
+    assert_eq!(first.asset_drawn, 2_975 * PRECISION);
+    // The fee is charged at the base rate the whole redemption raises
+    assert_eq!(
+        quote.redemption_rate,
+        fm_compute_redemption_rate(
+            fm_compute_base_rate_from_redemption(0, 8_000 * PRECISION, USDM_SUPPLY),
+            REDEMPTION_FEE_FLOOR
+        )
+    );
+    assert_eq!(
+        first.fee,
+        fm_compute_fee(2_975 * PRECISION, quote.redemption_rate)
+    );
+    assert_eq!(first.asset_to_redeemer, first.asset_drawn - first.fee);
+
+    // 5025 / 2050 moves the partially redeemed trove to the head
+    assert_eq!(
+        (quote.upper_partial_hint, quote.lower_partial_hint),
+        (null_hint(), id(1))
+    );
+
+    let single = quote_redemption(8_000 * PRECISION, 1, 0, USDM_SUPPLY, &assets).unwrap();
+    assert_eq!(single.troves.len(), 1);
+    assert_eq!(single.remaining_usdm, 2_975 * PRECISION);
+    assert!(single.asset(assets[0].asset_id).is_none());
+}
+
+#[test]
+fn partial_below_min_net_debt_stops_the_walk() {
+    let assets = assets();
+
+    let quote = quote_redemption(9_725 * PRECISION, 20, 0, USDM_SUPPLY, &assets).unwrap();
+    assert_eq!(quote.troves.len(), 1);
+    assert_eq!(quote.usdm_redeemed, 5_025 * PRECISION);
+    assert_eq!(quote.remaining_usdm, 4_700 * PRECISION);
+    assert_eq!(quote.cancelled_partial, Some(id(3)));
+    assert_eq!(quote.upper_partial_hint, null_hint());
+
+    // A base rate left by earlier redemptions adds up, the rate is capped at 100%
+    let raised =
+        quote_redemption(9_725 * PRECISION, 20, PRECISION / 10, USDM_SUPPLY, &assets).unwrap();
+    assert!(raised.redemption_rate > quote.redemption_rate);
+    let capped = quote_redemption(
+        9_725 * PRECISION,
+        20,
+        DECIMAL_PRECISION,
+        USDM_SUPPLY,
+        &assets,
+    )
+    .unwrap();
+    assert_eq!(capped.redemption_rate, DECIMAL_PRECISION);
+    assert_eq!(capped.assets[0].asset_to_redeemer, 0);
+
+    assert_eq!(
+        quote_redemption(0, 20, 0, USDM_SUPPLY, &assets),
+        Err(RedemptionError::ZeroAmount)
+    );
+}
+
+#[test]
+fn each_asset_redeems_under_its_own_risk_parameters() {
+    let mut assets = assets();
+
+    // Trove 3 is at 159% and now skipped along with trove 4
+    assets[0].risk_parameters.mcr = 1_600_000_000;
+    let quote = quote_redemption(8_000 * PRECISION, 20, 0, USDM_SUPPLY, &assets).unwrap();
+    assert_eq!(quote.troves[1].borrower, id(2));
+
+    // A lower min net debt lets the partial redemption of trove 3 through
+    assets[0].risk_parameters.mcr = default_risk_parameters().mcr;
+    assets[0].risk_parameters.min_net_debt = 100 * PRECISION;
+    let quote = quote_redemption(9_725 * PRECISION, 20, 0, USDM_SUPPLY, &assets).unwrap();
+    assert_eq!(quote.cancelled_partial, None);
+    assert_eq!(quote.remaining_usdm, 0);
+
+    // Every asset charges its own fee floor, the highest is what `max_fee_percentage` must cover
+    assets[1].risk_parameters.redemption_fee_floor = 3 * PRECISION / 100;
+    let quote = quote_redemption(9_725 * PRECISION, 20, 0, USDM_SUPPLY, &assets).unwrap();
+    let base_rate = fm_compute_base_rate_from_redemption(0, 9_725 * PRECISION, USDM_SUPPLY);
+    let first = quote.asset(assets[0].asset_id).unwrap();
+    let second = quote.asset(assets[1].asset_id).unwrap();
+    assert_eq!(first.redemption_rate, base_rate + REDEMPTION_FEE_FLOOR);
+    assert_eq!(second.redemption_rate, base_rate + 3 * PRECISION / 100);
+    assert_eq!(quote.redemption_rate, second.redemption_rate);
+    assert_eq!(
+        second.fee,
+        fm_compute_fee(second.asset_drawn, second.redemption_rate)
+    );
+}
+
+#[test]
+fn sunset_assets_are_redeemed_first() {
+    let mut assets = assets();
+
+    // Troves 3 and 2 at 159% and 179% go before trove 6 at 139%
+    assets[0].is_sunset = true;
+    let quote = quote_redemption(8_000 * PRECISION, 20, 0, USDM_SUPPLY, &assets).unwrap();
+    assert_eq!(
+        quote
+            .troves
+            .iter()
+            .map(|redeemed| (redeemed.borrower, redeemed.usdm_lot, redeemed.closed))
+            .collect::<Vec<_>>(),
+        vec![
+            (id(3), 5_025 * PRECISION, true),
+            (id(2), 2_975 * PRECISION, false)
+        ]
+    );
+
+    // Trove 5 at 298% still goes before trove 3 at 159%
+    assets[0].is_sunset = false;
+    assets[1].is_sunset = true;
+    let quote = quote_redemption(8_000 * PRECISION, 20, 0, USDM_SUPPLY, &assets).unwrap();
+    assert_eq!(
+        quote
+            .troves
+            .iter()
+            .map(|redeemed| redeemed.borrower)
+            .collect::<Vec<_>>(),
+        vec![id(6), id(5)]
+    );
+
+    // Once the sunset asset has no troves left the walk goes on by ICR
+    let quote = quote_redemption(11_000 * PRECISION, 20, 0, USDM_SUPPLY, &assets).unwrap();
+    assert_eq!(
+        quote
+            .troves
+            .iter()
+            .map(|redeemed| redeemed.borrower)
+            .collect::<Vec<_>>(),
+        vec![id(6), id(5), id(3)]
+    );
+}
+
+#[tokio::test]
+async fn quote_matches_multi_collateral_redemption() {
+    let (contracts, admin, mut wallets) = setup_protocol(5, true, false).await;
+
+    let healthy_wallet1 = wallets.pop().unwrap();
+    let healthy_wallet2 = wallets.pop().unwrap();
+    let healthy_wallet3 = wallets.pop().unwrap();
+
+    let multi_trove_getter = deploy_multi_trove_getter(
+        &admin,
+        &contracts.sorted_troves.contract.contract_id().into(),
+    )
+    .await;
+    let mut deployment = deployment_of(&contracts);
+    deployment.multi_trove_getter = Some(multi_trove_getter.contract_id().into());
+
+    for asset in contracts.asset_contracts.iter() {
+        oracle_abi::set_debug_timestamp(&asset.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset.mock_pyth_oracle, pyth_price_feed(1)).await;
+    }
+
+    let troves = [
+        (&healthy_wallet1, 0, 20_000, 10_000),
+        (&healthy_wallet2, 0, 9_000, 5_000),
+        (&healthy_wallet3, 0, 8_000, 5_000),
+        (&healthy_wallet2, 1, 15_000, 5_000),
+        (&healthy_wallet3, 1, 7_000, 5_000),
+    ];
+    for (wallet, asset, coll, debt) in troves {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            &contracts.asset_contracts[asset],
+            &contracts.borrow_operations,
+            &contracts.usdm,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            coll * PRECISION,
+            debt * PRECISION,
+        )
+        .await;
+    }
+
+    // The 2nd asset's 7k trove closes, the 1st asset's 8k trove absorbs the rest
+    let quote =
+        redeem_as_quoted(&healthy_wallet1, &contracts, &deployment, 8_000 * PRECISION).await;
+    assert_eq!(quote.troves.len(), 2);
+    assert!(quote.troves[0].closed);
+    assert_eq!(
+        quote.troves[0].asset_id,
+        contracts.asset_contracts[1].asset_id
+    );
+    assert_eq!(
+        quote.troves[1].usdm_lot,
+        8_000 * PRECISION - with_min_borrow_fee(5_000 * PRECISION)
+    );
+
+    // The 9k trove is now the 1st asset's tail, the 20k trove takes the remainder
+    let quote =
+        redeem_as_quoted(&healthy_wallet2, &contracts, &deployment, 6_000 * PRECISION).await;
+    assert_eq!(quote.troves.len(), 2);
+    assert!(quote.troves[0].closed);
+    assert_eq!(
+        quote.troves[1].borrower,
+        Identity::Address(healthy_wallet1.address().into())
+    );
+    assert_eq!(quote.remaining_usdm, 0);
+}
+
+// Quotes, redeems with the quoted hints and checks the balances and troves against the quote
+async fn redeem_as_quoted(
+    redeemer: &Wallet,
+    contracts: &ProtocolContracts<Wallet>,
+    deployment: &Deployment,
+    usdm_amount: u64,
+) -> RedemptionQuote {
+    let client = MoorClient::new(deployment.clone(), redeemer.clone());
+    let provider = redeemer.provider();
+    let fpt_staking = contracts.fpt_staking.contract.contract_id();
+
+    let quote = client.quote_redemption(usdm_amount, 20).await.unwrap();
+
+    let mut balances = Vec::new();
+    for asset in contracts.asset_contracts.iter() {
+        balances.push((
+            provider
+                .get_asset_balance(redeemer.address(), asset.asset_id)
+                .await
+                .unwrap(),
+            provider
+                .get_contract_asset_balance(fpt_staking, asset.asset_id)
+                .await
+                .unwrap(),
+        ));
+    }
+    let usdm_balance = provider
+        .get_asset_balance(redeemer.address(), contracts.usdm_asset_id)
+        .await
+        .unwrap();
+    let mut troves = Vec::new();
+    for redeemed in quote.troves.iter() {
+        troves.push(
+            client
+                .trove(redeemed.asset_id, redeemed.borrower)
+                .await
+                .unwrap(),
+        );
+    }
+
+    client
+        .redeem_collateral(
+            usdm_amount,
+            20,
+            quote.partial_redemption_hint,
+            quote.upper_partial_hint,
+            quote.lower_partial_hint,
+            quote.redemption_rate,
+        )
+        .await
+        .unwrap();
+
+    for (asset, (balance, fees)) in contracts.asset_contracts.iter().zip(balances) {
+        let (to_redeemer, fee) = quote.asset(asset.asset_id).map_or((0, 0), |redeemed| {
+            (redeemed.asset_to_redeemer, redeemed.fee)
+        });
+        assert_eq!(
+            provider
+                .get_asset_balance(redeemer.address(), asset.asset_id)
+                .await
+                .unwrap(),
+            balance + to_redeemer
+        );
+        assert_eq!(
+            provider
+                .get_contract_asset_balance(fpt_staking, asset.asset_id)
+                .await
+                .unwrap(),
+            fees + fee
+        );
+    }
+    assert_eq!(
+        provider
+            .get_asset_balance(redeemer.address(), contracts.usdm_asset_id)
+            .await
+            .unwrap(),
+        usdm_balance - quote.usdm_redeemed
+    );
+
+    for (redeemed, before) in quote.troves.iter().zip(troves) {
+        let trove = client
+            .trove(redeemed.asset_id, redeemed.borrower)
+            .await
+            .unwrap();
+        if redeemed.closed {
+            assert_eq!(trove.status, Status::ClosedByRedemption);
+            continue;
+        }
+
+        assert_eq!(trove.debt, before.debt - redeemed.usdm_lot);
+        assert_eq!(trove.coll, before.coll - redeemed.asset_lot);
+        let prev = sorted_troves_abi::get_prev(
+            &contracts.sorted_troves,
+            redeemed.borrower,
+            redeemed.asset_id,
+        )
+        .await
+        .value;
+        let next = sorted_troves_abi::get_next(
+            &contracts.sorted_troves,
+            redeemed.borrower,
+            redeemed.asset_id,
+        )
+        .await
+        .value;
+        assert_eq!(
+            (prev, next),
+            (quote.upper_partial_hint, quote.lower_partial_hint)
+        );
+    }
+
+    check_invariants(contracts).await;
+    quote
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::SystemTime,
+};
+
+use crate::{
+    paths::{ARTIFACT_PROFILE, BUILD_COMMAND},
+    setup::common::get_absolute_path_from_relative,
+};
+
+// Set to load artifacts even when their sources changed after the last build
+pub const SKIP_ARTIFACT_CHECK_ENV: &str = "SKIP_ARTIFACT_STALENESS_CHECK";
+
+// Shared Sway library every contract depends on
+const LIBRARIES_DIR: &str = "libraries";
+
+// Absolute path of a `forc build` artifact, panics if the artifact or the ABI next to it is missing
+// or older than any Sway source of its contract or of `libraries`
+pub fn get_artifact_path(relative_path: &str) -> String {
+    if let Err(message) = check_artifact(relative_path) {
+        panic!("{}", message);
+    }
+
+    get_absolute_path_from_relative(relative_path)
+}
+
+pub fn check_artifact(relative_path: &str) -> Result<(), String> {
+    static CHECKED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    let checked = CHECKED.get_or_init(Default::default);
+    if checked.lock().unwrap().contains(relative_path) {
+        return Ok(());
+    }
+
+    let (project_dir, artifact_file) = relative_path
+        .split_once("/out/")
+        .ok_or_else(|| format!("{} is not a forc build artifact", relative_path))?;
+    let file_name = Path::new(artifact_file)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let project = file_name
+        .strip_suffix(".bin")
+        .or_else(|| file_name.strip_suffix("-storage_slots.json"))
+        .ok_or_else(|| format!("{} is not a binary or storage artifact", relative_path))?;
+    let abi_path = format!(
+        "{}/out/{}/{}-abi.json",
+        project_dir, ARTIFACT_PROFILE, project
+    );
+
+    // The oldest of the binary and its ABI, `abigen!` reads the latter at compile time
+    let built_at = [relative_path, abi_path.as_str()]
+        .into_iter()
+        .map(|path| {
+            modified_at(Path::new(&get_absolute_path_from_relative(path))).ok_or_else(|| {
+                format!(
+                    "Missing {} artifact {}, run `{}` first",
+                    ARTIFACT_PROFILE, path, BUILD_COMMAND
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .min()
+        .unwrap();
+
+    if std::env::var(SKIP_ARTIFACT_CHECK_ENV).is_err() {
+        let sources = [
+            PathBuf::from(get_absolute_path_from_relative(project_dir)),
+            PathBuf::from(get_absolute_path_from_relative(LIBRARIES_DIR)),
+        ];
+        for source_dir in sources {
+            if let Some((modified, source)) = newest_source(&source_dir) {
+                if modified > built_at {
+                    return Err(format!(
+                        "Stale {} artifact {}: {} changed since the last build, run `{}` (or set {} to skip this check)",
+                        ARTIFACT_PROFILE,
+                        relative_path,
+                        source.display(),
+                        BUILD_COMMAND,
+                        SKIP_ARTIFACT_CHECK_ENV
+                    ));
+                }
+            }
+        }
+    }
+
+    checked.lock().unwrap().insert(relative_path.to_string());
+    Ok(())
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).ok()?.modified().ok()
+}
+
+// Most recently modified `.sw` file or `Forc.toml` under `dir`, skipping build outputs
+fn newest_source(dir: &Path) -> Option<(SystemTime, PathBuf)> {
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        let candidate = if path.is_dir() {
+            if path.ends_with("out") {
+                continue;
+            }
+            newest_source(&path)
+        } else if path.extension().is_some_and(|ext| ext == "sw") || path.ends_with("Forc.toml") {
+            modified_at(&path).map(|modified| (modified, path))
+        } else {
+            None
+        };
+
+        if let Some(candidate) = candidate {
+            if newest.as_ref().is_none_or(|(time, _)| candidate.0 > *time) {
+                newest = Some(candidate);
+            }
+        }
+    }
+
+    newest
+}
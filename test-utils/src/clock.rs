@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use fuels::{prelude::Account, programs::calls::Execution, types::Bits256};
+
+use crate::{
+    data_structures::{AssetContracts, ProtocolContracts},
+    interfaces::{
+        community_issuance::community_issuance_abi,
+        oracle::oracle_abi,
+        protocol_manager::protocol_manager_abi,
+        pyth_oracle::{pyth_oracle_abi, Price, DEFAULT_PYTH_PRICE_ID},
+        redstone_oracle::redstone_oracle_abi,
+        stork_oracle::{
+            TemporalNumericValue, TemporalNumericValueInput, DEFAULT_STORK_FEED_ID, NS_TO_SECONDS,
+        },
+        vesting::vesting_abi,
+    },
+};
+
+// Simulated time shared by every contract that can fake its clock in debug mode:
+// the Oracle debug timestamp, CommunityIssuance, ProtocolManager and Vesting current time,
+// the mock Redstone timestamp and the publish time of the latest mock Pyth and Stork prices.
+//
+// The Vesting clock is only driven when enabled with `with_vesting`, since its debug mode
+// is chosen when the schedules are instantiated rather than during `setup_protocol`.
+pub struct SimClock<'a, T: Account + Clone> {
+    contracts: &'a ProtocolContracts<T>,
+    now: u64,
+    sync_vesting: bool,
+}
+
+impl<'a, T: Account + Clone> SimClock<'a, T> {
+    // Creates the clock and moves every debug clock to `start`
+    pub async fn start(contracts: &'a ProtocolContracts<T>, start: u64) -> Self {
+        let mut clock = Self {
+            contracts,
+            now: start,
+            sync_vesting: false,
+        };
+        clock.set(start).await;
+        clock
+    }
+
+    pub async fn with_vesting(mut self) -> Self {
+        self.sync_vesting = true;
+        self.set(self.now).await;
+        self
+    }
+
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    pub async fn advance(&mut self, duration: Duration) {
+        self.set(self.now + duration.as_secs()).await;
+    }
+
+    pub async fn advance_seconds(&mut self, seconds: u64) {
+        self.set(self.now + seconds).await;
+    }
+
+    // Moves every debug clock to `timestamp` and republishes the latest mock oracle prices with it
+    pub async fn set(&mut self, timestamp: u64) {
+        self.now = timestamp;
+
+        community_issuance_abi::set_current_time(&self.contracts.community_issuance, timestamp)
+            .await;
+        protocol_manager_abi::set_current_time(&self.contracts.protocol_manager, timestamp).await;
+
+        if self.sync_vesting {
+            vesting_abi::set_timestamp(&self.contracts.vesting_contract, timestamp)
+                .await
+                .unwrap();
+        }
+
+        for asset_contracts in &self.contracts.asset_contracts {
+            oracle_abi::set_debug_timestamp(&asset_contracts.oracle, timestamp).await;
+            redstone_oracle_abi::set_timestamp(&asset_contracts.mock_redstone_oracle, timestamp)
+                .await;
+
+            if let Some(price) = latest_pyth_price(asset_contracts).await {
+                pyth_oracle_abi::update_price_feeds(
+                    &asset_contracts.mock_pyth_oracle,
+                    vec![(
+                        DEFAULT_PYTH_PRICE_ID,
+                        Price {
+                            publish_time: timestamp,
+                            ..price
+                        },
+                    )],
+                )
+                .await;
+            }
+
+            if let Some(value) = latest_stork_value(asset_contracts).await {
+                republish_stork_value(asset_contracts, value, timestamp * NS_TO_SECONDS).await;
+            }
+        }
+    }
+
+    // Asserts every clock that exposes its time agrees with the simulated time.
+    // The Oracle debug timestamp has no getter, it is only ever written through `set`.
+    pub async fn assert_in_sync(&self) {
+        let community_issuance_time =
+            community_issuance_abi::get_current_time(&self.contracts.community_issuance)
+                .await
+                .value;
+        assert_eq!(
+            community_issuance_time, self.now,
+            "CommunityIssuance clock is out of sync"
+        );
+
+        let protocol_manager_time =
+            protocol_manager_abi::get_current_time(&self.contracts.protocol_manager)
+                .await
+                .value;
+        assert_eq!(
+            protocol_manager_time, self.now,
+            "ProtocolManager clock is out of sync"
+        );
+
+        if self.sync_vesting {
+            let vesting_time = vesting_abi::get_current_time(&self.contracts.vesting_contract)
+                .await
+                .unwrap()
+                .value;
+            assert_eq!(vesting_time, self.now, "Vesting clock is out of sync");
+        }
+
+        for asset_contracts in &self.contracts.asset_contracts {
+            let redstone_time =
+                redstone_oracle_abi::read_timestamp(&asset_contracts.mock_redstone_oracle)
+                    .await
+                    .value;
+            assert_eq!(
+                redstone_time, self.now,
+                "Redstone clock is out of sync for asset {:?}",
+                asset_contracts.asset_id
+            );
+
+            if let Some(price) = latest_pyth_price(asset_contracts).await {
+                assert_eq!(
+                    price.publish_time, self.now,
+                    "Pyth publish time is out of sync for asset {:?}",
+                    asset_contracts.asset_id
+                );
+            }
+
+            if let Some(value) = latest_stork_value(asset_contracts).await {
+                assert_eq!(
+                    value.timestamp_ns,
+                    self.now * NS_TO_SECONDS,
+                    "Stork publish time is out of sync for asset {:?}",
+                    asset_contracts.asset_id
+                );
+            }
+        }
+    }
+}
+
+// The mocks revert when no price was ever published, in which case there is nothing to refresh
+async fn latest_pyth_price<T: Account + Clone>(
+    asset_contracts: &AssetContracts<T>,
+) -> Option<Price> {
+    asset_contracts
+        .mock_pyth_oracle
+        .methods()
+        .price_unsafe(DEFAULT_PYTH_PRICE_ID)
+        .simulate(Execution::state_read_only())
+        .await
+        .ok()
+        .map(|response| response.value)
+}
+
+async fn latest_stork_value<T: Account + Clone>(
+    asset_contracts: &AssetContracts<T>,
+) -> Option<TemporalNumericValue> {
+    asset_contracts
+        .mock_stork_oracle
+        .methods()
+        .get_temporal_numeric_value_unchecked_v1(DEFAULT_STORK_FEED_ID)
+        .simulate(Execution::state_read_only())
+        .await
+        .ok()
+        .map(|response| response.value)
+}
+
+async fn republish_stork_value<T: Account + Clone>(
+    asset_contracts: &AssetContracts<T>,
+    value: TemporalNumericValue,
+    timestamp_ns: u64,
+) {
+    let input = TemporalNumericValueInput {
+        temporal_numeric_value: TemporalNumericValue {
+            timestamp_ns,
+            quantized_value: value.quantized_value,
+        },
+        id: DEFAULT_STORK_FEED_ID,
+        publisher_merkle_root: Bits256([0; 32]),
+        value_compute_alg_hash: Bits256([0; 32]),
+        r: Bits256([0; 32]),
+        s: Bits256([0; 32]),
+        v: 0,
+    };
+
+    asset_contracts
+        .mock_stork_oracle
+        .methods()
+        .update_temporal_numeric_values_v1(vec![input])
+        .call()
+        .await
+        .unwrap();
+}
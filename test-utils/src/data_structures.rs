@@ -1,3 +1,4 @@
+use super::interfaces::protocol_manager::RiskParameters;
 use super::interfaces::{
     active_pool::ActivePool, borrow_operations::BorrowOperations,
     coll_surplus_pool::CollSurplusPool, community_issuance::CommunityIssuance,
@@ -7,13 +8,32 @@ use super::interfaces::{
     token::Token, trove_manager::TroveManagerContract, usdm_token::USDMToken,
     vesting::VestingContract,
 };
+use crate::fluid_math::{
+    BORROWING_FEE_FLOOR, CCR, MCR, MIN_NET_DEBT, POST_COLLATERAL_RATIO, REDEMPTION_FEE_FLOOR,
+    STABILITY_POOL_FEE,
+};
 use fuels::{
     accounts::Account,
     types::{AssetId, Bits256, ContractId, U256},
 };
 pub const PRECISION: u64 = 1_000_000_000;
+// Accepts any borrowing or redemption fee, for the wrappers that don't take a max fee
+pub const MAX_FEE_PERCENTAGE: u64 = PRECISION;
 pub const POST_LIQUIDATION_COLLATERAL_RATIO: u64 = 1_500_000_000;
 
+// The risk parameters every asset had before they were set per asset
+pub fn default_risk_parameters() -> RiskParameters {
+    RiskParameters {
+        mcr: MCR,
+        ccr: CCR,
+        min_net_debt: MIN_NET_DEBT,
+        borrowing_fee_floor: BORROWING_FEE_FLOOR,
+        redemption_fee_floor: REDEMPTION_FEE_FLOOR,
+        post_collateral_ratio: POST_COLLATERAL_RATIO,
+        stability_pool_fee: STABILITY_POOL_FEE,
+    }
+}
+
 pub struct ContractInstance<C> {
     pub contract: C,
     pub implementation_id: ContractId,
@@ -82,6 +102,7 @@ pub struct AssetContractsOptionalOracles<T: Account> {
     pub stork_feed_id: Option<Bits256>,
     pub fuel_vm_decimals: u32,
     pub redstone_config: Option<RedstoneConfig>,
+    pub risk_parameters: RiskParameters,
 }
 
 pub struct ExistingAssetContracts {
@@ -90,6 +111,8 @@ pub struct ExistingAssetContracts {
     pub stork_oracle: Option<StorkConfig>,
     pub pyth_oracle: Option<PythConfig>,
     pub redstone_oracle: Option<RedstoneConfig>,
+    // Registered with the protocol defaults when not set
+    pub risk_parameters: Option<RiskParameters>,
 }
 
 pub struct AssetConfig {
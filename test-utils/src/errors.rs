@@ -0,0 +1,246 @@
+use fuels::types::errors::{transaction::Reason, Error};
+
+// Every revert reason the protocol contracts can produce: the `require` messages of each contract,
+// the error enums of the Oracle and Token contracts and the sway_libs ownership errors.
+// `ProtocolError::ALL` is checked against the contract sources in `tests/protocol_errors.rs`.
+macro_rules! protocol_errors {
+    ($($variant:ident => $message:literal),* $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ProtocolError {
+            $($variant),*
+        }
+
+        impl ProtocolError {
+            pub const ALL: &'static [ProtocolError] = &[$(ProtocolError::$variant),*];
+
+            pub fn message(&self) -> &'static str {
+                match self {
+                    $(ProtocolError::$variant => $message),*
+                }
+            }
+        }
+    };
+}
+
+protocol_errors! {
+    ActivePoolAlreadyInitialized => "Active Pool: Already initialized",
+    ActivePoolAssetIdIsNotCorrect => "Active Pool: Asset ID is not correct",
+    ActivePoolCallerIsNotBorrowOperationsOrDefaultPool => "Active Pool: Caller is not BorrowOperations or DefaultPool",
+    ActivePoolCallerIsNotBorrowOperationsOrTroveManager => "Active Pool: Caller is not BorrowOperations or TroveManager",
+    ActivePoolCallerIsNotBorrowOperationsTroveManagerProtocolManagerOrDefaultPool => "Active Pool: Caller is not BorrowOperations, TroveManager, ProtocolManager, or DefaultPool",
+    ActivePoolCallerIsNotProtocolManager => "Active Pool: Caller is not ProtocolManager",
+    ActivePoolCallerIsNotInitializer => "Active Pool: Caller is not initializer",
+    BorrowOperationsAssetIsNotActive => "Borrow Operations: Asset is not active",
+    BorrowOperationsAssetIsPaused => "Borrow Operations: Asset is paused",
+    BorrowOperationsBorrowingMustImproveTCRInRecoveryMode => "Borrow Operations: Borrowing must improve TCR in Recovery Mode",
+    BorrowOperationsCallerIsNotInitializer => "Borrow Operations: Caller is not initializer",
+    BorrowOperationsCallerIsNotThePauser => "Borrow Operations: Caller is not the pauser",
+    BorrowOperationsCallerIsNotTheProtocolManager => "Borrow Operations: Caller is not the protocol manager",
+    BorrowOperationsCollateralWithdrawalCannotLowerTCRInRecoveryMode => "Borrow Operations: Collateral withdrawal cannot lower TCR in Recovery Mode",
+    BorrowOperationsContractIsPaused => "Borrow Operations: Contract is paused",
+    BorrowOperationsDebtCeilingReached => "Borrow Operations: Debt ceiling reached",
+    BorrowOperationsFeeExceededProvidedMaximum => "Borrow Operations: Fee exceeded provided maximum",
+    BorrowOperationsFlashMintAmountMustBeGreaterThan0 => "Borrow Operations: Flash mint amount must be greater than 0",
+    BorrowOperationsFlashMintNotRepaid => "Borrow Operations: Flash mint not repaid",
+    BorrowOperationsICRMustBeAtLeastCCRInRecoveryMode => "Borrow Operations: ICR must be at least CCR in Recovery Mode",
+    BorrowOperationsInvalidUSDMAssetBeingTransfered => "Borrow Operations: Invalid USDM asset being transfered",
+    BorrowOperationsInvalidAsset => "Borrow Operations: Invalid asset",
+    BorrowOperationsInvalidCollateralAssetBeingTransfered => "Borrow Operations: Invalid collateral asset being transfered",
+    BorrowOperationsMaxFeePercentageMustBeBetweenTheBorrowingFeeFloorAnd100 => "Borrow Operations: Max fee percentage must be between the borrowing fee floor and 100%",
+    BorrowOperationsMinimumCollateralRatioNotMet => "Borrow Operations: Minimum collateral ratio not met",
+    BorrowOperationsNoPendingTroveTransfer => "Borrow Operations: No pending trove transfer",
+    BorrowOperationsOperatorIsNotApproved => "Borrow Operations: Operator is not approved",
+    BorrowOperationsUSDMSentMustEqualTheDebtRepaid => "Borrow Operations: USDM sent must equal the debt repaid",
+    BorrowOperationsUserAlreadyHasAnActiveTrove => "Borrow Operations: User already has an active Trove",
+    BorrowOperationsUserDoesNotHaveAnActiveTrove => "Borrow Operations: User does not have an active Trove",
+    BorrowOperationsAlreadyInitialized => "Borrow Operations: already initialized",
+    BorrowOperationsCannotCloseTroveWithInsufficientUsdmBalance => "Borrow Operations: cannot close trove with insufficient usdm balance",
+    BorrowOperationsCollWithdrawalAndDebtChangeMustBeGreaterThan0 => "Borrow Operations: coll withdrawal and debt change must be greater than 0",
+    BorrowOperationsCollateralChangeMustBe0OrEqualToTheAmountSent => "Borrow Operations: collateral change must be 0 or equal to the amount sent",
+    BorrowOperationsDebtChangeMustBeGreaterThan0 => "Borrow Operations: debt change must be greater than 0",
+    BorrowOperationsNetDebtMustBeGreaterThan0 => "Borrow Operations: net debt must be greater than 0",
+    BorrowOperationsCloseTroveIsLocked => "BorrowOperations: Close trove is locked",
+    BorrowOperationsFlashMintIsLocked => "BorrowOperations: Flash mint is locked",
+    BorrowOperationsInternalAdjustTroveIsLocked => "BorrowOperations: Internal adjust trove is locked",
+    BorrowOperationsCannotWithdrawMoreThanTheTroveCollateral => "Cannot withdraw more than the Trove's collateral",
+    CollSurplusPoolCallerIsNotBO => "CollSurplusPool: Caller is not BO",
+    CollSurplusPoolCallerIsNotPM => "CollSurplusPool: Caller is not PM",
+    CollSurplusPoolCallerIsNotTM => "CollSurplusPool: Caller is not TM",
+    CollSurplusPoolCallerIsNotInitializer => "CollSurplusPool: Caller is not initializer",
+    CollSurplusPoolContractIsAlreadyInitialized => "CollSurplusPool: Contract is already initialized",
+    CollSurplusPoolInvalidAsset => "CollSurplusPool: Invalid asset",
+    CommunityIssuanceCallerIsNotInitializer => "CommunityIssuance: Caller is not initializer",
+    CommunityIssuanceCallerMustBeStabilityPool => "CommunityIssuance: Caller must be stability pool",
+    CommunityIssuanceContractIsAlreadyInitialized => "CommunityIssuance: Contract is already initialized",
+    CommunityIssuanceCumulativeIssuanceFractionIsGreaterThanDecimalPrecision => "CommunityIssuance: Cumulative issuance fraction is greater than DECIMAL_PRECISION",
+    CommunityIssuanceDebuggingMustBeEnabledToSetCurrentTime => "CommunityIssuance: Debugging must be enabled to set current time",
+    CommunityIssuanceRewardsCanOnlyBePubliclyIncreasedAfter1YearOfInactivity => "CommunityIssuance: Rewards can only be publicly increased after 1 year of inactivity",
+    CommunityIssuanceRewardsHaveAlreadyTransitioned => "CommunityIssuance: Rewards have already transitioned",
+    CommunityIssuanceTotalTransitionTimeMustBeGreaterThan1Week => "CommunityIssuance: Total transition time must be greater than 1 week",
+    DefaultPoolAssetIsNotCorrect => "DefaultPool: Asset is not correct",
+    DefaultPoolCallerIsNotAP => "DefaultPool: Caller is not AP",
+    DefaultPoolCallerIsNotPM => "DefaultPool: Caller is not PM",
+    DefaultPoolCallerIsNotTM => "DefaultPool: Caller is not TM",
+    DefaultPoolCallerIsNotInitializer => "DefaultPool: Caller is not initializer",
+    DefaultPoolContractIsAlreadyInitialized => "DefaultPool: Contract is already initialized",
+    FPTStakingCallerIsNotInitializer => "FPTStaking: Caller is not initializer",
+    FPTStakingCallerIsNotTheBorrowerOperations => "FPTStaking: Caller is not the Borrower Operations",
+    FPTStakingCallerIsNotTheProtocolManager => "FPTStaking: Caller is not the protocol manager",
+    FPTStakingCannotUnstakeMoreThanCurrentStakedAmount => "FPTStaking: Cannot unstake more than current staked amount",
+    FPTStakingContractIsAlreadyInitialized => "FPTStaking: Contract is already initialized",
+    FPTStakingFPTAmountMustBeGreaterThan0 => "FPTStaking: FPT amount must be greater than 0",
+    FPTStakingFPTContractNotInitializedOrWrongToken => "FPTStaking: FPT contract not initialized, or wrong token",
+    FPTStakingStakeIsLocked => "FPTStaking: Stake is locked",
+    FPTStakingUnstakeIsLocked => "FPTStaking: Unstake is locked",
+    FPTStakingUserMustHaveStakeGreaterThan0 => "FPTStaking: User must have stake greater than 0",
+    FPTTokenCallerIsNotInitializer => "FPTToken: Caller is not initializer",
+    FPTTokenContractIsAlreadyInitialized => "FPTToken: Contract is already initialized",
+    HintHelperAlreadyInitialized => "HintHelper: Already initialized",
+    MockPythPriceFeedNotFound => "Price feed not found",
+    ProtocolManagerAlreadyInitialized => "ProtocolManager: Already initialized",
+    ProtocolManagerAssetIsNotActive => "ProtocolManager: Asset is not active",
+    ProtocolManagerAssetIsNotRedeemable => "ProtocolManager: Asset is not redeemable",
+    ProtocolManagerAssetIsNotSunset => "ProtocolManager: Asset is not sunset",
+    ProtocolManagerAssetNotRegistered => "ProtocolManager: Asset not registered",
+    ProtocolManagerBorrowingFeeFloorMustBeAtMost5 => "ProtocolManager: Borrowing fee floor must be at most 5%",
+    ProtocolManagerCallerIsNotBorrowOperations => "ProtocolManager: Caller is not Borrow Operations",
+    ProtocolManagerCallerIsNotInitializer => "ProtocolManager: Caller is not initializer",
+    ProtocolManagerDebuggingMustBeEnabledToSetCurrentTime => "ProtocolManager: Debugging must be enabled to set current time",
+    ProtocolManagerFeeExceededProvidedMaximum => "ProtocolManager: Fee exceeded provided maximum",
+    ProtocolManagerInvalidAssetBeingTransfered => "ProtocolManager: Invalid asset being transfered",
+    ProtocolManagerMCRMustBeBetween110AndCCR => "ProtocolManager: MCR must be between 110% and CCR",
+    ProtocolManagerMaxFeePercentageMustBeBetweenTheRedemptionFeeFloorAnd100 => "ProtocolManager: Max fee percentage must be between the redemption fee floor and 100%",
+    ProtocolManagerMinNetDebtMustBeBetween100And100000USDM => "ProtocolManager: Min net debt must be between 100 and 100000 USDM",
+    ProtocolManagerNoAssetsToRedeemFrom => "ProtocolManager: No assets to redeem from",
+    ProtocolManagerPostCollateralRatioMustBeBetweenCCRAnd300 => "ProtocolManager: Post collateral ratio must be between CCR and 300%",
+    ProtocolManagerRedeemCollateralIsLocked => "ProtocolManager: Redeem collateral is locked",
+    ProtocolManagerRedemptionAmountMustBeGreaterThan0 => "ProtocolManager: Redemption amount must be greater than 0",
+    ProtocolManagerRedemptionFeeFloorMustBeAtMost5 => "ProtocolManager: Redemption fee floor must be at most 5%",
+    ProtocolManagerStabilityPoolFeeMustBeAtMost20 => "ProtocolManager: Stability pool fee must be at most 20%",
+    SortedTrovesAssetStillHasTroves => "SortedTroves: Asset still has troves",
+    SortedTrovesCallerIsNotInitializer => "SortedTroves: Caller is not initializer",
+    SortedTrovesContractIsAlreadyInitialized => "SortedTroves: Contract is already initialized",
+    SortedTrovesIdDoesNotExist => "SortedTroves: Id does not exist",
+    SortedTrovesIdMustExist => "SortedTroves: Id must exist",
+    SortedTrovesNotBOOrTM => "SortedTroves: Not BO or TM",
+    SortedTrovesNotPM => "SortedTroves: Not PM",
+    SortedTrovesNotTM => "SortedTroves: Not TM",
+    SortedTrovesIcrMustBeGreaterThan0 => "SortedTroves: icr must be greater than 0",
+    SortedTrovesIdAlreadyExists => "SortedTroves: id already exists",
+    SortedTrovesIdMustNotBeZero => "SortedTroves: id must not be zero",
+    SortedTrovesListIsFull => "SortedTroves: list is full",
+    SortedTrovesNicrMustBeGreaterThan0 => "SortedTroves: nicr must be greater than 0",
+    SortedTrovesSizeMustBeGreaterThan0 => "SortedTroves: size must be greater than 0",
+    StabilityPoolCallerIsNotATroveManager => "StabilityPool: Caller is not a trove manager",
+    StabilityPoolCallerIsNotInitializer => "StabilityPool: Caller is not initializer",
+    StabilityPoolCallerIsNotTheProtocolManager => "StabilityPool: Caller is not the protocol manager",
+    StabilityPoolContractIsAlreadyInitialized => "StabilityPool: Contract is already initialized",
+    StabilityPoolContractIsLocked => "StabilityPool: Contract is locked",
+    StabilityPoolDebtOffsetExceedsTotalUSDMDeposits => "StabilityPool: Debt offset exceeds total USDM deposits",
+    StabilityPoolNewPIs0 => "StabilityPool: New p is 0",
+    StabilityPoolOffsetIsLocked => "StabilityPool: Offset is locked",
+    StabilityPoolThereAreUndercollateralizedTroves => "StabilityPool: There are undercollateralized troves",
+    StabilityPoolUSDMAddressIsInvalid => "StabilityPool: USDM address is invalid",
+    StabilityPoolUSDMAmountMustBeGreaterThan0 => "StabilityPool: USDM amount must be greater than 0",
+    StabilityPoolUserDoesNotHaveAnActiveTrove => "StabilityPool: User does not have an active trove",
+    StabilityPoolUserHasNoInitialDeposit => "StabilityPool: User has no initial deposit",
+    StabilityPoolWithdrawIsLocked => "StabilityPool: Withdraw is locked",
+    MockStorkTemporalNumericValueNotFound => "Temporal numeric value not found",
+    TestsArtifactsFlashMintReceiverContractCallerIsNotTheBorrowOperationsContract => "TestsArtifactsFlashMintReceiverContract: Caller is not the Borrow Operations contract",
+    TestsArtifactsSortedTrovesContractCallerIsNotTheBorrowOperationsContract => "TestsArtifactsSortedTrovesContract: Caller is not the Borrow Operations contract",
+    TestsArtifactsStabilityPoolContractCallerIsNotTheBorrowOperationsContract => "TestsArtifactsStabilityPoolContract: Caller is not the Borrow Operations contract",
+    TroveManagerBorrowersNotSortedByNominalICR => "TroveManager: Borrowers not sorted by nominal ICR",
+    TroveManagerCallerIsNotInitializer => "TroveManager: Caller is not initializer",
+    TroveManagerCallerIsNotTheBorrowOperationsContract => "TroveManager: Caller is not the Borrow Operations contract",
+    TroveManagerCallerIsNotTheBorrowOperationsOrProtocolManagerContract => "TroveManager: Caller is not the Borrow Operations or Protocol Manager contract",
+    TroveManagerCallerIsNotTheProtocolManagerContract => "TroveManager: Caller is not the Protocol Manager contract",
+    TroveManagerContractIsAlreadyInitialized => "TroveManager: Contract is already initialized",
+    TroveManagerDuplicateBorrowerFound => "TroveManager: Duplicate borrower found",
+    TroveManagerInternalBatchLiquidateTrovesIsLocked => "TroveManager: Internal batch liquidate troves is locked",
+    TroveManagerInternalCloseTroveIsLocked => "TroveManager: Internal close trove is locked",
+    TroveManagerInternalRedeemCollateralFromTroveIsLocked => "TroveManager: Internal redeem collateral from trove is locked",
+    TroveManagerInvalidStatus => "TroveManager: Invalid status",
+    TroveManagerNoBorrowersToLiquidate => "TroveManager: No borrowers to liquidate",
+    TroveManagerNoDebtToLiquidate => "TroveManager: No debt to liquidate",
+    TroveManagerNoStakesToRedistributeTo => "TroveManager: No stakes to redistribute to",
+    TroveManagerRecipientAlreadyHasAnActiveTrove => "TroveManager: Recipient already has an active trove",
+    TroveManagerThereIsOnlyOneTroveInTheSystem => "TroveManager: There is only one trove in the system",
+    TroveManagerTotalStakesSnapshotIsZero => "TroveManager: Total stakes snapshot is zero",
+    TroveManagerTroveDoesNotExist => "TroveManager: Trove does not exist",
+    TroveManagerTroveIsNotActive => "TroveManager: Trove is not active",
+    USDMTokenCallerIsNotInitializer => "USDMToken: Caller is not initializer",
+    USDMTokenContractIsAlreadyInitialized => "USDMToken: Contract is already initialized",
+    USDMTokenNotAuthorized => "USDMToken: NotAuthorized",
+    VestingContractCallerIsNotInitializer => "VestingContract: Caller is not initializer",
+    VestingContractContractIsAlreadyInitialized => "VestingContract: Contract is already initialized",
+    VestingContractDebuggingMustBeEnabledToSetCurrentTime => "VestingContract: Debugging must be enabled to set current time",
+    VestingContractInvalidVestingSchedule => "VestingContract: Invalid vesting schedule",
+    VestingContractNothingToRedeem => "VestingContract: Nothing to redeem",
+    VestingContractScheduleAlreadyExists => "VestingContract: Schedule already exists",
+    VestingContractTotalAmountDoesNotMatch => "VestingContract: Total amount does not match",
+    // sway_libs ownership
+    NotOwner => "NotOwner",
+    CannotReinitialized => "CannotReinitialized",
+    // OracleError
+    OracleDebugNotEnabled => "DebugNotEnabled",
+    OracleNegativeValue => "NegativeValue",
+    OracleMultiplicationWouldExceedU64Maximum => "MultiplicationWouldExceedU64Maximum",
+    OraclePriceValueExceedsU64Maximum => "PriceValueExceedsU64Maximum",
+    // Token Error
+    TokenAddressAlreadyMint => "AddressAlreadyMint",
+    TokenCannotReinitialize => "CannotReinitialize",
+    TokenMintIsClosed => "MintIsClosed",
+}
+
+impl ProtocolError {
+    pub fn from_message(message: &str) -> Option<Self> {
+        let message = message.trim().trim_matches('"');
+        Self::ALL
+            .iter()
+            .find(|error| error.message() == message)
+            .copied()
+    }
+
+    // Decodes the revert reason of a failed call. Falls back to searching the error text for the
+    // longest known message, for errors that do not carry the decoded reason.
+    pub fn from_error(error: &Error) -> Option<Self> {
+        if let Error::Transaction(Reason::Failure { reason, .. }) = error {
+            if let Some(decoded) = Self::from_message(reason) {
+                return Some(decoded);
+            }
+        }
+
+        let text = error.to_string();
+        Self::ALL
+            .iter()
+            .filter(|error| text.contains(error.message()))
+            .max_by_key(|error| error.message().len())
+            .copied()
+    }
+}
+
+impl std::fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+// Asserts that a call result is a revert with the given `ProtocolError`
+#[macro_export]
+macro_rules! assert_reverts_with {
+    ($result:expr, $expected:expr) => {{
+        let expected: $crate::errors::ProtocolError = $expected;
+        match $result {
+            Ok(_) => panic!("Expected revert with {:?}, but the call succeeded", expected),
+            Err(error) => {
+                let decoded = $crate::errors::ProtocolError::from_error(&error);
+                assert_eq!(
+                    decoded,
+                    Some(expected),
+                    "Unexpected revert reason: {}",
+                    error
+                );
+            }
+        }
+    }};
+}
@@ -0,0 +1,144 @@
+use std::fmt::Debug;
+
+use fuels::{
+    core::traits::{Parameterize, Tokenizable},
+    programs::responses::CallResponse,
+};
+
+pub use crate::interfaces::{
+    borrow_operations::{
+        AdjustTroveEvent, CloseTroveEvent, FlashMintEvent, OpenTroveEvent, SetOperatorEvent,
+        TransferTroveEvent,
+    },
+    fpt_staking::{StakeEvent, UnstakeEvent},
+    stability_pool::{
+        ProvideToStabilityPoolEvent, StabilityPoolLiquidationEvent, WithdrawFromStabilityPoolEvent,
+    },
+    trove_manager::{RedemptionEvent, TroveFullLiquidationEvent, TrovePartialLiquidationEvent},
+};
+
+// Event structs logged by the protocol contracts, decodable from the response of any call
+// that registered the emitting contract through `with_contracts`
+pub trait ProtocolEvent: Tokenizable + Parameterize + Debug + PartialEq + Clone + 'static {
+    const NAME: &'static str;
+}
+
+macro_rules! impl_protocol_event {
+    ($($event:ident),* $(,)?) => {
+        $(
+            impl ProtocolEvent for $event {
+                const NAME: &'static str = stringify!($event);
+            }
+        )*
+
+        pub const PROTOCOL_EVENT_NAMES: &[&str] = &[$(stringify!($event)),*];
+    };
+}
+
+impl_protocol_event!(
+    OpenTroveEvent,
+    AdjustTroveEvent,
+    CloseTroveEvent,
+    SetOperatorEvent,
+    TransferTroveEvent,
+    FlashMintEvent,
+    TroveFullLiquidationEvent,
+    TrovePartialLiquidationEvent,
+    RedemptionEvent,
+    StakeEvent,
+    UnstakeEvent,
+    ProvideToStabilityPoolEvent,
+    WithdrawFromStabilityPoolEvent,
+    StabilityPoolLiquidationEvent,
+);
+
+// All events of type `E` emitted during the call, in emission order
+pub fn decode_events<E: ProtocolEvent, R>(response: &CallResponse<R>) -> Vec<E> {
+    response
+        .decode_logs_with_type::<E>()
+        .unwrap_or_else(|e| panic!("Failed to decode {} logs: {:?}", E::NAME, e))
+}
+
+// The single event of type `E` emitted during the call, panics if there is none or more than one
+pub fn decode_event<E: ProtocolEvent, R>(response: &CallResponse<R>) -> E {
+    let events = decode_events::<E, R>(response);
+    assert_eq!(
+        events.len(),
+        1,
+        "Expected exactly one {}, found {}: {:?}",
+        E::NAME,
+        events.len(),
+        events
+    );
+    events[0].clone()
+}
+
+// Names of all protocol events emitted during the call, in emission order.
+// Logs that are not protocol events (e.g. plain values) are skipped.
+pub fn event_names<R>(response: &CallResponse<R>) -> Vec<String> {
+    response
+        .decode_logs()
+        .results
+        .into_iter()
+        .filter_map(|log| log.ok())
+        .filter_map(|log| {
+            let name = log
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()?;
+            PROTOCOL_EVENT_NAMES
+                .contains(&name)
+                .then(|| name.to_string())
+        })
+        .collect()
+}
+
+// Asserts the call emitted exactly these protocol events in this order
+pub fn assert_events_in_order<R>(response: &CallResponse<R>, expected: &[&str]) {
+    let emitted = event_names(response);
+    assert_eq!(
+        emitted, expected,
+        "Emitted events do not match the expected sequence"
+    );
+}
+
+// Asserts that the call emitted an event matching the given fields and returns the first match.
+//
+// Without `..` the event must be equal to the one built from the fields, with `..` only the listed
+// fields are compared. Fields support the shorthand form, so `RedemptionEvent { borrower, .. }`
+// compares against the local `borrower`.
+#[macro_export]
+macro_rules! assert_emitted {
+    (@value $field:ident) => {
+        $field
+    };
+    (@value $field:ident : $value:expr) => {
+        $value
+    };
+    ($response:expr, $event:ident { $($field:ident $(: $value:expr)?),+ , .. }) => {{
+        let events = $crate::events::decode_events::<$event, _>(&$response);
+        events
+            .iter()
+            .find(|event| {
+                true $(&& event.$field == $crate::assert_emitted!(@value $field $(: $value)?))+
+            })
+            .cloned()
+            .unwrap_or_else(|| {
+                panic!(
+                    "No {} matching the expected fields was emitted, found: {:?}",
+                    stringify!($event),
+                    events
+                )
+            })
+    }};
+    ($response:expr, $event:ident { $($field:ident $(: $value:expr)?),* $(,)? }) => {{
+        let expected = $event { $($field: $crate::assert_emitted!(@value $field $(: $value)?)),* };
+        let events = $crate::events::decode_events::<$event, _>(&$response);
+        assert!(
+            events.contains(&expected),
+            "{:?} was not emitted, found: {:?}",
+            expected,
+            events
+        );
+        expected
+    }};
+}
@@ -1,10 +1,16 @@
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "ActivePool",
     abi = "contracts/active-pool-contract/out/debug/active-pool-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "ActivePool",
+    abi = "contracts/active-pool-contract/out/release/active-pool-contract-abi.json"
+));
 
 pub mod active_pool_abi {
     use super::*;
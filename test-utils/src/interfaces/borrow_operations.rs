@@ -1,17 +1,24 @@
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "BorrowOperations",
     abi = "contracts/borrow-operations-contract/out/debug/borrow-operations-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "BorrowOperations",
+    abi = "contracts/borrow-operations-contract/out/release/borrow-operations-contract-abi.json"
+));
 
 pub mod borrow_operations_abi {
     use super::*;
-    use crate::data_structures::ContractInstance;
+    use crate::data_structures::{ContractInstance, MAX_FEE_PERCENTAGE};
     use crate::interfaces::active_pool::ActivePool;
     use crate::interfaces::coll_surplus_pool::CollSurplusPool;
     use crate::interfaces::default_pool::DefaultPool;
+    use crate::interfaces::flash_mint_receiver::MockFlashMintReceiver;
     use crate::interfaces::fpt_staking::FPTStaking;
     use crate::interfaces::oracle::Oracle;
     use crate::interfaces::pyth_oracle::PythCore;
@@ -57,6 +64,42 @@ pub mod borrow_operations_abi {
     }
 
     pub async fn open_trove<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        oracle: &ContractInstance<Oracle<T>>,
+        mock_pyth: &PythCore<T>,
+        mock_redstone: &RedstoneCore<T>,
+        asset_token: &Token<T>,
+        usdm_token: &ContractInstance<USDMToken<T>>,
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        collateral_amount_deposit: u64,
+        usdm_amount_withdrawn: u64,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        open_trove_with_max_fee(
+            borrow_operations,
+            oracle,
+            mock_pyth,
+            mock_redstone,
+            asset_token,
+            usdm_token,
+            fpt_staking,
+            sorted_troves,
+            trove_manager,
+            active_pool,
+            MAX_FEE_PERCENTAGE,
+            collateral_amount_deposit,
+            usdm_amount_withdrawn,
+            upper_hint,
+            lower_hint,
+        )
+        .await
+    }
+
+    pub async fn open_trove_with_max_fee<T: Account + Clone>(
         borrow_operations: &ContractInstance<BorrowOperations<T>>,
         oracle: &ContractInstance<Oracle<T>>,
         mock_pyth: &PythCore<T>,
@@ -67,6 +110,7 @@ pub mod borrow_operations_abi {
         sorted_troves: &ContractInstance<SortedTroves<T>>,
         trove_manager: &ContractInstance<TroveManagerContract<T>>,
         active_pool: &ContractInstance<ActivePool<T>>,
+        max_fee_percentage: u64,
         collateral_amount_deposit: u64,
         usdm_amount_withdrawn: u64,
         upper_hint: Identity,
@@ -86,7 +130,12 @@ pub mod borrow_operations_abi {
         return borrow_operations
             .contract
             .methods()
-            .open_trove(usdm_amount_withdrawn, upper_hint, lower_hint)
+            .open_trove(
+                max_fee_percentage,
+                usdm_amount_withdrawn,
+                upper_hint,
+                lower_hint,
+            )
             .call_params(call_params)
             .unwrap()
             .with_contracts(&[
@@ -203,6 +252,40 @@ pub mod borrow_operations_abi {
     }
 
     pub async fn withdraw_usdm<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        oracle: &ContractInstance<Oracle<T>>,
+        pyth: &PythCore<T>,
+        redstone: &RedstoneCore<T>,
+        mock_token: &Token<T>,
+        usdm_token: &ContractInstance<USDMToken<T>>,
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        amount: u64,
+        lower_hint: Identity,
+        upper_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        withdraw_usdm_with_max_fee(
+            borrow_operations,
+            oracle,
+            pyth,
+            redstone,
+            mock_token,
+            usdm_token,
+            fpt_staking,
+            sorted_troves,
+            trove_manager,
+            active_pool,
+            MAX_FEE_PERCENTAGE,
+            amount,
+            lower_hint,
+            upper_hint,
+        )
+        .await
+    }
+
+    pub async fn withdraw_usdm_with_max_fee<T: Account + Clone>(
         borrow_operations: &ContractInstance<BorrowOperations<T>>,
         _oracle: &ContractInstance<Oracle<T>>,
         _pyth: &PythCore<T>,
@@ -213,6 +296,7 @@ pub mod borrow_operations_abi {
         _sorted_troves: &ContractInstance<SortedTroves<T>>,
         _trove_manager: &ContractInstance<TroveManagerContract<T>>,
         _active_pool: &ContractInstance<ActivePool<T>>,
+        max_fee_percentage: u64,
         amount: u64,
         lower_hint: Identity,
         upper_hint: Identity,
@@ -229,7 +313,13 @@ pub mod borrow_operations_abi {
         borrow_operations
             .contract
             .methods()
-            .withdraw_usdm(amount, lower_hint, upper_hint, mock_asset_id.into())
+            .withdraw_usdm(
+                max_fee_percentage,
+                amount,
+                lower_hint,
+                upper_hint,
+                mock_asset_id.into(),
+            )
             .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
             .with_tx_policies(tx_params)
             .determine_missing_contracts()
@@ -287,6 +377,56 @@ pub mod borrow_operations_abi {
             .await
     }
 
+    // `payment` is the collateral added or the USDM repaid, as `(asset_id, amount)`
+    pub async fn adjust_trove<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        mock_token: &Token<T>,
+        payment: Option<(AssetId, u64)>,
+        max_fee_percentage: u64,
+        coll_withdrawal: u64,
+        usdm_change: u64,
+        is_debt_increase: bool,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        let mock_asset_id: AssetId = mock_token
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+
+        let mut call_params = CallParameters::default();
+        if let Some((asset_id, amount)) = payment {
+            call_params = call_params.with_amount(amount).with_asset_id(asset_id);
+        }
+
+        borrow_operations
+            .contract
+            .methods()
+            .adjust_trove(
+                max_fee_percentage,
+                coll_withdrawal,
+                usdm_change,
+                is_debt_increase,
+                upper_hint,
+                lower_hint,
+                mock_asset_id.into(),
+            )
+            .call_params(call_params)
+            .unwrap()
+            .determine_missing_contracts()
+            .await
+            .unwrap()
+            // Withdrawn collateral and borrowed USDM
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
     pub async fn close_trove<T: Account + Clone>(
         borrow_operations: &ContractInstance<BorrowOperations<T>>,
         oracle: &ContractInstance<Oracle<T>>,
@@ -358,6 +498,9 @@ pub mod borrow_operations_abi {
             .with_tx_policies(tx_params)
             .call_params(call_params)
             .unwrap()
+            .determine_missing_contracts()
+            .await
+            .unwrap()
             .call()
             .await
     }
@@ -433,6 +576,299 @@ pub mod borrow_operations_abi {
             .await
     }
 
+    pub async fn set_asset_pause_status<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        asset: AssetId,
+        is_paused: bool,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .set_asset_pause_status(asset.into(), is_paused)
+            .with_contract_ids(&[borrow_operations.implementation_id.into()])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_asset_is_paused<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        asset: AssetId,
+    ) -> Result<CallResponse<bool>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .get_asset_is_paused(asset.into())
+            .with_contract_ids(&[borrow_operations.implementation_id.into()])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_operator<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        operator: Identity,
+        asset: AssetId,
+        permissions: OperatorPermissions,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .set_operator(operator, asset.into(), permissions)
+            .with_contract_ids(&[borrow_operations.implementation_id.into()])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_operator_permissions<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        owner: Identity,
+        operator: Identity,
+        asset: AssetId,
+    ) -> Result<CallResponse<OperatorPermissions>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .get_operator_permissions(owner, operator, asset.into())
+            .with_contract_ids(&[borrow_operations.implementation_id.into()])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    // Same as `adjust_trove` with the caller acting as an operator of `owner`
+    pub async fn adjust_trove_for<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        mock_token: &Token<T>,
+        owner: Identity,
+        payment: Option<(AssetId, u64)>,
+        max_fee_percentage: u64,
+        coll_withdrawal: u64,
+        usdm_change: u64,
+        is_debt_increase: bool,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        let mock_asset_id: AssetId = mock_token
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+
+        let mut call_params = CallParameters::default();
+        if let Some((asset_id, amount)) = payment {
+            call_params = call_params.with_amount(amount).with_asset_id(asset_id);
+        }
+
+        borrow_operations
+            .contract
+            .methods()
+            .adjust_trove_for(
+                owner,
+                max_fee_percentage,
+                coll_withdrawal,
+                usdm_change,
+                is_debt_increase,
+                upper_hint,
+                lower_hint,
+                mock_asset_id.into(),
+            )
+            .call_params(call_params)
+            .unwrap()
+            .determine_missing_contracts()
+            .await
+            .unwrap()
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn close_trove_for<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        mock_token: &Token<T>,
+        usdm_token: &ContractInstance<USDMToken<T>>,
+        owner: Identity,
+        amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+        let usdm_asset_id: AssetId = usdm_token
+            .contract
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+        let mock_asset_id: AssetId = mock_token
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+
+        let call_params: CallParameters = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(usdm_asset_id);
+
+        borrow_operations
+            .contract
+            .methods()
+            .close_trove_for(owner, mock_asset_id.into())
+            .call_params(call_params)
+            .unwrap()
+            .determine_missing_contracts()
+            .await
+            .unwrap()
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn propose_trove_transfer<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        new_owner: Identity,
+        asset: AssetId,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .propose_trove_transfer(new_owner, asset.into())
+            .with_contracts(&[&trove_manager.contract])
+            .with_contract_ids(&[
+                borrow_operations.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn cancel_trove_transfer<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        asset: AssetId,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .cancel_trove_transfer(asset.into())
+            .with_contract_ids(&[borrow_operations.implementation_id.into()])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn accept_trove_transfer<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        previous_owner: Identity,
+        asset: AssetId,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .accept_trove_transfer(previous_owner, asset.into())
+            .determine_missing_contracts()
+            .await
+            .unwrap()
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_pending_trove_transfer<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        owner: Identity,
+        asset: AssetId,
+    ) -> Result<CallResponse<Option<Identity>>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .get_pending_trove_transfer(owner, asset.into())
+            .with_contract_ids(&[borrow_operations.implementation_id.into()])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn flash_mint<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        receiver: &MockFlashMintReceiver<T>,
+        usdm_token: &ContractInstance<USDMToken<T>>,
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        borrow_operations
+            .contract
+            .methods()
+            .flash_mint(amount, receiver.contract_id().into())
+            .with_contracts(&[receiver, &usdm_token.contract, &fpt_staking.contract])
+            .with_contract_ids(&[
+                borrow_operations.implementation_id.into(),
+                receiver.contract_id().into(),
+                usdm_token.contract.contract_id().into(),
+                usdm_token.implementation_id.into(),
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_flash_mint_fee<T: Account + Clone>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        amount: u64,
+    ) -> CallResponse<u64> {
+        borrow_operations
+            .contract
+            .methods()
+            .get_flash_mint_fee(amount)
+            .with_contract_ids(&[borrow_operations.implementation_id.into()])
+            .call()
+            .await
+            .unwrap()
+    }
+
     pub async fn claim_coll<T: Account + Clone>(
         borrow_operations: &ContractInstance<BorrowOperations<T>>,
         active_pool: &ContractInstance<ActivePool<T>>,
@@ -1,10 +1,16 @@
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "CollSurplusPool",
     abi = "contracts/coll-surplus-pool-contract/out/debug/coll-surplus-pool-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "CollSurplusPool",
+    abi = "contracts/coll-surplus-pool-contract/out/release/coll-surplus-pool-contract-abi.json"
+));
 
 pub mod coll_surplus_pool_abi {
     use super::*;
@@ -14,7 +20,7 @@ pub mod coll_surplus_pool_abi {
     use fuels::types::transaction_builders::VariableOutputPolicy;
     use fuels::types::AssetId;
     use fuels::{
-        prelude::{Account, ContractId, TxPolicies, Wallet},
+        prelude::{Account, ContractId, TxPolicies},
         types::Identity,
     };
 
@@ -78,8 +84,8 @@ pub mod coll_surplus_pool_abi {
             .await
     }
 
-    pub async fn get_collateral(
-        coll_surplus_pool: &ContractInstance<CollSurplusPool<Wallet>>,
+    pub async fn get_collateral<T: Account + Clone>(
+        coll_surplus_pool: &ContractInstance<CollSurplusPool<T>>,
         acount: Identity,
         asset: AssetId,
     ) -> Result<CallResponse<u64>, Error> {
@@ -1,10 +1,16 @@
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "CommunityIssuance",
     abi = "contracts/community-issuance-contract/out/debug/community-issuance-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "CommunityIssuance",
+    abi = "contracts/community-issuance-contract/out/release/community-issuance-contract-abi.json"
+));
 
 pub mod community_issuance_abi {
     use fuels::prelude::{Account, TxPolicies};
@@ -43,6 +49,25 @@ pub mod community_issuance_abi {
         return res;
     }
 
+    pub async fn get_current_time<T: Account + Clone>(
+        instance: &ContractInstance<CommunityIssuance<T>>,
+    ) -> CallResponse<u64> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        instance
+            .contract
+            .methods()
+            .get_current_time()
+            .with_contract_ids(&[
+                instance.contract.contract_id().into(),
+                instance.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+            .unwrap()
+    }
+
     pub async fn set_current_time<T: Account + Clone>(
         instance: &ContractInstance<CommunityIssuance<T>>,
         time: u64,
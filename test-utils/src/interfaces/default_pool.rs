@@ -1,10 +1,16 @@
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "DefaultPool",
     abi = "contracts/default-pool-contract/out/debug/default-pool-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "DefaultPool",
+    abi = "contracts/default-pool-contract/out/release/default-pool-contract-abi.json"
+));
 
 pub mod default_pool_abi {
     use super::*;
@@ -0,0 +1,79 @@
+use fuels::prelude::abigen;
+use fuels::programs::responses::CallResponse;
+
+#[cfg(not(feature = "release-artifacts"))]
+abigen!(Contract(
+    name = "MockFlashMintReceiver",
+    abi = "contracts/tests-artifacts-flash-mint-receiver-contract/out/debug/tests-artifacts-flash-mint-receiver-contract-abi.json"
+));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "MockFlashMintReceiver",
+    abi = "contracts/tests-artifacts-flash-mint-receiver-contract/out/release/tests-artifacts-flash-mint-receiver-contract-abi.json"
+));
+
+pub mod flash_mint_receiver_abi {
+    use super::*;
+    use fuels::prelude::Account;
+    use fuels::{
+        prelude::{ContractId, Error, TxPolicies},
+        types::{AssetId, Identity},
+    };
+
+    pub async fn initialize<T: Account + Clone>(
+        receiver: &MockFlashMintReceiver<T>,
+        borrow_operations: ContractId,
+        usdm_asset_id: AssetId,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        receiver
+            .methods()
+            .initialize(borrow_operations, usdm_asset_id)
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    // The receiver sends back the amount and fee minus `shortfall`
+    pub async fn set_shortfall<T: Account + Clone>(
+        receiver: &MockFlashMintReceiver<T>,
+        shortfall: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        receiver
+            .methods()
+            .set_shortfall(shortfall)
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    // The receiver flash mints the same amount again from its callback
+    pub async fn set_reenter<T: Account + Clone>(
+        receiver: &MockFlashMintReceiver<T>,
+        reenter: bool,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        receiver
+            .methods()
+            .set_reenter(reenter)
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    // (initiator, amount, fee) of the last callback
+    pub async fn get_last_flash_mint<T: Account + Clone>(
+        receiver: &MockFlashMintReceiver<T>,
+    ) -> CallResponse<(Identity, u64, u64)> {
+        receiver
+            .methods()
+            .get_last_flash_mint()
+            .call()
+            .await
+            .unwrap()
+    }
+}
@@ -1,10 +1,16 @@
 use fuels::prelude::{abigen, TxPolicies};
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "FPTStaking",
     abi = "contracts/fpt-staking-contract/out/debug/fpt-staking-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "FPTStaking",
+    abi = "contracts/fpt-staking-contract/out/release/fpt-staking-contract-abi.json"
+));
 
 pub mod fpt_staking_abi {
 
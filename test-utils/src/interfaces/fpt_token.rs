@@ -1,9 +1,15 @@
 use fuels::{prelude::abigen, programs::responses::CallResponse};
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "FPTToken",
     abi = "contracts/fpt-token-contract/out/debug/fpt-token-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "FPTToken",
+    abi = "contracts/fpt-token-contract/out/release/fpt-token-contract-abi.json"
+));
 
 pub mod fpt_token_abi {
     use crate::interfaces::vesting::VestingContract;
@@ -1,10 +1,16 @@
 use fuels::prelude::abigen;
 
 use fuels::programs::responses::CallResponse;
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "HintHelper",
     abi = "contracts/hint-helper-contract/out/debug/hint-helper-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "HintHelper",
+    abi = "contracts/hint-helper-contract/out/release/hint-helper-contract-abi.json"
+));
 
 pub mod hint_helper_abi {
     use super::*;
@@ -3,6 +3,7 @@ pub mod borrow_operations;
 pub mod coll_surplus_pool;
 pub mod community_issuance;
 pub mod default_pool;
+pub mod flash_mint_receiver;
 pub mod fpt_staking;
 pub mod fpt_token;
 pub mod hint_helper;
@@ -1,10 +1,16 @@
 use fuels::prelude::abigen;
 
 use fuels::programs::responses::CallResponse;
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "MultiTroveGetter",
     abi = "contracts/multi-trove-getter-contract/out/debug/multi-trove-getter-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "MultiTroveGetter",
+    abi = "contracts/multi-trove-getter-contract/out/release/multi-trove-getter-contract-abi.json"
+));
 
 pub mod multi_trove_getter_abi {
     use super::*;
@@ -4,10 +4,16 @@ use crate::interfaces::{
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "Oracle",
     abi = "contracts/oracle-contract/out/debug/oracle-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "Oracle",
+    abi = "contracts/oracle-contract/out/release/oracle-contract-abi.json"
+));
 
 // 10 minutes
 pub const ORACLE_TIMEOUT: u64 = 600;
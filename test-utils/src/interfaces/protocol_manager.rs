@@ -1,14 +1,20 @@
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "ProtocolManager",
     abi = "contracts/protocol-manager-contract/out/debug/protocol-manager-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "ProtocolManager",
+    abi = "contracts/protocol-manager-contract/out/release/protocol-manager-contract-abi.json"
+));
 
 pub mod protocol_manager_abi {
     use super::*;
-    use crate::data_structures::{self, ContractInstance};
+    use crate::data_structures::{self, ContractInstance, MAX_FEE_PERCENTAGE};
     use crate::interfaces::active_pool::ActivePool;
     use crate::interfaces::borrow_operations::BorrowOperations;
     use crate::interfaces::coll_surplus_pool::CollSurplusPool;
@@ -39,6 +45,7 @@ pub mod protocol_manager_abi {
         active_pool: ContractId,
         sorted_troves: ContractId,
         admin: Identity,
+        debugging: bool,
     ) -> CallResponse<()> {
         let tx_params = TxPolicies::default().with_tip(1);
 
@@ -55,6 +62,7 @@ pub mod protocol_manager_abi {
                 active_pool,
                 sorted_troves,
                 admin,
+                debugging,
             )
             .with_tx_policies(tx_params)
             .with_contract_ids(&[
@@ -73,6 +81,7 @@ pub mod protocol_manager_abi {
         asset: AssetId,
         trove_manager: ContractId,
         oracle: ContractId,
+        risk_parameters: RiskParameters,
         borrow_operations: &ContractInstance<BorrowOperations<T>>,
         stability_pool: &ContractInstance<StabilityPool<T>>,
         usdm: &ContractInstance<USDMToken<T>>,
@@ -86,7 +95,7 @@ pub mod protocol_manager_abi {
         protocol_manager
             .contract
             .methods()
-            .register_asset(asset.into(), trove_manager, oracle)
+            .register_asset(asset.into(), trove_manager, oracle, risk_parameters)
             .with_tx_policies(tx_params)
             .with_contracts(&[
                 &borrow_operations.contract,
@@ -122,6 +131,154 @@ pub mod protocol_manager_abi {
             .await
     }
 
+    pub async fn set_risk_parameters<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+        risk_parameters: RiskParameters,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_risk_parameters(asset.into(), risk_parameters)
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_risk_parameters<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+    ) -> Result<CallResponse<RiskParameters>, Error> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_risk_parameters(asset.into())
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn set_debt_ceiling<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+        debt_ceiling: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_debt_ceiling(asset.into(), debt_ceiling)
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_debt_ceiling<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+    ) -> Result<CallResponse<u64>, Error> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_debt_ceiling(asset.into())
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    // Reads the asset's debt through its trove manager and the active and default pools
+    pub async fn get_remaining_debt_capacity<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+    ) -> Result<CallResponse<u64>, Error> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_remaining_debt_capacity(asset.into())
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .determine_missing_contracts()
+            .await?
+            .call()
+            .await
+    }
+
+    pub async fn sunset_asset<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .sunset_asset(asset.into())
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    // Calls into the sorted troves and stability pool
+    pub async fn delist_asset<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .delist_asset(asset.into())
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .determine_missing_contracts()
+            .await?
+            .call()
+            .await
+    }
+
+    pub async fn get_asset_status<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+    ) -> Result<CallResponse<AssetStatus>, Error> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_asset_status(asset.into())
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
     pub async fn redeem_collateral<T: Account + Clone>(
         protocol_manager: &ContractInstance<ProtocolManager<T>>,
         amount: u64,
@@ -137,6 +294,42 @@ pub mod protocol_manager_abi {
         sorted_troves: &ContractInstance<SortedTroves<T>>,
         aswith_contracts: &Vec<AssetContracts<T>>,
     ) -> CallResponse<()> {
+        redeem_collateral_with_max_fee(
+            protocol_manager,
+            amount,
+            max_iterations,
+            partial_redemption_hint,
+            upper_partial_hint,
+            lower_partial_hint,
+            MAX_FEE_PERCENTAGE,
+            usdm,
+            fpt_staking,
+            coll_surplus_pool,
+            default_pool,
+            active_pool,
+            sorted_troves,
+            aswith_contracts,
+        )
+        .await
+        .unwrap()
+    }
+
+    pub async fn redeem_collateral_with_max_fee<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        amount: u64,
+        max_iterations: u64,
+        partial_redemption_hint: u64,
+        upper_partial_hint: Option<Identity>,
+        lower_partial_hint: Option<Identity>,
+        max_fee_percentage: u64,
+        usdm: &ContractInstance<USDMToken<T>>,
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        coll_surplus_pool: &ContractInstance<CollSurplusPool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        aswith_contracts: &Vec<AssetContracts<T>>,
+    ) -> Result<CallResponse<()>, Error> {
         let tx_params = TxPolicies::default()
             .with_tip(1)
             .with_witness_limit(2000000)
@@ -202,6 +395,7 @@ pub mod protocol_manager_abi {
                 partial_redemption_hint,
                 upper_partial_hint.unwrap_or(Identity::Address(Address::zeroed())),
                 lower_partial_hint.unwrap_or(Identity::Address(Address::zeroed())),
+                max_fee_percentage,
             )
             .with_tx_policies(tx_params)
             .call_params(call_params)
@@ -210,8 +404,258 @@ pub mod protocol_manager_abi {
             .with_contract_ids(&with_contract_ids.to_vec())
             .with_variable_output_policy(VariableOutputPolicy::Exactly(10))
             .determine_missing_contracts()
+            .await?
+            .call()
+            .await
+    }
+
+    // Redeems only from the troves of `assets`
+    pub async fn redeem_collateral_from_assets<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        amount: u64,
+        assets: Vec<AssetId>,
+        max_iterations: u64,
+        partial_redemption_hint: u64,
+        upper_partial_hint: Option<Identity>,
+        lower_partial_hint: Option<Identity>,
+        usdm: &ContractInstance<USDMToken<T>>,
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        coll_surplus_pool: &ContractInstance<CollSurplusPool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        aswith_contracts: &Vec<AssetContracts<T>>,
+    ) -> Result<CallResponse<()>, Error> {
+        redeem_collateral_from_assets_with_max_fee(
+            protocol_manager,
+            amount,
+            assets,
+            max_iterations,
+            partial_redemption_hint,
+            upper_partial_hint,
+            lower_partial_hint,
+            MAX_FEE_PERCENTAGE,
+            usdm,
+            fpt_staking,
+            coll_surplus_pool,
+            default_pool,
+            active_pool,
+            sorted_troves,
+            aswith_contracts,
+        )
+        .await
+    }
+
+    pub async fn redeem_collateral_from_assets_with_max_fee<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        amount: u64,
+        assets: Vec<AssetId>,
+        max_iterations: u64,
+        partial_redemption_hint: u64,
+        upper_partial_hint: Option<Identity>,
+        lower_partial_hint: Option<Identity>,
+        max_fee_percentage: u64,
+        usdm: &ContractInstance<USDMToken<T>>,
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        coll_surplus_pool: &ContractInstance<CollSurplusPool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        aswith_contracts: &Vec<AssetContracts<T>>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_witness_limit(2000000)
+            .with_script_gas_limit(2000000);
+        let usdm_asset_id = usdm
+            .contract
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+
+        let call_params: CallParameters = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(usdm_asset_id);
+
+        let mut with_contracts: Vec<&dyn ContractDependency> = Vec::new();
+
+        for contracts in aswith_contracts.iter() {
+            with_contracts.push(&contracts.trove_manager.contract);
+            with_contracts.push(&contracts.oracle.contract);
+            with_contracts.push(&contracts.mock_pyth_oracle);
+            with_contracts.push(&contracts.mock_redstone_oracle);
+        }
+
+        with_contracts.push(&fpt_staking.contract);
+        with_contracts.push(&coll_surplus_pool.contract);
+        with_contracts.push(&default_pool.contract);
+        with_contracts.push(&active_pool.contract);
+        with_contracts.push(&usdm.contract);
+        with_contracts.push(&sorted_troves.contract);
+
+        let mut with_contract_ids: Vec<Bech32ContractId> = Vec::new();
+        with_contract_ids.push(sorted_troves.contract.contract_id().into());
+        with_contract_ids.push(sorted_troves.implementation_id.into());
+        with_contract_ids.push(fpt_staking.contract.contract_id().into());
+        with_contract_ids.push(fpt_staking.implementation_id.into());
+        with_contract_ids.push(coll_surplus_pool.contract.contract_id().into());
+        with_contract_ids.push(coll_surplus_pool.implementation_id.into());
+        with_contract_ids.push(default_pool.contract.contract_id().into());
+        with_contract_ids.push(default_pool.implementation_id.into());
+        with_contract_ids.push(active_pool.contract.contract_id().into());
+        with_contract_ids.push(active_pool.implementation_id.into());
+        with_contract_ids.push(usdm.contract.contract_id().into());
+        with_contract_ids.push(usdm.implementation_id.into());
+        with_contract_ids.push(protocol_manager.contract.contract_id().into());
+        with_contract_ids.push(protocol_manager.implementation_id.into());
+
+        for contracts in aswith_contracts.iter() {
+            with_contract_ids.push(contracts.trove_manager.contract.contract_id().into());
+            with_contract_ids.push(contracts.trove_manager.implementation_id.into());
+            with_contract_ids.push(contracts.oracle.contract.contract_id().into());
+            with_contract_ids.push(contracts.oracle.implementation_id.into());
+            with_contract_ids.push(contracts.mock_pyth_oracle.contract_id().into());
+            with_contract_ids.push(contracts.mock_redstone_oracle.contract_id().into());
+        }
+
+        protocol_manager
+            .contract
+            .methods()
+            .redeem_collateral_from_assets(
+                assets,
+                max_iterations,
+                partial_redemption_hint,
+                upper_partial_hint.unwrap_or(Identity::Address(Address::zeroed())),
+                lower_partial_hint.unwrap_or(Identity::Address(Address::zeroed())),
+                max_fee_percentage,
+            )
+            .with_tx_policies(tx_params)
+            .call_params(call_params)
+            .unwrap()
+            .with_contracts(&with_contracts)
+            .with_contract_ids(&with_contract_ids)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(10))
+            .determine_missing_contracts()
+            .await?
+            .call()
+            .await
+    }
+
+    // Base rate as stored at the last fee operation
+    pub async fn get_base_rate<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+    ) -> CallResponse<u64> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_base_rate()
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    // Base rate decayed up to the current time
+    pub async fn get_decayed_base_rate<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+    ) -> CallResponse<u64> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_decayed_base_rate()
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_borrowing_rate<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+    ) -> CallResponse<u64> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_borrowing_rate(asset.into())
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_redemption_rate<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset: AssetId,
+    ) -> CallResponse<u64> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_redemption_rate(asset.into())
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_last_fee_operation_time<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+    ) -> CallResponse<u64> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_last_fee_operation_time()
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_current_time<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+    ) -> CallResponse<u64> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_current_time()
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
             .await
             .unwrap()
+    }
+
+    pub async fn set_current_time<T: Account + Clone>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        time: u64,
+    ) -> CallResponse<()> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_current_time(time)
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
             .call()
             .await
             .unwrap()
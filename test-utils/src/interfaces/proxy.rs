@@ -1,10 +1,16 @@
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "Proxy",
     abi = "contracts/proxy-contract/out/debug/proxy-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "Proxy",
+    abi = "contracts/proxy-contract/out/release/proxy-contract-abi.json"
+));
 
 pub mod proxy_abi {
     use super::*;
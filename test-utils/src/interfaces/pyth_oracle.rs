@@ -3,10 +3,16 @@ use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 use fuels::types::Bits256;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "PythCore",
     abi = "contracts/mock-pyth-contract/out/debug/mock-pyth-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "PythCore",
+    abi = "contracts/mock-pyth-contract/out/release/mock-pyth-contract-abi.json"
+));
 
 pub const DEFAULT_PYTH_PRICE_ID: Bits256 = Bits256([0; 32]);
 pub const PYTH_TIMESTAMP: u64 = 1724166967;
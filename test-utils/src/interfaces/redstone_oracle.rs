@@ -3,10 +3,16 @@ use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 use fuels::types::U256;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "RedstoneCore",
     abi = "contracts/mock-redstone-contract/out/debug/mock-redstone-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "RedstoneCore",
+    abi = "contracts/mock-redstone-contract/out/release/mock-redstone-contract-abi.json"
+));
 
 pub const DEFAULT_REDSTONE_PRICE_ID: U256 = U256::zero();
 
@@ -2,10 +2,16 @@ use fuels::prelude::{abigen, ContractId};
 use fuels::programs::responses::CallResponse;
 use fuels::types::Identity;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "SortedTroves",
     abi = "contracts/sorted-troves-contract/out/debug/sorted-troves-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "SortedTroves",
+    abi = "contracts/sorted-troves-contract/out/release/sorted-troves-contract-abi.json"
+));
 
 pub mod sorted_troves_abi {
     use crate::{
@@ -9,10 +9,16 @@ use crate::interfaces::token::Token;
 use crate::interfaces::trove_manager::TroveManagerContract;
 use crate::interfaces::usdm_token::USDMToken;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "StabilityPool",
     abi = "contracts/stability-pool-contract/out/debug/stability-pool-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "StabilityPool",
+    abi = "contracts/stability-pool-contract/out/release/stability-pool-contract-abi.json"
+));
 
 pub mod stability_pool_abi {
 
@@ -4,10 +4,16 @@ use fuels::{
 };
 
 // We'll use the generated types from the ABI
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "StorkCore",
     abi = "contracts/mock-stork-contract/out/debug/mock-stork-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "StorkCore",
+    abi = "contracts/mock-stork-contract/out/release/mock-stork-contract-abi.json"
+));
 
 pub const DEFAULT_STORK_FEED_ID: Bits256 = Bits256([0; 32]);
 pub const NS_TO_SECONDS: u64 = 1_000_000_000;
@@ -21,16 +27,25 @@ pub struct StorkConfig {
 pub mod stork_oracle_abi {
     use super::*;
 
-    pub async fn set_temporal_value(
-        contract: &StorkCore<Wallet>,
+    pub async fn set_temporal_value<T: Account + Clone>(
+        contract: &StorkCore<T>,
         feed_id: Bits256,
         value: u64,
         timestamp_ns: u64,
+    ) {
+        set_quantized_value(contract, feed_id, value as u128, timestamp_ns).await;
+    }
+
+    // `quantized_value` uses Stork's 18 decimals, wider than u64 for realistic prices
+    pub async fn set_quantized_value<T: Account + Clone>(
+        contract: &StorkCore<T>,
+        feed_id: Bits256,
+        quantized_value: u128,
+        timestamp_ns: u64,
     ) {
         // construct quantized value - exactly matching the contract test
-        let quantized_value_u128 = value as u128;
         let indent = 1u128 << 127;
-        let value_with_indent = indent + quantized_value_u128;
+        let value_with_indent = indent + quantized_value;
 
         let temporal_value = TemporalNumericValue {
             timestamp_ns,
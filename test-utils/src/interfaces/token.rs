@@ -1,9 +1,15 @@
 use fuels::{prelude::abigen, programs::responses::CallResponse, types::Identity};
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "Token",
     abi = "contracts/token-contract/out/debug/token-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "Token",
+    abi = "contracts/token-contract/out/release/token-contract-abi.json"
+));
 
 pub mod token_abi {
     use super::*;
@@ -11,10 +11,16 @@ use crate::interfaces::usdm_token::USDMToken;
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "TroveManagerContract",
     abi = "contracts/trove-manager-contract/out/debug/trove-manager-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "TroveManagerContract",
+    abi = "contracts/trove-manager-contract/out/release/trove-manager-contract-abi.json"
+));
 
 pub mod trove_manager_abi {
 
@@ -44,6 +50,72 @@ pub mod trove_manager_abi {
             .unwrap()
     }
 
+    pub async fn get_entire_system_coll<T: Account + Clone>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_entire_system_coll()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_entire_system_debt<T: Account + Clone>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_entire_system_debt()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_tcr<T: Account + Clone>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        price: u64,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_tcr(price)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn check_recovery_mode<T: Account + Clone>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        price: u64,
+    ) -> CallResponse<bool> {
+        trove_manager
+            .contract
+            .methods()
+            .check_recovery_mode(price)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
     pub async fn batch_liquidate_troves<T: Account + Clone>(
         trove_manager: &ContractInstance<TroveManagerContract<T>>,
         community_issuance: &ContractInstance<CommunityIssuance<T>>,
@@ -351,6 +423,40 @@ pub mod trove_manager_abi {
             .await
     }
 
+    pub async fn get_trove_stake<T: Account + Clone>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        id: Identity,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_trove_stake(id)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_trove_rewards_snapshot<T: Account + Clone>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        id: Identity,
+    ) -> CallResponse<RewardSnapshot> {
+        trove_manager
+            .contract
+            .methods()
+            .get_trove_rewards_snapshot(id)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
     pub async fn get_pending_asset_reward<T: Account + Clone>(
         trove_manager: &ContractInstance<TroveManagerContract<T>>,
         id: Identity,
@@ -385,8 +491,37 @@ pub mod trove_manager_abi {
             .unwrap()
     }
 
-    pub fn get_redemption_fee(asset_drawdown: u64) -> u64 {
-        return asset_drawdown * 1 / 100;
+    pub async fn get_trove_owners_count<T: Account + Clone>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_trove_owners_count()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_trove_owner_by_index<T: Account + Clone>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        index: u64,
+    ) -> CallResponse<Identity> {
+        trove_manager
+            .contract
+            .methods()
+            .get_trove_owner_by_index(index)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
     }
 }
 
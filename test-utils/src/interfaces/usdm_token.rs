@@ -4,10 +4,16 @@ use fuels::{
     types::Identity,
 };
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "USDMToken",
     abi = "contracts/usdm-token-contract/out/debug/usdm-token-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "USDMToken",
+    abi = "contracts/usdm-token-contract/out/release/usdm-token-contract-abi.json"
+));
 
 pub mod usdm_token_abi {
     use crate::data_structures::ContractInstance;
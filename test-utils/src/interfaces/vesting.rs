@@ -12,10 +12,16 @@ use std::{fs::File, io::BufReader, str::FromStr};
 use crate::data_structures::PRECISION;
 use crate::setup::common::get_absolute_path_from_relative;
 
+#[cfg(not(feature = "release-artifacts"))]
 abigen!(Contract(
     name = "VestingContract",
     abi = "contracts/vesting-contract/out/debug/vesting-contract-abi.json"
 ));
+#[cfg(feature = "release-artifacts")]
+abigen!(Contract(
+    name = "VestingContract",
+    abi = "contracts/vesting-contract/out/release/vesting-contract-abi.json"
+));
 
 pub const TOTAL_AMOUNT_VESTED: u64 = 100_000_000 * 68 / 100 * PRECISION;
 
@@ -52,6 +58,18 @@ pub mod vesting_abi {
             .call()
             .await
     }
+
+    pub async fn get_current_time<T: Account + Clone>(
+        contract: &ContractInstance<VestingContract<T>>,
+    ) -> Result<CallResponse<u64>, Error> {
+        contract
+            .contract
+            .methods()
+            .get_current_time()
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .call()
+            .await
+    }
     pub async fn get_vesting_schedule_call<T: Account + Clone>(
         contract: &ContractInstance<VestingContract<T>>,
         recipient: Identity,
@@ -0,0 +1,199 @@
+use fuels::{prelude::Account, types::Identity};
+
+use crate::{
+    data_structures::{AssetContracts, ProtocolContracts},
+    interfaces::{
+        active_pool::active_pool_abi,
+        coll_surplus_pool::coll_surplus_pool_abi,
+        default_pool::default_pool_abi,
+        sorted_troves::sorted_troves_abi,
+        trove_manager::{trove_manager_abi, Status},
+        usdm_token::usdm_token_abi,
+    },
+    utils::assert_within_threshold,
+};
+
+// Checks the system-wide accounting identities of the protocol. Safe to call after any step of a test.
+pub async fn check_invariants<T: Account + Clone>(contracts: &ProtocolContracts<T>) {
+    check_invariants_with_accounts(contracts, &[]).await;
+}
+
+// Same as `check_invariants`, additionally verifying that the CollSurplusPool balance of every asset
+// is fully accounted for by the claimable surpluses of `accounts`
+pub async fn check_invariants_with_accounts<T: Account + Clone>(
+    contracts: &ProtocolContracts<T>,
+    accounts: &[Identity],
+) {
+    let mut total_system_debt: u64 = 0;
+    for asset_contracts in &contracts.asset_contracts {
+        total_system_debt += check_asset_invariants(contracts, asset_contracts, accounts).await;
+    }
+
+    let usdm_supply = usdm_token_abi::total_supply(&contracts.usdm)
+        .await
+        .value
+        .unwrap();
+
+    // Every minted USDM is backed by trove debt, burns on repayment, redemption and offset reduce both sides
+    assert_eq!(
+        usdm_supply, total_system_debt,
+        "USDM total supply does not match total system debt"
+    );
+}
+
+// Returns the total USDM debt (ActivePool + DefaultPool) of the asset
+async fn check_asset_invariants<T: Account + Clone>(
+    contracts: &ProtocolContracts<T>,
+    asset_contracts: &AssetContracts<T>,
+    accounts: &[Identity],
+) -> u64 {
+    let asset_id = asset_contracts.asset_id;
+    let trove_manager = &asset_contracts.trove_manager;
+
+    let trove_owners_count = trove_manager_abi::get_trove_owners_count(trove_manager)
+        .await
+        .value;
+
+    let mut troves_debt: u64 = 0;
+    let mut troves_coll: u64 = 0;
+    for index in 0..trove_owners_count {
+        let owner = trove_manager_abi::get_trove_owner_by_index(trove_manager, index)
+            .await
+            .value;
+
+        let status = trove_manager_abi::get_trove_status(trove_manager, owner.clone())
+            .await
+            .unwrap()
+            .value;
+        assert_eq!(
+            status,
+            Status::Active,
+            "Trove owner {:?} at index {} is not active",
+            owner,
+            index
+        );
+
+        // Entire debt and coll already include the pending redistribution rewards
+        let (debt, coll, _, _) = trove_manager_abi::get_entire_debt_and_coll(trove_manager, owner)
+            .await
+            .value;
+        troves_debt += debt;
+        troves_coll += coll;
+    }
+
+    let active_pool_debt = active_pool_abi::get_usdm_debt(&contracts.active_pool, asset_id)
+        .await
+        .value;
+    let default_pool_debt = default_pool_abi::get_usdm_debt(&contracts.default_pool, asset_id)
+        .await
+        .value;
+    let active_pool_coll = active_pool_abi::get_asset(&contracts.active_pool, asset_id)
+        .await
+        .value;
+    let default_pool_coll = default_pool_abi::get_asset(&contracts.default_pool, asset_id)
+        .await
+        .value;
+
+    // Redistribution rounds rewards per unit staked down, so a small remainder may stay in the DefaultPool
+    assert_within_threshold(
+        active_pool_debt + default_pool_debt,
+        troves_debt,
+        &format!(
+            "Sum of trove debts does not match pool debt for asset {:?}, troves: {}, pools: {}",
+            asset_id,
+            troves_debt,
+            active_pool_debt + default_pool_debt
+        ),
+    );
+    assert_within_threshold(
+        active_pool_coll + default_pool_coll,
+        troves_coll,
+        &format!(
+            "Sum of trove collateral does not match pool collateral for asset {:?}, troves: {}, pools: {}",
+            asset_id,
+            troves_coll,
+            active_pool_coll + default_pool_coll
+        ),
+    );
+
+    check_sorted_troves(contracts, asset_contracts, trove_owners_count).await;
+
+    if !accounts.is_empty() {
+        let surplus_pool_coll =
+            coll_surplus_pool_abi::get_asset(&contracts.coll_surplus_pool, asset_id)
+                .await
+                .unwrap()
+                .value;
+
+        let mut claimable_surplus: u64 = 0;
+        for account in accounts {
+            claimable_surplus += coll_surplus_pool_abi::get_collateral(
+                &contracts.coll_surplus_pool,
+                account.clone(),
+                asset_id,
+            )
+            .await
+            .unwrap()
+            .value;
+        }
+
+        assert_eq!(
+            surplus_pool_coll, claimable_surplus,
+            "CollSurplusPool balance does not match the sum of claimable surpluses for asset {:?}",
+            asset_id
+        );
+    }
+
+    active_pool_debt + default_pool_debt
+}
+
+async fn check_sorted_troves<T: Account + Clone>(
+    contracts: &ProtocolContracts<T>,
+    asset_contracts: &AssetContracts<T>,
+    trove_owners_count: u64,
+) {
+    let asset_id = asset_contracts.asset_id;
+    let trove_manager = &asset_contracts.trove_manager;
+
+    let size = sorted_troves_abi::get_size(&contracts.sorted_troves, asset_id)
+        .await
+        .value;
+    assert_eq!(
+        size, trove_owners_count,
+        "Sorted troves size does not match active trove count for asset {:?}",
+        asset_id
+    );
+
+    // Walk from the tail (lowest NICR) to the head, NICR must never decrease
+    let null_identity = Identity::Address(Default::default());
+    let mut current = sorted_troves_abi::get_last(&contracts.sorted_troves, asset_id)
+        .await
+        .value;
+    let mut previous_nicr: u64 = 0;
+    let mut visited: u64 = 0;
+    while current != null_identity {
+        let nicr = trove_manager_abi::get_nominal_icr(trove_manager, current.clone())
+            .await
+            .value;
+        assert!(
+            nicr >= previous_nicr,
+            "Sorted troves are not ordered by NICR for asset {:?}, {:?} has NICR {} below {}",
+            asset_id,
+            current,
+            nicr,
+            previous_nicr
+        );
+
+        previous_nicr = nicr;
+        visited += 1;
+        current = sorted_troves_abi::get_prev(&contracts.sorted_troves, current, asset_id)
+            .await
+            .value;
+    }
+
+    assert_eq!(
+        visited, size,
+        "Sorted troves list length does not match its size for asset {:?}",
+        asset_id
+    );
+}
@@ -1,5 +1,15 @@
+pub mod artifacts;
+pub mod clock;
 pub mod data_structures;
+pub mod errors;
+pub mod events;
 pub mod interfaces;
+pub mod invariants;
 pub mod paths;
+pub mod price_path;
+pub mod scenario;
 pub mod setup;
+pub mod snapshot;
 pub mod utils;
+
+pub use moor_sdk::fluid_math;
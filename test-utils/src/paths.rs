@@ -1,98 +1,120 @@
-pub const TOKEN_CONTRACT_BINARY_PATH: &str =
-    "contracts/token-contract/out/debug/token-contract.bin";
-pub const TOKEN_CONTRACT_STORAGE_PATH: &str =
-    "contracts/token-contract/out/debug/token-contract-storage_slots.json";
-
-pub const TROVE_MANAGER_CONTRACT_BINARY_PATH: &str =
-    "contracts/trove-manager-contract/out/debug/trove-manager-contract.bin";
-pub const TROVE_MANAGER_CONTRACT_STORAGE_PATH: &str =
-    "contracts/trove-manager-contract/out/debug/trove-manager-contract-storage_slots.json";
-
-pub const VESTING_CONTRACT_BINARY_PATH: &str =
-    "contracts/vesting-contract/out/debug/vesting-contract.bin";
-pub const VESTING_CONTRACT_STORAGE_PATH: &str =
-    "contracts/vesting-contract/out/debug/vesting-contract-storage_slots.json";
+// Every artifact is read from `out/debug`, or from `out/release` with the `release-artifacts`
+// feature, which also switches the ABIs used by `abigen!` in `interfaces`
+#[cfg(not(feature = "release-artifacts"))]
+macro_rules! profile {
+    () => {
+        "debug"
+    };
+}
+#[cfg(feature = "release-artifacts")]
+macro_rules! profile {
+    () => {
+        "release"
+    };
+}
+
+macro_rules! binary_path {
+    ($project:literal) => {
+        concat!(
+            "contracts/",
+            $project,
+            "/out/",
+            profile!(),
+            "/",
+            $project,
+            ".bin"
+        )
+    };
+}
+
+macro_rules! storage_path {
+    ($project:literal) => {
+        concat!(
+            "contracts/",
+            $project,
+            "/out/",
+            profile!(),
+            "/",
+            $project,
+            "-storage_slots.json"
+        )
+    };
+}
+
+pub const ARTIFACT_PROFILE: &str = profile!();
+
+// Command producing the artifacts of `ARTIFACT_PROFILE`
+pub const BUILD_COMMAND: &str = if cfg!(feature = "release-artifacts") {
+    "forc build --release"
+} else {
+    "forc build"
+};
+
+pub const TOKEN_CONTRACT_BINARY_PATH: &str = binary_path!("token-contract");
+pub const TOKEN_CONTRACT_STORAGE_PATH: &str = storage_path!("token-contract");
+
+pub const TROVE_MANAGER_CONTRACT_BINARY_PATH: &str = binary_path!("trove-manager-contract");
+pub const TROVE_MANAGER_CONTRACT_STORAGE_PATH: &str = storage_path!("trove-manager-contract");
+
+pub const VESTING_CONTRACT_BINARY_PATH: &str = binary_path!("vesting-contract");
+pub const VESTING_CONTRACT_STORAGE_PATH: &str = storage_path!("vesting-contract");
+
+pub const ORACLE_CONTRACT_BINARY_PATH: &str = binary_path!("oracle-contract");
+pub const ORACLE_CONTRACT_STORAGE_PATH: &str = storage_path!("oracle-contract");
+
+pub const STORK_ORACLE_CONTRACT_BINARY_PATH: &str = binary_path!("mock-stork-contract");
+
+pub const REDSTONE_ORACLE_CONTRACT_BINARY_PATH: &str = binary_path!("mock-redstone-contract");
+
+pub const PYTH_ORACLE_CONTRACT_BINARY_PATH: &str = binary_path!("mock-pyth-contract");
+
+pub const BORROW_OPERATIONS_CONTRACT_BINARY_PATH: &str = binary_path!("borrow-operations-contract");
+pub const BORROW_OPERATIONS_CONTRACT_STORAGE_PATH: &str =
+    storage_path!("borrow-operations-contract");
 
-pub const ORACLE_CONTRACT_BINARY_PATH: &str =
-    "contracts/oracle-contract/out/debug/oracle-contract.bin";
-pub const ORACLE_CONTRACT_STORAGE_PATH: &str =
-    "contracts/oracle-contract/out/debug/oracle-contract-storage_slots.json";
+pub const SORTED_TROVES_CONTRACT_BINARY_PATH: &str = binary_path!("sorted-troves-contract");
+pub const SORTED_TROVES_CONTRACT_STORAGE_PATH: &str = storage_path!("sorted-troves-contract");
 
-pub const STORK_ORACLE_CONTRACT_BINARY_PATH: &str =
-    "contracts/mock-stork-contract/out/debug/mock-stork-contract.bin";
+pub const ACTIVE_POOL_CONTRACT_BINARY_PATH: &str = binary_path!("active-pool-contract");
+pub const ACTIVE_POOL_CONTRACT_STORAGE_PATH: &str = storage_path!("active-pool-contract");
 
-pub const REDSTONE_ORACLE_CONTRACT_BINARY_PATH: &str =
-    "contracts/mock-redstone-contract/out/debug/mock-redstone-contract.bin";
+pub const STABILITY_POOL_CONTRACT_BINARY_PATH: &str = binary_path!("stability-pool-contract");
+pub const STABILITY_POOL_CONTRACT_STORAGE_PATH: &str = storage_path!("stability-pool-contract");
 
-pub const PYTH_ORACLE_CONTRACT_BINARY_PATH: &str =
-    "contracts/mock-pyth-contract/out/debug/mock-pyth-contract.bin";
+pub const DEFAULT_POOL_CONTRACT_BINARY_PATH: &str = binary_path!("default-pool-contract");
+pub const DEFAULT_POOL_CONTRACT_STORAGE_PATH: &str = storage_path!("default-pool-contract");
 
-pub const BORROW_OPERATIONS_CONTRACT_BINARY_PATH: &str =
-    "contracts/borrow-operations-contract/out/debug/borrow-operations-contract.bin";
-pub const BORROW_OPERATIONS_CONTRACT_STORAGE_PATH: &str =
-    "contracts/borrow-operations-contract/out/debug/borrow-operations-contract-storage_slots.json";
-
-pub const SORTED_TROVES_CONTRACT_BINARY_PATH: &str =
-    "contracts/sorted-troves-contract/out/debug/sorted-troves-contract.bin";
-pub const SORTED_TROVES_CONTRACT_STORAGE_PATH: &str =
-    "contracts/sorted-troves-contract/out/debug/sorted-troves-contract-storage_slots.json";
-
-pub const ACTIVE_POOL_CONTRACT_BINARY_PATH: &str =
-    "contracts/active-pool-contract/out/debug/active-pool-contract.bin";
-pub const ACTIVE_POOL_CONTRACT_STORAGE_PATH: &str =
-    "contracts/active-pool-contract/out/debug/active-pool-contract-storage_slots.json";
-
-pub const STABILITY_POOL_CONTRACT_BINARY_PATH: &str =
-    "contracts/stability-pool-contract/out/debug/stability-pool-contract.bin";
-pub const STABILITY_POOL_CONTRACT_STORAGE_PATH: &str =
-    "contracts/stability-pool-contract/out/debug/stability-pool-contract-storage_slots.json";
-
-pub const DEFAULT_POOL_CONTRACT_BINARY_PATH: &str =
-    "contracts/default-pool-contract/out/debug/default-pool-contract.bin";
-pub const DEFAULT_POOL_CONTRACT_STORAGE_PATH: &str =
-    "contracts/default-pool-contract/out/debug/default-pool-contract-storage_slots.json";
-
-pub const COLL_SURPLUS_POOL_CONTRACT_BINARY_PATH: &str =
-    "contracts/coll-surplus-pool-contract/out/debug/coll-surplus-pool-contract.bin";
+pub const COLL_SURPLUS_POOL_CONTRACT_BINARY_PATH: &str = binary_path!("coll-surplus-pool-contract");
 pub const COLL_SURPLUS_POOL_CONTRACT_STORAGE_PATH: &str =
-    "contracts/coll-surplus-pool-contract/out/debug/coll-surplus-pool-contract-storage_slots.json";
+    storage_path!("coll-surplus-pool-contract");
 
-pub const USDM_TOKEN_CONTRACT_BINARY_PATH: &str =
-    "contracts/usdm-token-contract/out/debug/usdm-token-contract.bin";
-pub const USDM_TOKEN_CONTRACT_STORAGE_PATH: &str =
-    "contracts/usdm-token-contract/out/debug/usdm-token-contract-storage_slots.json";
+pub const USDM_TOKEN_CONTRACT_BINARY_PATH: &str = binary_path!("usdm-token-contract");
+pub const USDM_TOKEN_CONTRACT_STORAGE_PATH: &str = storage_path!("usdm-token-contract");
 
-pub const FPT_TOKEN_CONTRACT_BINARY_PATH: &str =
-    "contracts/fpt-token-contract/out/debug/fpt-token-contract.bin";
-pub const FPT_TOKEN_CONTRACT_STORAGE_PATH: &str =
-    "contracts/fpt-token-contract/out/debug/fpt-token-contract-storage_slots.json";
+pub const FPT_TOKEN_CONTRACT_BINARY_PATH: &str = binary_path!("fpt-token-contract");
+pub const FPT_TOKEN_CONTRACT_STORAGE_PATH: &str = storage_path!("fpt-token-contract");
 
-pub const FPT_STAKING_CONTRACT_BINARY_PATH: &str =
-    "contracts/fpt-staking-contract/out/debug/fpt-staking-contract.bin";
-pub const FPT_STAKING_CONTRACT_STORAGE_PATH: &str =
-    "contracts/fpt-staking-contract/out/debug/fpt-staking-contract-storage_slots.json";
+pub const FPT_STAKING_CONTRACT_BINARY_PATH: &str = binary_path!("fpt-staking-contract");
+pub const FPT_STAKING_CONTRACT_STORAGE_PATH: &str = storage_path!("fpt-staking-contract");
 
-pub const PROTCOL_MANAGER_CONTRACT_BINARY_PATH: &str =
-    "contracts/protocol-manager-contract/out/debug/protocol-manager-contract.bin";
-pub const PROTCOL_MANAGER_CONTRACT_STORAGE_PATH: &str =
-    "contracts/protocol-manager-contract/out/debug/protocol-manager-contract-storage_slots.json";
+pub const PROTCOL_MANAGER_CONTRACT_BINARY_PATH: &str = binary_path!("protocol-manager-contract");
+pub const PROTCOL_MANAGER_CONTRACT_STORAGE_PATH: &str = storage_path!("protocol-manager-contract");
 
 pub const COMMUNITY_ISSUANCE_CONTRACT_BINARY_PATH: &str =
-    "contracts/community-issuance-contract/out/debug/community-issuance-contract.bin";
+    binary_path!("community-issuance-contract");
 pub const COMMUNITY_ISSUANCE_CONTRACT_STORAGE_PATH: &str =
-    "contracts/community-issuance-contract/out/debug/community-issuance-contract-storage_slots.json";
+    storage_path!("community-issuance-contract");
 
-pub const HINT_HELPER_CONTRACT_BINARY_PATH: &str =
-    "contracts/hint-helper-contract/out/debug/hint-helper-contract.bin";
-pub const HINT_HELPER_CONTRACT_STORAGE_PATH: &str =
-    "contracts/hint-helper-contract/out/debug/hint-helper-contract-storage_slots.json";
+pub const HINT_HELPER_CONTRACT_BINARY_PATH: &str = binary_path!("hint-helper-contract");
+pub const HINT_HELPER_CONTRACT_STORAGE_PATH: &str = storage_path!("hint-helper-contract");
 
 pub const MULTI_TROVE_GETTER_CONTRACT_BINARY_PATH: &str =
-    "contracts/multi-trove-getter-contract/out/debug/multi-trove-getter-contract.bin";
+    binary_path!("multi-trove-getter-contract");
 pub const MULTI_TROVE_GETTER_CONTRACT_STORAGE_PATH: &str =
-    "contracts/multi-trove-getter-contract/out/debug/multi-trove-getter-contract-storage_slots.json";
+    storage_path!("multi-trove-getter-contract");
+
+pub const FLASH_MINT_RECEIVER_CONTRACT_BINARY_PATH: &str =
+    binary_path!("tests-artifacts-flash-mint-receiver-contract");
 
-pub const PROXY_CONTRACT_BINARY_PATH: &str =
-    "contracts/proxy-contract/out/debug/proxy-contract.bin";
-pub const PROXY_CONTRACT_STORAGE_PATH: &str =
-    "contracts/proxy-contract/out/debug/proxy-contract-storage_slots.json";
+pub const PROXY_CONTRACT_BINARY_PATH: &str = binary_path!("proxy-contract");
+pub const PROXY_CONTRACT_STORAGE_PATH: &str = storage_path!("proxy-contract");
@@ -0,0 +1,304 @@
+use std::future::Future;
+
+use fuels::{prelude::Account, types::U256};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    data_structures::{AssetContracts, ProtocolContracts},
+    interfaces::{
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, Price},
+        redstone_oracle::redstone_oracle_abi,
+        stork_oracle::{stork_oracle_abi, NS_TO_SECONDS},
+    },
+    setup::common::get_absolute_path_from_relative,
+};
+
+// Prices in a path use the Fuel VM representation of the oracle, 9 decimals
+const PATH_DECIMALS: u32 = 9;
+const STORK_DECIMALS: u32 = 18;
+const BASIS_POINTS: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    Pyth,
+    Stork,
+    Redstone,
+}
+
+// Price of one feed at each step, `None` means the feed publishes nothing at that step
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PricePath {
+    prices: Vec<Option<u64>>,
+}
+
+impl PricePath {
+    pub fn new(prices: Vec<Option<u64>>) -> Self {
+        Self { prices }
+    }
+
+    pub fn from_prices(prices: Vec<u64>) -> Self {
+        Self::new(prices.into_iter().map(Some).collect())
+    }
+
+    // Reads the `price` column of a CSV file relative to the repo root, an empty cell is a step without update
+    pub fn from_csv(relative_path: &str) -> Self {
+        let mut reader = csv::Reader::from_path(get_absolute_path_from_relative(relative_path))
+            .expect("Failed to open price path CSV");
+
+        let price_column = reader
+            .headers()
+            .unwrap()
+            .iter()
+            .position(|header| header.trim() == "price")
+            .expect("Price path CSV has no `price` column");
+
+        let prices = reader
+            .records()
+            .map(|record| {
+                let record = record.unwrap();
+                let cell = record.get(price_column).unwrap_or("").trim();
+                if cell.is_empty() {
+                    None
+                } else {
+                    Some(
+                        cell.parse::<u64>()
+                            .expect("Invalid price in price path CSV"),
+                    )
+                }
+            })
+            .collect();
+
+        Self::new(prices)
+    }
+
+    pub fn constant(price: u64, steps: usize) -> Self {
+        Self::from_prices(vec![price; steps])
+    }
+
+    // Each step moves the price by a uniformly drawn amount of at most `max_move_bps`, deterministic for a seed
+    pub fn random_walk(start: u64, steps: usize, max_move_bps: u64, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut price = start;
+        let mut prices = Vec::with_capacity(steps);
+
+        for _ in 0..steps {
+            prices.push(price);
+            let move_bps = rng.gen_range(0..=max_move_bps);
+            let delta = (price as u128 * move_bps as u128 / BASIS_POINTS as u128) as u64;
+            price = if rng.gen_bool(0.5) {
+                price.saturating_add(delta)
+            } else {
+                price.saturating_sub(delta).max(1)
+            };
+        }
+
+        Self::from_prices(prices)
+    }
+
+    // Flat at `start`, then drops linearly to `end` over `crash_steps` steps starting at `crash_at`
+    pub fn crash(start: u64, end: u64, crash_at: usize, crash_steps: usize, steps: usize) -> Self {
+        let crash_steps = crash_steps.max(1);
+        let prices = (0..steps)
+            .map(|step| {
+                if step < crash_at {
+                    start
+                } else if step >= crash_at + crash_steps {
+                    end
+                } else {
+                    let progress = (step - crash_at + 1) as u128;
+                    let drop = (start.saturating_sub(end)) as u128 * progress / crash_steps as u128;
+                    start - drop as u64
+                }
+            })
+            .collect();
+
+        Self::from_prices(prices)
+    }
+
+    // No updates for steps in `from..to`, e.g. a feed outage longer than the oracle timeout
+    pub fn with_gap(mut self, from: usize, to: usize) -> Self {
+        for step in from..to.min(self.prices.len()) {
+            self.prices[step] = None;
+        }
+        self
+    }
+
+    // The feed stops publishing from `step` onwards
+    pub fn stale_from(self, step: usize) -> Self {
+        let len = self.prices.len();
+        self.with_gap(step, len)
+    }
+
+    // Same path offset by `deviation_bps`, to make another source disagree with this one
+    pub fn deviated(&self, deviation_bps: i64) -> Self {
+        let factor = (BASIS_POINTS as i64 + deviation_bps).max(0) as u128;
+        Self::new(
+            self.prices
+                .iter()
+                .map(|price| price.map(|p| (p as u128 * factor / BASIS_POINTS as u128) as u64))
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+
+    pub fn price_at(&self, step: usize) -> Option<u64> {
+        self.prices.get(step).copied().flatten()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PriceStep {
+    pub index: usize,
+    pub timestamp: u64,
+}
+
+struct Feed {
+    asset_index: usize,
+    source: PriceSource,
+    path: PricePath,
+}
+
+// Replays price paths on the mock Pyth, Stork and Redstone contracts of each asset.
+// Step `n` happens at `start_time + n * step_interval`: the Oracle debug timestamp is moved
+// there and every feed with a price at that step publishes it with that timestamp, so feeds
+// without updates naturally go stale.
+pub struct PriceDriver<'a, T: Account + Clone> {
+    contracts: &'a ProtocolContracts<T>,
+    feeds: Vec<Feed>,
+    start_time: u64,
+    step_interval: u64,
+    next_step: usize,
+}
+
+impl<'a, T: Account + Clone> PriceDriver<'a, T> {
+    pub fn new(contracts: &'a ProtocolContracts<T>, start_time: u64, step_interval: u64) -> Self {
+        Self {
+            contracts,
+            feeds: vec![],
+            start_time,
+            step_interval,
+            next_step: 0,
+        }
+    }
+
+    pub fn with_path(mut self, asset_index: usize, source: PriceSource, path: PricePath) -> Self {
+        assert!(
+            asset_index < self.contracts.asset_contracts.len(),
+            "No asset at index {}",
+            asset_index
+        );
+        self.feeds.push(Feed {
+            asset_index,
+            source,
+            path,
+        });
+        self
+    }
+
+    pub fn steps(&self) -> usize {
+        self.feeds
+            .iter()
+            .map(|feed| feed.path.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn timestamp_at(&self, step: usize) -> u64 {
+        self.start_time + step as u64 * self.step_interval
+    }
+
+    // Applies the next step, returns `None` once every path is exhausted
+    pub async fn step(&mut self) -> Option<PriceStep> {
+        if self.next_step >= self.steps() {
+            return None;
+        }
+
+        let step = PriceStep {
+            index: self.next_step,
+            timestamp: self.timestamp_at(self.next_step),
+        };
+        self.next_step += 1;
+
+        for asset_contracts in &self.contracts.asset_contracts {
+            oracle_abi::set_debug_timestamp(&asset_contracts.oracle, step.timestamp).await;
+        }
+
+        for feed in &self.feeds {
+            if let Some(price) = feed.path.price_at(step.index) {
+                let asset_contracts = &self.contracts.asset_contracts[feed.asset_index];
+                publish_price(asset_contracts, feed.source, price, step.timestamp).await;
+            }
+        }
+
+        Some(step)
+    }
+
+    // Applies every remaining step, calling `on_step` after each one (e.g. to run keepers or assert prices)
+    pub async fn run<F, Fut>(&mut self, mut on_step: F)
+    where
+        F: FnMut(PriceStep) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        while let Some(step) = self.step().await {
+            on_step(step).await;
+        }
+    }
+}
+
+pub async fn publish_price<T: Account + Clone>(
+    asset_contracts: &AssetContracts<T>,
+    source: PriceSource,
+    price: u64,
+    timestamp: u64,
+) {
+    match source {
+        PriceSource::Pyth => {
+            pyth_oracle_abi::update_price_feeds(
+                &asset_contracts.mock_pyth_oracle,
+                vec![(
+                    asset_contracts.pyth_price_id,
+                    Price {
+                        confidence: 0,
+                        exponent: PATH_DECIMALS,
+                        price,
+                        publish_time: timestamp,
+                    },
+                )],
+            )
+            .await;
+        }
+        PriceSource::Stork => {
+            let quantized_value = price as u128 * 10u128.pow(STORK_DECIMALS - PATH_DECIMALS);
+            stork_oracle_abi::set_quantized_value(
+                &asset_contracts.mock_stork_oracle,
+                asset_contracts.stork_feed_id,
+                quantized_value,
+                timestamp * NS_TO_SECONDS,
+            )
+            .await;
+        }
+        PriceSource::Redstone => {
+            let precision = asset_contracts.redstone_precision;
+            let value = if precision >= PATH_DECIMALS {
+                U256::from(price) * U256::from(10u64).pow(U256::from(precision - PATH_DECIMALS))
+            } else {
+                U256::from(price) / U256::from(10u64).pow(U256::from(PATH_DECIMALS - precision))
+            };
+            redstone_oracle_abi::write_prices(
+                &asset_contracts.mock_redstone_oracle,
+                vec![(asset_contracts.redstone_price_id, value)],
+            )
+            .await;
+            redstone_oracle_abi::set_timestamp(&asset_contracts.mock_redstone_oracle, timestamp)
+                .await;
+        }
+    }
+}
@@ -0,0 +1,1164 @@
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+use fuels::{
+    prelude::*,
+    programs::responses::CallResponse,
+    types::{AssetId, Identity},
+};
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{
+    data_structures::{AssetContracts, ContractInstance, ProtocolContracts, PRECISION},
+    errors::ProtocolError,
+    events::{event_names, PROTOCOL_EVENT_NAMES},
+    interfaces::{
+        active_pool::active_pool_abi,
+        borrow_operations::{borrow_operations_abi, BorrowOperations},
+        coll_surplus_pool::coll_surplus_pool_abi,
+        community_issuance::community_issuance_abi,
+        default_pool::default_pool_abi,
+        oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, ProtocolManager},
+        pyth_oracle::PYTH_TIMESTAMP,
+        stability_pool::{stability_pool_abi, StabilityPool},
+        token::token_abi,
+        trove_manager::{trove_manager_abi, Status, TroveManagerContract},
+    },
+    invariants::check_invariants_with_accounts,
+    price_path::{publish_price, PriceSource},
+    setup::common::{get_absolute_path_from_relative, setup_protocol},
+};
+
+// Declarative protocol test: actors, initial prices and a list of steps, each step being an action
+// against a fresh local protocol or a set of expectations on its state. See
+// `contracts/trove-manager-contract/tests/scenarios` for examples.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub actors: Vec<String>,
+    #[serde(default)]
+    pub second_asset: bool,
+    // Initial Pyth price of each asset, by asset index
+    #[serde(default)]
+    pub prices: Vec<Amount>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Step {
+    #[serde(flatten)]
+    pub action: Action,
+    // Revert reason the action must fail with, either the message or the `ProtocolError` variant
+    #[serde(default)]
+    pub expect_revert: Option<String>,
+    // Protocol events the action must emit in this order, other events may be emitted in between
+    #[serde(default)]
+    pub expect_events: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    SetPrice {
+        #[serde(default)]
+        asset: usize,
+        price: Amount,
+    },
+    AdvanceTime {
+        seconds: u64,
+    },
+    Mint {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        amount: Amount,
+    },
+    OpenTrove {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        collateral: Amount,
+        debt: Amount,
+    },
+    AddColl {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        amount: Amount,
+    },
+    WithdrawColl {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        amount: Amount,
+    },
+    WithdrawUsdm {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        amount: Amount,
+    },
+    RepayUsdm {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        amount: Amount,
+    },
+    // Repays the whole trove debt unless an amount is given
+    CloseTrove {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        #[serde(default)]
+        amount: Option<Amount>,
+    },
+    ProvideToStabilityPool {
+        actor: String,
+        amount: Amount,
+    },
+    WithdrawFromStabilityPool {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        amount: Amount,
+    },
+    Liquidate {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        borrower: String,
+    },
+    BatchLiquidate {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+        borrowers: Vec<String>,
+    },
+    Redeem {
+        actor: String,
+        amount: Amount,
+        #[serde(default = "default_max_iterations")]
+        max_iterations: u64,
+    },
+    ClaimCollateral {
+        actor: String,
+        #[serde(default)]
+        asset: usize,
+    },
+    Expect(Expectations),
+}
+
+fn default_max_iterations() -> u64 {
+    10
+}
+
+impl Action {
+    fn name(&self) -> &'static str {
+        match self {
+            Action::SetPrice { .. } => "set_price",
+            Action::AdvanceTime { .. } => "advance_time",
+            Action::Mint { .. } => "mint",
+            Action::OpenTrove { .. } => "open_trove",
+            Action::AddColl { .. } => "add_coll",
+            Action::WithdrawColl { .. } => "withdraw_coll",
+            Action::WithdrawUsdm { .. } => "withdraw_usdm",
+            Action::RepayUsdm { .. } => "repay_usdm",
+            Action::CloseTrove { .. } => "close_trove",
+            Action::ProvideToStabilityPool { .. } => "provide_to_stability_pool",
+            Action::WithdrawFromStabilityPool { .. } => "withdraw_from_stability_pool",
+            Action::Liquidate { .. } => "liquidate",
+            Action::BatchLiquidate { .. } => "batch_liquidate",
+            Action::Redeem { .. } => "redeem",
+            Action::ClaimCollateral { .. } => "claim_collateral",
+            Action::Expect(_) => "expect",
+        }
+    }
+
+    // Actions that are not a single protocol call, or whose helper unwraps the call result
+    fn supports_call_expectations(&self) -> bool {
+        !matches!(
+            self,
+            Action::SetPrice { .. }
+                | Action::AdvanceTime { .. }
+                | Action::Redeem { .. }
+                | Action::ClaimCollateral { .. }
+                | Action::Expect(_)
+        )
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expectations {
+    #[serde(default)]
+    pub troves: Vec<TroveExpectation>,
+    #[serde(default)]
+    pub balances: Vec<BalanceExpectation>,
+    #[serde(default)]
+    pub pools: Vec<PoolExpectation>,
+    #[serde(default)]
+    pub stability_pool: Option<StabilityPoolExpectation>,
+    // Runs `check_invariants_with_accounts` over every actor
+    #[serde(default)]
+    pub invariants: bool,
+}
+
+// Stored trove values, pending redistribution rewards are not included
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TroveExpectation {
+    pub actor: String,
+    #[serde(default)]
+    pub asset: usize,
+    #[serde(default)]
+    pub status: Option<TroveStatus>,
+    #[serde(default)]
+    pub coll: Option<Amount>,
+    #[serde(default)]
+    pub debt: Option<Amount>,
+    // Collateral claimable from the CollSurplusPool
+    #[serde(default)]
+    pub surplus: Option<Amount>,
+    #[serde(default)]
+    pub tolerance: Amount,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TroveStatus {
+    NonExistent,
+    Active,
+    ClosedByOwner,
+    ClosedByLiquidation,
+    ClosedByRedemption,
+}
+
+impl From<TroveStatus> for Status {
+    fn from(status: TroveStatus) -> Self {
+        match status {
+            TroveStatus::NonExistent => Status::NonExistent,
+            TroveStatus::Active => Status::Active,
+            TroveStatus::ClosedByOwner => Status::ClosedByOwner,
+            TroveStatus::ClosedByLiquidation => Status::ClosedByLiquidation,
+            TroveStatus::ClosedByRedemption => Status::ClosedByRedemption,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScenarioToken {
+    Usdm,
+    Fpt,
+    Asset,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BalanceExpectation {
+    pub actor: String,
+    pub token: ScenarioToken,
+    // Only used for `token: asset`
+    #[serde(default)]
+    pub asset: usize,
+    pub amount: Amount,
+    #[serde(default)]
+    pub tolerance: Amount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolExpectation {
+    #[serde(default)]
+    pub asset: usize,
+    #[serde(default)]
+    pub active_pool_coll: Option<Amount>,
+    #[serde(default)]
+    pub active_pool_debt: Option<Amount>,
+    #[serde(default)]
+    pub default_pool_coll: Option<Amount>,
+    #[serde(default)]
+    pub default_pool_debt: Option<Amount>,
+    #[serde(default)]
+    pub tolerance: Amount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StabilityPoolExpectation {
+    #[serde(default)]
+    pub total_usdm: Option<Amount>,
+    // Collateral held by the pool, by asset
+    #[serde(default)]
+    pub assets: Vec<AssetAmount>,
+    #[serde(default)]
+    pub deposits: Vec<DepositExpectation>,
+    #[serde(default)]
+    pub tolerance: Amount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssetAmount {
+    #[serde(default)]
+    pub asset: usize,
+    pub amount: Amount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DepositExpectation {
+    pub actor: String,
+    // Compounded deposit after liquidations
+    #[serde(default)]
+    pub usdm: Option<Amount>,
+    #[serde(default)]
+    pub gains: Vec<AssetAmount>,
+}
+
+// Token amount written in whole units, e.g. `1000`, `0.5` or `"1_000.25"`, stored with 9 decimals
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Amount(pub u64);
+
+impl Amount {
+    pub fn parse(value: &str) -> std::result::Result<Self, String> {
+        let value = value.trim().replace('_', "");
+        let (whole, fraction) = value.split_once('.').unwrap_or((&value, ""));
+
+        if fraction.len() > 9 {
+            return Err(format!("`{}` has more than 9 decimals", value));
+        }
+        let whole: u64 = whole
+            .parse()
+            .map_err(|_| format!("`{}` is not a valid amount", value))?;
+        let fraction: u64 = if fraction.is_empty() {
+            0
+        } else {
+            format!("{:0<9}", fraction)
+                .parse()
+                .map_err(|_| format!("`{}` is not a valid amount", value))?
+        };
+
+        whole
+            .checked_mul(PRECISION)
+            .and_then(|whole| whole.checked_add(fraction))
+            .map(Amount)
+            .ok_or_else(|| format!("`{}` does not fit in a u64", value))
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fraction = format!("{:09}", self.0 % PRECISION);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{}", self.0 / PRECISION)
+        } else {
+            write!(f, "{}.{}", self.0 / PRECISION, fraction)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawAmount {
+            Integer(u64),
+            Float(f64),
+            Text(String),
+        }
+
+        match RawAmount::deserialize(deserializer)? {
+            RawAmount::Integer(value) => value
+                .checked_mul(PRECISION)
+                .map(Amount)
+                .ok_or_else(|| de::Error::custom(format!("`{}` does not fit in a u64", value))),
+            RawAmount::Float(value) => Amount::parse(&value.to_string()).map_err(de::Error::custom),
+            RawAmount::Text(value) => Amount::parse(&value).map_err(de::Error::custom),
+        }
+    }
+}
+
+impl Scenario {
+    pub fn from_yaml(yaml: &str) -> std::result::Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    // Loads a scenario from a path relative to the repo root
+    pub fn from_file(relative_path: &str) -> Self {
+        let path = get_absolute_path_from_relative(relative_path);
+        let yaml = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read scenario {}: {}", path, e));
+        Self::from_yaml(&yaml).unwrap_or_else(|e| panic!("Invalid scenario {}: {}", path, e))
+    }
+
+    // Catches mistakes that would otherwise only surface halfway through the run
+    pub fn validate(&self) {
+        let mut actors = self.actors.clone();
+        actors.sort();
+        actors.dedup();
+        assert_eq!(
+            actors.len(),
+            self.actors.len(),
+            "Scenario `{}` has duplicate actors",
+            self.name
+        );
+
+        let assets = if self.second_asset { 2 } else { 1 };
+        assert!(
+            self.prices.len() <= assets,
+            "Scenario `{}` sets prices for {} assets but only deploys {}",
+            self.name,
+            self.prices.len(),
+            assets
+        );
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let context = step_context(&self.name, index, &step.action);
+
+            if !step.action.supports_call_expectations() {
+                assert!(
+                    step.expect_revert.is_none() && step.expect_events.is_empty(),
+                    "{}: expect_revert and expect_events are not supported for this action",
+                    context
+                );
+            }
+
+            if let Some(reason) = &step.expect_revert {
+                assert!(
+                    ProtocolError::ALL
+                        .iter()
+                        .any(|error| error_matches(*error, reason)),
+                    "{}: unknown revert reason `{}`",
+                    context,
+                    reason
+                );
+            }
+
+            for event in &step.expect_events {
+                assert!(
+                    PROTOCOL_EVENT_NAMES.contains(&event.as_str()),
+                    "{}: unknown event `{}`",
+                    context,
+                    event
+                );
+            }
+        }
+    }
+
+    // Runs the scenario on a freshly deployed protocol, panicking on the first failed expectation
+    pub async fn run(&self) {
+        self.validate();
+        println!("Running scenario `{}`", self.name);
+
+        let (contracts, admin, wallets) =
+            setup_protocol(self.actors.len() as u64 + 1, self.second_asset, false).await;
+
+        let mut runner = ScenarioRunner {
+            contracts,
+            admin,
+            actors: self.actors.iter().cloned().zip(wallets).collect(),
+            now: PYTH_TIMESTAMP,
+            prices: HashMap::new(),
+        };
+
+        for (asset, price) in self.prices.iter().enumerate() {
+            runner.set_price(asset, price.0).await;
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let context = step_context(&self.name, index, &step.action);
+            runner.run_step(step, &context).await;
+        }
+    }
+}
+
+// Runs every `.yaml`/`.yml` scenario in a directory relative to the repo root, in file name order
+pub async fn run_scenarios_in_dir(relative_dir: &str) {
+    let dir = get_absolute_path_from_relative(relative_dir);
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Failed to read scenario directory {}: {}", dir, e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        })
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "No scenarios found in {}", dir);
+
+    for path in paths {
+        let relative = Path::new(relative_dir).join(path.file_name().unwrap());
+        Scenario::from_file(relative.to_str().unwrap()).run().await;
+    }
+}
+
+fn step_context(scenario: &str, index: usize, action: &Action) -> String {
+    format!(
+        "Scenario `{}` step {} ({})",
+        scenario,
+        index + 1,
+        action.name()
+    )
+}
+
+fn error_matches(error: ProtocolError, reason: &str) -> bool {
+    error.message() == reason || format!("{:?}", error) == reason
+}
+
+struct ScenarioRunner {
+    contracts: ProtocolContracts<Wallet>,
+    admin: Wallet,
+    actors: HashMap<String, Wallet>,
+    now: u64,
+    // Latest price of each asset, republished when time advances so it does not go stale
+    prices: HashMap<usize, u64>,
+}
+
+impl ScenarioRunner {
+    async fn run_step(&mut self, step: &Step, context: &str) {
+        match &step.action {
+            Action::SetPrice { asset, price } => {
+                self.asset(*asset, context);
+                self.set_price(*asset, price.0).await;
+            }
+            Action::AdvanceTime { seconds } => self.advance_time(*seconds).await,
+            Action::Expect(expectations) => self.check(expectations, context).await,
+            action => {
+                let result = self.call(action, context).await;
+                match (result, &step.expect_revert) {
+                    (Ok(response), None) => {
+                        assert_events_emitted(&response, &step.expect_events, context)
+                    }
+                    (Err(error), None) => panic!("{} reverted: {:?}", context, error),
+                    (Ok(_), Some(reason)) => {
+                        panic!(
+                            "{} succeeded but was expected to revert with `{}`",
+                            context, reason
+                        )
+                    }
+                    (Err(error), Some(reason)) => {
+                        let decoded = ProtocolError::from_error(&error);
+                        assert!(
+                            decoded.is_some_and(|decoded| error_matches(decoded, reason)),
+                            "{} reverted with {:?}, expected `{}`",
+                            context,
+                            decoded.map_or_else(|| error.to_string(), |e| e.to_string()),
+                            reason
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    async fn call(
+        &self,
+        action: &Action,
+        context: &str,
+    ) -> std::result::Result<CallResponse<()>, Error> {
+        let zero = Identity::Address(Address::zeroed());
+
+        match action {
+            Action::Mint {
+                actor,
+                asset,
+                amount,
+            } => Ok(token_abi::mint_to_id(
+                &self.asset(*asset, context).asset,
+                amount.0,
+                self.identity(actor, context),
+            )
+            .await),
+            Action::OpenTrove {
+                actor,
+                asset,
+                collateral,
+                debt,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                borrow_operations_abi::open_trove(
+                    &self.borrow_operations(actor, context),
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &asset_contracts.asset,
+                    &self.contracts.usdm,
+                    &self.contracts.fpt_staking,
+                    &self.contracts.sorted_troves,
+                    &asset_contracts.trove_manager,
+                    &self.contracts.active_pool,
+                    collateral.0,
+                    debt.0,
+                    zero.clone(),
+                    zero,
+                )
+                .await
+            }
+            Action::AddColl {
+                actor,
+                asset,
+                amount,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                borrow_operations_abi::add_coll(
+                    &self.borrow_operations(actor, context),
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &asset_contracts.asset,
+                    &self.contracts.usdm,
+                    &self.contracts.sorted_troves,
+                    &asset_contracts.trove_manager,
+                    &self.contracts.active_pool,
+                    amount.0,
+                    zero.clone(),
+                    zero,
+                )
+                .await
+            }
+            Action::WithdrawColl {
+                actor,
+                asset,
+                amount,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                borrow_operations_abi::withdraw_coll(
+                    &self.borrow_operations(actor, context),
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &asset_contracts.asset,
+                    &self.contracts.sorted_troves,
+                    &asset_contracts.trove_manager,
+                    &self.contracts.active_pool,
+                    amount.0,
+                    zero.clone(),
+                    zero,
+                )
+                .await
+            }
+            Action::WithdrawUsdm {
+                actor,
+                asset,
+                amount,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                borrow_operations_abi::withdraw_usdm(
+                    &self.borrow_operations(actor, context),
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &asset_contracts.asset,
+                    &self.contracts.usdm,
+                    &self.contracts.fpt_staking,
+                    &self.contracts.sorted_troves,
+                    &asset_contracts.trove_manager,
+                    &self.contracts.active_pool,
+                    amount.0,
+                    zero.clone(),
+                    zero,
+                )
+                .await
+            }
+            Action::RepayUsdm {
+                actor,
+                asset,
+                amount,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                borrow_operations_abi::repay_usdm(
+                    &self.borrow_operations(actor, context),
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &asset_contracts.asset,
+                    &self.contracts.usdm,
+                    &self.contracts.sorted_troves,
+                    &asset_contracts.trove_manager,
+                    &self.contracts.active_pool,
+                    &self.contracts.default_pool,
+                    amount.0,
+                    zero.clone(),
+                    zero,
+                )
+                .await
+            }
+            Action::CloseTrove {
+                actor,
+                asset,
+                amount,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                let amount = match amount {
+                    Some(amount) => amount.0,
+                    None => {
+                        trove_manager_abi::get_trove_debt(
+                            &asset_contracts.trove_manager,
+                            self.identity(actor, context),
+                        )
+                        .await
+                        .value
+                    }
+                };
+                borrow_operations_abi::close_trove(
+                    &self.borrow_operations(actor, context),
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &asset_contracts.asset,
+                    &self.contracts.usdm,
+                    &self.contracts.fpt_staking,
+                    &self.contracts.sorted_troves,
+                    &asset_contracts.trove_manager,
+                    &self.contracts.active_pool,
+                    amount,
+                )
+                .await
+            }
+            Action::ProvideToStabilityPool { actor, amount } => {
+                stability_pool_abi::provide_to_stability_pool(
+                    &self.stability_pool(actor, context),
+                    &self.contracts.community_issuance,
+                    &self.contracts.usdm,
+                    &self.contracts.asset_contracts[0].asset,
+                    amount.0,
+                )
+                .await
+            }
+            Action::WithdrawFromStabilityPool {
+                actor,
+                asset,
+                amount,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                stability_pool_abi::withdraw_from_stability_pool(
+                    &self.stability_pool(actor, context),
+                    &self.contracts.community_issuance,
+                    &self.contracts.usdm,
+                    &asset_contracts.asset,
+                    &self.contracts.sorted_troves,
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &asset_contracts.trove_manager,
+                    amount.0,
+                )
+                .await
+            }
+            Action::Liquidate {
+                actor,
+                asset,
+                borrower,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                trove_manager_abi::liquidate(
+                    &self.trove_manager(actor, asset_contracts, context),
+                    &self.contracts.community_issuance,
+                    &self.contracts.stability_pool,
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &self.contracts.sorted_troves,
+                    &self.contracts.active_pool,
+                    &self.contracts.default_pool,
+                    &self.contracts.coll_surplus_pool,
+                    &self.contracts.usdm,
+                    self.identity(borrower, context),
+                    zero.clone(),
+                    zero,
+                )
+                .await
+            }
+            Action::BatchLiquidate {
+                actor,
+                asset,
+                borrowers,
+            } => {
+                let asset_contracts = self.asset(*asset, context);
+                trove_manager_abi::batch_liquidate_troves(
+                    &self.trove_manager(actor, asset_contracts, context),
+                    &self.contracts.community_issuance,
+                    &self.contracts.stability_pool,
+                    &asset_contracts.oracle,
+                    &asset_contracts.mock_pyth_oracle,
+                    &asset_contracts.mock_redstone_oracle,
+                    &self.contracts.sorted_troves,
+                    &self.contracts.active_pool,
+                    &self.contracts.default_pool,
+                    &self.contracts.coll_surplus_pool,
+                    &self.contracts.usdm,
+                    borrowers
+                        .iter()
+                        .map(|borrower| self.identity(borrower, context))
+                        .collect(),
+                    zero.clone(),
+                    zero,
+                )
+                .await
+            }
+            Action::Redeem {
+                actor,
+                amount,
+                max_iterations,
+            } => Ok(protocol_manager_abi::redeem_collateral(
+                &self.protocol_manager(actor, context),
+                amount.0,
+                *max_iterations,
+                0,
+                None,
+                None,
+                &self.contracts.usdm,
+                &self.contracts.fpt_staking,
+                &self.contracts.coll_surplus_pool,
+                &self.contracts.default_pool,
+                &self.contracts.active_pool,
+                &self.contracts.sorted_troves,
+                &self.contracts.asset_contracts,
+            )
+            .await),
+            Action::ClaimCollateral { actor, asset } => Ok(borrow_operations_abi::claim_coll(
+                &self.borrow_operations(actor, context),
+                &self.contracts.active_pool,
+                &self.contracts.coll_surplus_pool,
+                self.asset(*asset, context).asset_id,
+            )
+            .await),
+            Action::SetPrice { .. } | Action::AdvanceTime { .. } | Action::Expect(_) => {
+                unreachable!("{} is not a protocol call", context)
+            }
+        }
+    }
+
+    async fn set_price(&mut self, asset: usize, price: u64) {
+        let asset_contracts = &self.contracts.asset_contracts[asset];
+        oracle_abi::set_debug_timestamp(&asset_contracts.oracle, self.now).await;
+        publish_price(asset_contracts, PriceSource::Pyth, price, self.now).await;
+        self.prices.insert(asset, price);
+    }
+
+    async fn advance_time(&mut self, seconds: u64) {
+        self.now += seconds;
+        community_issuance_abi::set_current_time(&self.contracts.community_issuance, self.now)
+            .await;
+        protocol_manager_abi::set_current_time(&self.contracts.protocol_manager, self.now).await;
+
+        for (asset, asset_contracts) in self.contracts.asset_contracts.iter().enumerate() {
+            oracle_abi::set_debug_timestamp(&asset_contracts.oracle, self.now).await;
+            if let Some(price) = self.prices.get(&asset) {
+                publish_price(asset_contracts, PriceSource::Pyth, *price, self.now).await;
+            }
+        }
+    }
+
+    async fn check(&self, expectations: &Expectations, context: &str) {
+        for trove in &expectations.troves {
+            let asset_contracts = self.asset(trove.asset, context);
+            let id = self.identity(&trove.actor, context);
+            let what = format!("{}: trove of `{}`", context, trove.actor);
+
+            if let Some(status) = trove.status {
+                let actual =
+                    trove_manager_abi::get_trove_status(&asset_contracts.trove_manager, id.clone())
+                        .await
+                        .unwrap()
+                        .value;
+                assert_eq!(
+                    actual,
+                    Status::from(status),
+                    "{} has the wrong status",
+                    what
+                );
+            }
+            if let Some(coll) = trove.coll {
+                let actual =
+                    trove_manager_abi::get_trove_coll(&asset_contracts.trove_manager, id.clone())
+                        .await
+                        .value;
+                assert_amount(actual, coll, trove.tolerance, &format!("{} coll", what));
+            }
+            if let Some(debt) = trove.debt {
+                let actual =
+                    trove_manager_abi::get_trove_debt(&asset_contracts.trove_manager, id.clone())
+                        .await
+                        .value;
+                assert_amount(actual, debt, trove.tolerance, &format!("{} debt", what));
+            }
+            if let Some(surplus) = trove.surplus {
+                let actual = coll_surplus_pool_abi::get_collateral(
+                    &self.contracts.coll_surplus_pool,
+                    id,
+                    asset_contracts.asset_id,
+                )
+                .await
+                .unwrap()
+                .value;
+                assert_amount(
+                    actual,
+                    surplus,
+                    trove.tolerance,
+                    &format!("{} surplus", what),
+                );
+            }
+        }
+
+        for balance in &expectations.balances {
+            let wallet = self.wallet(&balance.actor, context);
+            let asset_id: AssetId = match balance.token {
+                ScenarioToken::Usdm => self.contracts.usdm_asset_id,
+                ScenarioToken::Fpt => self.contracts.fpt_asset_id,
+                ScenarioToken::Asset => self.asset(balance.asset, context).asset_id,
+            };
+            let actual = self
+                .admin
+                .provider()
+                .get_asset_balance(wallet.address(), asset_id)
+                .await
+                .unwrap();
+            assert_amount(
+                actual,
+                balance.amount,
+                balance.tolerance,
+                &format!(
+                    "{}: {:?} balance of `{}`",
+                    context, balance.token, balance.actor
+                ),
+            );
+        }
+
+        for pool in &expectations.pools {
+            let asset_id = self.asset(pool.asset, context).asset_id;
+            let what = format!("{}: asset {}", context, pool.asset);
+
+            if let Some(coll) = pool.active_pool_coll {
+                let actual = active_pool_abi::get_asset(&self.contracts.active_pool, asset_id)
+                    .await
+                    .value;
+                assert_amount(
+                    actual,
+                    coll,
+                    pool.tolerance,
+                    &format!("{} ActivePool coll", what),
+                );
+            }
+            if let Some(debt) = pool.active_pool_debt {
+                let actual = active_pool_abi::get_usdm_debt(&self.contracts.active_pool, asset_id)
+                    .await
+                    .value;
+                assert_amount(
+                    actual,
+                    debt,
+                    pool.tolerance,
+                    &format!("{} ActivePool debt", what),
+                );
+            }
+            if let Some(coll) = pool.default_pool_coll {
+                let actual = default_pool_abi::get_asset(&self.contracts.default_pool, asset_id)
+                    .await
+                    .value;
+                assert_amount(
+                    actual,
+                    coll,
+                    pool.tolerance,
+                    &format!("{} DefaultPool coll", what),
+                );
+            }
+            if let Some(debt) = pool.default_pool_debt {
+                let actual =
+                    default_pool_abi::get_usdm_debt(&self.contracts.default_pool, asset_id)
+                        .await
+                        .value;
+                assert_amount(
+                    actual,
+                    debt,
+                    pool.tolerance,
+                    &format!("{} DefaultPool debt", what),
+                );
+            }
+        }
+
+        if let Some(stability_pool) = &expectations.stability_pool {
+            let tolerance = stability_pool.tolerance;
+
+            if let Some(total) = stability_pool.total_usdm {
+                let actual =
+                    stability_pool_abi::get_total_usdm_deposits(&self.contracts.stability_pool)
+                        .await
+                        .unwrap()
+                        .value;
+                assert_amount(
+                    actual,
+                    total,
+                    tolerance,
+                    &format!("{}: StabilityPool USDM", context),
+                );
+            }
+            for asset in &stability_pool.assets {
+                let actual = stability_pool_abi::get_asset(
+                    &self.contracts.stability_pool,
+                    self.asset(asset.asset, context).asset_id,
+                )
+                .await
+                .unwrap()
+                .value;
+                assert_amount(
+                    actual,
+                    asset.amount,
+                    tolerance,
+                    &format!("{}: StabilityPool asset {}", context, asset.asset),
+                );
+            }
+            for deposit in &stability_pool.deposits {
+                let id = self.identity(&deposit.actor, context);
+                let what = format!("{}: StabilityPool deposit of `{}`", context, deposit.actor);
+
+                if let Some(usdm) = deposit.usdm {
+                    let actual = stability_pool_abi::get_compounded_usdm_deposit(
+                        &self.contracts.stability_pool,
+                        id.clone(),
+                    )
+                    .await
+                    .unwrap()
+                    .value;
+                    assert_amount(actual, usdm, tolerance, &what);
+                }
+                for gain in &deposit.gains {
+                    let actual = stability_pool_abi::get_depositor_asset_gain(
+                        &self.contracts.stability_pool,
+                        id.clone(),
+                        self.asset(gain.asset, context).asset_id,
+                    )
+                    .await
+                    .unwrap()
+                    .value;
+                    assert_amount(
+                        actual,
+                        gain.amount,
+                        tolerance,
+                        &format!("{} gain of asset {}", what, gain.asset),
+                    );
+                }
+            }
+        }
+
+        if expectations.invariants {
+            let accounts: Vec<Identity> = self
+                .actors
+                .values()
+                .map(|wallet| Identity::Address(wallet.address().into()))
+                .collect();
+            check_invariants_with_accounts(&self.contracts, &accounts).await;
+        }
+    }
+
+    fn asset(&self, index: usize, context: &str) -> &AssetContracts<Wallet> {
+        self.contracts
+            .asset_contracts
+            .get(index)
+            .unwrap_or_else(|| panic!("{}: no asset at index {}", context, index))
+    }
+
+    fn wallet(&self, actor: &str, context: &str) -> &Wallet {
+        self.actors
+            .get(actor)
+            .unwrap_or_else(|| panic!("{}: unknown actor `{}`", context, actor))
+    }
+
+    fn identity(&self, actor: &str, context: &str) -> Identity {
+        Identity::Address(self.wallet(actor, context).address().into())
+    }
+
+    fn borrow_operations(
+        &self,
+        actor: &str,
+        context: &str,
+    ) -> ContractInstance<BorrowOperations<Wallet>> {
+        ContractInstance::new(
+            BorrowOperations::new(
+                self.contracts
+                    .borrow_operations
+                    .contract
+                    .contract_id()
+                    .clone(),
+                self.wallet(actor, context).clone(),
+            ),
+            self.contracts.borrow_operations.implementation_id,
+        )
+    }
+
+    fn stability_pool(
+        &self,
+        actor: &str,
+        context: &str,
+    ) -> ContractInstance<StabilityPool<Wallet>> {
+        ContractInstance::new(
+            StabilityPool::new(
+                self.contracts.stability_pool.contract.contract_id().clone(),
+                self.wallet(actor, context).clone(),
+            ),
+            self.contracts.stability_pool.implementation_id,
+        )
+    }
+
+    fn protocol_manager(
+        &self,
+        actor: &str,
+        context: &str,
+    ) -> ContractInstance<ProtocolManager<Wallet>> {
+        ContractInstance::new(
+            ProtocolManager::new(
+                self.contracts
+                    .protocol_manager
+                    .contract
+                    .contract_id()
+                    .clone(),
+                self.wallet(actor, context).clone(),
+            ),
+            self.contracts.protocol_manager.implementation_id,
+        )
+    }
+
+    fn trove_manager(
+        &self,
+        actor: &str,
+        asset_contracts: &AssetContracts<Wallet>,
+        context: &str,
+    ) -> ContractInstance<TroveManagerContract<Wallet>> {
+        ContractInstance::new(
+            TroveManagerContract::new(
+                asset_contracts.trove_manager.contract.contract_id().clone(),
+                self.wallet(actor, context).clone(),
+            ),
+            asset_contracts.trove_manager.implementation_id,
+        )
+    }
+}
+
+fn assert_amount(actual: u64, expected: Amount, tolerance: Amount, what: &str) {
+    assert!(
+        actual.abs_diff(expected.0) <= tolerance.0,
+        "{} is {}, expected {} (tolerance {})",
+        what,
+        Amount(actual),
+        expected,
+        tolerance
+    );
+}
+
+fn assert_events_emitted(response: &CallResponse<()>, expected: &[String], context: &str) {
+    let emitted = event_names(response);
+    let mut remaining = emitted.iter();
+    for event in expected {
+        assert!(
+            remaining.any(|name| name == event),
+            "{}: expected events {:?} in this order, emitted {:?}",
+            context,
+            expected,
+            emitted
+        );
+    }
+}
@@ -4,6 +4,7 @@ use super::interfaces::{
     coll_surplus_pool::{CollSurplusPool, CollSurplusPoolConfigurables},
     community_issuance::{CommunityIssuance, CommunityIssuanceConfigurables},
     default_pool::{DefaultPool, DefaultPoolConfigurables},
+    flash_mint_receiver::MockFlashMintReceiver,
     fpt_staking::{FPTStaking, FPTStakingConfigurables},
     fpt_token::{FPTToken, FPTTokenConfigurables},
     hint_helper::HintHelper,
@@ -25,9 +26,10 @@ use fuels::prelude::{Contract, TxPolicies};
 pub mod common {
     use super::*;
     use crate::{
+        artifacts::get_artifact_path,
         data_structures::{
-            AssetContracts, AssetContractsOptionalOracles, ContractInstance,
-            ExistingAssetContracts, ProtocolContracts, RedstoneConfig, PRECISION,
+            default_risk_parameters, AssetContracts, AssetContractsOptionalOracles,
+            ContractInstance, ExistingAssetContracts, ProtocolContracts, RedstoneConfig, PRECISION,
         },
         interfaces::{
             active_pool::active_pool_abi,
@@ -50,14 +52,17 @@ pub mod common {
             usdm_token::usdm_token_abi,
         },
         paths::*,
+        snapshot,
     };
     use fuels::{
         // accounts::rand::{self, Rng},
+        accounts::ViewOnlyAccount,
         prelude::*,
         programs::responses::CallResponse,
         tx::StorageSlot,
         types::{Bits256, Bytes32, ContractId, Identity, U256},
     };
+    use moor_sdk::{deployment::RedstoneDeployment, AssetDeployment, Deployment};
     use pbr::ProgressBar;
     // use pbr::ProgressBar;
     use rand::Rng;
@@ -68,6 +73,16 @@ pub mod common {
         deploy_2nd_asset: bool,
         use_test_fpt: bool,
     ) -> (ProtocolContracts<Wallet>, Wallet, Vec<Wallet>) {
+        // Boot from a cached snapshot of the deployed protocol when possible, deploying from scratch is slow
+        if snapshot::snapshots_enabled() {
+            if let Some(setup) =
+                snapshot::setup_protocol_from_snapshot(num_wallets, deploy_2nd_asset, use_test_fpt)
+                    .await
+            {
+                return setup;
+            }
+        }
+
         // Launch a local network and deploy the contract
         let mut wallets = launch_custom_provider_and_get_wallets(
             WalletsConfig::new(
@@ -82,13 +97,23 @@ pub mod common {
         .unwrap();
         let wallet = wallets.pop().unwrap();
 
-        let mut contracts = deploy_core_contracts(&wallet, use_test_fpt, false).await;
-        initialize_core_contracts(&mut contracts, &wallet, use_test_fpt, true, false).await;
+        let contracts = deploy_protocol(&wallet, deploy_2nd_asset, use_test_fpt).await;
+
+        (contracts, wallet, wallets)
+    }
+
+    pub async fn deploy_protocol<T: Account + Clone>(
+        wallet: &T,
+        deploy_2nd_asset: bool,
+        use_test_fpt: bool,
+    ) -> ProtocolContracts<T> {
+        let mut contracts = deploy_core_contracts(wallet, use_test_fpt, false).await;
+        initialize_core_contracts(&mut contracts, wallet, use_test_fpt, true, false).await;
 
         // Add the first asset (Fuel)
         let mock_asset_contracts = add_asset(
             &mut contracts,
-            &wallet,
+            wallet,
             "MOCK".to_string(),
             "MCK".to_string(),
         )
@@ -99,7 +124,7 @@ pub mod common {
         if deploy_2nd_asset {
             let rock_asset_contracts = add_asset(
                 &mut contracts,
-                &wallet,
+                wallet,
                 "ROCK".to_string(),
                 "RCK".to_string(),
             )
@@ -107,14 +132,14 @@ pub mod common {
             contracts.asset_contracts.push(rock_asset_contracts);
         }
 
-        (contracts, wallet, wallets)
+        contracts
     }
 
-    pub async fn deploy_core_contracts(
-        wallet: &Wallet,
+    pub async fn deploy_core_contracts<T: Account + Clone>(
+        wallet: &T,
         use_test_fpt: bool,
         verbose: bool,
-    ) -> ProtocolContracts<Wallet> {
+    ) -> ProtocolContracts<T> {
         println!("Deploying core contracts...");
         let mut pb = ProgressBar::new(13);
 
@@ -200,9 +225,9 @@ pub mod common {
         }
     }
 
-    pub async fn initialize_core_contracts(
-        contracts: &mut ProtocolContracts<Wallet>,
-        wallet: &Wallet,
+    pub async fn initialize_core_contracts<T: Account + Clone>(
+        contracts: &mut ProtocolContracts<T>,
+        wallet: &T,
         use_test_fpt: bool,
         debug: bool,
         verbose: bool,
@@ -298,6 +323,7 @@ pub mod common {
             contracts.active_pool.contract.contract_id().into(),
             contracts.sorted_troves.contract.contract_id().into(),
             Identity::Address(wallet.address().into()),
+            debug,
         )
         .await;
         if verbose {
@@ -352,7 +378,9 @@ pub mod common {
         }
     }
 
-    async fn deploy_test_fpt_token(wallet: &Wallet) -> ContractInstance<FPTToken<Wallet>> {
+    async fn deploy_test_fpt_token<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<FPTToken<T>> {
         let mock_fpt_token = deploy_token(wallet).await;
 
         token_abi::initialize(
@@ -371,13 +399,13 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_token(wallet: &Wallet) -> Token<Wallet> {
+    pub async fn deploy_token<T: Account + Clone>(wallet: &T) -> Token<T> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
 
         let res = Contract::load_from(
-            &get_absolute_path_from_relative(TOKEN_CONTRACT_BINARY_PATH),
+            &get_artifact_path(TOKEN_CONTRACT_BINARY_PATH),
             LoadConfiguration::default().with_salt(salt),
         )
         .unwrap()
@@ -389,7 +417,7 @@ pub mod common {
             Err(_) => {
                 wait();
                 let id = Contract::load_from(
-                    &get_absolute_path_from_relative(TOKEN_CONTRACT_BINARY_PATH),
+                    &get_artifact_path(TOKEN_CONTRACT_BINARY_PATH),
                     LoadConfiguration::default().with_salt(salt),
                 )
                 .unwrap()
@@ -403,7 +431,7 @@ pub mod common {
         }
     }
 
-    pub async fn deploy_fpt_token(wallet: &Wallet) -> ContractInstance<FPTToken<Wallet>> {
+    pub async fn deploy_fpt_token<T: Account + Clone>(wallet: &T) -> ContractInstance<FPTToken<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -414,7 +442,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(FPT_TOKEN_CONTRACT_BINARY_PATH),
+            &get_artifact_path(FPT_TOKEN_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_configurables(configurables.clone())
                 .with_salt(salt),
@@ -438,7 +466,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_sorted_troves(wallet: &Wallet) -> ContractInstance<SortedTroves<Wallet>> {
+    pub async fn deploy_sorted_troves<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<SortedTroves<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -449,7 +479,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(SORTED_TROVES_CONTRACT_BINARY_PATH),
+            &get_artifact_path(SORTED_TROVES_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_configurables(configurables.clone())
                 .with_salt(salt),
@@ -473,9 +503,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_trove_manager_contract(
-        wallet: &Wallet,
-    ) -> ContractInstance<TroveManagerContract<Wallet>> {
+    pub async fn deploy_trove_manager_contract<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<TroveManagerContract<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -486,7 +516,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(TROVE_MANAGER_CONTRACT_BINARY_PATH),
+            &get_artifact_path(TROVE_MANAGER_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_configurables(configurables.clone())
                 .with_salt(salt),
@@ -510,10 +540,10 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_vesting_contract(
-        wallet: &Wallet,
+    pub async fn deploy_vesting_contract<T: Account + Clone>(
+        wallet: &T,
         total_amount: u64,
-    ) -> ContractInstance<VestingContract<Wallet>> {
+    ) -> ContractInstance<VestingContract<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -526,7 +556,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(VESTING_CONTRACT_BINARY_PATH),
+            &get_artifact_path(VESTING_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_configurables(configurables.clone())
                 .with_salt(salt),
@@ -550,13 +580,13 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_mock_stork_oracle(wallet: &Wallet) -> StorkCore<Wallet> {
+    pub async fn deploy_mock_stork_oracle<T: Account + Clone>(wallet: &T) -> StorkCore<T> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
 
         let res = Contract::load_from(
-            &get_absolute_path_from_relative(STORK_ORACLE_CONTRACT_BINARY_PATH),
+            &get_artifact_path(STORK_ORACLE_CONTRACT_BINARY_PATH),
             LoadConfiguration::default().with_salt(salt),
         )
         .unwrap()
@@ -569,7 +599,7 @@ pub mod common {
             }
             Err(_) => {
                 let id = Contract::load_from(
-                    &get_absolute_path_from_relative(STORK_ORACLE_CONTRACT_BINARY_PATH),
+                    &get_artifact_path(STORK_ORACLE_CONTRACT_BINARY_PATH),
                     LoadConfiguration::default().with_salt(salt),
                 )
                 .unwrap()
@@ -583,13 +613,13 @@ pub mod common {
         }
     }
 
-    pub async fn deploy_mock_pyth_oracle(wallet: &Wallet) -> PythCore<Wallet> {
+    pub async fn deploy_mock_pyth_oracle<T: Account + Clone>(wallet: &T) -> PythCore<T> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
 
         let res = Contract::load_from(
-            &get_absolute_path_from_relative(PYTH_ORACLE_CONTRACT_BINARY_PATH),
+            &get_artifact_path(PYTH_ORACLE_CONTRACT_BINARY_PATH),
             LoadConfiguration::default().with_salt(salt),
         )
         .unwrap()
@@ -602,7 +632,7 @@ pub mod common {
             }
             Err(_) => {
                 let id = Contract::load_from(
-                    &get_absolute_path_from_relative(PYTH_ORACLE_CONTRACT_BINARY_PATH),
+                    &get_artifact_path(PYTH_ORACLE_CONTRACT_BINARY_PATH),
                     LoadConfiguration::default().with_salt(salt),
                 )
                 .unwrap()
@@ -616,13 +646,13 @@ pub mod common {
         }
     }
 
-    pub async fn deploy_mock_redstone_oracle(wallet: &Wallet) -> RedstoneCore<Wallet> {
+    pub async fn deploy_mock_redstone_oracle<T: Account + Clone>(wallet: &T) -> RedstoneCore<T> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
 
         let res = Contract::load_from(
-            &get_absolute_path_from_relative(REDSTONE_ORACLE_CONTRACT_BINARY_PATH),
+            &get_artifact_path(REDSTONE_ORACLE_CONTRACT_BINARY_PATH),
             LoadConfiguration::default().with_salt(salt),
         )
         .unwrap()
@@ -635,7 +665,7 @@ pub mod common {
             }
             Err(_) => {
                 let id = Contract::load_from(
-                    &get_absolute_path_from_relative(REDSTONE_ORACLE_CONTRACT_BINARY_PATH),
+                    &get_artifact_path(REDSTONE_ORACLE_CONTRACT_BINARY_PATH),
                     LoadConfiguration::default().with_salt(salt),
                 )
                 .unwrap()
@@ -649,12 +679,12 @@ pub mod common {
         }
     }
 
-    pub async fn deploy_oracle(
-        wallet: &Wallet,
+    pub async fn deploy_oracle<T: Account + Clone>(
+        wallet: &T,
         fuel_vm_decimals: u32,
         debug: bool,
         initializer: Identity,
-    ) -> ContractInstance<Oracle<Wallet>> {
+    ) -> ContractInstance<Oracle<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -668,7 +698,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(ORACLE_CONTRACT_BINARY_PATH),
+            &get_artifact_path(ORACLE_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -689,9 +719,9 @@ pub mod common {
         ContractInstance::new(Oracle::new(proxy.contract_id(), wallet.clone()), id.into())
     }
 
-    pub async fn deploy_protocol_manager(
-        wallet: &Wallet,
-    ) -> ContractInstance<ProtocolManager<Wallet>> {
+    pub async fn deploy_protocol_manager<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<ProtocolManager<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -702,7 +732,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(PROTCOL_MANAGER_CONTRACT_BINARY_PATH),
+            &get_artifact_path(PROTCOL_MANAGER_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -726,9 +756,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_borrow_operations(
-        wallet: &Wallet,
-    ) -> ContractInstance<BorrowOperations<Wallet>> {
+    pub async fn deploy_borrow_operations<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<BorrowOperations<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -739,7 +769,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(BORROW_OPERATIONS_CONTRACT_BINARY_PATH),
+            &get_artifact_path(BORROW_OPERATIONS_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_configurables(configurables.clone())
                 .with_salt(salt),
@@ -779,14 +809,14 @@ pub mod common {
             .to_string()
     }
 
-    pub async fn deploy_asset_contracts(
-        wallet: &Wallet,
+    pub async fn deploy_asset_contracts<T: Account + Clone>(
+        wallet: &T,
         existing_contracts: &ExistingAssetContracts,
         debug: bool,
         deploy_stork: bool,
         deploy_pyth: bool,
         deploy_redstone: bool,
-    ) -> AssetContractsOptionalOracles<Wallet> {
+    ) -> AssetContractsOptionalOracles<T> {
         println!("Deploying asset contracts...");
         let mut pb = ProgressBar::new(6);
 
@@ -802,7 +832,7 @@ pub mod common {
                 )
             }
             None => {
-                let asset = deploy_token(&wallet).await;
+                let asset = deploy_token(wallet).await;
                 pb.inc();
                 let asset_id: AssetId = asset
                     .contract_id()
@@ -841,7 +871,7 @@ pub mod common {
             }
             None => {
                 if deploy_stork {
-                    let stork = deploy_mock_stork_oracle(&wallet).await;
+                    let stork = deploy_mock_stork_oracle(wallet).await;
                     let stork_feed_id = Bits256::from(asset_id);
                     pb.inc();
                     stork_oracle_abi::set_temporal_value(
@@ -869,7 +899,7 @@ pub mod common {
             }
             None => {
                 if deploy_pyth {
-                    let pyth = deploy_mock_pyth_oracle(&wallet).await;
+                    let pyth = deploy_mock_pyth_oracle(wallet).await;
                     let pyth_price_id = Bits256::from(asset_id);
                     pb.inc();
                     let pyth_feed = vec![(
@@ -901,7 +931,7 @@ pub mod common {
             }
             None => {
                 if deploy_redstone {
-                    let redstone = deploy_mock_redstone_oracle(&wallet).await;
+                    let redstone = deploy_mock_redstone_oracle(wallet).await;
                     let redstone_price_id = U256::from(rand::thread_rng().gen_range(1..1_000_000));
                     let redstone_feed = redstone_price_feed_with_id(redstone_price_id, vec![1]);
                     redstone_oracle_abi::write_prices(&redstone, redstone_feed).await;
@@ -920,7 +950,7 @@ pub mod common {
 
         // Always deploy a new oracle and trove manager
         let oracle = deploy_oracle(
-            &wallet,
+            wallet,
             fuel_vm_decimals,
             debug,
             Identity::Address(wallet.address().into()),
@@ -930,7 +960,7 @@ pub mod common {
 
         let _ = oracle_abi::initialize(&oracle, None, None, None).await;
 
-        let trove_manager = deploy_trove_manager_contract(&wallet).await;
+        let trove_manager = deploy_trove_manager_contract(wallet).await;
         pb.inc();
 
         // Set up price feeds if we deployed new oracles
@@ -970,6 +1000,11 @@ pub mod common {
             None => println!("No Redstone Oracle"),
         }
         println!("Fuel VM Decimals: {}", fuel_vm_decimals);
+        let risk_parameters = existing_contracts
+            .risk_parameters
+            .clone()
+            .unwrap_or_else(default_risk_parameters);
+        println!("Risk Parameters: {:?}", risk_parameters);
 
         AssetContractsOptionalOracles {
             symbol: existing_contracts.symbol.clone(),
@@ -983,15 +1018,16 @@ pub mod common {
             pyth_price_id,
             stork_feed_id,
             fuel_vm_decimals,
+            risk_parameters,
         }
     }
 
-    pub async fn add_asset(
-        contracts: &mut ProtocolContracts<Wallet>,
-        wallet: &Wallet,
+    pub async fn add_asset<T: Account + Clone>(
+        contracts: &mut ProtocolContracts<T>,
+        wallet: &T,
         name: String,
         symbol: String,
-    ) -> AssetContracts<Wallet> {
+    ) -> AssetContracts<T> {
         // Deploy oracle contracts
         let stork = deploy_mock_stork_oracle(wallet).await;
         let pyth = deploy_mock_pyth_oracle(wallet).await;
@@ -1064,6 +1100,7 @@ pub mod common {
                 .into(),
             trove_manager.contract.contract_id().into(),
             oracle.contract.contract_id().into(),
+            default_risk_parameters(),
             &contracts.borrow_operations,
             &contracts.stability_pool,
             &contracts.usdm,
@@ -1154,6 +1191,7 @@ pub mod common {
             asset_contracts.asset_id,
             asset_contracts.trove_manager.contract.contract_id().into(),
             asset_contracts.oracle.contract.contract_id().into(),
+            asset_contracts.risk_parameters.clone(),
             &core_protocol_contracts.borrow_operations,
             &core_protocol_contracts.stability_pool,
             &core_protocol_contracts.usdm,
@@ -1166,7 +1204,9 @@ pub mod common {
         .await
     }
 
-    pub async fn deploy_active_pool(wallet: &Wallet) -> ContractInstance<ActivePool<Wallet>> {
+    pub async fn deploy_active_pool<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<ActivePool<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -1177,7 +1217,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(ACTIVE_POOL_CONTRACT_BINARY_PATH),
+            &get_artifact_path(ACTIVE_POOL_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_configurables(configurables.clone())
                 .with_salt(salt),
@@ -1201,7 +1241,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_stability_pool(wallet: &Wallet) -> ContractInstance<StabilityPool<Wallet>> {
+    pub async fn deploy_stability_pool<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<StabilityPool<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -1212,7 +1254,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(STABILITY_POOL_CONTRACT_BINARY_PATH),
+            &get_artifact_path(STABILITY_POOL_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -1236,7 +1278,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_default_pool(wallet: &Wallet) -> ContractInstance<DefaultPool<Wallet>> {
+    pub async fn deploy_default_pool<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<DefaultPool<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -1247,7 +1291,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(DEFAULT_POOL_CONTRACT_BINARY_PATH),
+            &get_artifact_path(DEFAULT_POOL_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -1271,9 +1315,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_coll_surplus_pool(
-        wallet: &Wallet,
-    ) -> ContractInstance<CollSurplusPool<Wallet>> {
+    pub async fn deploy_coll_surplus_pool<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<CollSurplusPool<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -1284,7 +1328,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(COLL_SURPLUS_POOL_CONTRACT_BINARY_PATH),
+            &get_artifact_path(COLL_SURPLUS_POOL_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -1308,9 +1352,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_community_issuance(
-        wallet: &Wallet,
-    ) -> ContractInstance<CommunityIssuance<Wallet>> {
+    pub async fn deploy_community_issuance<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<CommunityIssuance<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -1321,7 +1365,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(COMMUNITY_ISSUANCE_CONTRACT_BINARY_PATH),
+            &get_artifact_path(COMMUNITY_ISSUANCE_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -1345,7 +1389,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_fpt_staking(wallet: &Wallet) -> ContractInstance<FPTStaking<Wallet>> {
+    pub async fn deploy_fpt_staking<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<FPTStaking<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -1356,7 +1402,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(FPT_STAKING_CONTRACT_BINARY_PATH),
+            &get_artifact_path(FPT_STAKING_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -1380,7 +1426,9 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_usdm_token(wallet: &Wallet) -> ContractInstance<USDMToken<Wallet>> {
+    pub async fn deploy_usdm_token<T: Account + Clone>(
+        wallet: &T,
+    ) -> ContractInstance<USDMToken<T>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
         let tx_policies = TxPolicies::default().with_tip(1);
@@ -1391,7 +1439,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(USDM_TOKEN_CONTRACT_BINARY_PATH),
+            &get_artifact_path(USDM_TOKEN_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -1415,12 +1463,12 @@ pub mod common {
         )
     }
 
-    pub async fn deploy_hint_helper(wallet: &Wallet) -> HintHelper<Wallet> {
+    pub async fn deploy_hint_helper<T: Account + Clone>(wallet: &T) -> HintHelper<T> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(HINT_HELPER_CONTRACT_BINARY_PATH),
+            &get_artifact_path(HINT_HELPER_CONTRACT_BINARY_PATH),
             LoadConfiguration::default().with_salt(salt),
         )
         .unwrap()
@@ -1432,10 +1480,29 @@ pub mod common {
         HintHelper::new(id, wallet.clone())
     }
 
-    pub async fn deploy_multi_trove_getter(
-        wallet: &Wallet,
+    pub async fn deploy_flash_mint_receiver<T: Account + Clone>(
+        wallet: &T,
+    ) -> MockFlashMintReceiver<T> {
+        let mut rng = rand::thread_rng();
+        let salt = rng.gen::<[u8; 32]>();
+
+        let id = Contract::load_from(
+            &get_artifact_path(FLASH_MINT_RECEIVER_CONTRACT_BINARY_PATH),
+            LoadConfiguration::default().with_salt(salt),
+        )
+        .unwrap()
+        .deploy(&wallet.clone(), TxPolicies::default().with_tip(1))
+        .await
+        .unwrap()
+        .contract_id;
+
+        MockFlashMintReceiver::new(id, wallet.clone())
+    }
+
+    pub async fn deploy_multi_trove_getter<T: Account + Clone>(
+        wallet: &T,
         sorted_troves_contract_id: &ContractId,
-    ) -> MultiTroveGetter<Wallet> {
+    ) -> MultiTroveGetter<T> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
 
@@ -1444,7 +1511,7 @@ pub mod common {
             .unwrap();
 
         let id = Contract::load_from(
-            &get_absolute_path_from_relative(MULTI_TROVE_GETTER_CONTRACT_BINARY_PATH),
+            &get_artifact_path(MULTI_TROVE_GETTER_CONTRACT_BINARY_PATH),
             LoadConfiguration::default()
                 .with_salt(salt)
                 .with_configurables(configurables.clone()),
@@ -1485,11 +1552,11 @@ pub mod common {
         std::thread::sleep(std::time::Duration::from_secs(12));
     }
 
-    pub async fn deploy_proxy(
+    pub async fn deploy_proxy<T: Account + Clone>(
         target: ContractId,
-        owner: Wallet,
+        owner: T,
         additional_storage_path: Option<&str>,
-    ) -> Proxy<Wallet> {
+    ) -> Proxy<T> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
 
@@ -1554,7 +1621,7 @@ pub mod common {
         let storage_configuration = match additional_storage_path {
             Some(path) => StorageConfiguration::default()
                 .add_slot_overrides(storage_slots)
-                .add_slot_overrides_from_file(get_absolute_path_from_relative(path))
+                .add_slot_overrides_from_file(get_artifact_path(path))
                 .unwrap(),
             None => StorageConfiguration::default().add_slot_overrides(storage_slots),
         };
@@ -1563,7 +1630,7 @@ pub mod common {
             LoadConfiguration::default().with_storage_configuration(storage_configuration);
 
         let contract_id = Contract::load_from(
-            &get_absolute_path_from_relative(PROXY_CONTRACT_BINARY_PATH),
+            &get_artifact_path(PROXY_CONTRACT_BINARY_PATH),
             contract_configuration,
         )
         .unwrap()
@@ -1575,4 +1642,55 @@ pub mod common {
 
         Proxy::new(contract_id, owner)
     }
+
+    // Manifest for a local deployment, as `deploy-scripts` would have written it
+    pub fn deployment_of(contracts: &ProtocolContracts<Wallet>) -> Deployment {
+        Deployment {
+            borrow_operations: contracts.borrow_operations.contract.contract_id().into(),
+            borrow_operations_implementation_id: contracts.borrow_operations.implementation_id,
+            usdm: contracts.usdm.contract.contract_id().into(),
+            usdm_implementation_id: contracts.usdm.implementation_id,
+            usdm_asset_id: contracts.usdm_asset_id,
+            stability_pool: contracts.stability_pool.contract.contract_id().into(),
+            stability_pool_implementation_id: contracts.stability_pool.implementation_id,
+            protocol_manager: contracts.protocol_manager.contract.contract_id().into(),
+            protocol_manager_implementation_id: contracts.protocol_manager.implementation_id,
+            fpt_staking: contracts.fpt_staking.contract.contract_id().into(),
+            fpt_staking_implementation_id: contracts.fpt_staking.implementation_id,
+            fpt_token: contracts.fpt_token.contract.contract_id().into(),
+            fpt_token_implementation_id: contracts.fpt_token.implementation_id,
+            fpt_asset_id: contracts.fpt_asset_id,
+            community_issuance: contracts.community_issuance.contract.contract_id().into(),
+            community_issuance_implementation_id: contracts.community_issuance.implementation_id,
+            coll_surplus_pool: contracts.coll_surplus_pool.contract.contract_id().into(),
+            coll_surplus_pool_implementation_id: contracts.coll_surplus_pool.implementation_id,
+            default_pool: contracts.default_pool.contract.contract_id().into(),
+            default_pool_implementation_id: contracts.default_pool.implementation_id,
+            active_pool: contracts.active_pool.contract.contract_id().into(),
+            active_pool_implementation_id: contracts.active_pool.implementation_id,
+            sorted_troves: contracts.sorted_troves.contract.contract_id().into(),
+            sorted_troves_implementation_id: contracts.sorted_troves.implementation_id,
+            hint_helper: None,
+            multi_trove_getter: None,
+            asset_contracts: contracts
+                .asset_contracts
+                .iter()
+                .map(|asset| AssetDeployment {
+                    symbol: None,
+                    asset_id: asset.asset_id,
+                    asset_contract: asset.asset.contract_id().into(),
+                    oracle: asset.oracle.contract.contract_id().into(),
+                    oracle_implementation_id: asset.oracle.implementation_id,
+                    trove_manager: asset.trove_manager.contract.contract_id().into(),
+                    trove_manager_implementation_id: asset.trove_manager.implementation_id,
+                    pyth_contract: Some(asset.mock_pyth_oracle.contract_id().into()),
+                    stork_contract: Some(asset.mock_stork_oracle.contract_id().into()),
+                    redstone: Some(RedstoneDeployment {
+                        redstone_contract: asset.mock_redstone_oracle.contract_id().into(),
+                    }),
+                    fuel_vm_decimals: Some(asset.fuel_vm_decimals),
+                })
+                .collect(),
+        }
+    }
 }
@@ -0,0 +1,465 @@
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
+
+use fuel_core_chain_config::{ChainConfig, CoinConfig, SnapshotMetadata, StateConfig};
+use fuels::{
+    accounts::signers::private_key::PrivateKeySigner,
+    crypto::{Hasher, SecretKey},
+    prelude::*,
+    test_helpers::{setup_single_asset_coins, DbType, FuelService, NodeConfig},
+    types::{coin::Coin, Bits256, ContractId, U256},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    artifacts::get_artifact_path,
+    data_structures::{AssetContracts, ContractInstance, ProtocolContracts},
+    interfaces::{
+        active_pool::ActivePool, borrow_operations::BorrowOperations,
+        coll_surplus_pool::CollSurplusPool, community_issuance::CommunityIssuance,
+        default_pool::DefaultPool, fpt_staking::FPTStaking, fpt_token::FPTToken, oracle::Oracle,
+        protocol_manager::ProtocolManager, pyth_oracle::PythCore, redstone_oracle::RedstoneCore,
+        sorted_troves::SortedTroves, stability_pool::StabilityPool, stork_oracle::StorkCore,
+        token::Token, trove_manager::TroveManagerContract, usdm_token::USDMToken,
+        vesting::VestingContract,
+    },
+    paths::*,
+    setup::common::{deploy_protocol, get_absolute_path_from_relative},
+};
+
+// Set to disable the snapshot cache and deploy the protocol from scratch in every test
+pub const DISABLE_SNAPSHOT_ENV: &str = "DISABLE_PROTOCOL_SNAPSHOT";
+
+const SNAPSHOT_CACHE_DIR: &str = "target/protocol-snapshots";
+const SNAPSHOT_FORMAT_VERSION: &str = "2";
+const COINS_PER_WALLET: u64 = 1;
+const AMOUNT_PER_COIN: u64 = 1_000_000_000;
+
+// Every artifact that ends up on chain, a change to any of them invalidates the cached snapshots
+const SNAPSHOT_INPUTS: &[&str] = &[
+    TOKEN_CONTRACT_BINARY_PATH,
+    TOKEN_CONTRACT_STORAGE_PATH,
+    TROVE_MANAGER_CONTRACT_BINARY_PATH,
+    TROVE_MANAGER_CONTRACT_STORAGE_PATH,
+    VESTING_CONTRACT_BINARY_PATH,
+    VESTING_CONTRACT_STORAGE_PATH,
+    ORACLE_CONTRACT_BINARY_PATH,
+    ORACLE_CONTRACT_STORAGE_PATH,
+    STORK_ORACLE_CONTRACT_BINARY_PATH,
+    REDSTONE_ORACLE_CONTRACT_BINARY_PATH,
+    PYTH_ORACLE_CONTRACT_BINARY_PATH,
+    BORROW_OPERATIONS_CONTRACT_BINARY_PATH,
+    BORROW_OPERATIONS_CONTRACT_STORAGE_PATH,
+    SORTED_TROVES_CONTRACT_BINARY_PATH,
+    SORTED_TROVES_CONTRACT_STORAGE_PATH,
+    ACTIVE_POOL_CONTRACT_BINARY_PATH,
+    ACTIVE_POOL_CONTRACT_STORAGE_PATH,
+    STABILITY_POOL_CONTRACT_BINARY_PATH,
+    STABILITY_POOL_CONTRACT_STORAGE_PATH,
+    DEFAULT_POOL_CONTRACT_BINARY_PATH,
+    DEFAULT_POOL_CONTRACT_STORAGE_PATH,
+    COLL_SURPLUS_POOL_CONTRACT_BINARY_PATH,
+    COLL_SURPLUS_POOL_CONTRACT_STORAGE_PATH,
+    USDM_TOKEN_CONTRACT_BINARY_PATH,
+    USDM_TOKEN_CONTRACT_STORAGE_PATH,
+    FPT_TOKEN_CONTRACT_BINARY_PATH,
+    FPT_TOKEN_CONTRACT_STORAGE_PATH,
+    FPT_STAKING_CONTRACT_BINARY_PATH,
+    FPT_STAKING_CONTRACT_STORAGE_PATH,
+    PROTCOL_MANAGER_CONTRACT_BINARY_PATH,
+    PROTCOL_MANAGER_CONTRACT_STORAGE_PATH,
+    COMMUNITY_ISSUANCE_CONTRACT_BINARY_PATH,
+    COMMUNITY_ISSUANCE_CONTRACT_STORAGE_PATH,
+    PROXY_CONTRACT_BINARY_PATH,
+    PROXY_CONTRACT_STORAGE_PATH,
+];
+
+// Rust side of the deployment: initialization calls, asset registration and their defaults
+const SNAPSHOT_SETUP_SOURCES: &[&str] = &[
+    include_str!("setup.rs"),
+    include_str!("data_structures.rs"),
+    include_str!("interfaces/protocol_manager.rs"),
+];
+
+pub fn snapshots_enabled() -> bool {
+    env::var(DISABLE_SNAPSHOT_ENV).is_err()
+}
+
+// Boots a fresh node from a snapshot of the canonical protocol deployment, building and caching the
+// snapshot on first use. Returns the same shape as `setup_protocol`: the admin owning every
+// contract, plus `num_wallets - 1` freshly funded wallets.
+//
+// Returns `None` if the snapshot could not be built (e.g. the `fuel-core` binary in PATH lacks
+// the `snapshot` command), callers should then fall back to deploying from scratch. A failed
+// build is not retried for the rest of the process.
+pub async fn setup_protocol_from_snapshot(
+    num_wallets: u64,
+    deploy_2nd_asset: bool,
+    use_test_fpt: bool,
+) -> Option<(ProtocolContracts<Wallet>, Wallet, Vec<Wallet>)> {
+    let snapshot_dir = cached_snapshot_dir(deploy_2nd_asset, use_test_fpt).await?;
+
+    let protocol: ProtocolSnapshot =
+        serde_json::from_str(&fs::read_to_string(snapshot_dir.join("protocol.json")).ok()?).ok()?;
+
+    let metadata = SnapshotMetadata::read(snapshot_dir.join("state")).ok()?;
+    let chain_config = ChainConfig::from_snapshot_metadata(&metadata).ok()?;
+    let mut state_config = StateConfig::from_snapshot_metadata(metadata).ok()?;
+
+    // The admin keeps its remaining coins from the deployment, every other wallet is funded at boot
+    let signers: Vec<PrivateKeySigner> = (1..num_wallets)
+        .map(|_| PrivateKeySigner::new(SecretKey::random(&mut rand::thread_rng())))
+        .collect();
+    for signer in &signers {
+        state_config
+            .coins
+            .extend(coin_configs(setup_single_asset_coins(
+                signer.address(),
+                AssetId::zeroed(),
+                COINS_PER_WALLET,
+                AMOUNT_PER_COIN,
+            )));
+    }
+
+    let service = FuelService::start(NodeConfig::default(), chain_config, state_config)
+        .await
+        .ok()?;
+    let address = service.bound_address();
+    // Same as `setup_test_provider`, the node lives as long as the test runtime
+    tokio::spawn(async move {
+        let _service = service;
+        futures::future::pending::<()>().await;
+    });
+    let provider = Provider::connect(address.to_string()).await.ok()?;
+
+    let admin_key = SecretKey::from_str(&protocol.admin_secret_key).ok()?;
+    let admin = Wallet::new(PrivateKeySigner::new(admin_key), provider.clone());
+    let wallets = signers
+        .into_iter()
+        .map(|signer| Wallet::new(signer, provider.clone()))
+        .collect();
+
+    Some((protocol.load(&admin), admin, wallets))
+}
+
+async fn cached_snapshot_dir(deploy_2nd_asset: bool, use_test_fpt: bool) -> Option<PathBuf> {
+    let key = snapshot_key(deploy_2nd_asset, use_test_fpt)?;
+    let cache_dir = PathBuf::from(get_absolute_path_from_relative(SNAPSHOT_CACHE_DIR));
+    let snapshot_dir = cache_dir.join(&key);
+
+    if snapshot_dir.join("protocol.json").exists() {
+        return Some(snapshot_dir);
+    }
+
+    // Checked before deploying, so a missing snapshot command doesn't cost a second deployment
+    static FAILED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    let failed = FAILED.get_or_init(Default::default);
+    if !fuel_core_supports_snapshot() || failed.lock().unwrap().contains(&key) {
+        return None;
+    }
+
+    // Build in a scratch directory and move it in place at the end, so concurrent tests
+    // never observe a partially written snapshot
+    let scratch_dir = cache_dir.join(format!("{}-{}", key, rand::thread_rng().gen::<u64>()));
+    let built = build_snapshot(&scratch_dir, deploy_2nd_asset, use_test_fpt).await;
+    if built.is_none() {
+        failed.lock().unwrap().insert(key);
+        let _ = fs::remove_dir_all(&scratch_dir);
+        return None;
+    }
+
+    // Another test may have won the race, in which case its snapshot is kept
+    if fs::rename(scratch_dir.join("snapshot"), &snapshot_dir).is_err() {
+        let _ = fs::remove_dir_all(&scratch_dir);
+        return snapshot_dir
+            .join("protocol.json")
+            .exists()
+            .then_some(snapshot_dir);
+    }
+    let _ = fs::remove_dir_all(&scratch_dir);
+
+    Some(snapshot_dir)
+}
+
+fn fuel_core_supports_snapshot() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| {
+        Command::new("fuel-core")
+            .args(["snapshot", "--help"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
+
+async fn build_snapshot(
+    scratch_dir: &Path,
+    deploy_2nd_asset: bool,
+    use_test_fpt: bool,
+) -> Option<()> {
+    let db_dir = scratch_dir.join("db");
+    let output_dir = scratch_dir.join("snapshot");
+    fs::create_dir_all(&output_dir).ok()?;
+
+    let admin_key = SecretKey::random(&mut rand::thread_rng());
+    let admin_signer = PrivateKeySigner::new(admin_key);
+    let state_config = StateConfig {
+        coins: coin_configs(setup_single_asset_coins(
+            admin_signer.address(),
+            AssetId::zeroed(),
+            COINS_PER_WALLET,
+            AMOUNT_PER_COIN,
+        )),
+        ..StateConfig::default()
+    };
+    let node_config = NodeConfig {
+        database_type: DbType::RocksDb(Some(db_dir.clone())),
+        ..NodeConfig::default()
+    };
+
+    let service = FuelService::start(node_config, ChainConfig::local_testnet(), state_config)
+        .await
+        .ok()?;
+    let provider = Provider::connect(service.bound_address().to_string())
+        .await
+        .ok()?;
+    let admin = Wallet::new(admin_signer, provider);
+
+    let contracts = deploy_protocol(&admin, deploy_2nd_asset, use_test_fpt).await;
+    let protocol = ProtocolSnapshot::new(&contracts, &admin_key);
+
+    // RocksDB must be closed before fuel-core can export it
+    service.stop().await.ok()?;
+
+    let status = Command::new("fuel-core")
+        .arg("snapshot")
+        .arg("--db-path")
+        .arg(&db_dir)
+        .arg("--output-directory")
+        .arg(output_dir.join("state"))
+        .arg("everything")
+        .status()
+        .ok()?;
+    if !status.success() {
+        return None;
+    }
+
+    fs::write(
+        output_dir.join("protocol.json"),
+        serde_json::to_string_pretty(&protocol).ok()?,
+    )
+    .ok()?;
+
+    Some(())
+}
+
+fn snapshot_key(deploy_2nd_asset: bool, use_test_fpt: bool) -> Option<String> {
+    let mut hasher = Hasher::default();
+    hasher.input(SNAPSHOT_FORMAT_VERSION.as_bytes());
+    hasher.input([deploy_2nd_asset as u8, use_test_fpt as u8]);
+
+    for path in SNAPSHOT_INPUTS {
+        hasher.input(fs::read(get_artifact_path(path)).ok()?);
+    }
+    for source in SNAPSHOT_SETUP_SOURCES {
+        hasher.input(source.as_bytes());
+    }
+
+    Some(hex::encode(hasher.finalize()))
+}
+
+fn coin_configs(coins: Vec<Coin>) -> Vec<CoinConfig> {
+    coins
+        .into_iter()
+        .map(|coin| CoinConfig {
+            tx_id: *coin.utxo_id.tx_id(),
+            output_index: coin.utxo_id.output_index(),
+            tx_pointer_block_height: Default::default(),
+            tx_pointer_tx_idx: Default::default(),
+            owner: coin.owner.into(),
+            amount: coin.amount,
+            asset_id: coin.asset_id,
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize)]
+struct InstanceIds {
+    contract_id: String,
+    implementation_id: String,
+}
+
+impl InstanceIds {
+    fn new<C>(contract_id: ContractId, instance: &ContractInstance<C>) -> Self {
+        Self {
+            contract_id: contract_id.to_string(),
+            implementation_id: instance.implementation_id.to_string(),
+        }
+    }
+
+    fn contract_id(&self) -> ContractId {
+        self.contract_id.parse().unwrap()
+    }
+
+    fn implementation_id(&self) -> ContractId {
+        self.implementation_id.parse().unwrap()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AssetSnapshot {
+    asset: String,
+    oracle: InstanceIds,
+    mock_stork_oracle: String,
+    mock_pyth_oracle: String,
+    mock_redstone_oracle: String,
+    trove_manager: InstanceIds,
+    asset_id: String,
+    stork_feed_id: String,
+    pyth_price_id: String,
+    redstone_price_id: String,
+    redstone_precision: u32,
+    fuel_vm_decimals: u32,
+}
+
+// Contract ids of a snapshotted deployment, enough to rebuild `ProtocolContracts` for any wallet
+#[derive(Serialize, Deserialize)]
+struct ProtocolSnapshot {
+    admin_secret_key: String,
+    borrow_operations: InstanceIds,
+    usdm: InstanceIds,
+    stability_pool: InstanceIds,
+    protocol_manager: InstanceIds,
+    asset_contracts: Vec<AssetSnapshot>,
+    fpt_staking: InstanceIds,
+    coll_surplus_pool: InstanceIds,
+    sorted_troves: InstanceIds,
+    default_pool: InstanceIds,
+    active_pool: InstanceIds,
+    fpt_token: InstanceIds,
+    community_issuance: InstanceIds,
+    vesting_contract: InstanceIds,
+    fpt_asset_id: String,
+    usdm_asset_id: String,
+}
+
+impl ProtocolSnapshot {
+    fn new(contracts: &ProtocolContracts<Wallet>, admin_secret_key: &SecretKey) -> Self {
+        macro_rules! ids {
+            ($instance:expr) => {
+                InstanceIds::new($instance.contract.contract_id().into(), &$instance)
+            };
+        }
+
+        Self {
+            admin_secret_key: admin_secret_key.to_string(),
+            borrow_operations: ids!(contracts.borrow_operations),
+            usdm: ids!(contracts.usdm),
+            stability_pool: ids!(contracts.stability_pool),
+            protocol_manager: ids!(contracts.protocol_manager),
+            asset_contracts: contracts
+                .asset_contracts
+                .iter()
+                .map(|asset_contracts| AssetSnapshot {
+                    asset: ContractId::from(asset_contracts.asset.contract_id()).to_string(),
+                    oracle: ids!(asset_contracts.oracle),
+                    mock_stork_oracle: ContractId::from(
+                        asset_contracts.mock_stork_oracle.contract_id(),
+                    )
+                    .to_string(),
+                    mock_pyth_oracle: ContractId::from(
+                        asset_contracts.mock_pyth_oracle.contract_id(),
+                    )
+                    .to_string(),
+                    mock_redstone_oracle: ContractId::from(
+                        asset_contracts.mock_redstone_oracle.contract_id(),
+                    )
+                    .to_string(),
+                    trove_manager: ids!(asset_contracts.trove_manager),
+                    asset_id: asset_contracts.asset_id.to_string(),
+                    stork_feed_id: hex::encode(asset_contracts.stork_feed_id.0),
+                    pyth_price_id: hex::encode(asset_contracts.pyth_price_id.0),
+                    redstone_price_id: asset_contracts.redstone_price_id.to_string(),
+                    redstone_precision: asset_contracts.redstone_precision,
+                    fuel_vm_decimals: asset_contracts.fuel_vm_decimals,
+                })
+                .collect(),
+            fpt_staking: ids!(contracts.fpt_staking),
+            coll_surplus_pool: ids!(contracts.coll_surplus_pool),
+            sorted_troves: ids!(contracts.sorted_troves),
+            default_pool: ids!(contracts.default_pool),
+            active_pool: ids!(contracts.active_pool),
+            fpt_token: ids!(contracts.fpt_token),
+            community_issuance: ids!(contracts.community_issuance),
+            vesting_contract: ids!(contracts.vesting_contract),
+            fpt_asset_id: contracts.fpt_asset_id.to_string(),
+            usdm_asset_id: contracts.usdm_asset_id.to_string(),
+        }
+    }
+
+    fn load(&self, wallet: &Wallet) -> ProtocolContracts<Wallet> {
+        macro_rules! instance {
+            ($contract:ident, $ids:expr) => {
+                ContractInstance::new(
+                    $contract::new($ids.contract_id(), wallet.clone()),
+                    $ids.implementation_id(),
+                )
+            };
+        }
+
+        let contract_id = |id: &str| -> ContractId { id.parse().unwrap() };
+        let bits256 = |id: &str| -> Bits256 { Bits256::from_hex_str(id).unwrap() };
+
+        ProtocolContracts {
+            borrow_operations: instance!(BorrowOperations, self.borrow_operations),
+            usdm: instance!(USDMToken, self.usdm),
+            stability_pool: instance!(StabilityPool, self.stability_pool),
+            protocol_manager: instance!(ProtocolManager, self.protocol_manager),
+            asset_contracts: self
+                .asset_contracts
+                .iter()
+                .map(|asset| AssetContracts {
+                    asset: Token::new(contract_id(&asset.asset), wallet.clone()),
+                    oracle: instance!(Oracle, asset.oracle),
+                    mock_stork_oracle: StorkCore::new(
+                        contract_id(&asset.mock_stork_oracle),
+                        wallet.clone(),
+                    ),
+                    mock_pyth_oracle: PythCore::new(
+                        contract_id(&asset.mock_pyth_oracle),
+                        wallet.clone(),
+                    ),
+                    mock_redstone_oracle: RedstoneCore::new(
+                        contract_id(&asset.mock_redstone_oracle),
+                        wallet.clone(),
+                    ),
+                    trove_manager: instance!(TroveManagerContract, asset.trove_manager),
+                    asset_id: AssetId::from_str(&asset.asset_id).unwrap(),
+                    stork_feed_id: bits256(&asset.stork_feed_id),
+                    pyth_price_id: bits256(&asset.pyth_price_id),
+                    redstone_price_id: U256::from_dec_str(&asset.redstone_price_id).unwrap(),
+                    redstone_precision: asset.redstone_precision,
+                    fuel_vm_decimals: asset.fuel_vm_decimals,
+                })
+                .collect(),
+            fpt_staking: instance!(FPTStaking, self.fpt_staking),
+            coll_surplus_pool: instance!(CollSurplusPool, self.coll_surplus_pool),
+            sorted_troves: instance!(SortedTroves, self.sorted_troves),
+            default_pool: instance!(DefaultPool, self.default_pool),
+            active_pool: instance!(ActivePool, self.active_pool),
+            fpt_token: instance!(FPTToken, self.fpt_token),
+            community_issuance: instance!(CommunityIssuance, self.community_issuance),
+            vesting_contract: instance!(VestingContract, self.vesting_contract),
+            fpt_asset_id: AssetId::from_str(&self.fpt_asset_id).unwrap(),
+            usdm_asset_id: AssetId::from_str(&self.usdm_asset_id).unwrap(),
+        }
+    }
+}
@@ -2,36 +2,50 @@ use std::path::{Path, PathBuf};
 
 use fuels::programs::responses::CallResponse;
 
-const DECIMAL_PRECISION: u64 = 1_000_000_000;
+use crate::fluid_math::{
+    checked_fm_compute_cr, fm_compute_base_rate_from_redemption, fm_compute_borrow_fee,
+    fm_compute_fee, fm_compute_nominal_cr, fm_compute_redemption_fee, fm_compute_redemption_rate,
+    fm_multiply_ratio, ONE, REDEMPTION_FEE_FLOOR, STABILITY_POOL_FEE,
+};
 
 // 0.5% min borrow fee
 pub fn with_min_borrow_fee(debt: u64) -> u64 {
-    let net_debt = debt * 1_005 / 1_000;
-    return net_debt;
+    debt + fm_compute_borrow_fee(debt)
 }
 
 // 1% min redemption fee
 pub fn with_min_redemption_fee(amount: u64) -> u64 {
-    let amount_with_fee = amount * 1_010 / 1_000;
-    return amount_with_fee;
+    amount + fm_compute_redemption_fee(amount)
+}
+
+// Fee on the collateral drawn by a redemption of `usdm_redeemed` out of `usdm_supply`, charged
+// after the base rate has risen by the redeemed fraction
+pub fn redemption_fee(
+    asset_drawn: u64,
+    usdm_redeemed: u64,
+    usdm_supply: u64,
+    decayed_base_rate: u64,
+) -> u64 {
+    let base_rate =
+        fm_compute_base_rate_from_redemption(decayed_base_rate, usdm_redeemed, usdm_supply);
+    let redemption_rate = fm_compute_redemption_rate(base_rate, REDEMPTION_FEE_FLOOR);
+    fm_compute_fee(asset_drawn, redemption_rate)
 }
 
 pub fn calculate_icr(coll: u64, debt: u64) -> u64 {
-    let icr = coll as u128 * DECIMAL_PRECISION as u128 / debt as u128;
-    return icr.try_into().unwrap();
+    fm_compute_nominal_cr(coll, debt)
 }
 
 pub fn calculate_cr(price: u64, coll: u64, debt: u64) -> u64 {
     if debt == 0 {
         return 0;
     }
-    let cr = price as u128 * coll as u128 / debt as u128;
-    return cr.try_into().unwrap_or(0);
+    checked_fm_compute_cr(coll, debt, price).unwrap_or(0)
 }
 
+// 10% liquidation penalty paid to the stability pool
 pub fn with_liquidation_penalty(amount: u64) -> u64 {
-    let amount_with_penalty = amount * 1_10 / 1_00;
-    return amount_with_penalty;
+    fm_multiply_ratio(amount, ONE + STABILITY_POOL_FEE, ONE)
 }
 
 pub fn resolve_relative_path(path: &str) -> String {
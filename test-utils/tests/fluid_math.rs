@@ -0,0 +1,209 @@
+use fuels::types::U256;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use test_utils::{
+    fluid_math::*,
+    utils::{with_liquidation_penalty, with_min_borrow_fee, with_min_redemption_fee},
+};
+
+const CASES: usize = 10_000;
+
+fn sample_u64(rng: &mut StdRng) -> u64 {
+    match rng.gen_range(0..5) {
+        0 => [0, 1, DECIMAL_PRECISION, u64::MAX, u64::MAX - 1][rng.gen_range(0..5)],
+        1 => 10u64.pow(rng.gen_range(0..20)),
+        2 => rng.gen_range(0..1_000_000) * DECIMAL_PRECISION,
+        _ => rng.gen(),
+    }
+}
+
+// The checked variants must report a revert exactly when the exact result does not fit,
+// computed here in a wider type than the one the mirror uses
+#[test]
+fn ratios_revert_exactly_on_overflow() {
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for _ in 0..CASES {
+        let (a, b, c) = (
+            sample_u64(&mut rng),
+            sample_u64(&mut rng),
+            sample_u64(&mut rng),
+        );
+        let exact = |value: U256, denominator: u64| {
+            (denominator > 0)
+                .then(|| value / U256::from(denominator))
+                .and_then(|ratio| u64::try_from(ratio).ok())
+        };
+        let product = U256::from(a) * U256::from(b);
+
+        assert_eq!(checked_fm_multiply_ratio(a, b, c), exact(product, c));
+        assert_eq!(
+            checked_fm_compute_cr(a, c, b),
+            if c == 0 {
+                Some(u64::MAX)
+            } else {
+                exact(product, c)
+            }
+        );
+        assert_eq!(
+            checked_fm_compute_nominal_cr(a, c),
+            if c == 0 {
+                Some(u64::MAX)
+            } else {
+                exact(U256::from(a) * U256::from(DECIMAL_PRECISION), c)
+            }
+        );
+    }
+}
+
+#[test]
+fn fees_never_overflow() {
+    let mut rng = StdRng::seed_from_u64(2);
+
+    for _ in 0..CASES {
+        let debt = sample_u64(&mut rng);
+
+        let borrow_fee = fm_compute_borrow_fee(debt);
+        let redemption_fee = fm_compute_redemption_fee(debt);
+        assert!(borrow_fee <= debt / 200 && redemption_fee <= debt / 100);
+        assert!(borrow_fee <= redemption_fee);
+
+        // The test helpers only overflow where the contracts would, on the final addition
+        if let Some(expected) = debt.checked_add(borrow_fee) {
+            assert_eq!(with_min_borrow_fee(debt), expected);
+        }
+        if let Some(expected) = debt.checked_add(redemption_fee) {
+            assert_eq!(with_min_redemption_fee(debt), expected);
+        }
+        if let Some(expected) = checked_fm_multiply_ratio(debt, ONE + STABILITY_POOL_FEE, ONE) {
+            assert_eq!(with_liquidation_penalty(debt), expected);
+        }
+    }
+}
+
+#[test]
+fn convert_precision_reverts_exactly_on_overflow() {
+    let mut rng = StdRng::seed_from_u64(3);
+
+    for _ in 0..CASES {
+        let price = sample_u64(&mut rng);
+        let precision = rng.gen_range(0..40u32);
+
+        let exact = if precision >= 9 {
+            10u64
+                .checked_pow(precision - 9)
+                .map(|magnitude| price / magnitude)
+        } else {
+            u64::try_from(price as u128 * 10u128.pow(9 - precision)).ok()
+        };
+        assert_eq!(checked_convert_precision(price, precision), exact);
+
+        let wide_price = U256::from(price) * U256::from(rng.gen::<u64>() % 1_000 + 1);
+        let exact = if precision >= 9 {
+            10u64
+                .checked_pow(precision - 9)
+                .and_then(|magnitude| u64::try_from(wide_price / U256::from(magnitude)).ok())
+        } else {
+            u64::try_from(wide_price * U256::from(10u64.pow(9 - precision))).ok()
+        };
+        assert_eq!(
+            checked_convert_precision_u256_and_downcast(wide_price, precision),
+            exact
+        );
+    }
+
+    // The Sway magnitude is a u64, so precisions of 29 or more revert even for a zero price
+    assert_eq!(checked_convert_precision(0, 29), None);
+    assert_eq!(checked_convert_precision(0, 28), Some(0));
+}
+
+#[test]
+fn dec_mul_rounds_half_up_and_reverts_on_overflow() {
+    let mut rng = StdRng::seed_from_u64(4);
+
+    for _ in 0..CASES {
+        let (a, b): (u128, u128) = (rng.gen::<u64>() as u128, rng.gen::<u64>() as u128);
+        let product = U256::from(a) * U256::from(b);
+        let rounded = (product + U256::from(DECIMAL_PRECISION / 2)) / U256::from(DECIMAL_PRECISION);
+        assert_eq!(checked_dec_mul(a, b), u128::try_from(rounded).ok());
+    }
+
+    assert_eq!(dec_mul(1_500_000_000, 1_500_000_000), 2_250_000_000);
+    assert_eq!(dec_mul(1, DECIMAL_PRECISION as u128 / 2), 1);
+    assert_eq!(dec_mul(1, DECIMAL_PRECISION as u128 / 2 - 1), 0);
+    assert_eq!(checked_dec_mul(u128::MAX, 2), None);
+    assert_eq!(checked_dec_mul(u128::MAX, 1), None);
+}
+
+#[test]
+fn dec_pow_decays_without_overflow() {
+    let mut rng = StdRng::seed_from_u64(5);
+
+    // Matches the Sway unit tests of fluid_math
+    assert_eq!(dec_pow(ONE, 0), ONE as u128);
+    assert_eq!(dec_pow(3 * ONE, 1), 3 * ONE as u128);
+    assert_eq!(dec_pow(1_500_000_000, 2), 2_250_000_000);
+    assert_eq!(dec_pow(3 * ONE, 2), 9 * ONE as u128);
+
+    for _ in 0..CASES / 10 {
+        // Bases at or below ONE are decay factors, they can never overflow
+        let base = ONE - rng.gen_range(0..ONE);
+        let minutes = rng.gen_range(1..=DEC_POW_MAX_MINUTES * 2);
+        assert!(checked_dec_pow(base, minutes).unwrap() <= ONE as u128);
+
+        // The exponent is capped
+        assert_eq!(
+            dec_pow(base, DEC_POW_MAX_MINUTES + minutes),
+            dec_pow(base, DEC_POW_MAX_MINUTES)
+        );
+    }
+
+    assert_eq!(dec_pow(ONE, DEC_POW_MAX_MINUTES), ONE as u128);
+    assert_eq!(checked_dec_pow(2 * ONE, 200), None);
+}
+
+#[test]
+fn base_rate_decays_and_rises_with_redemptions() {
+    let mut rng = StdRng::seed_from_u64(6);
+
+    // A 12 hour half-life
+    assert_eq!(fm_decay_base_rate(ONE, 0), ONE);
+    let half = fm_decay_base_rate(ONE, 720);
+    assert!(half.abs_diff(ONE / 2) < ONE / 1_000);
+
+    for _ in 0..CASES {
+        let base_rate = rng.gen_range(0..=ONE);
+        let minutes = rng.gen_range(1..DEC_POW_MAX_MINUTES);
+        let decayed = fm_decay_base_rate(base_rate, minutes);
+        assert!(decayed <= base_rate);
+        assert!(fm_decay_base_rate(decayed, 1) <= decayed);
+
+        let (usdm_redeemed, usdm_supply) = (sample_u64(&mut rng), sample_u64(&mut rng));
+        match checked_fm_compute_base_rate_from_redemption(base_rate, usdm_redeemed, usdm_supply) {
+            Some(new_base_rate) => {
+                assert!(new_base_rate >= base_rate && new_base_rate <= ONE);
+                if usdm_redeemed <= usdm_supply && base_rate + ONE / 2 <= ONE {
+                    let fraction = usdm_redeemed as u128 * ONE as u128 / usdm_supply as u128;
+                    assert_eq!(new_base_rate as u128, base_rate as u128 + fraction / 2);
+                }
+            }
+            None => assert_eq!(usdm_supply, 0),
+        }
+
+        // The rates are floored and capped whatever the base rate
+        let borrowing_rate = fm_compute_borrowing_rate(base_rate, BORROWING_FEE_FLOOR);
+        let redemption_rate = fm_compute_redemption_rate(base_rate, REDEMPTION_FEE_FLOOR);
+        assert!((BORROWING_FEE_FLOOR..=MAX_BORROWING_FEE).contains(&borrowing_rate));
+        assert!((REDEMPTION_FEE_FLOOR..=ONE).contains(&redemption_rate));
+
+        // At the floors the fees are the flat ones
+        let debt = sample_u64(&mut rng);
+        assert_eq!(
+            fm_compute_fee(debt, fm_compute_borrowing_rate(0, BORROWING_FEE_FLOOR)),
+            fm_compute_borrow_fee(debt)
+        );
+        assert_eq!(
+            fm_compute_fee(debt, fm_compute_redemption_rate(0, REDEMPTION_FEE_FLOOR)),
+            fm_compute_redemption_fee(debt)
+        );
+    }
+}
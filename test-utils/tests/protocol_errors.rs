@@ -0,0 +1,94 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use test_utils::{errors::ProtocolError, setup::common::get_absolute_path_from_relative};
+
+fn collect_sway_sources(dir: &Path, sources: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            // Build output and dependencies are not protocol sources
+            if path.ends_with("out") || path.ends_with(".git") {
+                continue;
+            }
+            collect_sway_sources(&path, sources);
+        } else if path.extension().is_some_and(|ext| ext == "sw") {
+            sources.push(fs::read_to_string(&path).unwrap());
+        }
+    }
+}
+
+// Revert reasons of every `require(condition, reason)`: string literals and enum variants
+fn require_reasons(source: &str) -> Vec<String> {
+    let mut reasons = vec![];
+
+    for (start, _) in source.match_indices("require(") {
+        let mut depth = 0;
+        let mut end = start;
+        for (offset, c) in source[start..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = start + offset;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let call = &source[start..end];
+
+        let literals: Vec<&str> = call.split('"').skip(1).step_by(2).collect();
+        if !literals.is_empty() {
+            reasons.extend(literals.into_iter().map(|literal| literal.to_string()));
+            continue;
+        }
+
+        // e.g. `require(DEBUG, OracleError::DebugNotEnabled)`
+        if let Some(reason) = call.trim_end().trim_end_matches(',').rsplit(',').next() {
+            let reason = reason.trim();
+            let variant = reason
+                .rsplit_once("::")
+                .map_or(reason, |(_, variant)| variant);
+            reasons.push(variant.to_string());
+        }
+    }
+
+    reasons
+}
+
+#[test]
+fn protocol_error_covers_every_require_message() {
+    let mut sources = vec![];
+    collect_sway_sources(
+        Path::new(&get_absolute_path_from_relative("contracts")),
+        &mut sources,
+    );
+    assert!(!sources.is_empty(), "No contract sources found");
+
+    let known: HashSet<&str> = ProtocolError::ALL.iter().map(|e| e.message()).collect();
+    let mut missing: Vec<String> = sources
+        .iter()
+        .flat_map(|source| require_reasons(source))
+        .filter(|reason| !known.contains(reason.as_str()))
+        .collect();
+    missing.sort();
+    missing.dedup();
+
+    assert!(
+        missing.is_empty(),
+        "Revert reasons missing from ProtocolError: {:#?}",
+        missing
+    );
+}
+
+#[test]
+fn protocol_error_messages_are_unique() {
+    let messages: HashSet<&str> = ProtocolError::ALL.iter().map(|e| e.message()).collect();
+    assert_eq!(messages.len(), ProtocolError::ALL.len());
+
+    for error in ProtocolError::ALL {
+        assert_eq!(ProtocolError::from_message(error.message()), Some(*error));
+    }
+}